@@ -7,3 +7,10 @@
 pub mod qoi;
 pub mod rect;
 mod windows;
+pub mod y4m;
+
+/// Splits a slice into arrays of length `N`, ignoring any remainder.
+fn array_chunks<T, const N: usize>(slice: &[T]) -> &[[T; N]] {
+    let len = slice.len() / N;
+    unsafe { std::slice::from_raw_parts(slice.as_ptr() as _, len) }
+}
@@ -1,10 +1,15 @@
-pub fn xrgb8888_encode_qoi(bytes: &[u8], width: u32, height: u32, stride: u32) -> Vec<u8> {
-    const OP_RGB: u8 = 0b1111_1110;
-    const OP_INDEX: u8 = 0b0000_0000;
-    const OP_DIFF: u8 = 0b0100_0000;
-    const OP_LUMA: u8 = 0b1000_0000;
-    const OP_RUN: u8 = 0b1100_0000;
+use {
+    crate::array_chunks,
+    std::{
+        error::Error,
+        fmt::{self, Display, Formatter},
+    },
+};
+
+#[cfg(test)]
+mod tests;
 
+pub fn xrgb8888_encode_qoi(bytes: &[u8], width: u32, height: u32, stride: u32) -> Vec<u8> {
     let mut res = vec![];
     let width_bytes_be = width.to_be_bytes();
     let height_bytes_be = height.to_be_bytes();
@@ -85,7 +90,122 @@ pub fn xrgb8888_encode_qoi(bytes: &[u8], width: u32, height: u32, stride: u32) -
     res
 }
 
-fn array_chunks<T, const N: usize>(slice: &[T]) -> &[[T; N]] {
-    let len = slice.len() / N;
-    unsafe { std::slice::from_raw_parts(slice.as_ptr() as _, len) }
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum QoiError {
+    Truncated,
+    Magic,
+    InvalidSize(u32, u32),
+}
+
+impl Display for QoiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            QoiError::Truncated => write!(f, "The file is too small"),
+            QoiError::Magic => write!(f, "The file does not start with the QOI magic"),
+            QoiError::InvalidSize(w, h) => write!(f, "The image has the invalid size {w}x{h}"),
+        }
+    }
+}
+
+impl Error for QoiError {}
+
+pub const MAGIC: &[u8] = b"qoif";
+
+const HEADER_SIZE: usize = 14;
+
+const OP_INDEX: u8 = 0x00;
+const OP_DIFF: u8 = 0x40;
+const OP_LUMA: u8 = 0x80;
+const OP_RUN: u8 = 0xc0;
+const OP_RGB: u8 = 0xfe;
+const OP_RGBA: u8 = 0xff;
+const MASK_2: u8 = 0xc0;
+
+pub struct QoiImage {
+    pub width: u32,
+    pub height: u32,
+    /// The pixels in RGBA order.
+    pub pixels: Vec<[u8; 4]>,
+}
+
+/// Decodes an image in the Quite OK Image Format.
+///
+/// See <https://qoiformat.org/qoi-specification.pdf>.
+pub fn decode(data: &[u8], max_pixels: u64) -> Result<QoiImage, QoiError> {
+    if data.len() < HEADER_SIZE {
+        return Err(QoiError::Truncated);
+    }
+    if &data[..4] != MAGIC {
+        return Err(QoiError::Magic);
+    }
+    let width = u32::from_be_bytes(data[4..8].try_into().unwrap());
+    let height = u32::from_be_bytes(data[8..12].try_into().unwrap());
+    let num_pixels = width as u64 * height as u64;
+    if num_pixels == 0 || num_pixels > max_pixels {
+        return Err(QoiError::InvalidSize(width, height));
+    }
+    // The dimensions come from the untrusted header. Every pixel requires at least one
+    // byte unless it is part of a run, so don't reserve more than the file can describe
+    // without runs and grow the buffer as needed.
+    let mut pixels = Vec::with_capacity((num_pixels as usize).min(data.len()));
+    let mut index = [[0u8; 4]; 64];
+    let mut px = [0, 0, 0, 255u8];
+    let mut pos = HEADER_SIZE;
+    macro_rules! next {
+        () => {{
+            let Some(&b) = data.get(pos) else {
+                return Err(QoiError::Truncated);
+            };
+            pos += 1;
+            b
+        }};
+    }
+    while pixels.len() < num_pixels as usize {
+        let b1 = next!();
+        let mut run = 1;
+        match b1 {
+            OP_RGB => {
+                px[0] = next!();
+                px[1] = next!();
+                px[2] = next!();
+            }
+            OP_RGBA => {
+                px[0] = next!();
+                px[1] = next!();
+                px[2] = next!();
+                px[3] = next!();
+            }
+            _ => match b1 & MASK_2 {
+                OP_INDEX => px = index[b1 as usize],
+                OP_DIFF => {
+                    px[0] = px[0].wrapping_add((b1 >> 4) & 3).wrapping_sub(2);
+                    px[1] = px[1].wrapping_add((b1 >> 2) & 3).wrapping_sub(2);
+                    px[2] = px[2].wrapping_add(b1 & 3).wrapping_sub(2);
+                }
+                OP_LUMA => {
+                    let b2 = next!();
+                    let vg = (b1 & 0x3f).wrapping_sub(32);
+                    px[0] = px[0]
+                        .wrapping_add(vg.wrapping_sub(8))
+                        .wrapping_add((b2 >> 4) & 0x0f);
+                    px[1] = px[1].wrapping_add(vg);
+                    px[2] = px[2]
+                        .wrapping_add(vg.wrapping_sub(8))
+                        .wrapping_add(b2 & 0x0f);
+                }
+                OP_RUN => run += (b1 & 0x3f) as usize,
+                _ => unreachable!(),
+            },
+        }
+        let hash =
+            px[0] as usize * 3 + px[1] as usize * 5 + px[2] as usize * 7 + px[3] as usize * 11;
+        index[hash % 64] = px;
+        let run = run.min(num_pixels as usize - pixels.len());
+        pixels.extend(std::iter::repeat_n(px, run));
+    }
+    Ok(QoiImage {
+        width,
+        height,
+        pixels,
+    })
 }
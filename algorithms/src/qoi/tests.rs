@@ -0,0 +1,84 @@
+use crate::qoi::{decode, xrgb8888_encode_qoi, QoiError};
+
+/// Creates an XRGB8888 image whose pixels exercise all encoder operations.
+fn image(width: u32, height: u32, stride: u32) -> Vec<u8> {
+    let mut res = vec![0xaa; (stride * height) as usize];
+    for y in 0..height {
+        for x in 0..width {
+            let (r, g, b) = match (x + y * width) % 7 {
+                0 | 1 => (0, 0, 0),
+                2 => (1, 2, 255),
+                3 => (10, 20, 30),
+                4 => (12, 21, 33),
+                5 => (x as u8, y as u8, (x * y) as u8),
+                _ => (10, 20, 30),
+            };
+            let offset = (y * stride + x * 4) as usize;
+            res[offset..offset + 4].copy_from_slice(&[b, g, r, 0]);
+        }
+    }
+    res
+}
+
+fn round_trip(width: u32, height: u32, stride: u32) {
+    let src = image(width, height, stride);
+    let encoded = xrgb8888_encode_qoi(&src, width, height, stride);
+    let decoded = decode(&encoded, u64::MAX).unwrap();
+    assert_eq!(decoded.width, width);
+    assert_eq!(decoded.height, height);
+    assert_eq!(decoded.pixels.len(), (width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let offset = (y * stride + x * 4) as usize;
+            let [b, g, r, _] = src[offset..offset + 4] else {
+                unreachable!();
+            };
+            assert_eq!(decoded.pixels[(y * width + x) as usize], [r, g, b, 255]);
+        }
+    }
+}
+
+#[test]
+fn round_trip_small() {
+    round_trip(5, 3, 20);
+}
+
+#[test]
+fn round_trip_padded() {
+    round_trip(17, 9, 80);
+}
+
+#[test]
+fn round_trip_long_run() {
+    let (width, height) = (200, 2);
+    let src = vec![0; width * height * 4];
+    let encoded = xrgb8888_encode_qoi(&src, width as u32, height as u32, width as u32 * 4);
+    let decoded = decode(&encoded, u64::MAX).unwrap();
+    assert!(decoded.pixels.iter().all(|p| *p == [0, 0, 0, 255]));
+    assert_eq!(decoded.pixels.len(), width * height);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(decode(b"qoif", 100).err(), Some(QoiError::Truncated));
+    assert_eq!(
+        decode(b"png\0xxxxxxxxxxxxxx", 100).err(),
+        Some(QoiError::Magic)
+    );
+    let src = image(4, 4, 16);
+    let encoded = xrgb8888_encode_qoi(&src, 4, 4, 16);
+    assert_eq!(
+        decode(&encoded, 15).err(),
+        Some(QoiError::InvalidSize(4, 4))
+    );
+    assert_eq!(decode(&encoded[..16], 100).err(), Some(QoiError::Truncated));
+}
+
+#[test]
+fn huge_header() {
+    let mut data = b"qoif".to_vec();
+    data.extend_from_slice(&u32::MAX.to_be_bytes());
+    data.extend_from_slice(&u32::MAX.to_be_bytes());
+    data.extend_from_slice(&[4, 0]);
+    assert_eq!(decode(&data, u64::MAX).err(), Some(QoiError::Truncated));
+}
@@ -0,0 +1,51 @@
+use crate::array_chunks;
+
+#[cfg(test)]
+mod tests;
+
+pub fn y4m_header(width: u32, height: u32, fps: u32) -> Vec<u8> {
+    format!("YUV4MPEG2 W{width} H{height} F{fps}:1 Ip A1:1 C444 XCOLORRANGE=FULL\n").into_bytes()
+}
+
+/// Appends an XRGB8888 image as a YUV4MPEG2 frame to `res`.
+///
+/// The frame has the dimensions `width` x `height`. Pixels outside the source image are
+/// black. The conversion uses the full-range BT.601 matrix.
+pub fn xrgb8888_encode_y4m_frame(
+    bytes: &[u8],
+    src_width: u32,
+    src_height: u32,
+    stride: u32,
+    width: u32,
+    height: u32,
+    res: &mut Vec<u8>,
+) {
+    res.extend_from_slice(b"FRAME\n");
+    let plane_size = (width * height) as usize;
+    let start = res.len();
+    res.resize(start + plane_size, 0);
+    res.resize(start + 3 * plane_size, 128);
+    let (y_plane, uv) = res[start..].split_at_mut(plane_size);
+    let (u_plane, v_plane) = uv.split_at_mut(plane_size);
+    let copy_width = width.min(src_width) as usize;
+    let copy_height = height.min(src_height) as usize;
+    for (row, line) in bytes
+        .chunks_exact(stride as _)
+        .take(copy_height)
+        .enumerate()
+    {
+        let offset = row * width as usize;
+        let pixels = array_chunks::<_, 4>(&line[..copy_width * 4]);
+        for (col, &pixel) in pixels.iter().enumerate() {
+            let b = pixel[0] as i32;
+            let g = pixel[1] as i32;
+            let r = pixel[2] as i32;
+            let y = (77 * r + 150 * g + 29 * b + 128) >> 8;
+            let u = ((-43 * r - 85 * g + 128 * b + 128) >> 8) + 128;
+            let v = ((128 * r - 107 * g - 21 * b + 128) >> 8) + 128;
+            y_plane[offset + col] = y.clamp(0, 255) as u8;
+            u_plane[offset + col] = u.clamp(0, 255) as u8;
+            v_plane[offset + col] = v.clamp(0, 255) as u8;
+        }
+    }
+}
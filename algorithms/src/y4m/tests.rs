@@ -0,0 +1,68 @@
+use crate::y4m::{xrgb8888_encode_y4m_frame, y4m_header};
+
+fn pixel(r: u8, g: u8, b: u8) -> [u8; 4] {
+    [b, g, r, 0]
+}
+
+#[test]
+fn header() {
+    assert_eq!(
+        y4m_header(640, 480, 30),
+        b"YUV4MPEG2 W640 H480 F30:1 Ip A1:1 C444 XCOLORRANGE=FULL\n",
+    );
+}
+
+#[test]
+fn colors() {
+    let mut src = vec![];
+    for p in [
+        pixel(0, 0, 0),
+        pixel(255, 255, 255),
+        pixel(255, 0, 0),
+        pixel(0, 0, 255),
+    ] {
+        src.extend_from_slice(&p);
+    }
+    let mut res = vec![];
+    xrgb8888_encode_y4m_frame(&src, 4, 1, 16, 4, 1, &mut res);
+    let (magic, planes) = res.split_at(6);
+    assert_eq!(magic, b"FRAME\n");
+    assert_eq!(planes.len(), 12);
+    let (y, uv) = planes.split_at(4);
+    let (u, v) = uv.split_at(4);
+    assert_eq!(y, [0, 255, 77, 29]);
+    assert_eq!(u, [128, 128, 85, 255]);
+    assert_eq!(v, [128, 128, 255, 107]);
+}
+
+#[test]
+fn crop_and_pad() {
+    // A 2x2 source with a stride of 3 pixels encoded into a 3x1 frame.
+    let mut src = vec![];
+    for p in [
+        pixel(255, 255, 255),
+        pixel(255, 255, 255),
+        pixel(1, 2, 3),
+        pixel(0, 0, 0),
+        pixel(0, 0, 0),
+        pixel(1, 2, 3),
+    ] {
+        src.extend_from_slice(&p);
+    }
+    let mut res = vec![];
+    xrgb8888_encode_y4m_frame(&src, 2, 2, 12, 3, 1, &mut res);
+    let planes = &res[6..];
+    assert_eq!(planes.len(), 9);
+    assert_eq!(&planes[..3], [255, 255, 0]);
+    assert_eq!(&planes[3..6], [128, 128, 128]);
+    assert_eq!(&planes[6..], [128, 128, 128]);
+}
+
+#[test]
+fn appends() {
+    let src = pixel(0, 0, 0);
+    let mut res = b"prefix".to_vec();
+    xrgb8888_encode_y4m_frame(&src, 1, 1, 4, 1, 1, &mut res);
+    xrgb8888_encode_y4m_frame(&src, 1, 1, 4, 1, 1, &mut res);
+    assert_eq!(res, b"prefixFRAME\n\x00\x80\x80FRAME\n\x00\x80\x80");
+}
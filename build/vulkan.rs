@@ -1,7 +1,7 @@
 use {
     crate::open,
     anyhow::{bail, Context},
-    shaderc::CompileOptions,
+    shaderc::{CompileOptions, IncludeType, ResolvedInclude},
     std::{io::Write, path::Path},
 };
 
@@ -15,6 +15,32 @@ pub fn main() -> anyhow::Result<()> {
     compile_tex_frag("tex.frag.spv", false, false)?;
     compile_tex_frag("tex.frag.mult+opaque.spv", false, true)?;
     compile_tex_frag("tex.frag.mult+alpha.spv", true, true)?;
+    compile_rounded("fill.vert", "fill.vert.rounded.spv", false)?;
+    compile_rounded("fill.frag", "fill.frag.rounded.spv", false)?;
+    compile_rounded("tex.vert", "tex.vert.rounded.spv", false)?;
+    compile_rounded("tex.frag", "tex.frag.rounded+opaque.spv", false)?;
+    compile_rounded("tex.frag", "tex.frag.rounded+alpha.spv", true)?;
+    compile_simple("blur_down.frag")?;
+    compile_simple("blur_up.frag")?;
+    compile_rounded("blur_up.frag", "blur_up.frag.rounded.spv", false)?;
+    Ok(())
+}
+
+fn compile_rounded(name: &str, out: &str, alpha: bool) -> anyhow::Result<()> {
+    let mut opts = CompileOptions::new().unwrap();
+    opts.add_macro_definition("ROUNDED", None);
+    if alpha {
+        opts.add_macro_definition("ALPHA", None);
+    }
+    opts.set_include_callback(|name, _: IncludeType, _, _| {
+        let content = std::fs::read_to_string(format!("{}/{}", ROOT, name))
+            .map_err(|e| format!("Could not read {name}: {e}"))?;
+        Ok(ResolvedInclude {
+            resolved_name: name.to_string(),
+            content,
+        })
+    });
+    compile_shader(name, out, Some(&opts)).with_context(|| out.to_string())?;
     Ok(())
 }
 
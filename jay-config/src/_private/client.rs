@@ -5,10 +5,12 @@ use {
         _private::{
             bincode_ops,
             ipc::{
-                ClientMessage, InitMessage, Response, ServerFeature, ServerMessage, WorkspaceSource,
+                ClientMessage, InitMessage, Response, ServerFeature, ServerMessage, StatusBlock,
+                StatusClick, WorkspaceSource,
             },
             logging, Config, ConfigEntry, ConfigEntryGen, PollableId, WireMode, VERSION,
         },
+        animation::{AnimationCurve, AnimationKind},
         exec::Command,
         input::{
             acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
@@ -21,14 +23,16 @@ use {
         },
         logging::LogLevel,
         tasks::{JoinHandle, JoinSlot},
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{colors::Colorable, sized::Resizable, Color, WindowBorder},
         timer::Timer,
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
             Connector, DrmDevice, Format, GfxApi, Mode, TearingMode, Transform, VrrMode,
+            WallpaperMode,
         },
         xwayland::XScalingMode,
-        Axis, Direction, ModifiedKeySym, PciId, Workspace,
+        Axis, BarMode, BarPosition, Direction, ModifiedKeySym, PciId, ResizeAmount, Workspace,
+        WorkspaceLayout,
     },
     bincode::Options,
     futures_util::task::ArcWake,
@@ -99,6 +103,7 @@ pub(crate) struct Client {
     on_del_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_idle: RefCell<Option<Callback>>,
     on_switch_event: RefCell<HashMap<InputDevice, Callback<SwitchEvent>>>,
+    on_status_click: RefCell<Option<Callback<StatusClick>>>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
     read_interests: RefCell<HashMap<PollableId, Interest>>,
@@ -231,6 +236,7 @@ pub unsafe extern "C" fn init(
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
         on_switch_event: Default::default(),
+        on_status_click: Default::default(),
         bufs: Default::default(),
         reload: Cell::new(false),
         read_interests: Default::default(),
@@ -337,6 +343,10 @@ impl Client {
         self.send(&ClientMessage::Move { seat, direction });
     }
 
+    pub fn resize(&self, seat: Seat, axis: Axis, amount: ResizeAmount) {
+        self.send(&ClientMessage::Resize { seat, axis, amount });
+    }
+
     pub fn unbind<T: Into<ModifiedKeySym>>(&self, seat: Seat, mod_sym: T) {
         let mod_sym = mod_sym.into();
         if let Entry::Occupied(mut oe) = self.key_handlers.borrow_mut().entry((seat, mod_sym)) {
@@ -369,6 +379,12 @@ impl Client {
         mono
     }
 
+    pub fn stacked(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetStacked { seat });
+        get_response!(res, false, GetStacked { stacked });
+        stacked
+    }
+
     pub fn get_timer(&self, name: &str) -> Timer {
         let res = self.send_with_response(&ClientMessage::GetTimer { name });
         get_response!(res, Timer(0), GetTimer { timer });
@@ -436,6 +452,58 @@ impl Client {
         capture
     }
 
+    pub fn set_default_workspace_layout(&self, layout: WorkspaceLayout) {
+        self.send(&ClientMessage::SetDefaultWorkspaceLayout { layout });
+    }
+
+    pub fn set_workspace_layout(&self, workspace: WorkspaceSource, layout: WorkspaceLayout) {
+        self.send(&ClientMessage::SetWorkspaceLayout { workspace, layout });
+    }
+
+    pub fn get_workspace_layout(&self, workspace: WorkspaceSource) -> WorkspaceLayout {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceLayout { workspace });
+        get_response!(res, WorkspaceLayout::MANUAL, GetWorkspaceLayout { layout });
+        layout
+    }
+
+    pub fn cycle_workspace_layout(&self, workspace: WorkspaceSource) {
+        self.send(&ClientMessage::CycleWorkspaceLayout { workspace });
+    }
+
+    pub fn set_column_width_presets(&self, presets: &[f64]) {
+        self.send(&ClientMessage::SetColumnWidthPresets {
+            presets: presets.to_vec(),
+        });
+    }
+
+    pub fn cycle_column_width(&self, seat: Seat) {
+        self.send(&ClientMessage::CycleColumnWidth { seat });
+    }
+
+    pub fn set_workspace_master_count(&self, workspace: WorkspaceSource, count: u32) {
+        self.send(&ClientMessage::SetWorkspaceMasterCount { workspace, count });
+    }
+
+    pub fn get_workspace_master_count(&self, workspace: WorkspaceSource) -> u32 {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceMasterCount { workspace });
+        get_response!(res, 1, GetWorkspaceMasterCount { count });
+        count
+    }
+
+    pub fn set_workspace_master_ratio(&self, workspace: WorkspaceSource, ratio: f64) {
+        self.send(&ClientMessage::SetWorkspaceMasterRatio { workspace, ratio });
+    }
+
+    pub fn get_workspace_master_ratio(&self, workspace: WorkspaceSource) -> f64 {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceMasterRatio { workspace });
+        get_response!(res, 0.5, GetWorkspaceMasterRatio { ratio });
+        ratio
+    }
+
+    pub fn promote_to_master(&self, seat: Seat) {
+        self.send(&ClientMessage::PromoteToMaster { seat });
+    }
+
     pub fn show_workspace(&self, seat: Seat, workspace: Workspace) {
         self.send(&ClientMessage::ShowWorkspace { seat, workspace });
     }
@@ -479,6 +547,28 @@ impl Client {
         self.send(&ClientMessage::SetFont { font });
     }
 
+    pub fn set_title_font(&self, font: &str) {
+        self.send(&ClientMessage::SetTitleFont { font });
+    }
+
+    pub fn set_bar_font(&self, font: &str) {
+        self.send(&ClientMessage::SetBarFont { font });
+    }
+
+    pub fn set_status_font(&self, font: &str) {
+        self.send(&ClientMessage::SetStatusFont { font });
+    }
+
+    pub fn set_sticky_app_ids(&self, app_ids: &[&str]) {
+        let app_ids = app_ids.iter().map(|s| s.to_string()).collect();
+        self.send(&ClientMessage::SetStickyAppIds { app_ids });
+    }
+
+    pub fn set_font_fallbacks(&self, fonts: &[&str]) {
+        let fonts = fonts.iter().map(|f| f.to_string()).collect();
+        self.send(&ClientMessage::SetFontFallbacks { fonts });
+    }
+
     pub fn get_font(&self) -> String {
         let res = self.send_with_response(&ClientMessage::GetFont);
         get_response!(res, String::new(), GetFont { font });
@@ -542,6 +632,189 @@ impl Client {
         self.send(&ClientMessage::SetMono { seat, mono });
     }
 
+    pub fn set_stacked(&self, seat: Seat, stacked: bool) {
+        self.send(&ClientMessage::SetStacked { seat, stacked });
+    }
+
+    pub fn toggle_layout(&self, seat: Seat) {
+        self.send(&ClientMessage::ToggleLayout { seat });
+    }
+
+    pub fn mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::Mark { seat, mark });
+    }
+
+    pub fn unmark(&self, seat: Seat) {
+        self.send(&ClientMessage::Unmark { seat });
+    }
+
+    pub fn marks(&self, seat: Seat) -> Vec<String> {
+        let res = self.send_with_response(&ClientMessage::GetMarks { seat });
+        get_response!(res, vec![], GetMarks { marks });
+        marks
+    }
+
+    pub fn focus_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::FocusMark { seat, mark });
+    }
+
+    pub fn swap_with_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::SwapWithMark { seat, mark });
+    }
+
+    pub fn move_to_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::MoveToMark { seat, mark });
+    }
+
+    pub fn set_show_marks(&self, show: bool) {
+        self.send(&ClientMessage::SetShowMarks { show });
+    }
+
+    pub fn focus_previous(&self, seat: Seat) {
+        self.send(&ClientMessage::FocusPrevious { seat });
+    }
+
+    pub fn focus_next_in_history(&self, seat: Seat) {
+        self.send(&ClientMessage::FocusNextInHistory { seat });
+    }
+
+    pub fn window_switcher(&self, seat: Seat, reverse: bool) {
+        self.send(&ClientMessage::WindowSwitcher { seat, reverse });
+    }
+
+    pub fn sticky(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetSticky { seat });
+        get_response!(res, false, GetSticky { sticky });
+        sticky
+    }
+
+    pub fn set_sticky(&self, seat: Seat, sticky: bool) {
+        self.send(&ClientMessage::SetSticky { seat, sticky });
+    }
+
+    pub fn minimize(&self, seat: Seat) {
+        self.send(&ClientMessage::Minimize { seat });
+    }
+
+    pub fn unminimize_last(&self, seat: Seat) {
+        self.send(&ClientMessage::UnminimizeLast { seat });
+    }
+
+    pub fn set_swallow_terminals(&self, app_ids: &[&str]) {
+        let app_ids = app_ids.iter().map(|s| s.to_string()).collect();
+        self.send(&ClientMessage::SetSwallowTerminals { app_ids });
+    }
+
+    pub fn set_swallow_exceptions(&self, app_ids: &[&str]) {
+        let app_ids = app_ids.iter().map(|s| s.to_string()).collect();
+        self.send(&ClientMessage::SetSwallowExceptions { app_ids });
+    }
+
+    pub fn save_layout(&self, workspace: WorkspaceSource, path: &str) {
+        self.send(&ClientMessage::SaveLayout { workspace, path });
+    }
+
+    pub fn load_layout(&self, workspace: WorkspaceSource, path: &str) {
+        self.send(&ClientMessage::LoadLayout { workspace, path });
+    }
+
+    pub fn connector_set_wallpaper(
+        &self,
+        connector: Connector,
+        path: Option<&str>,
+        mode: WallpaperMode,
+    ) {
+        self.send(&ClientMessage::ConnectorSetWallpaper {
+            connector,
+            path,
+            mode,
+        });
+    }
+
+    pub fn set_animations_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAnimationsEnabled { enabled });
+    }
+
+    pub fn set_animation_duration(&self, kind: AnimationKind, duration: Duration) {
+        self.send(&ClientMessage::SetAnimationDuration { kind, duration });
+    }
+
+    pub fn set_animation_curve(&self, kind: AnimationKind, curve: AnimationCurve) {
+        self.send(&ClientMessage::SetAnimationCurve { kind, curve });
+    }
+
+    pub fn set_animations_in_screencasts(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAnimationsInScreencasts { enabled });
+    }
+
+    pub fn set_blur_app_ids(&self, app_ids: &[&str]) {
+        let app_ids = app_ids.iter().map(|s| s.to_string()).collect();
+        self.send(&ClientMessage::SetBlurAppIds { app_ids });
+    }
+
+    pub fn set_blur_layer_namespaces(&self, namespaces: &[&str]) {
+        let namespaces = namespaces.iter().map(|s| s.to_string()).collect();
+        self.send(&ClientMessage::SetBlurLayerNamespaces { namespaces });
+    }
+
+    pub fn set_window_opacities(&self, opacities: &[(&str, f64)]) {
+        let opacities = opacities
+            .iter()
+            .map(|(app_id, opacity)| (app_id.to_string(), *opacity))
+            .collect();
+        self.send(&ClientMessage::SetWindowOpacities { opacities });
+    }
+
+    pub fn set_window_opacity(&self, seat: Seat, opacity: Option<f64>) {
+        self.send(&ClientMessage::SetWindowOpacity { seat, opacity });
+    }
+
+    pub fn set_title_format(&self, format: Option<&str>) {
+        self.send(&ClientMessage::SetTitleFormat { format });
+    }
+
+    pub fn set_hidden_title_app_ids(&self, app_ids: &[&str]) {
+        let app_ids = app_ids.iter().map(|s| s.to_string()).collect();
+        self.send(&ClientMessage::SetHiddenTitleAppIds { app_ids });
+    }
+
+    pub fn toggle_title_hidden(&self, seat: Seat) {
+        self.send(&ClientMessage::ToggleTitleHidden { seat });
+    }
+
+    pub fn set_show_title_buttons(&self, show: bool) {
+        self.send(&ClientMessage::SetShowTitleButtons { show });
+    }
+
+    pub fn set_window_borders(&self, borders: &[(&str, WindowBorder)]) {
+        let borders = borders
+            .iter()
+            .map(|(app_id, border)| (app_id.to_string(), *border))
+            .collect();
+        self.send(&ClientMessage::SetWindowBorders { borders });
+    }
+
+    pub fn set_window_border(&self, seat: Seat, border: WindowBorder) {
+        self.send(&ClientMessage::SetWindowBorder { seat, border });
+    }
+
+    pub fn set_bar_position(&self, position: BarPosition) {
+        self.send(&ClientMessage::SetBarPosition { position });
+    }
+
+    pub fn set_bar_mode(&self, mode: BarMode) {
+        self.send(&ClientMessage::SetBarMode { mode });
+    }
+
+    pub fn set_bar_modifiers(&self, modifiers: Modifiers) {
+        self.send(&ClientMessage::SetBarModifiers { modifiers });
+    }
+
+    pub fn set_bar_connectors(&self, connectors: Option<&[&str]>) {
+        let connectors = connectors.map(|c| c.iter().map(|c| c.to_string()).collect());
+        self.send(&ClientMessage::SetBarConnectors { connectors });
+    }
+
     pub fn set_env(&self, key: &str, val: &str) {
         self.send(&ClientMessage::SetEnv { key, val });
     }
@@ -562,6 +835,15 @@ impl Client {
         for old in self.status_task.replace(tasks) {
             old.abort();
         }
+        self.on_status_click.take();
+    }
+
+    pub fn set_status_blocks(&self, blocks: Vec<StatusBlock>, separator: &str) {
+        self.send(&ClientMessage::SetStatusBlocks { blocks, separator });
+    }
+
+    pub fn on_status_click<F: FnMut(StatusClick) + 'static>(&self, f: F) {
+        *self.on_status_click.borrow_mut() = Some(cb(f));
     }
 
     pub fn set_i3bar_separator(&self, separator: &str) {
@@ -1440,6 +1722,12 @@ impl Client {
                     run_cb("switch event", &cb, event);
                 }
             }
+            ServerMessage::StatusClicked { click } => {
+                let cb = self.on_status_click.borrow().clone();
+                if let Some(cb) = cb {
+                    run_cb("status click", &cb, click);
+                }
+            }
         }
     }
 
@@ -1,20 +1,21 @@
 use {
     crate::{
+        _private::{PollableId, WireMode},
+        animation::{AnimationCurve, AnimationKind},
         input::{
             acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
             Seat, SwitchEvent,
         },
         keyboard::{mods::Modifiers, syms::KeySym, Keymap},
         logging::LogLevel,
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{colors::Colorable, sized::Resizable, Color, WindowBorder},
         timer::Timer,
         video::{
             connector_type::ConnectorType, Connector, DrmDevice, Format, GfxApi, TearingMode,
-            Transform, VrrMode,
+            Transform, VrrMode, WallpaperMode,
         },
-        Axis, Direction, PciId, Workspace,
-        _private::{PollableId, WireMode},
         xwayland::XScalingMode,
+        Axis, BarMode, BarPosition, Direction, PciId, ResizeAmount, Workspace, WorkspaceLayout,
     },
     serde::{Deserialize, Serialize},
     std::time::Duration,
@@ -92,6 +93,9 @@ pub enum ServerMessage {
         input_device: InputDevice,
         event: SwitchEvent,
     },
+    StatusClicked {
+        click: StatusClick,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -527,9 +531,249 @@ pub enum ClientMessage<'a> {
     SetXScalingMode {
         mode: XScalingMode,
     },
+    Resize {
+        seat: Seat,
+        axis: Axis,
+        amount: ResizeAmount,
+    },
+    SetDefaultWorkspaceLayout {
+        layout: WorkspaceLayout,
+    },
+    SetWorkspaceLayout {
+        workspace: WorkspaceSource,
+        layout: WorkspaceLayout,
+    },
+    GetWorkspaceLayout {
+        workspace: WorkspaceSource,
+    },
+    CycleWorkspaceLayout {
+        workspace: WorkspaceSource,
+    },
+    SetWorkspaceMasterCount {
+        workspace: WorkspaceSource,
+        count: u32,
+    },
+    GetWorkspaceMasterCount {
+        workspace: WorkspaceSource,
+    },
+    SetWorkspaceMasterRatio {
+        workspace: WorkspaceSource,
+        ratio: f64,
+    },
+    GetWorkspaceMasterRatio {
+        workspace: WorkspaceSource,
+    },
+    PromoteToMaster {
+        seat: Seat,
+    },
+    SetColumnWidthPresets {
+        presets: Vec<f64>,
+    },
+    CycleColumnWidth {
+        seat: Seat,
+    },
+    GetStacked {
+        seat: Seat,
+    },
+    SetStacked {
+        seat: Seat,
+        stacked: bool,
+    },
+    ToggleLayout {
+        seat: Seat,
+    },
+    Mark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    Unmark {
+        seat: Seat,
+    },
+    GetMarks {
+        seat: Seat,
+    },
+    FocusMark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    SwapWithMark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    MoveToMark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    SetShowMarks {
+        show: bool,
+    },
+    FocusPrevious {
+        seat: Seat,
+    },
+    FocusNextInHistory {
+        seat: Seat,
+    },
+    WindowSwitcher {
+        seat: Seat,
+        reverse: bool,
+    },
+    GetSticky {
+        seat: Seat,
+    },
+    SetSticky {
+        seat: Seat,
+        sticky: bool,
+    },
+    Minimize {
+        seat: Seat,
+    },
+    UnminimizeLast {
+        seat: Seat,
+    },
+    SetSwallowTerminals {
+        app_ids: Vec<String>,
+    },
+    SetSwallowExceptions {
+        app_ids: Vec<String>,
+    },
+    SaveLayout {
+        workspace: WorkspaceSource,
+        path: &'a str,
+    },
+    LoadLayout {
+        workspace: WorkspaceSource,
+        path: &'a str,
+    },
+    ConnectorSetWallpaper {
+        connector: Connector,
+        path: Option<&'a str>,
+        mode: WallpaperMode,
+    },
+    SetAnimationsEnabled {
+        enabled: bool,
+    },
+    SetAnimationDuration {
+        kind: AnimationKind,
+        duration: Duration,
+    },
+    SetAnimationCurve {
+        kind: AnimationKind,
+        curve: AnimationCurve,
+    },
+    SetAnimationsInScreencasts {
+        enabled: bool,
+    },
+    SetBlurAppIds {
+        app_ids: Vec<String>,
+    },
+    SetBlurLayerNamespaces {
+        namespaces: Vec<String>,
+    },
+    SetWindowOpacities {
+        opacities: Vec<(String, f64)>,
+    },
+    SetWindowOpacity {
+        seat: Seat,
+        opacity: Option<f64>,
+    },
+    SetTitleFormat {
+        format: Option<&'a str>,
+    },
+    SetHiddenTitleAppIds {
+        app_ids: Vec<String>,
+    },
+    ToggleTitleHidden {
+        seat: Seat,
+    },
+    SetShowTitleButtons {
+        show: bool,
+    },
+    SetWindowBorders {
+        borders: Vec<(String, WindowBorder)>,
+    },
+    SetWindowBorder {
+        seat: Seat,
+        border: WindowBorder,
+    },
+    SetBarPosition {
+        position: BarPosition,
+    },
+    SetBarMode {
+        mode: BarMode,
+    },
+    SetBarModifiers {
+        modifiers: Modifiers,
+    },
+    SetBarConnectors {
+        connectors: Option<Vec<String>>,
+    },
+    SetStatusBlocks {
+        blocks: Vec<StatusBlock>,
+        separator: &'a str,
+    },
+    SetTitleFont {
+        font: &'a str,
+    },
+    SetBarFont {
+        font: &'a str,
+    },
+    SetStatusFont {
+        font: &'a str,
+    },
+    SetFontFallbacks {
+        fonts: Vec<String>,
+    },
+    SetStickyAppIds {
+        app_ids: Vec<String>,
+    },
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StatusBlock {
+    pub text: String,
+    pub markup: bool,
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub border: Option<Color>,
+    /// The widths of the top, right, bottom, and left borders.
+    pub border_widths: [i32; 4],
+    pub min_width: Option<StatusBlockMinWidth>,
+    pub align: StatusBlockAlign,
+    pub urgent: bool,
+    pub separator: bool,
+    pub separator_block_width: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum StatusBlockMinWidth {
+    Pixels(i32),
+    Text(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StatusBlockAlign {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct StatusClick {
+    pub block: usize,
+    pub button: u32,
+    pub modifiers: Modifiers,
+    pub x: i32,
+    pub y: i32,
+    pub relative_x: i32,
+    pub relative_y: i32,
+    pub output_x: i32,
+    pub output_y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub scale: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 pub enum WorkspaceSource {
     Seat(Seat),
     Explicit(Workspace),
@@ -675,6 +919,24 @@ pub enum Response {
     GetSocketPath {
         path: String,
     },
+    GetWorkspaceLayout {
+        layout: WorkspaceLayout,
+    },
+    GetWorkspaceMasterCount {
+        count: u32,
+    },
+    GetWorkspaceMasterRatio {
+        ratio: f64,
+    },
+    GetStacked {
+        stacked: bool,
+    },
+    GetMarks {
+        marks: Vec<String>,
+    },
+    GetSticky {
+        sticky: bool,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -0,0 +1,89 @@
+//! Tools for configuring animations.
+//!
+//! Animations are disabled by default. They are never shown while a window is displayed
+//! fullscreen or while tearing is enabled for an output.
+
+use {
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+};
+
+/// A kind of animation.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AnimationKind(pub u32);
+
+impl AnimationKind {
+    /// Windows fading in when they are mapped.
+    ///
+    /// The default duration is 150 ms.
+    pub const OPEN: Self = Self(0);
+    /// Windows fading out when they are unmapped.
+    ///
+    /// The default duration is 150 ms.
+    pub const CLOSE: Self = Self(1);
+    /// Tiled windows moving to their new position when the layout changes.
+    ///
+    /// The default duration is 150 ms.
+    pub const TILE: Self = Self(2);
+    /// Workspaces sliding in and out when the workspace shown on an output changes.
+    ///
+    /// The default duration is 200 ms.
+    pub const WORKSPACE: Self = Self(3);
+    /// Floating windows moving to their new position.
+    ///
+    /// Windows that are moved or resized with the pointer are not animated.
+    ///
+    /// The default duration is 150 ms.
+    pub const FLOAT: Self = Self(4);
+}
+
+/// The easing curve of an animation.
+///
+/// The default curve is `EaseOut`.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum AnimationCurve {
+    /// The animation progresses at a constant speed.
+    Linear,
+    /// Equivalent to `CubicBezier(0.25, 0.1, 0.25, 1.0)`.
+    Ease,
+    /// Equivalent to `CubicBezier(0.42, 0.0, 1.0, 1.0)`.
+    EaseIn,
+    /// Equivalent to `CubicBezier(0.0, 0.0, 0.58, 1.0)`.
+    EaseOut,
+    /// Equivalent to `CubicBezier(0.42, 0.0, 0.58, 1.0)`.
+    EaseInOut,
+    /// A cubic Bézier curve with the control points `(x1, y1)` and `(x2, y2)`.
+    ///
+    /// This has the same semantics as the CSS `cubic-bezier` function. `x1` and `x2` must
+    /// be in the range `[0, 1]`.
+    CubicBezier(f64, f64, f64, f64),
+}
+
+/// Enables or disables animations.
+///
+/// The default is `false`.
+pub fn set_animations_enabled(enabled: bool) {
+    get!().set_animations_enabled(enabled)
+}
+
+/// Sets the duration of a kind of animation.
+///
+/// A duration of 0 disables this kind of animation.
+pub fn set_animation_duration(kind: AnimationKind, duration: Duration) {
+    get!().set_animation_duration(kind, duration)
+}
+
+/// Sets the easing curve of a kind of animation.
+pub fn set_animation_curve(kind: AnimationKind, curve: AnimationCurve) {
+    get!().set_animation_curve(kind, curve)
+}
+
+/// Sets whether animations are shown on outputs that are being screencast.
+///
+/// If this is `false`, animations are disabled on an output while a screencast of the
+/// output is active.
+///
+/// The default is `true`.
+pub fn set_animations_in_screencasts(enabled: bool) {
+    get!().set_animations_in_screencasts(enabled)
+}
@@ -5,11 +5,12 @@ pub mod capability;
 
 use {
     crate::{
+        _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
         input::{acceleration::AccelProfile, capability::Capability},
         keyboard::{mods::Modifiers, Keymap},
-        Axis, Direction, ModifiedKeySym, Workspace,
-        _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
+        theme::WindowBorder,
         video::Connector,
+        Axis, Direction, ModifiedKeySym, ResizeAmount, Workspace, WorkspaceLayout,
     },
     serde::{Deserialize, Serialize},
     std::time::Duration,
@@ -267,6 +268,15 @@ impl Seat {
         get!().move_(self, direction)
     }
 
+    /// Resizes the focused window along the axis.
+    ///
+    /// Positive amounts grow the window and negative amounts shrink it. Tiled windows
+    /// take the space from their neighbors. The minimum and maximum size requested by the
+    /// client are respected.
+    pub fn resize(self, axis: Axis, amount: ResizeAmount) {
+        get!().resize(self, axis, amount)
+    }
+
     /// Sets the keymap of the seat.
     pub fn set_keymap(self, keymap: Keymap) {
         get!().seat_set_keymap(self, keymap)
@@ -300,6 +310,98 @@ impl Seat {
         self.set_mono(!self.mono());
     }
 
+    /// Returns whether the parent-container of the currently focused window is in
+    /// stacked-mode.
+    ///
+    /// In stacked-mode, only a single child is shown and the titles of all children are
+    /// listed on top of each other above it.
+    pub fn stacked(self) -> bool {
+        get!(false).stacked(self)
+    }
+
+    /// Sets whether the parent-container of the currently focused window is in
+    /// stacked-mode.
+    pub fn set_stacked(self, stacked: bool) {
+        get!().set_stacked(self, stacked)
+    }
+
+    /// Toggles whether the parent-container of the currently focused window is in
+    /// stacked-mode.
+    pub fn toggle_stacked(self) {
+        self.set_stacked(!self.stacked());
+    }
+
+    /// Cycles the parent-container of the currently focused window between split-mode,
+    /// mono-mode, and stacked-mode.
+    pub fn toggle_layout(self) {
+        get!().toggle_layout(self)
+    }
+
+    /// Adds a mark to the currently focused window.
+    ///
+    /// A mark identifies at most one window. If another window already has this mark, the
+    /// mark is removed from that window.
+    pub fn mark(self, mark: &str) {
+        get!().mark(self, mark)
+    }
+
+    /// Removes all marks from the currently focused window.
+    pub fn unmark(self) {
+        get!().unmark(self)
+    }
+
+    /// Returns the marks of the currently focused window.
+    pub fn marks(self) -> Vec<String> {
+        get!(vec![]).marks(self)
+    }
+
+    /// Focuses the window with the mark.
+    ///
+    /// This switches to the workspace of the window if necessary.
+    pub fn focus_mark(self, mark: &str) {
+        get!().focus_mark(self, mark)
+    }
+
+    /// Swaps the currently focused window with the window with the mark.
+    ///
+    /// Both windows must be tiled.
+    pub fn swap_with_mark(self, mark: &str) {
+        get!().swap_with_mark(self, mark)
+    }
+
+    /// Moves the currently focused window next to the window with the mark.
+    pub fn move_to_mark(self, mark: &str) {
+        get!().move_to_mark(self, mark)
+    }
+
+    /// Focuses the previously focused window.
+    ///
+    /// The focus history contains the windows of all workspaces and outputs. Repeated
+    /// invocations move further back in the history.
+    pub fn focus_previous(self) {
+        get!().focus_previous(self)
+    }
+
+    /// Undoes the last invocation of [`Seat::focus_previous`].
+    pub fn focus_next_in_history(self) {
+        get!().focus_next_in_history(self)
+    }
+
+    /// Opens the window switcher or selects the next window in it.
+    ///
+    /// The window switcher lists all windows, most recently focused first. The selected
+    /// window is focused once all modifiers have been released.
+    pub fn window_switcher(self) {
+        get!().window_switcher(self, false)
+    }
+
+    /// Opens the window switcher or selects the previous window in it.
+    ///
+    /// See [`Seat::window_switcher`].
+    pub fn window_switcher_reverse(self) {
+        get!().window_switcher(self, true)
+    }
+
     /// Returns the split axis of the parent-container of the currently focused window.
     pub fn split(self) -> Axis {
         get!(Axis::Horizontal).split(self)
@@ -351,6 +453,62 @@ impl Seat {
         get!().toggle_floating(self);
     }
 
+    /// Returns whether the currently focused window is sticky.
+    pub fn sticky(self) -> bool {
+        get!(false).sticky(self)
+    }
+
+    /// Sets whether the currently focused window is sticky.
+    ///
+    /// Sticky floating windows stay visible when the workspace of their output changes.
+    /// Tiled windows are not affected until they become floating. This overrides
+    /// [`set_sticky_app_ids`](crate::set_sticky_app_ids) for this window.
+    pub fn set_sticky(self, sticky: bool) {
+        get!().set_sticky(self, sticky)
+    }
+
+    /// Toggles whether the currently focused window is sticky.
+    pub fn toggle_sticky(self) {
+        self.set_sticky(!self.sticky());
+    }
+
+    /// Sets the opacity of the currently focused window.
+    ///
+    /// The opacity must be between `0.0` (fully transparent) and `1.0` (opaque). If
+    /// `opacity` is `None`, the opacity configured with
+    /// [`set_window_opacities`](crate::set_window_opacities) is used.
+    pub fn set_window_opacity(self, opacity: Option<f64>) {
+        get!().set_window_opacity(self, opacity)
+    }
+
+    /// Toggles whether the title bar of the currently focused window is hidden.
+    ///
+    /// This overrides the app-ids configured with
+    /// [`set_hidden_title_app_ids`](crate::set_hidden_title_app_ids).
+    pub fn toggle_title_hidden(self) {
+        get!().toggle_title_hidden(self)
+    }
+
+    /// Overrides the border of the currently focused window.
+    ///
+    /// Fields that are `None` use the values configured with
+    /// [`set_window_borders`](crate::set_window_borders) or the theme.
+    pub fn set_window_border(self, border: WindowBorder) {
+        get!().set_window_border(self, border)
+    }
+
+    /// Minimizes the currently focused window.
+    ///
+    /// Minimized windows are removed from the layout until they are restored.
+    pub fn minimize(self) {
+        get!().minimize(self)
+    }
+
+    /// Restores the most recently minimized window on the current workspace.
+    pub fn unminimize_last(self) {
+        get!().unminimize_last(self)
+    }
+
     /// Returns the workspace that is currently active on the output that contains the seat's
     /// cursor.
     ///
@@ -397,6 +555,76 @@ impl Seat {
         get!().move_to_output(WorkspaceSource::Seat(self), connector);
     }
 
+    /// Sets the layout of the workspace that is currently active on the output that
+    /// contains the seat's cursor.
+    pub fn set_workspace_layout(self, layout: WorkspaceLayout) {
+        get!().set_workspace_layout(WorkspaceSource::Seat(self), layout)
+    }
+
+    /// Returns the layout of the workspace that is currently active on the output that
+    /// contains the seat's cursor.
+    pub fn get_workspace_layout(self) -> WorkspaceLayout {
+        get!(WorkspaceLayout::MANUAL).get_workspace_layout(WorkspaceSource::Seat(self))
+    }
+
+    /// Switches the workspace that is currently active on the output that contains the
+    /// seat's cursor to the next layout.
+    pub fn cycle_workspace_layout(self) {
+        get!().cycle_workspace_layout(WorkspaceSource::Seat(self))
+    }
+
+    /// Writes the layout of the workspace that is currently active on the output that
+    /// contains the seat's cursor to a file.
+    ///
+    /// See [`Workspace::save_layout`](crate::Workspace::save_layout).
+    pub fn save_layout(self, path: &str) {
+        get!().save_layout(WorkspaceSource::Seat(self), path)
+    }
+
+    /// Adds the layout stored in a file to the workspace that is currently active on
+    /// the output that contains the seat's cursor.
+    ///
+    /// See [`Workspace::load_layout`](crate::Workspace::load_layout).
+    pub fn load_layout(self, path: &str) {
+        get!().load_layout(WorkspaceSource::Seat(self), path)
+    }
+
+    /// Sets the width of the focused column of a [`WorkspaceLayout::SCROLLING`]
+    /// workspace to the next width in the list of presets.
+    ///
+    /// See [`set_column_width_presets`](crate::set_column_width_presets).
+    pub fn cycle_column_width(self) {
+        get!().cycle_column_width(self)
+    }
+
+    /// Changes the number of windows in the master area of the workspace that is
+    /// currently active on the output that contains the seat's cursor.
+    pub fn adjust_master_count(self, delta: i32) {
+        let get = get!();
+        let ws = WorkspaceSource::Seat(self);
+        let count = get.get_workspace_master_count(ws);
+        get.set_workspace_master_count(ws, count.saturating_add_signed(delta));
+    }
+
+    /// Changes the fraction of the workspace width used by the master area of the
+    /// workspace that is currently active on the output that contains the seat's cursor.
+    pub fn adjust_master_ratio(self, delta: f64) {
+        let get = get!();
+        let ws = WorkspaceSource::Seat(self);
+        let ratio = get.get_workspace_master_ratio(ws);
+        get.set_workspace_master_ratio(ws, ratio + delta);
+    }
+
+    /// Moves the currently focused window to the master area of its workspace.
+    ///
+    /// If the window already is the first window of the workspace, it is swapped with
+    /// the second window instead.
+    ///
+    /// This has no effect if the workspace uses the [`WorkspaceLayout::MANUAL`] layout.
+    pub fn promote_to_master(self) {
+        get!().promote_to_master(self)
+    }
+
     /// Set whether the current key event is forwarded to the focused client.
     ///
     /// This only has an effect if called from a keyboard shortcut.
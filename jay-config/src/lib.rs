@@ -56,6 +56,7 @@ use {
 mod macros;
 #[doc(hidden)]
 pub mod _private;
+pub mod animation;
 pub mod embedded;
 pub mod exec;
 pub mod input;
@@ -95,6 +96,17 @@ impl Axis {
     }
 }
 
+/// An amount by which a window is resized.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ResizeAmount {
+    /// A number of logical pixels.
+    Px(i32),
+    /// A number of percentage points of the size of the parent container.
+    ///
+    /// For floating windows, this refers to the size of the workspace.
+    Ppt(i32),
+}
+
 /// Exits the compositor.
 pub fn quit() {
     get!().quit()
@@ -137,6 +149,22 @@ pub fn toggle_default_workspace_capture() {
     get.set_default_workspace_capture(!get.get_default_workspace_capture());
 }
 
+/// Sets the layout of newly created workspaces.
+///
+/// The default is [`WorkspaceLayout::MANUAL`].
+pub fn set_default_workspace_layout(layout: WorkspaceLayout) {
+    get!().set_default_workspace_layout(layout)
+}
+
+/// Sets the column widths used by [`Seat::cycle_column_width`](input::Seat::cycle_column_width).
+///
+/// The widths are fractions of the workspace width in the range `(0, 1]`.
+///
+/// The default is `[0.333, 0.5, 0.667]`.
+pub fn set_column_width_presets(presets: &[f64]) {
+    get!().set_column_width_presets(presets)
+}
+
 /// A workspace.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Workspace(pub u64);
@@ -171,6 +199,124 @@ impl Workspace {
     pub fn move_to_output(self, output: Connector) {
         get!().move_to_output(WorkspaceSource::Explicit(self), output);
     }
+
+    /// Sets the layout of the workspace.
+    ///
+    /// The default is determined by `set_default_workspace_layout`.
+    pub fn set_layout(self, layout: WorkspaceLayout) {
+        get!().set_workspace_layout(WorkspaceSource::Explicit(self), layout)
+    }
+
+    /// Returns the layout of the workspace.
+    pub fn get_layout(self) -> WorkspaceLayout {
+        get!(WorkspaceLayout::MANUAL).get_workspace_layout(WorkspaceSource::Explicit(self))
+    }
+
+    /// Switches the workspace to the next layout.
+    ///
+    /// The layouts are cycled in the order in which they are defined in
+    /// [`WorkspaceLayout`].
+    pub fn cycle_layout(self) {
+        get!().cycle_workspace_layout(WorkspaceSource::Explicit(self))
+    }
+
+    /// Sets the number of windows in the master area of the
+    /// [`WorkspaceLayout::MASTER_STACK`] layout.
+    ///
+    /// The default is `1`.
+    pub fn set_master_count(self, count: u32) {
+        get!().set_workspace_master_count(WorkspaceSource::Explicit(self), count)
+    }
+
+    /// Returns the number of windows in the master area.
+    pub fn get_master_count(self) -> u32 {
+        get!(1).get_workspace_master_count(WorkspaceSource::Explicit(self))
+    }
+
+    /// Sets the fraction of the workspace width used by the master area of the
+    /// [`WorkspaceLayout::MASTER_STACK`] layout.
+    ///
+    /// The value is clamped to the range `[0.1, 0.9]`. The default is `0.5`.
+    pub fn set_master_ratio(self, ratio: f64) {
+        get!().set_workspace_master_ratio(WorkspaceSource::Explicit(self), ratio)
+    }
+
+    /// Returns the fraction of the workspace width used by the master area.
+    pub fn get_master_ratio(self) -> f64 {
+        get!(0.5).get_workspace_master_ratio(WorkspaceSource::Explicit(self))
+    }
+
+    /// Writes the layout of the tiled windows of this workspace to a JSON file.
+    ///
+    /// The file can be loaded with [`Workspace::load_layout`]. Each window is saved
+    /// with its app-id as the criterion for the window that replaces it.
+    pub fn save_layout(self, path: &str) {
+        get!().save_layout(WorkspaceSource::Explicit(self), path)
+    }
+
+    /// Adds the layout stored in a JSON file to this workspace.
+    ///
+    /// The windows of the layout are created as placeholders. When a tiled window is
+    /// mapped that matches the criteria of a placeholder, the window replaces the
+    /// placeholder.
+    ///
+    /// The file contains a tree of nodes. Containers have the form
+    ///
+    /// ```json
+    /// {
+    ///   "type": "container",
+    ///   "split": "horizontal",
+    ///   "mono": false,
+    ///   "stacked": false,
+    ///   "factor": 1.0,
+    ///   "children": []
+    /// }
+    /// ```
+    ///
+    /// and windows have the form
+    ///
+    /// ```json
+    /// { "type": "window", "app_id": "firefox", "title": "Mozilla", "factor": 1.0 }
+    /// ```
+    ///
+    /// `factor` is the share of the parent container taken up by the node. The
+    /// app-id of a window must be equal to `app_id` and its title must contain
+    /// `title`. All fields except `type`, `split`, and `children` are optional.
+    ///
+    /// If the workspace does not exist, it is created.
+    pub fn load_layout(self, path: &str) {
+        get!().load_layout(WorkspaceSource::Explicit(self), path)
+    }
+}
+
+/// The layout of a workspace.
+///
+/// Except for [`WorkspaceLayout::MANUAL`], layouts arrange the tiled windows of the
+/// workspace automatically whenever windows are added or removed. The order of the
+/// windows is the order in which they appear in the tree.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct WorkspaceLayout(pub u32);
+
+impl WorkspaceLayout {
+    /// Windows are arranged manually by splitting containers.
+    pub const MANUAL: Self = Self(0);
+    /// The first windows are stacked on top of each other in the master area on the
+    /// left. The remaining windows are stacked on top of each other on the right.
+    pub const MASTER_STACK: Self = Self(1);
+    /// Each window takes up half of the remaining space, alternating between
+    /// horizontal and vertical splits while spiraling inwards.
+    pub const SPIRAL: Self = Self(2);
+    /// All windows are placed next to each other in columns of equal width.
+    pub const COLUMNS: Self = Self(3);
+    /// All windows are placed on top of each other in rows of equal height.
+    pub const ROWS: Self = Self(4);
+    /// Windows are placed in columns on an infinite horizontal strip that scrolls to
+    /// keep the focused column visible.
+    ///
+    /// New windows open in a new column to the right of the focused column. The width
+    /// of a column can be changed with [`Seat::cycle_column_width`](input::Seat::cycle_column_width)
+    /// and the resize actions.
+    pub const SCROLLING: Self = Self(5);
 }
 
 /// Returns the workspace with the given name.
@@ -244,6 +390,196 @@ pub fn set_ui_drag_enabled(enabled: bool) {
     get!().set_ui_drag_enabled(enabled);
 }
 
+/// Sets whether the marks of windows are shown in their title bars.
+///
+/// The default is `true`.
+pub fn set_show_marks(show: bool) {
+    get!().set_show_marks(show);
+}
+
+/// Sets the app-ids of terminals whose windows are swallowed by programs launched
+/// from them.
+///
+/// When a window is mapped whose process is a descendant of the process of a tiled
+/// terminal window, the window takes the place of the terminal in the tree. The
+/// terminal is shown again when the window is closed.
+///
+/// The default is `[]` which disables swallowing.
+pub fn set_swallow_terminals(app_ids: &[&str]) {
+    get!().set_swallow_terminals(app_ids);
+}
+
+/// Sets the app-ids of windows that never swallow a terminal.
+///
+/// See [`set_swallow_terminals`].
+///
+/// The default is `[]`.
+pub fn set_swallow_exceptions(app_ids: &[&str]) {
+    get!().set_swallow_exceptions(app_ids);
+}
+
+/// Sets the app-ids of windows whose background is blurred.
+///
+/// The contents below these windows are blurred before the windows are drawn so that
+/// translucent windows show a blurred background. The strength of the blur can be
+/// configured with the [`BLUR_PASSES`](theme::sized::BLUR_PASSES) and
+/// [`BLUR_OFFSET`](theme::sized::BLUR_OFFSET) theme sizes.
+///
+/// Clients can also request a blurred background for parts of their surfaces via the
+/// `ext-background-effect-v1` protocol.
+///
+/// Only the Vulkan renderer supports blurring.
+///
+/// The default is `[]`.
+pub fn set_blur_app_ids(app_ids: &[&str]) {
+    get!().set_blur_app_ids(app_ids);
+}
+
+/// Sets the namespaces of layer-shell surfaces whose background is blurred.
+///
+/// See [`set_blur_app_ids`].
+///
+/// The default is `[]`.
+pub fn set_blur_layer_namespaces(namespaces: &[&str]) {
+    get!().set_blur_layer_namespaces(namespaces);
+}
+
+/// Sets the opacity of windows by app-id.
+///
+/// Each entry maps an app-id to an opacity between `0.0` (fully transparent) and `1.0`
+/// (opaque). The opacity is applied to the contents of the window and combined with
+/// the opacity requested by the client. It can be overridden for individual windows
+/// with [`Seat::set_window_opacity`](input::Seat::set_window_opacity).
+///
+/// The default is `[]`.
+pub fn set_window_opacities(opacities: &[(&str, f64)]) {
+    get!().set_window_opacities(opacities);
+}
+
+/// Sets the format of the titles shown in title bars.
+///
+/// The following placeholders are replaced by the properties of the window:
+///
+/// - `{title}`: The title of the window.
+/// - `{app_id}`: The app-id of the window.
+/// - `{pid}`: The process id of the client that created the window.
+/// - `{xwayland}`: `[X] ` if the window is an Xwayland window.
+/// - `{marks}`: The marks of the window, each formatted as `[mark] `, if
+///   [`set_show_marks`] is enabled.
+///
+/// `{{` and `}}` are shown as `{` and `}`. Unknown placeholders are shown unchanged. If
+/// `format` is `None`, the title is shown with the marks in front of it.
+///
+/// The default is `None`.
+pub fn set_title_format(format: Option<&str>) {
+    get!().set_title_format(format);
+}
+
+/// Sets the app-ids of windows whose title bars are hidden while they are tiled.
+///
+/// Only the borders between these windows and their neighbors are shown. Title bars
+/// of tabbed and stacked containers are not affected. This can be overridden for
+/// individual windows with
+/// [`Seat::toggle_title_hidden`](input::Seat::toggle_title_hidden).
+///
+/// The default is `[]`.
+pub fn set_hidden_title_app_ids(app_ids: &[&str]) {
+    get!().set_hidden_title_app_ids(app_ids);
+}
+
+/// Sets the app-ids of windows that are sticky.
+///
+/// Sticky floating windows stay visible when the workspace of their output changes.
+/// This can be overridden for individual windows with
+/// [`Seat::set_sticky`](input::Seat::set_sticky).
+///
+/// The default is `[]`.
+pub fn set_sticky_app_ids(app_ids: &[&str]) {
+    get!().set_sticky_app_ids(app_ids);
+}
+
+/// Sets whether close, tile, and fullscreen buttons are shown in the title bars of
+/// floating windows.
+///
+/// The tile button makes the window tiled again.
+///
+/// The default is `false`.
+pub fn set_show_title_buttons(show: bool) {
+    get!().set_show_title_buttons(show);
+}
+
+/// Sets the borders of windows by app-id.
+///
+/// Each entry overrides the border color, the focused border color, or the border
+/// width of the windows with the app-id. The overrides can be replaced for individual
+/// windows with [`Seat::set_window_border`](input::Seat::set_window_border).
+///
+/// The default is `[]`.
+pub fn set_window_borders(borders: &[(&str, theme::WindowBorder)]) {
+    get!().set_window_borders(borders);
+}
+
+/// The position of the built-in bar on an output.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct BarPosition(pub u32);
+
+impl BarPosition {
+    /// The bar is shown at the top of the output.
+    pub const TOP: Self = Self(0);
+    /// The bar is shown at the bottom of the output.
+    pub const BOTTOM: Self = Self(1);
+}
+
+/// When the built-in bar is shown.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct BarMode(pub u32);
+
+impl BarMode {
+    /// The bar is always shown and the space it occupies is not used by windows.
+    pub const VISIBLE: Self = Self(0);
+    /// The bar is never shown.
+    ///
+    /// This is useful when an external bar is used.
+    pub const HIDDEN: Self = Self(1);
+    /// The bar is shown on top of the windows while the modifiers set with
+    /// [`set_bar_modifiers`] are held.
+    ///
+    /// Windows use the space of the bar while it is hidden.
+    pub const ON_MODIFIER: Self = Self(2);
+}
+
+/// Sets the position of the built-in bar.
+///
+/// The default is [`BarPosition::TOP`].
+pub fn set_bar_position(position: BarPosition) {
+    get!().set_bar_position(position);
+}
+
+/// Sets when the built-in bar is shown.
+///
+/// The default is [`BarMode::VISIBLE`].
+pub fn set_bar_mode(mode: BarMode) {
+    get!().set_bar_mode(mode);
+}
+
+/// Sets the modifiers that show the bar while they are held if the bar uses
+/// [`BarMode::ON_MODIFIER`].
+///
+/// The default is [`LOGO`](keyboard::mods::LOGO).
+pub fn set_bar_modifiers(modifiers: keyboard::mods::Modifiers) {
+    get!().set_bar_modifiers(modifiers);
+}
+
+/// Sets the connectors on which the built-in bar is shown.
+///
+/// Connectors are identified by their names, e.g. `DP-1`. The bar is hidden on all
+/// other connectors. If `connectors` is `None`, the bar is shown on all connectors.
+///
+/// The default is `None`.
+pub fn set_bar_connectors(connectors: Option<&[&str]>) {
+    get!().set_bar_connectors(connectors);
+}
+
 /// Sets the distance at which ui dragging starts.
 ///
 /// The default is `10`.
@@ -1,12 +1,22 @@
 //! Knobs for changing the status text.
 
+#[cfg(test)]
+mod tests;
+
 use {
-    crate::{exec::Command, io::Async, tasks::spawn},
+    crate::{
+        _private::ipc::{StatusBlock, StatusBlockAlign, StatusBlockMinWidth, StatusClick},
+        exec::Command,
+        io::Async,
+        keyboard::mods::{CTRL, LOCK, MOD1, MOD2, MOD3, MOD4, MOD5, SHIFT},
+        tasks::spawn,
+        theme::Color,
+    },
     bstr::ByteSlice,
     error_reporter::Report,
     futures_util::{io::BufReader, AsyncBufReadExt},
-    serde::Deserialize,
-    std::borrow::BorrowMut,
+    serde::{Deserialize, Serialize},
+    std::{borrow::BorrowMut, cell::RefCell, rc::Rc},
     uapi::{c, OwnedFd},
 };
 
@@ -40,6 +50,9 @@ pub enum MessageFormat {
     ///
     /// The separator between individual components can be set using [`set_i3bar_separator`].
     ///
+    /// If the command enables `click_events` in its header, clicks on the blocks of the
+    /// status are written to the stdin of the command.
+    ///
     /// [i3bar]: https://github.com/i3/i3/blob/next/docs/i3bar-protocol
     I3Bar,
 }
@@ -48,7 +61,9 @@ pub enum MessageFormat {
 ///
 /// The [`stdout`](Command::stdout) and [`stderr`](Command::stderr)` of the command will
 /// be overwritten by this function. The stdout will be used for the status text and the
-/// stderr will be appended to the compositor log.
+/// stderr will be appended to the compositor log. If the format is
+/// [`MessageFormat::I3Bar`], the [`stdin`](Command::stdin) of the command will also be
+/// overwritten and used for click events.
 ///
 /// The format of stdout is determined by the `format` parameter.
 pub fn set_status_command(format: MessageFormat, mut command: impl BorrowMut<Command>) {
@@ -74,6 +89,24 @@ pub fn set_status_command(format: MessageFormat, mut command: impl BorrowMut<Com
     let (mut read, write) = pipe!();
     let (mut stderr_read, stderr_write) = pipe!();
     let command = command.borrow_mut();
+    let mut clicks = None;
+    if format == MessageFormat::I3Bar {
+        let (stdin_read, stdin_write) = match uapi::pipe2(c::O_CLOEXEC) {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Could not create a pipe: {}", Report::new(e));
+                return;
+            }
+        };
+        // Click events are written without blocking. They are dropped if the command
+        // does not read its stdin.
+        if let Err(e) = uapi::fcntl_setfl(stdin_write.raw(), c::O_NONBLOCK) {
+            log::error!("Could not make the pipe non-blocking: {}", Report::new(e));
+            return;
+        }
+        command.stdin(stdin_read);
+        clicks = Some(stdin_write);
+    }
     command.stdout(write).stderr(stderr_write).spawn();
     let name = command.prog.clone();
     let name2 = command.prog.clone();
@@ -96,7 +129,7 @@ pub fn set_status_command(format: MessageFormat, mut command: impl BorrowMut<Com
     });
     let handle = spawn(async move {
         if format == MessageFormat::I3Bar {
-            handle_i3bar(name, read).await;
+            handle_i3bar(name, read, clicks).await;
             return;
         }
         let mut line = String::new();
@@ -130,19 +163,26 @@ pub fn unset_status_command() {
 
 /// Sets the separator for i3bar status commands.
 ///
-/// The separator should be specified in [pango][pango] markup language.
+/// The separator should be specified in [pango][pango] markup language. It is shown
+/// between two blocks unless the left block sets `separator` to `false`.
 ///
 /// [pango]: https://docs.gtk.org/Pango/pango_markup.html
 pub fn set_i3bar_separator(separator: &str) {
     get!().set_i3bar_separator(separator);
 }
 
-async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
-    use std::fmt::Write;
-
+async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>, clicks: Option<OwnedFd>) {
     #[derive(Deserialize)]
-    struct Version {
+    struct Header {
         version: i32,
+        #[serde(default)]
+        click_events: bool,
+    }
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MinWidth {
+        Pixels(i32),
+        Text(String),
     }
     #[derive(Deserialize)]
     struct Component {
@@ -150,6 +190,18 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
         full_text: String,
         color: Option<String>,
         background: Option<String>,
+        border: Option<String>,
+        border_top: Option<i32>,
+        border_right: Option<i32>,
+        border_bottom: Option<i32>,
+        border_left: Option<i32>,
+        min_width: Option<MinWidth>,
+        align: Option<String>,
+        urgent: Option<bool>,
+        name: Option<String>,
+        instance: Option<String>,
+        separator: Option<bool>,
+        separator_block_width: Option<i32>,
     }
     let mut line = String::new();
     macro_rules! read_line {
@@ -166,9 +218,8 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
         }};
     }
     read_line!();
-    match serde_json::from_str::<Version>(&line) {
-        Ok(v) if v.version == 1 => {}
-        Ok(v) => log::warn!("Unexpected i3bar format version: {}", v.version),
+    let header = match serde_json::from_str::<Header>(&line) {
+        Ok(h) => h,
         Err(e) => {
             log::warn!(
                 "Could not deserialize i3bar version message: {}",
@@ -176,9 +227,38 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
             );
             return;
         }
+    };
+    if header.version != 1 {
+        log::warn!("Unexpected i3bar format version: {}", header.version);
+    }
+    let ids = Rc::new(RefCell::new(Vec::<(Option<String>, Option<String>)>::new()));
+    if let Some(clicks) = clicks.filter(|_| header.click_events) {
+        let ids = ids.clone();
+        let mut first = true;
+        get!().on_status_click(move |click| {
+            let ids = ids.borrow();
+            let Some((name, instance)) = ids.get(click.block) else {
+                return;
+            };
+            let mut msg = match first {
+                true => "[\n".to_string(),
+                false => ",".to_string(),
+            };
+            msg.push_str(&i3bar_click_event(name, instance, &click));
+            msg.push('\n');
+            // Writes of at most PIPE_BUF bytes to a pipe are atomic. An event is
+            // therefore either written completely or, if the pipe is full, not at all.
+            if msg.len() > c::PIPE_BUF {
+                log::warn!("Dropping a click event of {} bytes", msg.len());
+                return;
+            }
+            match uapi::write(clicks.raw(), msg.as_bytes()) {
+                Ok(_) => first = false,
+                Err(e) => log::warn!("Could not write a click event: {}", Report::new(e)),
+            }
+        });
     }
     read_line!();
-    let mut status = String::new();
     loop {
         read_line!();
         let mut line = line.as_str();
@@ -200,38 +280,118 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
             Some(s) => s.as_str(),
             _ => r##" <span color="#333333">|</span> "##,
         };
-        status.clear();
-        let mut first = true;
-        for component in &components {
+        let mut blocks = vec![];
+        let mut ids = RefCell::borrow_mut(&ids);
+        ids.clear();
+        for component in components {
             if component.full_text.is_empty() {
                 continue;
             }
-            if !first {
-                status.push_str(separator);
-            }
-            first = false;
-            let have_span = component.color.is_some() || component.background.is_some();
-            if have_span {
-                status.push_str("<span");
-                if let Some(color) = &component.color {
-                    let _ = write!(status, r#" color="{color}""#);
-                }
-                if let Some(color) = &component.background {
-                    let _ = write!(status, r#" bgcolor="{color}""#);
-                }
-                status.push_str(">");
-            }
-            if component.markup.as_deref() == Some("pango")
-                || !escape_pango(&component.full_text, &mut status)
-            {
-                status.push_str(&component.full_text);
-            }
-            if have_span {
-                status.push_str("</span>");
-            }
+            let border_width = |w: Option<i32>| match component.border {
+                Some(_) => w.unwrap_or(1).max(0),
+                None => 0,
+            };
+            let align = match component.align.as_deref() {
+                Some("center") => StatusBlockAlign::Center,
+                Some("right") => StatusBlockAlign::Right,
+                _ => StatusBlockAlign::Left,
+            };
+            blocks.push(StatusBlock {
+                markup: component.markup.as_deref() == Some("pango"),
+                color: component.color.as_deref().and_then(parse_color),
+                background: component.background.as_deref().and_then(parse_color),
+                border: component.border.as_deref().and_then(parse_color),
+                border_widths: [
+                    border_width(component.border_top),
+                    border_width(component.border_right),
+                    border_width(component.border_bottom),
+                    border_width(component.border_left),
+                ],
+                min_width: component.min_width.map(|w| match w {
+                    MinWidth::Pixels(p) => StatusBlockMinWidth::Pixels(p),
+                    MinWidth::Text(t) => StatusBlockMinWidth::Text(t),
+                }),
+                align,
+                urgent: component.urgent.unwrap_or(false),
+                separator: component.separator.unwrap_or(true),
+                separator_block_width: component.separator_block_width.unwrap_or(9).max(0),
+                text: component.full_text,
+            });
+            ids.push((component.name, component.instance));
         }
-        set_status(&status);
+        drop(ids);
+        get!().set_status_blocks(blocks, separator);
+    }
+}
+
+fn i3bar_click_event(
+    name: &Option<String>,
+    instance: &Option<String>,
+    click: &StatusClick,
+) -> String {
+    #[derive(Serialize)]
+    struct ClickEvent<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        instance: Option<&'a str>,
+        button: u32,
+        modifiers: Vec<&'static str>,
+        x: i32,
+        y: i32,
+        relative_x: i32,
+        relative_y: i32,
+        output_x: i32,
+        output_y: i32,
+        width: i32,
+        height: i32,
+        scale: f64,
     }
+    let names = [
+        (SHIFT, "Shift"),
+        (LOCK, "Lock"),
+        (CTRL, "Control"),
+        (MOD1, "Mod1"),
+        (MOD2, "Mod2"),
+        (MOD3, "Mod3"),
+        (MOD4, "Mod4"),
+        (MOD5, "Mod5"),
+    ];
+    let modifiers = names
+        .iter()
+        .filter(|(m, _)| click.modifiers.0 & m.0 != 0)
+        .map(|(_, n)| *n)
+        .collect();
+    let event = ClickEvent {
+        name: name.as_deref(),
+        instance: instance.as_deref(),
+        button: click.button,
+        modifiers,
+        x: click.x,
+        y: click.y,
+        relative_x: click.relative_x,
+        relative_y: click.relative_y,
+        output_x: click.output_x,
+        output_y: click.output_y,
+        width: click.width,
+        height: click.height,
+        scale: click.scale,
+    };
+    serde_json::to_string(&event).unwrap()
+}
+
+fn parse_color(s: &str) -> Option<Color> {
+    let hex = s.strip_prefix("#")?;
+    let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    let color = match hex.len() {
+        6 => Color::new(byte(0)?, byte(2)?, byte(4)?),
+        8 => Color::new_straight(byte(0)?, byte(2)?, byte(4)?, byte(6)?),
+        _ => {
+            log::warn!("Could not parse i3bar color `{s}`");
+            return None;
+        }
+    };
+    Some(color)
 }
 
 fn escape_pango(src: &str, dst: &mut String) -> bool {
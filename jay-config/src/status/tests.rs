@@ -0,0 +1,76 @@
+use crate::{
+    _private::ipc::StatusClick,
+    keyboard::mods::{Modifiers, CTRL, MOD4, SHIFT},
+    status::{i3bar_click_event, parse_color},
+};
+
+fn click(modifiers: Modifiers) -> StatusClick {
+    StatusClick {
+        block: 0,
+        button: 1,
+        modifiers,
+        x: 110,
+        y: 5,
+        relative_x: 10,
+        relative_y: 5,
+        output_x: 110,
+        output_y: 5,
+        width: 40,
+        height: 20,
+        scale: 1.5,
+    }
+}
+
+#[test]
+fn click_event() {
+    let event = i3bar_click_event(
+        &Some("clock".to_string()),
+        &Some("local".to_string()),
+        &click(SHIFT | MOD4),
+    );
+    let event: serde_json::Value = serde_json::from_str(&event).unwrap();
+    assert_eq!(
+        event,
+        serde_json::json!({
+            "name": "clock",
+            "instance": "local",
+            "button": 1,
+            "modifiers": ["Shift", "Mod4"],
+            "x": 110,
+            "y": 5,
+            "relative_x": 10,
+            "relative_y": 5,
+            "output_x": 110,
+            "output_y": 5,
+            "width": 40,
+            "height": 20,
+            "scale": 1.5,
+        }),
+    );
+}
+
+#[test]
+fn click_event_without_ids() {
+    let event = i3bar_click_event(&None, &None, &click(CTRL));
+    let event: serde_json::Value = serde_json::from_str(&event).unwrap();
+    assert!(event.get("name").is_none());
+    assert!(event.get("instance").is_none());
+    assert_eq!(event["modifiers"], serde_json::json!(["Control"]));
+}
+
+#[test]
+fn colors() {
+    let rgba = |s| parse_color(s).map(|c| c.to_u8_straight());
+    assert_eq!(rgba("#ff8000"), Some([255, 128, 0, 255]));
+    assert_eq!(rgba("#FF800080"), Some([255, 128, 0, 128]));
+    assert_eq!(rgba("#000000ff"), Some([0, 0, 0, 255]));
+}
+
+#[test]
+fn invalid_colors() {
+    assert!(parse_color("ff8000").is_none());
+    assert!(parse_color("#ff80").is_none());
+    assert!(parse_color("#ff80001").is_none());
+    assert!(parse_color("#gg8000").is_none());
+    assert!(parse_color("#ff80é").is_none());
+}
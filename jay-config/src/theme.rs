@@ -123,6 +123,22 @@ impl Color {
     }
 }
 
+/// Overrides of the border of a window.
+///
+/// Fields that are `None` use the values of the theme.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Default)]
+pub struct WindowBorder {
+    /// The color of the border.
+    pub color: Option<Color>,
+    /// The color of the border while the window has the keyboard focus.
+    pub focused_color: Option<Color>,
+    /// The width of the border.
+    ///
+    /// Tiled windows share the borders between them, so this only affects floating
+    /// windows.
+    pub width: Option<i32>,
+}
+
 /// Resets all sizes to their defaults.
 pub fn reset_sizes() {
     get!().reset_sizes();
@@ -149,9 +165,53 @@ pub fn set_font(font: &str) {
     get!().set_font(font)
 }
 
-/// Resets the font to the default.
+/// Sets the font used for the titles of windows and in the window switcher.
+///
+/// If this is not set, the font set with [`set_font`] is used.
+///
+/// The font name should be specified in [pango][pango] syntax.
+///
+/// [pango]: https://docs.gtk.org/Pango/type_func.FontDescription.from_string.html
+pub fn set_title_font(font: &str) {
+    get!().set_title_font(font)
+}
+
+/// Sets the font used for the workspace names in the bar.
+///
+/// If this is not set, the font set with [`set_font`] is used.
+///
+/// The font name should be specified in [pango][pango] syntax.
+///
+/// [pango]: https://docs.gtk.org/Pango/type_func.FontDescription.from_string.html
+pub fn set_bar_font(font: &str) {
+    get!().set_bar_font(font)
+}
+
+/// Sets the font used for the status text in the bar.
+///
+/// If this is not set, the font set with [`set_font`] is used.
+///
+/// The font name should be specified in [pango][pango] syntax.
+///
+/// [pango]: https://docs.gtk.org/Pango/type_func.FontDescription.from_string.html
+pub fn set_status_font(font: &str) {
+    get!().set_status_font(font)
+}
+
+/// Sets the font families that are used for characters that are not available in the
+/// configured fonts.
+///
+/// The families are tried in order. For example, `&["Noto Color Emoji"]` can be used
+/// to display emoji in the status text.
+///
+/// Default: none.
+pub fn set_font_fallbacks(fonts: &[&str]) {
+    get!().set_font_fallbacks(fonts)
+}
+
+/// Resets all fonts and the font fallbacks to the defaults.
 ///
-/// Currently the default is `monospace 8`.
+/// Currently the default font is `monospace 8`.
 pub fn reset_font() {
     get!().reset_font()
 }
@@ -261,6 +321,25 @@ pub mod colors {
         ///
         /// Default: `#9d28c67f`.
         const 15 => HIGHLIGHT_COLOR,
+        /// The color of the shadows drawn below floating windows.
+        ///
+        /// Default: `#0000007f`.
+        const 16 => SHADOW_COLOR,
+        /// The color drawn over windows that do not have the keyboard focus.
+        ///
+        /// The alpha channel of this color determines how much inactive windows are
+        /// dimmed.
+        ///
+        /// Default: `#00000000`.
+        const 17 => INACTIVE_DIM_COLOR,
+        /// The background color of title bar buttons below the cursor.
+        ///
+        /// Default: `#ffffff33`.
+        const 18 => TITLE_BUTTON_HOVER_COLOR,
+        /// The color of the borders of windows that have the keyboard focus.
+        ///
+        /// Default: The [`BORDER_COLOR`].
+        const 19 => FOCUSED_BORDER_COLOR,
     }
 
     /// Sets the color of GUI element.
@@ -312,5 +391,31 @@ pub mod sized {
         ///
         /// Default: 4
         const 02 => BORDER_WIDTH,
+        /// The radius of the rounded corners of windows.
+        ///
+        /// Default: 0
+        const 03 => CORNER_RADIUS,
+        /// The blur radius of the shadows drawn below floating windows.
+        ///
+        /// Default: 0
+        const 04 => SHADOW_BLUR,
+        /// The horizontal offset of the shadows drawn below floating windows.
+        ///
+        /// Default: 0
+        const 05 => SHADOW_OFFSET_X,
+        /// The vertical offset of the shadows drawn below floating windows.
+        ///
+        /// Default: 0
+        const 06 => SHADOW_OFFSET_Y,
+        /// The number of downsampling passes of the background blur.
+        ///
+        /// Larger values produce a stronger blur. `0` disables the background blur.
+        ///
+        /// Default: 3
+        const 07 => BLUR_PASSES,
+        /// The sampling distance of the background blur.
+        ///
+        /// Default: 2
+        const 08 => BLUR_OFFSET,
     }
 }
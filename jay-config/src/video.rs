@@ -268,6 +268,21 @@ impl Connector {
         get!().set_tearing_mode(Some(self), mode)
     }
 
+    /// Sets the wallpaper of the connector.
+    ///
+    /// The image must be a PNG or QOI file. The previous wallpaper stays visible until
+    /// the new image has been loaded.
+    pub fn set_wallpaper(self, path: &str, mode: WallpaperMode) {
+        get!().connector_set_wallpaper(self, Some(path), mode)
+    }
+
+    /// Removes the wallpaper of the connector.
+    ///
+    /// The background color is shown instead.
+    pub fn unset_wallpaper(self) {
+        get!().connector_set_wallpaper(self, None, WallpaperMode::FILL)
+    }
+
     /// Sets the format to use for framebuffers.
     pub fn set_format(self, format: Format) {
         get!().connector_set_format(self, format);
@@ -566,6 +581,25 @@ pub enum Transform {
     FlipRotate270,
 }
 
+/// How a wallpaper is scaled to the size of an output.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct WallpaperMode(pub u32);
+
+impl WallpaperMode {
+    /// The image is scaled to cover the output. Parts of the image that overflow the
+    /// output are cropped.
+    pub const FILL: Self = Self(0);
+    /// The image is scaled to fit into the output.
+    pub const FIT: Self = Self(1);
+    /// The image is shown unscaled in the center of the output.
+    pub const CENTER: Self = Self(2);
+    /// The unscaled image is repeated to cover the output.
+    ///
+    /// If this would require more than 4096 copies of the image, the image is shown as
+    /// with [`FILL`](Self::FILL) instead.
+    pub const TILE: Self = Self(3);
+}
+
 /// The VRR mode of a connector.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
 pub struct VrrMode(pub u32);
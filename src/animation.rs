@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        gfx_api::{GfxTexture, SampleRect},
+        ifs::wl_surface::SurfaceBuffer,
+        rect::Rect,
+        tree::WorkspaceNode,
+    },
+    jay_config::animation::{
+        AnimationCurve as ConfigAnimationCurve, AnimationKind as ConfigAnimationKind,
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AnimationKind {
+    Open,
+    Close,
+    Tile,
+    Workspace,
+    Float,
+}
+
+const NUM_KINDS: usize = 5;
+
+impl AnimationKind {
+    pub fn from_config(kind: ConfigAnimationKind) -> Option<Self> {
+        let res = match kind {
+            ConfigAnimationKind::OPEN => Self::Open,
+            ConfigAnimationKind::CLOSE => Self::Close,
+            ConfigAnimationKind::TILE => Self::Tile,
+            ConfigAnimationKind::WORKSPACE => Self::Workspace,
+            ConfigAnimationKind::FLOAT => Self::Float,
+            _ => return None,
+        };
+        Some(res)
+    }
+}
+
+/// A cubic Bézier curve from (0, 0) to (1, 1) as used by CSS transitions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AnimationCurve {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl AnimationCurve {
+    pub const LINEAR: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+    pub const EASE: Self = Self::new(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN: Self = Self::new(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: Self = Self::new(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: Self = Self::new(0.42, 0.0, 0.58, 1.0);
+
+    const fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Self { x1, y1, x2, y2 }
+    }
+
+    pub fn from_config(curve: ConfigAnimationCurve) -> Option<Self> {
+        let res = match curve {
+            ConfigAnimationCurve::Linear => Self::LINEAR,
+            ConfigAnimationCurve::Ease => Self::EASE,
+            ConfigAnimationCurve::EaseIn => Self::EASE_IN,
+            ConfigAnimationCurve::EaseOut => Self::EASE_OUT,
+            ConfigAnimationCurve::EaseInOut => Self::EASE_IN_OUT,
+            ConfigAnimationCurve::CubicBezier(x1, y1, x2, y2) => {
+                let valid_x = |x: f64| (0.0..=1.0).contains(&x);
+                if !valid_x(x1) || !valid_x(x2) || !y1.is_finite() || !y2.is_finite() {
+                    return None;
+                }
+                Self::new(x1, y1, x2, y2)
+            }
+        };
+        Some(res)
+    }
+
+    fn bezier(p1: f64, p2: f64, t: f64) -> f64 {
+        let u = 1.0 - t;
+        3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+    }
+
+    fn bezier_derivative(p1: f64, p2: f64, t: f64) -> f64 {
+        let u = 1.0 - t;
+        3.0 * u * u * p1 + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (1.0 - p2)
+    }
+
+    /// Maps the elapsed fraction of the duration to the progress of the animation.
+    pub fn apply(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        if x >= 1.0 {
+            return 1.0;
+        }
+        let mut t = x;
+        for _ in 0..8 {
+            let dx = Self::bezier(self.x1, self.x2, t) - x;
+            if dx.abs() < 1e-6 {
+                return Self::bezier(self.y1, self.y2, t);
+            }
+            let d = Self::bezier_derivative(self.x1, self.x2, t);
+            if d.abs() < 1e-6 {
+                break;
+            }
+            t -= dx / d;
+        }
+        let (mut lo, mut hi) = (0.0, 1.0);
+        t = x;
+        for _ in 0..32 {
+            let v = Self::bezier(self.x1, self.x2, t);
+            if (v - x).abs() < 1e-6 {
+                break;
+            }
+            if v < x {
+                lo = t;
+            } else {
+                hi = t;
+            }
+            t = (lo + hi) / 2.0;
+        }
+        Self::bezier(self.y1, self.y2, t)
+    }
+}
+
+struct AnimationSettings {
+    duration_nsec: Cell<u64>,
+    curve: Cell<AnimationCurve>,
+}
+
+pub struct Animations {
+    pub enabled: Cell<bool>,
+    pub in_screencasts: Cell<bool>,
+    settings: [AnimationSettings; NUM_KINDS],
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        let settings = |duration_msec: u64| AnimationSettings {
+            duration_nsec: Cell::new(duration_msec * 1_000_000),
+            curve: Cell::new(AnimationCurve::EASE_OUT),
+        };
+        Self {
+            enabled: Cell::new(false),
+            in_screencasts: Cell::new(true),
+            settings: [
+                settings(150),
+                settings(150),
+                settings(150),
+                settings(200),
+                settings(150),
+            ],
+        }
+    }
+}
+
+impl Animations {
+    pub fn set_duration(&self, kind: AnimationKind, duration_nsec: u64) {
+        self.settings[kind as usize]
+            .duration_nsec
+            .set(duration_nsec);
+    }
+
+    pub fn set_curve(&self, kind: AnimationKind, curve: AnimationCurve) {
+        self.settings[kind as usize].curve.set(curve);
+    }
+
+    /// Creates an animation starting at `now`.
+    ///
+    /// Returns `None` if animations are disabled or the duration of the animation is 0.
+    pub fn create(&self, kind: AnimationKind, now: u64) -> Option<Animation> {
+        if !self.enabled.get() {
+            return None;
+        }
+        let settings = &self.settings[kind as usize];
+        let duration = settings.duration_nsec.get();
+        if duration == 0 {
+            return None;
+        }
+        Some(Animation {
+            start: now,
+            duration,
+            curve: settings.curve.get(),
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Animation {
+    start: u64,
+    duration: u64,
+    curve: AnimationCurve,
+}
+
+impl Animation {
+    pub fn end(&self) -> u64 {
+        self.start.saturating_add(self.duration)
+    }
+
+    /// Returns the progress of the animation in `[0, 1)` or `None` if the animation has
+    /// finished.
+    pub fn progress(&self, now: u64) -> Option<f64> {
+        if now >= self.end() {
+            return None;
+        }
+        let elapsed = now.saturating_sub(self.start) as f64 / self.duration as f64;
+        Some(self.curve.apply(elapsed))
+    }
+}
+
+/// Moves a rectangle from `from` towards `to`.
+pub fn interpolate_rect(from: Rect, to: Rect, progress: f64) -> Rect {
+    let lerp = |a: i32, b: i32| a + ((b - a) as f64 * progress).round() as i32;
+    Rect::new(
+        lerp(from.x1(), to.x1()),
+        lerp(from.y1(), to.y1()),
+        lerp(from.x2(), to.x2()),
+        lerp(from.y2(), to.y2()),
+    )
+    .unwrap_or(to)
+}
+
+/// A window that moves from `from` to its current position.
+#[derive(Copy, Clone, Debug)]
+pub struct MoveAnimation {
+    pub animation: Animation,
+    pub from: Rect,
+}
+
+/// The workspace that was previously shown on an output and slides out of view.
+pub struct WorkspaceAnimation {
+    pub animation: Animation,
+    pub old: Rc<WorkspaceNode>,
+    /// `1` if the new workspace comes in from the right, `-1` if it comes in from the
+    /// left.
+    pub direction: i32,
+}
+
+/// The last contents of an unmapped window that fade out.
+pub struct ClosingWindow {
+    pub animation: Animation,
+    pub buffer: Rc<SurfaceBuffer>,
+    pub texture: Rc<dyn GfxTexture>,
+    pub tpoints: SampleRect,
+    /// The absolute position of the buffer.
+    pub rect: Rect,
+    /// The absolute area that the buffer is clipped to.
+    pub bounds: Rect,
+}
@@ -0,0 +1,77 @@
+use crate::{
+    animation::{interpolate_rect, AnimationCurve},
+    rect::Rect,
+};
+
+const CURVES: [AnimationCurve; 5] = [
+    AnimationCurve::LINEAR,
+    AnimationCurve::EASE,
+    AnimationCurve::EASE_IN,
+    AnimationCurve::EASE_OUT,
+    AnimationCurve::EASE_IN_OUT,
+];
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-3, "{a} != {b}");
+}
+
+#[test]
+fn endpoints() {
+    for curve in CURVES {
+        assert_eq!(curve.apply(-1.0), 0.0);
+        assert_eq!(curve.apply(0.0), 0.0);
+        assert_eq!(curve.apply(1.0), 1.0);
+        assert_eq!(curve.apply(2.0), 1.0);
+    }
+}
+
+#[test]
+fn linear() {
+    for x in [0.1, 0.25, 0.5, 0.75, 0.9] {
+        assert_close(AnimationCurve::LINEAR.apply(x), x);
+    }
+}
+
+#[test]
+fn monotonic() {
+    for curve in CURVES {
+        let mut prev = 0.0;
+        for i in 1..=100 {
+            let y = curve.apply(i as f64 / 100.0);
+            assert!(y >= prev - 1e-6);
+            prev = y;
+        }
+    }
+}
+
+#[test]
+fn known_values() {
+    // The ease-in-out curve is symmetric around its midpoint.
+    assert_close(AnimationCurve::EASE_IN_OUT.apply(0.5), 0.5);
+    assert_close(
+        AnimationCurve::EASE_IN_OUT.apply(0.2) + AnimationCurve::EASE_IN_OUT.apply(0.8),
+        1.0,
+    );
+    assert!(AnimationCurve::EASE_IN.apply(0.5) < 0.5);
+    assert!(AnimationCurve::EASE_OUT.apply(0.5) > 0.5);
+}
+
+#[test]
+fn interpolate() {
+    let from = Rect::new(0, 0, 100, 100).unwrap();
+    let to = Rect::new(100, 50, 300, 150).unwrap();
+    assert_eq!(interpolate_rect(from, to, 0.0), from);
+    assert_eq!(interpolate_rect(from, to, 1.0), to);
+    assert_eq!(
+        interpolate_rect(from, to, 0.5),
+        Rect::new(50, 25, 200, 125).unwrap(),
+    );
+}
+
+#[test]
+fn interpolate_invalid() {
+    let from = Rect::new(0, 0, 100, 100).unwrap();
+    let to = Rect::new(10, 10, 20, 20).unwrap();
+    // Overshooting curves can produce progress values outside of [0, 1].
+    assert_eq!(interpolate_rect(from, to, 20.0), to);
+}
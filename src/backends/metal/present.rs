@@ -527,7 +527,7 @@ impl MetalConnector {
                 for opt in &mut ops {
                     match opt {
                         GfxApiOpt::Sync => {}
-                        GfxApiOpt::FillRect(_) => {
+                        GfxApiOpt::FillRect(_) | GfxApiOpt::Blur(_) => {
                             // Top-most layer must be a texture.
                             return None;
                         }
@@ -540,6 +540,10 @@ impl MetalConnector {
                 // Direct scanout with alpha factor is not supported.
                 return None;
             }
+            if ct.clip.is_some() {
+                // Direct scanout with rounded corners is not supported.
+                return None;
+            }
             if !ct.tex.format().has_alpha && ct.target.is_covering() {
                 // Texture covers the entire screen and is opaque.
                 break 'ct ct;
@@ -548,7 +552,7 @@ impl MetalConnector {
                 match opt {
                     GfxApiOpt::Sync => {}
                     GfxApiOpt::FillRect(fr) => {
-                        if fr.color == Color::SOLID_BLACK {
+                        if fr.color == Color::SOLID_BLACK && fr.clip.is_none() {
                             // Black fills can be ignored because this is the CRTC background color.
                             if fr.rect.is_covering() {
                                 // If fill covers the entire screen, we don't have to look further.
@@ -563,6 +567,10 @@ impl MetalConnector {
                         // Texture could be visible.
                         return None;
                     }
+                    GfxApiOpt::Blur(_) => {
+                        // Blurred contents could be visible.
+                        return None;
+                    }
                 }
             }
             if let Some(clear) = pass.clear {
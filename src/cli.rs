@@ -7,6 +7,7 @@ mod input;
 mod log;
 mod quit;
 mod randr;
+mod record;
 mod run_privileged;
 pub mod screenshot;
 mod seat_test;
@@ -18,7 +19,7 @@ use {
     crate::{
         cli::{
             damage_tracking::DamageTrackingArgs, input::InputArgs, randr::RandrArgs,
-            xwayland::XwaylandArgs,
+            record::RecordArgs, xwayland::XwaylandArgs,
         },
         compositor::start_compositor,
         format::{ref_formats, Format},
@@ -61,6 +62,8 @@ pub enum Cmd {
     Unlock,
     /// Take a screenshot.
     Screenshot(ScreenshotArgs),
+    /// Record an output, workspace, or window to a file.
+    Record(RecordArgs),
     /// Inspect/modify the idle (screensaver) settings.
     Idle(IdleArgs),
     /// Run a privileged program.
@@ -257,6 +260,7 @@ pub fn main() {
         Cmd::Quit => quit::main(cli.global),
         Cmd::SetLogLevel(a) => set_log_level::main(cli.global, a),
         Cmd::Screenshot(a) => screenshot::main(cli.global, a),
+        Cmd::Record(a) => record::main(cli.global, a),
         Cmd::Idle(a) => idle::main(cli.global, a),
         Cmd::Unlock => unlock::main(cli.global),
         Cmd::RunPrivileged(a) => run_privileged::main(cli.global, a),
@@ -0,0 +1,772 @@
+use {
+    crate::{
+        allocator::BufferObject,
+        cli::{screenshot::import_dmabuf_unmapped, GlobalArgs},
+        format::formats,
+        object::WL_DISPLAY_ID,
+        time::Time,
+        tools::tool_client::{with_tool_client, Handle, ToolClient},
+        utils::{
+            asyncevent::AsyncEvent, buf::TypedBuf, clonecell::CloneCell, errorfmt::ErrorFmt,
+            queue::AsyncQueue,
+        },
+        video::dmabuf::{DmaBuf, DmaBufIds, DmaBufPlane, PlaneVec},
+        wire::{
+            jay_compositor, jay_render_ctx, jay_screencast, jay_select_toplevel,
+            jay_select_workspace, jay_workspace, jay_workspace_watcher, wl_display, wl_output,
+            wl_registry, wl_seat, JayCompositorId, JayOutputId, JayScreencastId, JayToplevelId,
+            JayWorkspaceId, WlOutput, WlOutputId, WlRegistryId, WlSeat, WlSeatId,
+        },
+    },
+    chrono::Local,
+    clap::{Args, Subcommand, ValueEnum},
+    jay_algorithms::{
+        qoi::xrgb8888_encode_qoi,
+        y4m::{xrgb8888_encode_y4m_frame, y4m_header},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        fs::File,
+        io::{BufWriter, Write},
+        mem,
+        path::PathBuf,
+        rc::Rc,
+        time::Duration,
+    },
+    uapi::{c, OwnedFd},
+};
+
+/// Screencasts created from jay_compositor objects with a version lower than this use
+/// compositor-allocated buffers.
+const SERVER_BUFFERS_VERSION: u32 = 6;
+
+#[derive(Args, Debug)]
+pub struct RecordArgs {
+    /// The format to use for the recording.
+    #[clap(value_enum, long, default_value_t)]
+    pub format: RecordFormat,
+    /// The frame rate of Y4M recordings.
+    ///
+    /// Frames are only captured when the recorded content changes. If no new frame is
+    /// available when the next frame is due, the previous frame is repeated.
+    #[clap(long, default_value_t = 30)]
+    pub fps: u32,
+    /// Stop the recording after this duration, e.g. `1m 30s`.
+    ///
+    /// By default, the recording continues until the program is interrupted or the
+    /// recorded object disappears.
+    #[clap(long, value_parser = humantime::parse_duration)]
+    pub duration: Option<Duration>,
+    /// The seat to use for interactive selection.
+    #[clap(long)]
+    pub seat: Option<String>,
+    /// The filename of the recording.
+    ///
+    /// For QOI recordings, this is a directory that will contain one image per frame.
+    ///
+    /// If no filename is given, the recording will be saved under
+    /// %Y-%m-%d-%H%M%S_jay.y4m or %Y-%m-%d-%H%M%S_jay respectively in the current
+    /// directory.
+    ///
+    /// The filename can contain the usual strftime parameters.
+    #[clap(long)]
+    pub filename: Option<String>,
+    #[clap(subcommand)]
+    pub target: RecordTarget,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone, Hash, Default, PartialEq)]
+pub enum RecordFormat {
+    /// An uncompressed YUV4MPEG2 stream with full chroma resolution.
+    #[default]
+    Y4m,
+    /// A directory of QOI images, one per captured frame.
+    ///
+    /// Each image is named after the index of the frame followed by the number of
+    /// milliseconds between the start of the recording and the time the frame was
+    /// captured.
+    Qoi,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RecordTarget {
+    /// Record an output.
+    Output(RecordOutputArgs),
+    /// Record a workspace.
+    ///
+    /// Nothing is captured while the workspace is not visible.
+    Workspace(RecordWorkspaceArgs),
+    /// Record a window that is selected by clicking on it.
+    Window,
+}
+
+#[derive(Args, Debug)]
+pub struct RecordOutputArgs {
+    /// The name of the output, e.g. `DP-1`.
+    ///
+    /// If no name is given, the output is selected by clicking on it.
+    pub name: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct RecordWorkspaceArgs {
+    /// The name of the workspace.
+    ///
+    /// If no name is given, the workspace is selected by clicking on it.
+    pub name: Option<String>,
+}
+
+pub fn main(global: GlobalArgs, args: RecordArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        if args.fps == 0 {
+            fatal!("The frame rate must be positive");
+        }
+        let recorder = Rc::new(Recorder {
+            tc: tc.clone(),
+            sink: RefCell::new(create_sink(&args)),
+            args,
+            start: Time::now_unchecked(),
+            drm_dev: Default::default(),
+            dma_buf_ids: Default::default(),
+            pending_planes: Default::default(),
+            pending_buffers: Default::default(),
+            buffers: Default::default(),
+            stop: Default::default(),
+        });
+        recorder.run().await;
+    });
+}
+
+struct Recorder {
+    tc: Rc<ToolClient>,
+    args: RecordArgs,
+    start: Time,
+    drm_dev: CloneCell<Option<Rc<OwnedFd>>>,
+    dma_buf_ids: DmaBufIds,
+    pending_planes: RefCell<PlaneVec<DmaBufPlane>>,
+    pending_buffers: RefCell<Vec<DmaBuf>>,
+    buffers: RefCell<Vec<Rc<dyn BufferObject>>>,
+    sink: RefCell<Sink>,
+    stop: AsyncEvent,
+}
+
+enum Target {
+    Output {
+        output: JayOutputId,
+        workspace: Option<JayWorkspaceId>,
+    },
+    Toplevel(JayToplevelId),
+}
+
+#[derive(Default)]
+struct Globals {
+    outputs: Vec<(u32, u32)>,
+    seats: Vec<(u32, u32)>,
+}
+
+struct WorkspaceInfo {
+    id: JayWorkspaceId,
+    name: RefCell<String>,
+    output: Cell<u32>,
+}
+
+impl Recorder {
+    async fn run(self: &Rc<Self>) {
+        let tc = &self.tc;
+        let comp = tc.bind_jay_compositor(SERVER_BUFFERS_VERSION).await;
+        let (registry, globals) = self.globals().await;
+        let target = match &self.args.target {
+            RecordTarget::Output(a) => match &a.name {
+                Some(name) => {
+                    let output = self.find_output(registry, &globals, name).await;
+                    Target::Output {
+                        output: self.jay_output(comp, output),
+                        workspace: None,
+                    }
+                }
+                None => {
+                    let (output, _) = self.select_workspace(comp, registry, &globals).await;
+                    Target::Output {
+                        output: self.jay_output(comp, output),
+                        workspace: None,
+                    }
+                }
+            },
+            RecordTarget::Workspace(a) => {
+                let (output, workspace) = match &a.name {
+                    Some(name) => self.find_workspace(comp, registry, &globals, name).await,
+                    None => self.select_workspace(comp, registry, &globals).await,
+                };
+                Target::Output {
+                    output: self.jay_output(comp, output),
+                    workspace: Some(workspace),
+                }
+            }
+            RecordTarget::Window => {
+                Target::Toplevel(self.select_toplevel(comp, registry, &globals).await)
+            }
+        };
+        self.watch_render_ctx(comp);
+        let sc: JayScreencastId = tc.id();
+        tc.send(jay_compositor::CreateScreencast {
+            self_id: comp,
+            id: sc,
+        });
+        self.handle_screencast_events(sc);
+        match target {
+            Target::Output { output, workspace } => {
+                tc.send(jay_screencast::SetOutput {
+                    self_id: sc,
+                    output,
+                });
+                tc.send(jay_screencast::SetAllowAllWorkspaces {
+                    self_id: sc,
+                    allow_all: workspace.is_none() as _,
+                });
+                if let Some(workspace) = workspace {
+                    tc.send(jay_screencast::AllowWorkspace {
+                        self_id: sc,
+                        workspace,
+                    });
+                }
+            }
+            Target::Toplevel(id) => {
+                tc.send(jay_screencast::SetToplevel { self_id: sc, id });
+            }
+        }
+        tc.send(jay_screencast::SetUseLinearBuffers {
+            self_id: sc,
+            use_linear: 1,
+        });
+        tc.send(jay_screencast::SetRunning {
+            self_id: sc,
+            running: 1,
+        });
+        tc.send(jay_screencast::Configure { self_id: sc });
+        let _signals = tc
+            .eng
+            .spawn("record signals", self.clone().stop_on_signal());
+        let _timeout = self
+            .args
+            .duration
+            .map(|d| tc.eng.spawn("record timeout", self.clone().stop_after(d)));
+        let _ticker = match self.args.format {
+            RecordFormat::Y4m => Some(tc.eng.spawn("record ticker", self.clone().tick())),
+            RecordFormat::Qoi => None,
+        };
+        eprintln!("Recording. Press ctrl-c to stop.");
+        self.stop.triggered().await;
+        self.sink.borrow_mut().finish();
+    }
+
+    async fn globals(self: &Rc<Self>) -> (WlRegistryId, Rc<RefCell<Globals>>) {
+        let tc = &self.tc;
+        let registry: WlRegistryId = tc.id();
+        tc.send(wl_display::GetRegistry {
+            self_id: WL_DISPLAY_ID,
+            registry,
+        });
+        let globals = Rc::new(RefCell::new(Globals::default()));
+        wl_registry::Global::handle(tc, registry, globals.clone(), |g, ev| {
+            let mut g = g.borrow_mut();
+            if ev.interface == WlOutput.name() {
+                g.outputs.push((ev.name, ev.version));
+            } else if ev.interface == WlSeat.name() {
+                g.seats.push((ev.name, ev.version));
+            }
+        });
+        tc.round_trip().await;
+        (registry, globals)
+    }
+
+    fn bind_output(&self, registry: WlRegistryId, name: u32, version: u32) -> WlOutputId {
+        let id: WlOutputId = self.tc.id();
+        self.tc.send(wl_registry::Bind {
+            self_id: registry,
+            name,
+            interface: WlOutput.name(),
+            version: version.min(4),
+            id: id.into(),
+        });
+        id
+    }
+
+    async fn find_output(
+        &self,
+        registry: WlRegistryId,
+        globals: &RefCell<Globals>,
+        name: &str,
+    ) -> WlOutputId {
+        let tc = &self.tc;
+        let names = Rc::new(RefCell::new(vec![]));
+        for &(global, version) in &globals.borrow().outputs {
+            let id = self.bind_output(registry, global, version);
+            wl_output::Name::handle(tc, id, names.clone(), move |names, ev| {
+                names.borrow_mut().push((ev.name.to_string(), id));
+            });
+        }
+        tc.round_trip().await;
+        let names = names.borrow();
+        for (n, id) in &*names {
+            if n == name {
+                return *id;
+            }
+        }
+        let available: Vec<_> = names.iter().map(|(n, _)| n.as_str()).collect();
+        fatal!(
+            "There is no output named `{}`. Available outputs: {}",
+            name,
+            available.join(", "),
+        );
+    }
+
+    fn jay_output(&self, comp: JayCompositorId, output: WlOutputId) -> JayOutputId {
+        let id: JayOutputId = self.tc.id();
+        self.tc.send(jay_compositor::GetOutput {
+            self_id: comp,
+            id,
+            output,
+        });
+        id
+    }
+
+    fn output_by_global_name(
+        &self,
+        registry: WlRegistryId,
+        globals: &RefCell<Globals>,
+        name: u32,
+    ) -> WlOutputId {
+        for &(global, version) in &globals.borrow().outputs {
+            if global == name {
+                return self.bind_output(registry, global, version);
+            }
+        }
+        fatal!("The output of the workspace is not advertised by the compositor");
+    }
+
+    async fn find_workspace(
+        &self,
+        comp: JayCompositorId,
+        registry: WlRegistryId,
+        globals: &RefCell<Globals>,
+        name: &str,
+    ) -> (WlOutputId, JayWorkspaceId) {
+        let tc = &self.tc;
+        let watcher = tc.id();
+        tc.send(jay_compositor::WatchWorkspaces {
+            self_id: comp,
+            id: watcher,
+        });
+        let workspaces = Rc::new(RefCell::new(Vec::<Rc<WorkspaceInfo>>::new()));
+        jay_workspace_watcher::New::handle(
+            tc,
+            watcher,
+            (tc.clone(), workspaces.clone()),
+            |(tc, workspaces), ev| {
+                let ws = Rc::new(WorkspaceInfo {
+                    id: ev.id,
+                    name: Default::default(),
+                    output: Cell::new(0),
+                });
+                jay_workspace::Name::handle(tc, ev.id, ws.clone(), |ws, ev| {
+                    *ws.name.borrow_mut() = ev.name.to_string();
+                });
+                jay_workspace::Output::handle(tc, ev.id, ws.clone(), |ws, ev| {
+                    ws.output.set(ev.global_name);
+                });
+                workspaces.borrow_mut().push(ws);
+            },
+        );
+        tc.round_trip().await;
+        let workspaces = workspaces.borrow();
+        for ws in &*workspaces {
+            if *ws.name.borrow() == name {
+                let output = self.output_by_global_name(registry, globals, ws.output.get());
+                return (output, ws.id);
+            }
+        }
+        let available: Vec<_> = workspaces
+            .iter()
+            .map(|ws| ws.name.borrow().clone())
+            .collect();
+        fatal!(
+            "There is no workspace named `{}`. Available workspaces: {}",
+            name,
+            available.join(", "),
+        );
+    }
+
+    async fn seat(&self, registry: WlRegistryId, globals: &RefCell<Globals>) -> WlSeatId {
+        let tc = &self.tc;
+        let names = Rc::new(RefCell::new(vec![]));
+        for &(global, version) in &globals.borrow().seats {
+            let id: WlSeatId = tc.id();
+            tc.send(wl_registry::Bind {
+                self_id: registry,
+                name: global,
+                interface: WlSeat.name(),
+                version: version.min(2),
+                id: id.into(),
+            });
+            wl_seat::Name::handle(tc, id, names.clone(), move |names, ev| {
+                names.borrow_mut().push((ev.name.to_string(), id));
+            });
+        }
+        tc.round_trip().await;
+        let names = names.borrow();
+        let wanted = self.args.seat.as_deref();
+        let seat = names
+            .iter()
+            .find(|(n, _)| Some(n.as_str()) == wanted)
+            .or_else(|| {
+                if wanted.is_some() {
+                    return None;
+                }
+                names.iter().find(|(n, _)| n == "default").or(names.first())
+            });
+        match seat {
+            Some((_, id)) => *id,
+            None => match wanted {
+                Some(name) => fatal!("There is no seat named `{}`", name),
+                None => fatal!("The compositor has no seats"),
+            },
+        }
+    }
+
+    async fn select_workspace(
+        &self,
+        comp: JayCompositorId,
+        registry: WlRegistryId,
+        globals: &RefCell<Globals>,
+    ) -> (WlOutputId, JayWorkspaceId) {
+        let tc = &self.tc;
+        let seat = self.seat(registry, globals).await;
+        let id = tc.id();
+        tc.send(jay_compositor::SelectWorkspace {
+            self_id: comp,
+            id,
+            seat,
+        });
+        let result = Rc::new(AsyncQueue::new());
+        jay_select_workspace::Cancelled::handle(tc, id, result.clone(), |res, _| {
+            res.push(None);
+        });
+        jay_select_workspace::Selected::handle(tc, id, result.clone(), |res, ev| {
+            res.push(Some((ev.output, ev.id)));
+        });
+        eprintln!("Click on the workspace to record.");
+        match result.pop().await {
+            Some((output, ws)) => (self.output_by_global_name(registry, globals, output), ws),
+            None => fatal!("The selection was cancelled"),
+        }
+    }
+
+    async fn select_toplevel(
+        &self,
+        comp: JayCompositorId,
+        registry: WlRegistryId,
+        globals: &RefCell<Globals>,
+    ) -> JayToplevelId {
+        let tc = &self.tc;
+        let seat = self.seat(registry, globals).await;
+        let id = tc.id();
+        tc.send(jay_compositor::SelectToplevel {
+            self_id: comp,
+            id,
+            seat,
+        });
+        let result = Rc::new(AsyncQueue::new());
+        jay_select_toplevel::Done::handle(tc, id, result.clone(), |res, ev| {
+            res.push(ev.id);
+        });
+        eprintln!("Click on the window to record.");
+        let tl = result.pop().await;
+        if tl.is_none() {
+            fatal!("The selection was cancelled");
+        }
+        tl
+    }
+
+    fn watch_render_ctx(self: &Rc<Self>, comp: JayCompositorId) {
+        let tc = &self.tc;
+        let id = tc.id();
+        tc.send(jay_compositor::GetRenderCtx { self_id: comp, id });
+        jay_render_ctx::NoDevice::handle(tc, id, self.clone(), |r, _| {
+            r.drm_dev.take();
+        });
+        jay_render_ctx::Device::handle(tc, id, self.clone(), |r, ev| {
+            r.drm_dev.set(Some(ev.fd));
+        });
+    }
+
+    fn handle_screencast_events(self: &Rc<Self>, sc: JayScreencastId) {
+        let tc = &self.tc;
+        jay_screencast::Plane::handle(tc, sc, self.clone(), |r, ev| {
+            r.pending_planes.borrow_mut().push(DmaBufPlane {
+                offset: ev.offset,
+                stride: ev.stride,
+                fd: ev.fd,
+            });
+        });
+        jay_screencast::Buffer::handle(tc, sc, self.clone(), |r, ev| {
+            let Some(format) = formats().get(&ev.format) else {
+                fatal!("The compositor sent an unknown format {}", ev.format);
+            };
+            r.pending_buffers.borrow_mut().push(DmaBuf {
+                id: r.dma_buf_ids.next(),
+                width: ev.width,
+                height: ev.height,
+                format,
+                modifier: ev.modifier,
+                planes: mem::take(&mut *r.pending_planes.borrow_mut()),
+            });
+        });
+        jay_screencast::BuffersDone::handle(tc, sc, self.clone(), move |r, ev| {
+            r.import_buffers();
+            r.tc.send(jay_screencast::AckBuffers {
+                self_id: sc,
+                serial: ev.serial,
+            });
+        });
+        jay_screencast::ConfigDone::handle(tc, sc, self.clone(), move |r, ev| {
+            r.tc.send(jay_screencast::AckConfig {
+                self_id: sc,
+                serial: ev.serial,
+            });
+        });
+        jay_screencast::Ready::handle(tc, sc, self.clone(), move |r, ev| {
+            r.capture(ev.idx as usize);
+            r.tc.send(jay_screencast::ReleaseBuffer {
+                self_id: sc,
+                idx: ev.idx,
+            });
+        });
+        jay_screencast::Destroyed::handle(tc, sc, self.clone(), |r, _| {
+            eprintln!("The recorded object no longer exists.");
+            r.stop.trigger();
+        });
+    }
+
+    fn import_buffers(&self) {
+        let drm_dev = self.drm_dev.get();
+        let mut buffers = vec![];
+        for buf in self.pending_buffers.borrow_mut().drain(..) {
+            match import_dmabuf_unmapped(drm_dev.as_ref(), &buf) {
+                Ok(bo) => buffers.push(bo),
+                Err(e) => fatal!("Could not import a screencast buffer: {}", ErrorFmt(e)),
+            }
+        }
+        *self.buffers.borrow_mut() = buffers;
+    }
+
+    fn capture(&self, idx: usize) {
+        let Some(bo) = self.buffers.borrow().get(idx).cloned() else {
+            return;
+        };
+        // A mapping only reflects the contents at the time it was created.
+        let map = match bo.clone().map_read() {
+            Ok(m) => m,
+            Err(e) => {
+                log::error!("Could not map a screencast buffer: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let dmabuf = bo.dmabuf();
+        let frame = Frame {
+            data: unsafe { map.data() },
+            width: dmabuf.width as u32,
+            height: dmabuf.height as u32,
+            stride: map.stride() as u32,
+        };
+        self.sink.borrow_mut().frame(self.start.elapsed(), &frame);
+    }
+
+    async fn tick(self: Rc<Self>) {
+        let start = self.start.nsec();
+        let interval = 1_000_000_000 / self.args.fps as u64;
+        for n in 1.. {
+            if let Err(e) = self.tc.ring.timeout(start + n * interval).await {
+                log::error!("Could not wait for the next frame: {}", ErrorFmt(e));
+                self.stop.trigger();
+                return;
+            }
+            self.sink.borrow_mut().tick();
+        }
+    }
+
+    async fn stop_after(self: Rc<Self>, duration: Duration) {
+        let deadline = self.start.nsec() + duration.as_nanos() as u64;
+        if let Err(e) = self.tc.ring.timeout(deadline).await {
+            log::error!(
+                "Could not wait for the end of the recording: {}",
+                ErrorFmt(e)
+            );
+        }
+        self.stop.trigger();
+    }
+
+    async fn stop_on_signal(self: Rc<Self>) {
+        let mut set: c::sigset_t = uapi::pod_zeroed();
+        uapi::sigaddset(&mut set, c::SIGINT).unwrap();
+        uapi::sigaddset(&mut set, c::SIGTERM).unwrap();
+        if let Err(e) = uapi::pthread_sigmask(c::SIG_BLOCK, Some(&set), None) {
+            log::error!("Could not block signals: {}", ErrorFmt(e));
+            return;
+        }
+        let fd = match uapi::signalfd_new(&set, c::SFD_CLOEXEC) {
+            Ok(fd) => Rc::new(fd),
+            Err(e) => {
+                log::error!("Could not create a signalfd: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let mut buf = TypedBuf::<c::signalfd_siginfo>::new();
+        if let Err(e) = self.tc.ring.read(&fd, buf.buf()).await {
+            log::error!("Could not read from signal fd: {}", ErrorFmt(e));
+        }
+        self.stop.trigger();
+    }
+}
+
+struct Frame<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: u32,
+}
+
+enum Sink {
+    Y4m {
+        path: String,
+        file: BufWriter<File>,
+        fps: u32,
+        size: Option<(u32, u32)>,
+        frame: Vec<u8>,
+        frames: u64,
+    },
+    Qoi {
+        path: PathBuf,
+        frames: u64,
+    },
+}
+
+fn create_sink(args: &RecordArgs) -> Sink {
+    let filename = match &args.filename {
+        Some(f) => f.clone(),
+        _ => match args.format {
+            RecordFormat::Y4m => "%Y-%m-%d-%H%M%S_jay.y4m".to_string(),
+            RecordFormat::Qoi => "%Y-%m-%d-%H%M%S_jay".to_string(),
+        },
+    };
+    let path = Local::now().format(&filename).to_string();
+    match args.format {
+        RecordFormat::Y4m => {
+            let file = match File::create(&path) {
+                Ok(f) => f,
+                Err(e) => fatal!("Could not create `{}`: {}", path, ErrorFmt(e)),
+            };
+            Sink::Y4m {
+                path,
+                file: BufWriter::new(file),
+                fps: args.fps,
+                size: None,
+                frame: vec![],
+                frames: 0,
+            }
+        }
+        RecordFormat::Qoi => {
+            if let Err(e) = std::fs::create_dir_all(&path) {
+                fatal!("Could not create `{}`: {}", path, ErrorFmt(e));
+            }
+            Sink::Qoi {
+                path: path.into(),
+                frames: 0,
+            }
+        }
+    }
+}
+
+impl Sink {
+    fn frame(&mut self, time: Duration, frame: &Frame<'_>) {
+        match self {
+            Sink::Y4m {
+                path,
+                file,
+                fps,
+                size,
+                frame: buf,
+                ..
+            } => {
+                let &mut (width, height) = size.get_or_insert_with(|| {
+                    let header = y4m_header(frame.width, frame.height, *fps);
+                    if let Err(e) = file.write_all(&header) {
+                        fatal!("Could not write to `{}`: {}", path, ErrorFmt(e));
+                    }
+                    (frame.width, frame.height)
+                });
+                if (frame.width, frame.height) != (width, height) {
+                    log::warn!(
+                        "The size of the recorded object changed from {}x{} to {}x{}. Cropping.",
+                        width,
+                        height,
+                        frame.width,
+                        frame.height,
+                    );
+                }
+                buf.clear();
+                xrgb8888_encode_y4m_frame(
+                    frame.data,
+                    frame.width,
+                    frame.height,
+                    frame.stride,
+                    width,
+                    height,
+                    buf,
+                );
+            }
+            Sink::Qoi { path, frames } => {
+                let data = xrgb8888_encode_qoi(frame.data, frame.width, frame.height, frame.stride);
+                let path = path.join(format!("{:08}-{}.qoi", frames, time.as_millis()));
+                if let Err(e) = std::fs::write(&path, data) {
+                    fatal!("Could not write `{}`: {}", path.display(), ErrorFmt(e));
+                }
+                *frames += 1;
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        if let Sink::Y4m {
+            path,
+            file,
+            frame,
+            frames,
+            ..
+        } = self
+        {
+            if frame.is_empty() {
+                return;
+            }
+            if let Err(e) = file.write_all(frame) {
+                fatal!("Could not write to `{}`: {}", path, ErrorFmt(e));
+            }
+            *frames += 1;
+        }
+    }
+
+    fn finish(&mut self) {
+        match self {
+            Sink::Y4m {
+                path, file, frames, ..
+            } => {
+                if let Err(e) = file.flush() {
+                    fatal!("Could not write to `{}`: {}", path, ErrorFmt(e));
+                }
+                eprintln!("Wrote {} frames to `{}`.", frames, path);
+            }
+            Sink::Qoi { path, frames } => {
+                eprintln!("Wrote {} frames to `{}`.", frames, path.display());
+            }
+        }
+    }
+}
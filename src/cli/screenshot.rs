@@ -1,9 +1,10 @@
 use {
     crate::{
-        allocator::{Allocator, AllocatorError, BufferUsage, MappedBuffer},
+        allocator::{Allocator, AllocatorError, BufferObject, BufferUsage, MappedBuffer},
         cli::{GlobalArgs, ScreenshotArgs, ScreenshotFormat},
         format::XRGB8888,
         gfx_apis,
+        rect::Rect,
         tools::tool_client::{with_tool_client, Handle, ToolClient},
         udmabuf::{Udmabuf, UdmabufError},
         utils::{errorfmt::ErrorFmt, queue::AsyncQueue, windows::WindowsExt},
@@ -143,24 +144,47 @@ pub enum ScreenshotError {
     CreateVulkanAllocator(#[source] AllocatorError),
     #[error("Could not map the dmabuf with any allocator")]
     MapDmabufAny,
+    #[error("Could not import the dmabuf with any allocator")]
+    ImportDmabufAny,
 }
 
-fn map(
-    allocator: Rc<dyn Allocator>,
-    buf: &DmaBuf,
-) -> Result<Box<dyn MappedBuffer>, ScreenshotError> {
+/// A buffer object together with a mapping of its contents.
+pub type ImportedDmabuf = (Rc<dyn BufferObject>, Box<dyn MappedBuffer>);
+
+fn import(allocator: Rc<dyn Allocator>, buf: &DmaBuf) -> Result<ImportedDmabuf, ScreenshotError> {
     let bo = allocator
         .import_dmabuf(buf, BufferUsage::none())
         .map_err(ScreenshotError::ImportDmabuf)?;
-    let bo_map = bo.map_read().map_err(ScreenshotError::MapDmabuf)?;
-    Ok(bo_map)
+    let map = bo.clone().map_read().map_err(ScreenshotError::MapDmabuf)?;
+    Ok((bo, map))
 }
 
-pub fn buf_to_bytes(
+/// Imports a dmabuf with the first allocator that can map it.
+///
+/// The returned mapping reflects the contents of the buffer at the time of the import.
+pub fn import_dmabuf(
     drm_dev: Option<&Rc<OwnedFd>>,
     buf: &DmaBuf,
-    format: ScreenshotFormat,
-) -> Result<Vec<u8>, ScreenshotError> {
+) -> Result<ImportedDmabuf, ScreenshotError> {
+    import_with_any_allocator(drm_dev, |a| import(a, buf)).ok_or(ScreenshotError::MapDmabufAny)
+}
+
+/// Imports a dmabuf with the first allocator that can import it without mapping it.
+pub fn import_dmabuf_unmapped(
+    drm_dev: Option<&Rc<OwnedFd>>,
+    buf: &DmaBuf,
+) -> Result<Rc<dyn BufferObject>, ScreenshotError> {
+    import_with_any_allocator(drm_dev, |a| {
+        a.import_dmabuf(buf, BufferUsage::none())
+            .map_err(ScreenshotError::ImportDmabuf)
+    })
+    .ok_or(ScreenshotError::ImportDmabufAny)
+}
+
+fn import_with_any_allocator<T>(
+    drm_dev: Option<&Rc<OwnedFd>>,
+    mut import: impl FnMut(Rc<dyn Allocator>) -> Result<T, ScreenshotError>,
+) -> Option<T> {
     let mut allocators =
         Vec::<Box<dyn FnOnce() -> Result<Rc<dyn Allocator>, ScreenshotError>>>::new();
     match drm_dev {
@@ -187,25 +211,31 @@ pub fn buf_to_bytes(
             allocators.push(udmabuf);
         }
     }
-    let bo_map = 'create_bo_map: {
-        for allocator in allocators {
-            let allocator = match allocator() {
-                Ok(a) => a,
-                Err(e) => {
-                    log::error!("Could not create allocator: {}", ErrorFmt(e));
-                    continue;
-                }
-            };
-            match map(allocator, buf) {
-                Ok(m) => break 'create_bo_map m,
-                Err(e) => {
-                    log::error!("Could not map dmabuf: {}", ErrorFmt(e));
-                    continue;
-                }
-            };
-        }
-        return Err(ScreenshotError::MapDmabufAny);
-    };
+    for allocator in allocators {
+        let allocator = match allocator() {
+            Ok(a) => a,
+            Err(e) => {
+                log::error!("Could not create allocator: {}", ErrorFmt(e));
+                continue;
+            }
+        };
+        match import(allocator) {
+            Ok(res) => return Some(res),
+            Err(e) => {
+                log::error!("Could not import dmabuf: {}", ErrorFmt(e));
+                continue;
+            }
+        };
+    }
+    None
+}
+
+pub fn buf_to_bytes(
+    drm_dev: Option<&Rc<OwnedFd>>,
+    buf: &DmaBuf,
+    format: ScreenshotFormat,
+) -> Result<Vec<u8>, ScreenshotError> {
+    let (_, bo_map) = import_dmabuf(drm_dev, buf)?;
     let data = unsafe { bo_map.data() };
     if format == ScreenshotFormat::Qoi {
         return Ok(xrgb8888_encode_qoi(
@@ -215,23 +245,53 @@ pub fn buf_to_bytes(
             bo_map.stride() as u32,
         ));
     }
+    Ok(xrgb8888_encode_png(
+        data,
+        buf.width,
+        buf.height,
+        bo_map.stride(),
+    ))
+}
 
+/// Copies the contents of a dmabuf into tightly packed XRGB8888 rows.
+///
+/// If a region is given, only the part of the buffer inside the region is copied.
+/// Returns the pixels together with their width and height.
+pub fn buf_to_xrgb8888(
+    drm_dev: Option<&Rc<OwnedFd>>,
+    buf: &DmaBuf,
+    region: Option<Rect>,
+) -> Result<(Vec<u8>, i32, i32), ScreenshotError> {
+    let (_, bo_map) = import_dmabuf(drm_dev, buf)?;
+    let data = unsafe { bo_map.data() };
+    let stride = bo_map.stride() as usize;
+    let mut rect = Rect::new_sized_unchecked(0, 0, buf.width, buf.height);
+    if let Some(region) = region {
+        rect = rect.intersect(region);
+    }
+    let mut pixels = Vec::with_capacity(rect.width() as usize * rect.height() as usize * 4);
+    for y in rect.y1()..rect.y2() {
+        let line = &data[y as usize * stride..];
+        pixels.extend_from_slice(&line[rect.x1() as usize * 4..rect.x2() as usize * 4]);
+    }
+    Ok((pixels, rect.width(), rect.height()))
+}
+
+pub fn xrgb8888_encode_png(data: &[u8], width: i32, height: i32, stride: i32) -> Vec<u8> {
     let mut out = vec![];
-    {
-        let mut image_data = Vec::with_capacity((buf.width * buf.height * 4) as usize);
-        let lines = data[..(buf.height as usize * bo_map.stride() as usize)]
-            .chunks_exact(bo_map.stride() as usize);
-        for line in lines {
-            for pixel in line[..(buf.width as usize * 4)].array_chunks_ext::<4>() {
-                image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
-            }
+    let mut image_data = Vec::with_capacity((width * height * 4) as usize);
+    let lines = data[..(height as usize * stride as usize)].chunks_exact(stride as usize);
+    for line in lines {
+        for pixel in line[..(width as usize * 4)].array_chunks_ext::<4>() {
+            image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
         }
-        let mut encoder = Encoder::new(&mut out, buf.width as _, buf.height as _);
-        encoder.set_color(ColorType::Rgba);
-        encoder.set_depth(BitDepth::Eight);
-        encoder.set_srgb(SrgbRenderingIntent::Perceptual);
-        let mut writer = encoder.write_header().unwrap();
-        writer.write_image_data(&image_data).unwrap();
     }
-    Ok(out)
+    let mut encoder = Encoder::new(&mut out, width as _, height as _);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_srgb(SrgbRenderingIntent::Perceptual);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&image_data).unwrap();
+    drop(writer);
+    out
 }
@@ -36,8 +36,10 @@ use {
         tracy::enable_profiler,
         tree::{
             container_layout, container_render_positions, container_render_titles, float_layout,
-            float_titles, output_render_data, placeholder_render_textures, DisplayNode, NodeIds,
-            OutputNode, TearingMode, VrrMode, WorkspaceNode,
+            float_titles, output_render_data, placeholder_render_textures, scrolling_layout,
+            workspace_layout, DisplayNode, NodeIds, OutputNode, TearingMode, VrrMode,
+            WorkspaceNode, DEFAULT_COLUMN_WIDTH_PRESETS, DEFAULT_MASTER_COUNT,
+            DEFAULT_MASTER_RATIO,
         },
         user_session::import_environment,
         utils::{
@@ -52,8 +54,16 @@ use {
     },
     ahash::AHashSet,
     forker::ForkerProxy,
-    jay_config::{_private::DEFAULT_SEAT_NAME, video::GfxApi},
-    std::{cell::Cell, env, future::Future, ops::Deref, rc::Rc, sync::Arc, time::Duration},
+    jay_config::{_private::DEFAULT_SEAT_NAME, keyboard::mods::LOGO, video::GfxApi},
+    std::{
+        cell::{Cell, RefCell},
+        env,
+        future::Future,
+        ops::Deref,
+        rc::Rc,
+        sync::Arc,
+        time::Duration,
+    },
     thiserror::Error,
     uapi::c,
 };
@@ -180,6 +190,8 @@ fn start_compositor2(
         input_device_handlers: Default::default(),
         theme: Default::default(),
         pending_container_layout: Default::default(),
+        pending_workspace_layout: Default::default(),
+        pending_scrolling_layout: Default::default(),
         pending_container_render_positions: Default::default(),
         pending_container_render_title: Default::default(),
         pending_output_render_data: Default::default(),
@@ -196,6 +208,7 @@ fn start_compositor2(
         outputs: Default::default(),
         drm_devs: Default::default(),
         status: Default::default(),
+        status_blocks: Default::default(),
         idle: IdleState {
             input: Default::default(),
             change: Default::default(),
@@ -235,10 +248,33 @@ fn start_compositor2(
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
+        global_shortcuts: Default::default(),
+        input_captures: Default::default(),
         default_workspace_capture: Cell::new(true),
+        default_workspace_layout: Default::default(),
+        column_width_presets: RefCell::new(DEFAULT_COLUMN_WIDTH_PRESETS.to_vec()),
+        show_marks: Cell::new(true),
+        title_format: Default::default(),
+        hidden_title_app_ids: Default::default(),
+        sticky_app_ids: Default::default(),
+        show_title_buttons: Cell::new(false),
+        swallow_terminals: Default::default(),
+        swallow_exceptions: Default::default(),
+        blur_app_ids: Default::default(),
+        blur_layer_namespaces: Default::default(),
+        window_opacities: Default::default(),
+        window_borders: Default::default(),
+        bar_position: Default::default(),
+        bar_mode: Default::default(),
+        bar_modifiers: Cell::new(LOGO),
+        bar_connectors: Default::default(),
+        bar_revealed: Cell::new(false),
+        layout_placeholders: Default::default(),
+        animations: Default::default(),
         default_gfx_api: Cell::new(GfxApi::Vulkan),
         activation_tokens: Default::default(),
         toplevel_lists: Default::default(),
+        background_effect_managers: Default::default(),
         dma_buf_ids: Default::default(),
         drm_feedback_ids: Default::default(),
         direct_scanout_enabled: Cell::new(true),
@@ -272,6 +308,7 @@ fn start_compositor2(
         ui_drag_enabled: Cell::new(true),
         ui_drag_threshold_squared: Cell::new(10),
         toplevels: Default::default(),
+        minimized: Default::default(),
         const_40hz_latch: Default::default(),
         tray_item_ids: Default::default(),
         data_control_device_ids: Default::default(),
@@ -404,6 +441,16 @@ fn start_global_event_handlers(
             output_render_data(state.clone()),
         ),
         eng.spawn2("float layout", Phase::Layout, float_layout(state.clone())),
+        eng.spawn2(
+            "workspace layout",
+            Phase::Layout,
+            workspace_layout(state.clone()),
+        ),
+        eng.spawn2(
+            "scrolling layout",
+            Phase::Layout,
+            scrolling_layout(state.clone()),
+        ),
         eng.spawn2(
             "float titles",
             Phase::PostLayout,
@@ -520,6 +567,7 @@ fn create_dummy_output(state: &Rc<State>) {
         vrr_mode: Cell::new(VrrMode::NEVER),
         vrr_cursor_hz: Default::default(),
         tearing_mode: Cell::new(&TearingMode::Never),
+        wallpaper: Default::default(),
     });
     let connector = Rc::new(DummyOutput {
         id: state.connector_ids.next(),
@@ -566,10 +614,15 @@ fn create_dummy_output(state: &Rc<State>) {
         workspace_rect: Default::default(),
         non_exclusive_rect_rel: Default::default(),
         non_exclusive_rect: Default::default(),
+        bar_rect: Default::default(),
+        bar_rect_rel: Default::default(),
+        bar_mode: Default::default(),
+        bar_position: Default::default(),
         render_data: Default::default(),
         state: state.clone(),
         is_dummy: true,
         status: Default::default(),
+        status_blocks: Default::default(),
         scroll: Default::default(),
         pointer_positions: Default::default(),
         pointer_down: Default::default(),
@@ -590,6 +643,12 @@ fn create_dummy_output(state: &Rc<State>) {
         before_latch_event: Default::default(),
         tray_start_rel: Default::default(),
         tray_items: Default::default(),
+        window_switcher: Default::default(),
+        wallpaper: Default::default(),
+        wallpaper_job: Default::default(),
+        workspace_animation: Default::default(),
+        closing_windows: Default::default(),
+        blurred_areas: Default::default(),
     });
     let dummy_workspace = Rc::new(WorkspaceNode {
         id: state.node_ids.next(),
@@ -598,6 +657,7 @@ fn create_dummy_output(state: &Rc<State>) {
         output: CloneCell::new(dummy_output.clone()),
         position: Default::default(),
         container: Default::default(),
+        scrolling: Default::default(),
         stacked: Default::default(),
         seat_state: Default::default(),
         name: "dummy".to_string(),
@@ -612,6 +672,11 @@ fn create_dummy_output(state: &Rc<State>) {
         title_texture: Default::default(),
         attention_requests: Default::default(),
         render_highlight: Default::default(),
+        layout: Default::default(),
+        master_count: Cell::new(DEFAULT_MASTER_COUNT),
+        master_ratio: Cell::new(DEFAULT_MASTER_RATIO),
+        layout_scheduled: Cell::new(false),
+        reset_layout_factors: Cell::new(false),
     });
     *dummy_workspace.output_link.borrow_mut() =
         Some(dummy_output.workspaces.add_last(dummy_workspace.clone()));
@@ -17,7 +17,7 @@ use {
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{InitMessage, ServerFeature, ServerMessage, V1InitMessage},
+            ipc::{InitMessage, ServerFeature, ServerMessage, StatusClick, V1InitMessage},
             ConfigEntry, VERSION,
         },
         input::{InputDevice, Seat, SwitchEvent},
@@ -152,6 +152,10 @@ impl ConfigProxy {
             event,
         });
     }
+
+    pub fn status_clicked(&self, click: StatusClick) {
+        self.send(&ServerMessage::StatusClicked { click });
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -1,5 +1,6 @@
 use {
     crate::{
+        animation::{AnimationCurve, AnimationKind},
         async_engine::SpawnedFuture,
         backend::{
             self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceCapability,
@@ -15,8 +16,11 @@ use {
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized},
         tree::{
-            move_ws_to_output, ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase,
-            OutputNode, TearingMode, VrrMode, WsMoveConfig,
+            load_layout, move_ws_to_output, save_layout, BarMode, BarPosition, ContainerNode,
+            ContainerSplit, FloatNode, Node, NodeVisitorBase, OutputNode, SavedLayoutError,
+            StatusBlock, StatusBlockAlign, StatusBlockMinWidth, StatusBlocks, TearingMode, VrrMode,
+            WindowBorder, WorkspaceLayout, WorkspaceNode, WsMoveConfig, DEFAULT_MASTER_COUNT,
+            DEFAULT_MASTER_RATIO,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -28,15 +32,21 @@ use {
             stack::Stack,
             timer::{TimerError, TimerFd},
         },
+        wallpaper::{WallpaperConfig, WallpaperMode},
         xkbcommon::{XkbCommonError, XkbKeymap},
     },
     bincode::Options,
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage, WorkspaceSource},
+            ipc::{
+                ClientMessage, Response, ServerMessage, StatusBlock as ConfigStatusBlock,
+                StatusBlockAlign as ConfigStatusBlockAlign,
+                StatusBlockMinWidth as ConfigStatusBlockMinWidth, WorkspaceSource,
+            },
             PollableId, WireMode,
         },
+        animation::{AnimationCurve as ConfigAnimationCurve, AnimationKind as ConfigAnimationKind},
         input::{
             acceleration::{AccelProfile, ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_FLAT},
             capability::{
@@ -51,10 +61,11 @@ use {
         timer::Timer as JayTimer,
         video::{
             Connector, DrmDevice, Format as ConfigFormat, GfxApi, TearingMode as ConfigTearingMode,
-            Transform, VrrMode as ConfigVrrMode,
+            Transform, VrrMode as ConfigVrrMode, WallpaperMode as ConfigWallpaperMode,
         },
         xwayland::XScalingMode,
-        Axis, Direction, Workspace,
+        Axis, BarMode as ConfigBarMode, BarPosition as ConfigBarPosition, Direction, ResizeAmount,
+        Workspace, WorkspaceLayout as ConfigWorkspaceLayout,
     },
     libloading::Library,
     log::Level,
@@ -375,6 +386,36 @@ impl ConfigProxyHandler {
         self.state.set_status(status);
     }
 
+    fn handle_set_status_blocks(&self, blocks: Vec<ConfigStatusBlock>, separator: &str) {
+        let blocks = blocks
+            .into_iter()
+            .map(|b| StatusBlock {
+                text: b.text,
+                markup: b.markup,
+                color: b.color.map(Into::into),
+                background: b.background.map(Into::into),
+                border: b.border.map(Into::into),
+                border_widths: b.border_widths.map(|w| w.max(0)),
+                min_width: b.min_width.map(|w| match w {
+                    ConfigStatusBlockMinWidth::Pixels(w) => StatusBlockMinWidth::Pixels(w),
+                    ConfigStatusBlockMinWidth::Text(t) => StatusBlockMinWidth::Text(t),
+                }),
+                align: match b.align {
+                    ConfigStatusBlockAlign::Left => StatusBlockAlign::Left,
+                    ConfigStatusBlockAlign::Center => StatusBlockAlign::Center,
+                    ConfigStatusBlockAlign::Right => StatusBlockAlign::Right,
+                },
+                urgent: b.urgent,
+                separator: b.separator,
+                separator_block_width: b.separator_block_width.max(0),
+            })
+            .collect();
+        self.state.set_status_blocks(StatusBlocks {
+            blocks,
+            separator: separator.to_string(),
+        });
+    }
+
     fn get_timer(&self, timer: JayTimer) -> Result<Rc<TimerData>, CphError> {
         match self.timers_by_id.get(&timer.0) {
             Some(t) => Ok(t),
@@ -497,6 +538,12 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_resize(&self, seat: Seat, axis: Axis, amount: ResizeAmount) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.resize_focused(axis.into(), amount);
+        Ok(())
+    }
+
     fn handle_get_repeat_rate(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         let (rate, delay) = seat.get_rate();
@@ -516,6 +563,20 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn get_workspace_node(
+        &self,
+        workspace: WorkspaceSource,
+    ) -> Result<Option<Rc<WorkspaceNode>>, CphError> {
+        let ws = match workspace {
+            WorkspaceSource::Explicit(ws) => {
+                let name = self.get_workspace(ws)?;
+                self.state.workspaces.get(name.as_str())
+            }
+            WorkspaceSource::Seat(s) => self.get_seat(s)?.get_output().workspace.get(),
+        };
+        Ok(ws.filter(|ws| !ws.is_dummy))
+    }
+
     fn get_workspace(&self, ws: Workspace) -> Result<Rc<String>, CphError> {
         match self.workspaces_by_id.get(&ws.0) {
             Some(ws) => Ok(ws),
@@ -745,6 +806,119 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_default_workspace_layout(
+        &self,
+        layout: ConfigWorkspaceLayout,
+    ) -> Result<(), CphError> {
+        let Some(layout) = WorkspaceLayout::from_config(layout) else {
+            return Err(CphError::UnknownWorkspaceLayout(layout));
+        };
+        self.state.default_workspace_layout.set(layout);
+        Ok(())
+    }
+
+    fn handle_set_workspace_layout(
+        &self,
+        workspace: WorkspaceSource,
+        layout: ConfigWorkspaceLayout,
+    ) -> Result<(), CphError> {
+        let Some(layout) = WorkspaceLayout::from_config(layout) else {
+            return Err(CphError::UnknownWorkspaceLayout(layout));
+        };
+        if let Some(ws) = self.get_workspace_node(workspace)? {
+            ws.set_layout(layout);
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_layout(&self, workspace: WorkspaceSource) -> Result<(), CphError> {
+        let layout = match self.get_workspace_node(workspace)? {
+            Some(ws) => ws.layout.get(),
+            None => self.state.default_workspace_layout.get(),
+        };
+        self.respond(Response::GetWorkspaceLayout {
+            layout: layout.to_config(),
+        });
+        Ok(())
+    }
+
+    fn handle_cycle_workspace_layout(&self, workspace: WorkspaceSource) -> Result<(), CphError> {
+        if let Some(ws) = self.get_workspace_node(workspace)? {
+            ws.set_layout(ws.layout.get().next());
+        }
+        Ok(())
+    }
+
+    fn handle_set_workspace_master_count(
+        &self,
+        workspace: WorkspaceSource,
+        count: u32,
+    ) -> Result<(), CphError> {
+        if let Some(ws) = self.get_workspace_node(workspace)? {
+            ws.set_master_count(count);
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_master_count(
+        &self,
+        workspace: WorkspaceSource,
+    ) -> Result<(), CphError> {
+        let count = match self.get_workspace_node(workspace)? {
+            Some(ws) => ws.master_count.get(),
+            None => DEFAULT_MASTER_COUNT,
+        };
+        self.respond(Response::GetWorkspaceMasterCount { count });
+        Ok(())
+    }
+
+    fn handle_set_workspace_master_ratio(
+        &self,
+        workspace: WorkspaceSource,
+        ratio: f64,
+    ) -> Result<(), CphError> {
+        if !ratio.is_finite() {
+            return Err(CphError::InvalidMasterRatio(ratio));
+        }
+        if let Some(ws) = self.get_workspace_node(workspace)? {
+            ws.set_master_ratio(ratio);
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_master_ratio(
+        &self,
+        workspace: WorkspaceSource,
+    ) -> Result<(), CphError> {
+        let ratio = match self.get_workspace_node(workspace)? {
+            Some(ws) => ws.master_ratio.get(),
+            None => DEFAULT_MASTER_RATIO,
+        };
+        self.respond(Response::GetWorkspaceMasterRatio { ratio });
+        Ok(())
+    }
+
+    fn handle_promote_to_master(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.promote_to_master();
+        Ok(())
+    }
+
+    fn handle_set_column_width_presets(&self, mut presets: Vec<f64>) -> Result<(), CphError> {
+        for &width in &presets {
+            if !width.is_finite() || width <= 0.0 || width > 1.0 {
+                return Err(CphError::InvalidColumnWidth(width));
+            }
+        }
+        presets.sort_by(|a, b| a.total_cmp(b));
+        *self.state.column_width_presets.borrow_mut() = presets;
+        Ok(())
+    }
+
+    fn handle_cycle_column_width(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.cycle_column_width();
+        Ok(())
+    }
+
     fn handle_set_gfx_api(&self, device: Option<DrmDevice>, api: GfxApi) -> Result<(), CphError> {
         match device {
             Some(dev) => self.get_drm_device(dev)?.dev.set_gfx_api(api),
@@ -880,20 +1054,10 @@ impl ConfigProxyHandler {
         connector: Connector,
     ) -> Result<(), CphError> {
         let output = self.get_output_node(connector)?;
-        let ws = match workspace {
-            WorkspaceSource::Explicit(ws) => {
-                let name = self.get_workspace(ws)?;
-                match self.state.workspaces.get(name.as_str()) {
-                    Some(ws) => ws,
-                    _ => return Ok(()),
-                }
-            }
-            WorkspaceSource::Seat(s) => match self.get_seat(s)?.get_output().workspace.get() {
-                Some(ws) => ws,
-                _ => return Ok(()),
-            },
+        let Some(ws) = self.get_workspace_node(workspace)? else {
+            return Ok(());
         };
-        if ws.is_dummy || output.is_dummy {
+        if output.is_dummy {
             return Ok(());
         }
         if ws.output.get().id == output.id {
@@ -1115,6 +1279,64 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_wallpaper(
+        &self,
+        connector: Connector,
+        path: Option<&str>,
+        mode: ConfigWallpaperMode,
+    ) -> Result<(), CphError> {
+        let Some(mode) = WallpaperMode::from_config(mode) else {
+            return Err(CphError::UnknownWallpaperMode(mode));
+        };
+        let connector = self.get_output_node(connector)?;
+        let config = path.map(|path| WallpaperConfig {
+            path: path.to_string(),
+            mode,
+        });
+        let wallpaper = &connector.global.persistent.wallpaper;
+        if *wallpaper.borrow() != config {
+            *wallpaper.borrow_mut() = config;
+            connector.load_wallpaper();
+        }
+        Ok(())
+    }
+
+    fn handle_set_animations_enabled(&self, enabled: bool) {
+        self.state.animations.enabled.set(enabled);
+    }
+
+    fn handle_set_animations_in_screencasts(&self, enabled: bool) {
+        self.state.animations.in_screencasts.set(enabled);
+    }
+
+    fn handle_set_animation_duration(
+        &self,
+        kind: ConfigAnimationKind,
+        duration: Duration,
+    ) -> Result<(), CphError> {
+        let Some(kind) = AnimationKind::from_config(kind) else {
+            return Err(CphError::UnknownAnimationKind(kind));
+        };
+        let nsec = duration.as_nanos().try_into().unwrap_or(u64::MAX);
+        self.state.animations.set_duration(kind, nsec);
+        Ok(())
+    }
+
+    fn handle_set_animation_curve(
+        &self,
+        kind: ConfigAnimationKind,
+        curve: ConfigAnimationCurve,
+    ) -> Result<(), CphError> {
+        let Some(kind) = AnimationKind::from_config(kind) else {
+            return Err(CphError::UnknownAnimationKind(kind));
+        };
+        let Some(curve) = AnimationCurve::from_config(curve) else {
+            return Err(CphError::InvalidAnimationCurve(curve));
+        };
+        self.state.animations.set_curve(kind, curve);
+        Ok(())
+    }
+
     fn handle_set_vrr_cursor_hz(
         &self,
         connector: Option<Connector>,
@@ -1255,6 +1477,217 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_stacked(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetStacked {
+            stacked: seat.get_stacked().unwrap_or(false),
+        });
+        Ok(())
+    }
+
+    fn handle_set_stacked(&self, seat: Seat, stacked: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_stacked(stacked);
+        Ok(())
+    }
+
+    fn handle_toggle_layout(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.toggle_layout();
+        Ok(())
+    }
+
+    fn handle_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        self.get_seat(seat)?.mark(mark);
+        Ok(())
+    }
+
+    fn handle_unmark(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.unmark();
+        Ok(())
+    }
+
+    fn handle_get_marks(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetMarks {
+            marks: seat.get_marks(),
+        });
+        Ok(())
+    }
+
+    fn handle_focus_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        self.get_seat(seat)?.focus_mark(mark);
+        Ok(())
+    }
+
+    fn handle_swap_with_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        self.get_seat(seat)?.swap_with_mark(mark);
+        Ok(())
+    }
+
+    fn handle_move_to_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        self.get_seat(seat)?.move_to_mark(mark);
+        Ok(())
+    }
+
+    fn handle_focus_previous(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.focus_previous();
+        Ok(())
+    }
+
+    fn handle_focus_next_in_history(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.focus_next_in_history();
+        Ok(())
+    }
+
+    fn handle_window_switcher(&self, seat: Seat, reverse: bool) -> Result<(), CphError> {
+        self.get_seat(seat)?.window_switcher(reverse);
+        Ok(())
+    }
+
+    fn handle_get_sticky(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetSticky {
+            sticky: seat.get_sticky().unwrap_or(false),
+        });
+        Ok(())
+    }
+
+    fn handle_set_sticky(&self, seat: Seat, sticky: bool) -> Result<(), CphError> {
+        self.get_seat(seat)?.set_sticky(sticky);
+        Ok(())
+    }
+
+    fn handle_set_window_opacity(&self, seat: Seat, opacity: Option<f64>) -> Result<(), CphError> {
+        let opacity = opacity.map(|o| o.clamp(0.0, 1.0) as f32);
+        self.get_seat(seat)?.set_window_opacity(opacity);
+        Ok(())
+    }
+
+    fn handle_set_window_opacities(&self, opacities: Vec<(String, f64)>) {
+        let opacities = opacities
+            .into_iter()
+            .map(|(app_id, opacity)| (app_id, opacity.clamp(0.0, 1.0) as f32))
+            .collect();
+        *self.state.window_opacities.borrow_mut() = opacities;
+        self.state.damage(self.state.root.extents.get());
+    }
+
+    fn window_border(&self, border: jay_config::theme::WindowBorder) -> WindowBorder {
+        let sized = ThemeSized::border_width;
+        WindowBorder {
+            color: border.color.map(|c| c.into()),
+            focused_color: border.focused_color.map(|c| c.into()),
+            width: border.width.map(|w| w.clamp(sized.min(), sized.max())),
+        }
+    }
+
+    fn handle_set_window_borders(&self, borders: Vec<(String, jay_config::theme::WindowBorder)>) {
+        let borders = borders
+            .into_iter()
+            .map(|(app_id, border)| (app_id, self.window_border(border)))
+            .collect();
+        *self.state.window_borders.borrow_mut() = borders;
+        self.spaces_change();
+    }
+
+    fn handle_set_window_border(
+        &self,
+        seat: Seat,
+        border: jay_config::theme::WindowBorder,
+    ) -> Result<(), CphError> {
+        self.get_seat(seat)?
+            .set_window_border(self.window_border(border));
+        Ok(())
+    }
+
+    fn handle_set_bar_position(&self, position: ConfigBarPosition) -> Result<(), CphError> {
+        let Some(position) = BarPosition::from_config(position) else {
+            return Err(CphError::UnknownBarPosition(position));
+        };
+        self.state.bar_position.set(position);
+        self.state.bar_changed();
+        Ok(())
+    }
+
+    fn handle_set_bar_mode(&self, mode: ConfigBarMode) -> Result<(), CphError> {
+        let Some(mode) = BarMode::from_config(mode) else {
+            return Err(CphError::UnknownBarMode(mode));
+        };
+        self.state.bar_mode.set(mode);
+        self.state.bar_changed();
+        Ok(())
+    }
+
+    fn handle_set_bar_modifiers(&self, modifiers: Modifiers) {
+        self.state.bar_modifiers.set(modifiers);
+        self.state.update_bar_revealed();
+    }
+
+    fn handle_set_bar_connectors(&self, connectors: Option<Vec<String>>) {
+        *self.state.bar_connectors.borrow_mut() = connectors;
+        self.state.bar_changed();
+    }
+
+    fn handle_minimize(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.minimize();
+        Ok(())
+    }
+
+    fn handle_unminimize_last(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.unminimize_last();
+        Ok(())
+    }
+
+    fn handle_save_layout(&self, workspace: WorkspaceSource, path: &str) -> Result<(), CphError> {
+        if let Some(ws) = self.get_workspace_node(workspace)? {
+            save_layout(&ws, path)?;
+        }
+        Ok(())
+    }
+
+    fn handle_load_layout(&self, workspace: WorkspaceSource, path: &str) -> Result<(), CphError> {
+        let ws = match self.get_workspace_node(workspace)? {
+            Some(ws) => ws,
+            _ => {
+                let WorkspaceSource::Explicit(ws) = workspace else {
+                    return Ok(());
+                };
+                let name = self.get_workspace(ws)?;
+                let Some(output) = self.state.root.outputs.lock().values().next().cloned() else {
+                    return Ok(());
+                };
+                output.create_workspace(&name)
+            }
+        };
+        load_layout(&self.state, &ws, path)?;
+        Ok(())
+    }
+
+    fn handle_set_show_marks(&self, show: bool) {
+        self.state.show_marks.set(show);
+        self.colors_changed();
+    }
+
+    fn handle_set_title_format(&self, format: Option<&str>) {
+        *self.state.title_format.borrow_mut() = format.map(|f| f.to_string());
+        self.colors_changed();
+    }
+
+    fn handle_set_hidden_title_app_ids(&self, app_ids: Vec<String>) {
+        *self.state.hidden_title_app_ids.borrow_mut() = app_ids;
+        self.spaces_change();
+    }
+
+    fn handle_toggle_title_hidden(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.toggle_title_hidden();
+        Ok(())
+    }
+
+    fn handle_set_show_title_buttons(&self, show: bool) {
+        self.state.show_title_buttons.set(show);
+        self.colors_changed();
+    }
+
     fn handle_get_split(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         self.respond(Response::GetSplit {
@@ -1490,6 +1923,7 @@ impl ConfigProxyHandler {
         }
         self.state.root.clone().node_visit(&mut V);
         self.state.damage(self.state.root.extents.get());
+        self.state.update_blur_capabilities();
     }
 
     fn colors_changed(&self) {
@@ -1513,6 +1947,12 @@ impl ConfigProxyHandler {
         let sized = match sized {
             TITLE_HEIGHT => ThemeSized::title_height,
             BORDER_WIDTH => ThemeSized::border_width,
+            CORNER_RADIUS => ThemeSized::corner_radius,
+            SHADOW_BLUR => ThemeSized::shadow_blur,
+            SHADOW_OFFSET_X => ThemeSized::shadow_offset_x,
+            SHADOW_OFFSET_Y => ThemeSized::shadow_offset_y,
+            BLUR_PASSES => ThemeSized::blur_passes,
+            BLUR_OFFSET => ThemeSized::blur_offset,
             _ => return Err(CphError::UnknownSized(sized.0)),
         };
         Ok(sized)
@@ -1549,14 +1989,36 @@ impl ConfigProxyHandler {
     }
 
     fn handle_reset_font(&self) {
-        self.state
-            .theme
-            .font
-            .set(self.state.theme.default_font.clone());
+        self.state.theme.reset_fonts();
     }
 
     fn handle_set_font(&self, font: &str) {
         self.state.theme.font.set(Arc::new(font.to_string()));
+        self.state.theme.fonts_changed();
+    }
+
+    fn handle_set_title_font(&self, font: &str) {
+        let theme = &self.state.theme;
+        theme.title_font.set(Some(Arc::new(font.to_string())));
+        theme.fonts_changed();
+    }
+
+    fn handle_set_bar_font(&self, font: &str) {
+        let theme = &self.state.theme;
+        theme.bar_font.set(Some(Arc::new(font.to_string())));
+        theme.fonts_changed();
+    }
+
+    fn handle_set_status_font(&self, font: &str) {
+        let theme = &self.state.theme;
+        theme.status_font.set(Some(Arc::new(font.to_string())));
+        theme.fonts_changed();
+    }
+
+    fn handle_set_font_fallbacks(&self, fonts: Vec<String>) {
+        let theme = &self.state.theme;
+        *theme.font_fallbacks.borrow_mut() = fonts;
+        theme.fonts_changed();
     }
 
     fn handle_get_font(&self) {
@@ -1585,13 +2047,20 @@ impl ConfigProxyHandler {
             BAR_STATUS_TEXT_COLOR => &colors.bar_text,
             ATTENTION_REQUESTED_BACKGROUND_COLOR => &colors.attention_requested_background,
             HIGHLIGHT_COLOR => &colors.highlight,
+            SHADOW_COLOR => &colors.shadow,
+            INACTIVE_DIM_COLOR => &colors.inactive_dim,
+            TITLE_BUTTON_HOVER_COLOR => &colors.title_button_hover,
             _ => return Err(CphError::UnknownColor(colorable.0)),
         };
         Ok(colorable)
     }
 
     fn handle_get_color(&self, colorable: Colorable) -> Result<(), CphError> {
-        let color = self.get_color(colorable)?.get();
+        use jay_config::theme::colors::FOCUSED_BORDER_COLOR;
+        let color = match colorable {
+            FOCUSED_BORDER_COLOR => self.state.theme.colors.focused_border(),
+            _ => self.get_color(colorable)?.get(),
+        };
         let color =
             jay_config::theme::Color::new_f32_premultiplied(color.r, color.g, color.b, color.a);
         self.respond(Response::GetColor { color });
@@ -1603,7 +2072,16 @@ impl ConfigProxyHandler {
         colorable: Colorable,
         color: jay_config::theme::Color,
     ) -> Result<(), CphError> {
-        self.get_color(colorable)?.set(color.into());
+        use jay_config::theme::colors::FOCUSED_BORDER_COLOR;
+        match colorable {
+            FOCUSED_BORDER_COLOR => self
+                .state
+                .theme
+                .colors
+                .focused_border
+                .set(Some(color.into())),
+            _ => self.get_color(colorable)?.set(color.into()),
+        }
         self.colors_changed();
         Ok(())
     }
@@ -1980,6 +2458,160 @@ impl ConfigProxyHandler {
             ClientMessage::SetXScalingMode { mode } => self
                 .handle_set_x_scaling_mode(mode)
                 .wrn("set_x_scaling_mode")?,
+            ClientMessage::Resize { seat, axis, amount } => {
+                self.handle_resize(seat, axis, amount).wrn("resize")?
+            }
+            ClientMessage::SetDefaultWorkspaceLayout { layout } => self
+                .handle_set_default_workspace_layout(layout)
+                .wrn("set_default_workspace_layout")?,
+            ClientMessage::SetWorkspaceLayout { workspace, layout } => self
+                .handle_set_workspace_layout(workspace, layout)
+                .wrn("set_workspace_layout")?,
+            ClientMessage::GetWorkspaceLayout { workspace } => self
+                .handle_get_workspace_layout(workspace)
+                .wrn("get_workspace_layout")?,
+            ClientMessage::CycleWorkspaceLayout { workspace } => self
+                .handle_cycle_workspace_layout(workspace)
+                .wrn("cycle_workspace_layout")?,
+            ClientMessage::SetWorkspaceMasterCount { workspace, count } => self
+                .handle_set_workspace_master_count(workspace, count)
+                .wrn("set_workspace_master_count")?,
+            ClientMessage::GetWorkspaceMasterCount { workspace } => self
+                .handle_get_workspace_master_count(workspace)
+                .wrn("get_workspace_master_count")?,
+            ClientMessage::SetWorkspaceMasterRatio { workspace, ratio } => self
+                .handle_set_workspace_master_ratio(workspace, ratio)
+                .wrn("set_workspace_master_ratio")?,
+            ClientMessage::GetWorkspaceMasterRatio { workspace } => self
+                .handle_get_workspace_master_ratio(workspace)
+                .wrn("get_workspace_master_ratio")?,
+            ClientMessage::PromoteToMaster { seat } => self
+                .handle_promote_to_master(seat)
+                .wrn("promote_to_master")?,
+            ClientMessage::SetColumnWidthPresets { presets } => self
+                .handle_set_column_width_presets(presets)
+                .wrn("set_column_width_presets")?,
+            ClientMessage::CycleColumnWidth { seat } => self
+                .handle_cycle_column_width(seat)
+                .wrn("cycle_column_width")?,
+            ClientMessage::GetStacked { seat } => {
+                self.handle_get_stacked(seat).wrn("get_stacked")?
+            }
+            ClientMessage::SetStacked { seat, stacked } => {
+                self.handle_set_stacked(seat, stacked).wrn("set_stacked")?
+            }
+            ClientMessage::ToggleLayout { seat } => {
+                self.handle_toggle_layout(seat).wrn("toggle_layout")?
+            }
+            ClientMessage::Mark { seat, mark } => self.handle_mark(seat, mark).wrn("mark")?,
+            ClientMessage::Unmark { seat } => self.handle_unmark(seat).wrn("unmark")?,
+            ClientMessage::GetMarks { seat } => self.handle_get_marks(seat).wrn("get_marks")?,
+            ClientMessage::FocusMark { seat, mark } => {
+                self.handle_focus_mark(seat, mark).wrn("focus_mark")?
+            }
+            ClientMessage::SwapWithMark { seat, mark } => self
+                .handle_swap_with_mark(seat, mark)
+                .wrn("swap_with_mark")?,
+            ClientMessage::MoveToMark { seat, mark } => {
+                self.handle_move_to_mark(seat, mark).wrn("move_to_mark")?
+            }
+            ClientMessage::SetShowMarks { show } => self.handle_set_show_marks(show),
+            ClientMessage::FocusPrevious { seat } => {
+                self.handle_focus_previous(seat).wrn("focus_previous")?
+            }
+            ClientMessage::FocusNextInHistory { seat } => self
+                .handle_focus_next_in_history(seat)
+                .wrn("focus_next_in_history")?,
+            ClientMessage::WindowSwitcher { seat, reverse } => self
+                .handle_window_switcher(seat, reverse)
+                .wrn("window_switcher")?,
+            ClientMessage::GetSticky { seat } => self.handle_get_sticky(seat).wrn("get_sticky")?,
+            ClientMessage::SetSticky { seat, sticky } => {
+                self.handle_set_sticky(seat, sticky).wrn("set_sticky")?
+            }
+            ClientMessage::Minimize { seat } => self.handle_minimize(seat).wrn("minimize")?,
+            ClientMessage::UnminimizeLast { seat } => {
+                self.handle_unminimize_last(seat).wrn("unminimize_last")?
+            }
+            ClientMessage::SetSwallowTerminals { app_ids } => {
+                *self.state.swallow_terminals.borrow_mut() = app_ids
+            }
+            ClientMessage::SetSwallowExceptions { app_ids } => {
+                *self.state.swallow_exceptions.borrow_mut() = app_ids
+            }
+            ClientMessage::SaveLayout { workspace, path } => self
+                .handle_save_layout(workspace, path)
+                .wrn("save_layout")?,
+            ClientMessage::LoadLayout { workspace, path } => self
+                .handle_load_layout(workspace, path)
+                .wrn("load_layout")?,
+            ClientMessage::ConnectorSetWallpaper {
+                connector,
+                path,
+                mode,
+            } => self
+                .handle_connector_set_wallpaper(connector, path, mode)
+                .wrn("connector_set_wallpaper")?,
+            ClientMessage::SetAnimationsEnabled { enabled } => {
+                self.handle_set_animations_enabled(enabled)
+            }
+            ClientMessage::SetAnimationDuration { kind, duration } => self
+                .handle_set_animation_duration(kind, duration)
+                .wrn("set_animation_duration")?,
+            ClientMessage::SetAnimationCurve { kind, curve } => self
+                .handle_set_animation_curve(kind, curve)
+                .wrn("set_animation_curve")?,
+            ClientMessage::SetAnimationsInScreencasts { enabled } => {
+                self.handle_set_animations_in_screencasts(enabled)
+            }
+            ClientMessage::SetBlurAppIds { app_ids } => {
+                *self.state.blur_app_ids.borrow_mut() = app_ids;
+                self.state.damage(self.state.root.extents.get());
+            }
+            ClientMessage::SetBlurLayerNamespaces { namespaces } => {
+                *self.state.blur_layer_namespaces.borrow_mut() = namespaces;
+                self.state.damage(self.state.root.extents.get());
+            }
+            ClientMessage::SetWindowOpacities { opacities } => {
+                self.handle_set_window_opacities(opacities)
+            }
+            ClientMessage::SetWindowOpacity { seat, opacity } => self
+                .handle_set_window_opacity(seat, opacity)
+                .wrn("set_window_opacity")?,
+            ClientMessage::SetTitleFormat { format } => self.handle_set_title_format(format),
+            ClientMessage::SetHiddenTitleAppIds { app_ids } => {
+                self.handle_set_hidden_title_app_ids(app_ids)
+            }
+            ClientMessage::ToggleTitleHidden { seat } => self
+                .handle_toggle_title_hidden(seat)
+                .wrn("toggle_title_hidden")?,
+            ClientMessage::SetShowTitleButtons { show } => self.handle_set_show_title_buttons(show),
+            ClientMessage::SetWindowBorders { borders } => self.handle_set_window_borders(borders),
+            ClientMessage::SetWindowBorder { seat, border } => self
+                .handle_set_window_border(seat, border)
+                .wrn("set_window_border")?,
+            ClientMessage::SetBarPosition { position } => self
+                .handle_set_bar_position(position)
+                .wrn("set_bar_position")?,
+            ClientMessage::SetBarMode { mode } => {
+                self.handle_set_bar_mode(mode).wrn("set_bar_mode")?
+            }
+            ClientMessage::SetBarModifiers { modifiers } => {
+                self.handle_set_bar_modifiers(modifiers)
+            }
+            ClientMessage::SetBarConnectors { connectors } => {
+                self.handle_set_bar_connectors(connectors)
+            }
+            ClientMessage::SetStatusBlocks { blocks, separator } => {
+                self.handle_set_status_blocks(blocks, separator)
+            }
+            ClientMessage::SetTitleFont { font } => self.handle_set_title_font(font),
+            ClientMessage::SetBarFont { font } => self.handle_set_bar_font(font),
+            ClientMessage::SetStatusFont { font } => self.handle_set_status_font(font),
+            ClientMessage::SetFontFallbacks { fonts } => self.handle_set_font_fallbacks(fonts),
+            ClientMessage::SetStickyAppIds { app_ids } => {
+                *self.state.sticky_app_ids.borrow_mut() = app_ids;
+            }
         }
         Ok(())
     }
@@ -2033,6 +2665,8 @@ enum CphError {
     FailedRequest(&'static str, #[source] Box<Self>),
     #[error(transparent)]
     TimerError(#[from] TimerError),
+    #[error(transparent)]
+    SavedLayoutError(#[from] SavedLayoutError),
     #[error("The requested monitor scale {0} is too small")]
     ScaleTooSmall(f64),
     #[error("The requested monitor scale {0} is too large")]
@@ -2043,6 +2677,12 @@ enum CphError {
     PollableDoesNotExist,
     #[error("Unknown VRR mode {0:?}")]
     UnknownVrrMode(ConfigVrrMode),
+    #[error("Unknown wallpaper mode {0:?}")]
+    UnknownWallpaperMode(ConfigWallpaperMode),
+    #[error("Unknown animation kind {0:?}")]
+    UnknownAnimationKind(ConfigAnimationKind),
+    #[error("Invalid animation curve {0:?}")]
+    InvalidAnimationCurve(ConfigAnimationCurve),
     #[error("Invalid cursor hz {0}")]
     InvalidCursorHz(f64),
     #[error("Unknown tearing mode {0:?}")]
@@ -2051,6 +2691,16 @@ enum CphError {
     UnknownFormat(ConfigFormat),
     #[error("Unknown x scaling mode {0:?}")]
     UnknownXScalingMode(XScalingMode),
+    #[error("Unknown workspace layout {0:?}")]
+    UnknownWorkspaceLayout(ConfigWorkspaceLayout),
+    #[error("Invalid master ratio {0}")]
+    InvalidMasterRatio(f64),
+    #[error("Column width {0} is not in the range (0, 1]")]
+    InvalidColumnWidth(f64),
+    #[error("Unknown bar position {0:?}")]
+    UnknownBarPosition(ConfigBarPosition),
+    #[error("Unknown bar mode {0:?}")]
+    UnknownBarMode(ConfigBarMode),
 }
 
 trait WithRequestName {
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         async_engine::AsyncEngine,
@@ -53,6 +56,46 @@ pub async fn visualize_damage(state: Rc<State>) {
     }
 }
 
+/// Returns the area whose rendering can change when the contents of `rect` change.
+///
+/// Blurred backgrounds sample the contents around them. If the damage is close enough
+/// to a blurred area to be sampled, it is therefore expanded by the distance that the
+/// samples of the blur can reach.
+pub fn expand_damage(state: &State, rect: Rect) -> Rect {
+    let sizes = &state.theme.sizes;
+    let passes = sizes.blur_passes.get();
+    if passes <= 0 || rect.is_empty() {
+        return rect;
+    }
+    let expanded = expand_rect(rect, blur_margin(passes, sizes.blur_offset.get()));
+    let outputs = state.root.outputs.lock();
+    let blurred = outputs
+        .values()
+        .any(|o| intersects_any(&expanded, &o.blurred_areas.borrow()));
+    match blurred {
+        true => expanded,
+        false => rect,
+    }
+}
+
+/// Returns the distance that the samples of the blur can reach.
+fn blur_margin(passes: i32, offset: i32) -> i32 {
+    (offset + 1) * (2 << passes)
+}
+
+fn expand_rect(rect: Rect, margin: i32) -> Rect {
+    Rect::new_unchecked(
+        rect.x1().saturating_sub(margin),
+        rect.y1().saturating_sub(margin),
+        rect.x2().saturating_add(margin),
+        rect.y2().saturating_add(margin),
+    )
+}
+
+fn intersects_any(rect: &Rect, areas: &[Rect]) -> bool {
+    areas.iter().any(|a| a.intersects(rect))
+}
+
 fn damage_all(state: &State) {
     for connector in state.connectors.lock().values() {
         if connector.connected.get() {
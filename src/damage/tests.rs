@@ -0,0 +1,32 @@
+use crate::{
+    damage::{blur_margin, expand_rect, intersects_any},
+    rect::Rect,
+};
+
+#[test]
+fn margin() {
+    assert_eq!(blur_margin(1, 0), 4);
+    assert_eq!(blur_margin(3, 2), 48);
+}
+
+#[test]
+fn expand() {
+    let rect = Rect::new_unchecked(10, 20, 30, 40);
+    assert_eq!(expand_rect(rect, 5), Rect::new_unchecked(5, 15, 35, 45));
+    let rect = Rect::new_unchecked(i32::MIN + 1, 0, i32::MAX - 1, 1);
+    assert_eq!(
+        expand_rect(rect, 5),
+        Rect::new_unchecked(i32::MIN, -5, i32::MAX, 6),
+    );
+}
+
+#[test]
+fn only_near_blur() {
+    let blurred = [Rect::new_unchecked(100, 100, 200, 200)];
+    let near = Rect::new_unchecked(80, 80, 90, 90);
+    let far = Rect::new_unchecked(0, 0, 10, 10);
+    assert!(!intersects_any(&near, &blurred));
+    assert!(intersects_any(&expand_rect(near, 16), &blurred));
+    assert!(!intersects_any(&expand_rect(far, 16), &blurred));
+    assert!(!intersects_any(&expand_rect(near, 16), &[]));
+}
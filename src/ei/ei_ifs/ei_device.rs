@@ -16,7 +16,7 @@ use {
             ei_device::{
                 ClientFrame, ClientStartEmulating, ClientStopEmulating, Destroyed, DeviceType,
                 Done, EiDeviceRequestHandler, Interface, Paused, Region, RegionMappingId, Release,
-                Resumed, ServerFrame, ServerStartEmulating,
+                Resumed, ServerFrame, ServerStartEmulating, ServerStopEmulating,
             },
             EiDeviceId,
         },
@@ -89,6 +89,13 @@ impl EiDevice {
         });
     }
 
+    pub fn send_stop_emulating(&self, serial: u32) {
+        self.client.event(ServerStopEmulating {
+            self_id: self.id,
+            serial,
+        });
+    }
+
     pub fn send_region(&self, rect: Rect, scale: Scale) {
         self.client.event(Region {
             self_id: self.id,
@@ -64,6 +64,36 @@ impl EiSeat {
         self.context() == EiContext::Sender
     }
 
+    /// Returns whether seat events should be forwarded to this client.
+    ///
+    /// Input-capture clients only receive events while their capture is active on
+    /// this seat.
+    fn receives_events(&self) -> bool {
+        if self.is_sender() {
+            return false;
+        }
+        if !self.client.input_capture.get() {
+            return true;
+        }
+        match self.seat.input_capture() {
+            Some(ic) => ic.ei_client_id.get() == Some(self.client.id),
+            _ => false,
+        }
+    }
+
+    pub fn set_emulating(&self, sequence: Option<u32>) {
+        if self.is_sender() {
+            return;
+        }
+        let Some(device) = self.device.get() else {
+            return;
+        };
+        match sequence {
+            Some(sequence) => device.send_start_emulating(self.client.serial(), sequence),
+            None => device.send_stop_emulating(self.client.serial()),
+        }
+    }
+
     pub fn regions_changed(self: &Rc<Self>) {
         if self.touchscreen.is_none() && self.pointer_absolute.is_none() {
             return;
@@ -109,7 +139,7 @@ impl EiSeat {
         state: u32,
         kb_state: &KeyboardState,
     ) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         let old_id = self.kb_state_id.get();
@@ -123,7 +153,7 @@ impl EiSeat {
     }
 
     pub fn handle_motion_abs(&self, time_usec: u64, x: Fixed, y: Fixed) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(v) = self.pointer_absolute.get() {
@@ -133,7 +163,7 @@ impl EiSeat {
     }
 
     pub fn handle_motion(&self, time_usec: u64, dx: Fixed, dy: Fixed) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(v) = self.pointer.get() {
@@ -143,7 +173,7 @@ impl EiSeat {
     }
 
     pub fn handle_button(&self, time_usec: u64, button: u32, state: KeyState) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(b) = self.button.get() {
@@ -153,7 +183,7 @@ impl EiSeat {
     }
 
     pub fn handle_pending_scroll(&self, time_usec: u64, ps: &PendingScroll) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(b) = self.scroll.get() {
@@ -171,7 +201,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_down(&self, id: u32, x: Fixed, y: Fixed) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -180,7 +210,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_motion(&self, id: u32, x: Fixed, y: Fixed) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -189,7 +219,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_up(&self, id: u32) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -198,7 +228,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_frame(&self, time_usec: u64) {
-        if self.is_sender() {
+        if !self.receives_events() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -357,7 +387,7 @@ impl EiSeat {
         }
         device.send_done();
         device.send_resumed(self.client.serial());
-        if self.context() == EiContext::Receiver {
+        if self.receives_events() {
             device.send_start_emulating(self.client.serial(), 1);
         }
         if let Some(kb) = self.keyboard.get() {
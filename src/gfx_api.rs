@@ -36,6 +36,7 @@ pub enum GfxApiOpt {
     Sync,
     FillRect(FillRect),
     CopyTexture(CopyTexture),
+    Blur(Blur),
 }
 
 pub struct GfxRenderPass {
@@ -167,10 +168,35 @@ impl FramebufferRect {
     }
 }
 
+/// A rectangle with rounded corners that an operation is clipped to.
+///
+/// All coordinates are framebuffer pixels before the output transform has been applied.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RoundedClip {
+    /// The target rectangle of the operation as `[x1, y1, x2, y2]`.
+    pub target: [f32; 4],
+    /// The rectangle that the operation is clipped to as `[x1, y1, x2, y2]`.
+    pub rect: [f32; 4],
+    pub radius: f32,
+    /// The width of the transition from opaque to transparent at the edge of the
+    /// rectangle.
+    pub blur: f32,
+}
+
+impl RoundedClip {
+    /// Returns the corners of the target in the same order as
+    /// [`FramebufferRect::to_points`].
+    pub fn target_points(&self) -> [[f32; 2]; 4] {
+        let [x1, y1, x2, y2] = self.target;
+        [[x2, y1], [x1, y1], [x2, y2], [x1, y2]]
+    }
+}
+
 #[derive(Debug)]
 pub struct FillRect {
     pub rect: FramebufferRect,
     pub color: Color,
+    pub clip: Option<RoundedClip>,
 }
 
 pub struct CopyTexture {
@@ -181,6 +207,18 @@ pub struct CopyTexture {
     pub acquire_sync: AcquireSync,
     pub release_sync: ReleaseSync,
     pub alpha: Option<f32>,
+    pub clip: Option<RoundedClip>,
+}
+
+/// Blurs the contents of the framebuffer that have been rendered so far.
+#[derive(Debug)]
+pub struct Blur {
+    pub rect: FramebufferRect,
+    pub clip: Option<RoundedClip>,
+    /// The number of times the contents are downsampled and upsampled.
+    pub passes: u32,
+    /// The distance of the samples from the center in texels.
+    pub offset: f32,
 }
 
 #[derive(Clone, Debug)]
@@ -466,6 +504,7 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            blurred_areas: Default::default(),
         };
         cursor.render_hardware_cursor(&mut renderer);
         self.render(acquire_sync, release_sync, &ops, Some(&Color::TRANSPARENT))
@@ -734,6 +773,7 @@ pub fn create_render_pass(
             let (width, height) = logical_size(physical_size, transform);
             Rect::new(0, 0, width, height).unwrap()
         },
+        blurred_areas: Default::default(),
     };
     node.node_render(&mut renderer, 0, 0, None);
     if let Some(rect) = cursor_rect {
@@ -807,6 +847,9 @@ pub fn renderer_base<'a>(
         transform,
         fb_width: width as _,
         fb_height: height as _,
+        alpha: None,
+        clip: None,
+        scissor: None,
     }
 }
 
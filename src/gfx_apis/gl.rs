@@ -69,7 +69,7 @@ use {
     crate::{
         gfx_api::{
             AcquireSync, CopyTexture, FillRect, GfxApiOpt, GfxContext, GfxError, GfxTexture,
-            ReleaseSync, SyncFile,
+            ReleaseSync, RoundedClip, SyncFile,
         },
         gfx_apis::gl::{
             egl::image::EglImage,
@@ -226,7 +226,8 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
         copy_tex.clear();
         while i < ops.len() {
             match &ops[i] {
-                GfxApiOpt::Sync => {
+                GfxApiOpt::Sync | GfxApiOpt::Blur(_) => {
+                    // The GL renderer does not support blurring.
                     i += 1;
                     if has_ops!() {
                         break;
@@ -250,6 +251,10 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
                 let mut color = None;
                 while i < fill_rect.len() {
                     let fr = fill_rect[i];
+                    if fr.clip.is_some() {
+                        i += 1;
+                        continue;
+                    }
                     match color {
                         None => color = Some(fr.color),
                         Some(c) if c == fr.color => {}
@@ -270,6 +275,11 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
                     fill_boxes3(&fb.ctx, triangles, &color);
                 }
             }
+            for fr in &*fill_rect {
+                if let Some(clip) = &fr.clip {
+                    fill_rounded(&fb.ctx, fr, clip);
+                }
+            }
         }
         for tex in &*copy_tex {
             render_texture(&fb.ctx, tex);
@@ -317,6 +327,37 @@ fn fill_boxes3(ctx: &GlRenderContext, boxes: &[[f32; 2]], color: &Color) {
     }
 }
 
+fn fill_rounded(ctx: &GlRenderContext, fr: &FillRect, clip: &RoundedClip) {
+    let gles = ctx.ctx.dpy.gles;
+    let prog = &ctx.fill_rounded_prog;
+    let pos = fr.rect.to_points();
+    let local = clip.target_points();
+    let [x1, y1, x2, y2] = clip.rect;
+    let color = &fr.color;
+    unsafe {
+        (gles.glEnable)(GL_BLEND);
+        (gles.glUseProgram)(prog.prog.prog);
+        (gles.glUniform4f)(prog.color, color.r, color.g, color.b, color.a);
+        (gles.glUniform4f)(prog.rounded.clip, x1, y1, x2, y2);
+        (gles.glUniform1f)(prog.rounded.radius, clip.radius);
+        (gles.glUniform1f)(prog.blur, clip.blur);
+        (gles.glVertexAttribPointer)(prog.pos as _, 2, GL_FLOAT, GL_FALSE, 0, pos.as_ptr() as _);
+        (gles.glVertexAttribPointer)(
+            prog.rounded.local as _,
+            2,
+            GL_FLOAT,
+            GL_FALSE,
+            0,
+            local.as_ptr() as _,
+        );
+        (gles.glEnableVertexAttribArray)(prog.pos as _);
+        (gles.glEnableVertexAttribArray)(prog.rounded.local as _);
+        (gles.glDrawArrays)(GL_TRIANGLE_STRIP, 0, 4);
+        (gles.glDisableVertexAttribArray)(prog.pos as _);
+        (gles.glDisableVertexAttribArray)(prog.rounded.local as _);
+    }
+}
+
 fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
     let texture = tex.tex.as_gl();
     if !texture.gl.contents_valid.get() {
@@ -345,9 +386,10 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
             },
             false => &ctx.tex_internal,
         };
-        let copy_type = match tex.alpha.is_some() {
-            true => TexCopyType::Multiply,
-            false => TexCopyType::Identity,
+        let copy_type = match (tex.clip.is_some(), tex.alpha.is_some()) {
+            (true, _) => TexCopyType::Rounded,
+            (false, true) => TexCopyType::Multiply,
+            (false, false) => TexCopyType::Identity,
         };
         let source_type = match texture.gl.format.has_alpha {
             true => TexSourceType::HasAlpha,
@@ -367,8 +409,24 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
         let texcoord = tex.source.to_points();
         let pos = tex.target.to_points();
 
-        if let Some(alpha) = tex.alpha {
-            (gles.glUniform1f)(prog.alpha, alpha);
+        if copy_type != TexCopyType::Identity {
+            (gles.glUniform1f)(prog.alpha, tex.alpha.unwrap_or(1.0));
+        }
+
+        let local = tex.clip.map(|clip| clip.target_points());
+        if let (Some(clip), Some(local), Some(rounded)) = (&tex.clip, &local, &prog.rounded) {
+            let [x1, y1, x2, y2] = clip.rect;
+            (gles.glUniform4f)(rounded.clip, x1, y1, x2, y2);
+            (gles.glUniform1f)(rounded.radius, clip.radius);
+            (gles.glVertexAttribPointer)(
+                rounded.local as _,
+                2,
+                GL_FLOAT,
+                GL_FALSE,
+                0,
+                local.as_ptr() as _,
+            );
+            (gles.glEnableVertexAttribArray)(rounded.local as _);
         }
 
         (gles.glVertexAttribPointer)(
@@ -388,6 +446,9 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
 
         (gles.glDisableVertexAttribArray)(prog.texcoord as _);
         (gles.glDisableVertexAttribArray)(prog.pos as _);
+        if let Some(rounded) = &prog.rounded {
+            (gles.glDisableVertexAttribArray)(rounded.local as _);
+        }
 
         (gles.glBindTexture)(target, 0);
     }
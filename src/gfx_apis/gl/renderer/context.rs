@@ -40,30 +40,65 @@ pub(crate) struct TexProg {
     pub(crate) texcoord: GLint,
     pub(crate) tex: GLint,
     pub(crate) alpha: GLint,
+    pub(crate) rounded: Option<RoundedLocations>,
+}
+
+/// The locations of the inputs of the programs that clip to rectangles with rounded
+/// corners.
+pub(crate) struct RoundedLocations {
+    pub(crate) local: GLint,
+    pub(crate) clip: GLint,
+    pub(crate) radius: GLint,
+}
+
+impl RoundedLocations {
+    unsafe fn from(prog: &GlProgram) -> Self {
+        unsafe {
+            Self {
+                local: prog.get_attrib_location(c"local"),
+                clip: prog.get_uniform_location(c"clip"),
+                radius: prog.get_uniform_location(c"radius"),
+            }
+        }
+    }
 }
 
 impl TexProg {
-    unsafe fn from(prog: GlProgram, alpha_multiplier: bool) -> Self {
+    unsafe fn from(prog: GlProgram, copy_type: TexCopyType) -> Self {
         unsafe {
-            let alpha = match alpha_multiplier {
-                true => prog.get_uniform_location(c"alpha"),
-                false => 0,
+            let alpha = match copy_type {
+                TexCopyType::Multiply | TexCopyType::Rounded => prog.get_uniform_location(c"alpha"),
+                TexCopyType::Identity => 0,
+            };
+            let rounded = match copy_type {
+                TexCopyType::Rounded => Some(RoundedLocations::from(&prog)),
+                _ => None,
             };
             Self {
                 pos: prog.get_attrib_location(c"pos"),
                 texcoord: prog.get_attrib_location(c"texcoord"),
                 tex: prog.get_uniform_location(c"tex"),
                 alpha,
+                rounded,
                 prog,
             }
         }
     }
 }
 
+pub(crate) struct RoundedFillProg {
+    pub(crate) prog: GlProgram,
+    pub(crate) pos: GLint,
+    pub(crate) color: GLint,
+    pub(crate) blur: GLint,
+    pub(crate) rounded: RoundedLocations,
+}
+
 #[derive(Copy, Clone, PartialEq, Enum)]
 pub(in crate::gfx_apis::gl) enum TexCopyType {
     Identity,
     Multiply,
+    Rounded,
 }
 
 #[derive(Copy, Clone, PartialEq, Enum)]
@@ -86,6 +121,8 @@ pub(in crate::gfx_apis::gl) struct GlRenderContext {
     pub(crate) fill_prog_pos: GLint,
     pub(crate) fill_prog_color: GLint,
 
+    pub(crate) fill_rounded_prog: RoundedFillProg,
+
     pub(in crate::gfx_apis::gl) gl_state: RefCell<GfxGlState>,
 
     pub(in crate::gfx_apis::gl) buffer_resv_user: BufferResvUser,
@@ -116,34 +153,47 @@ impl GlRenderContext {
     }
 
     unsafe fn new(ctx: &Rc<EglContext>, node: &Rc<CString>) -> Result<Self, RenderError> {
+        const ROUNDED: &str = "#define ROUNDED\n";
         let tex_vert = include_str!("../shaders/tex.vert.glsl");
         let tex_frag = include_str!("../shaders/tex.frag.glsl");
+        let tex_vert_rounded = format!("{ROUNDED}{tex_vert}");
         let create_programs = |external: bool| {
-            let create_program = |alpha_multiplier: bool, alpha: bool| {
+            let create_program = |copy_type: TexCopyType, alpha: bool| {
                 let mut tex_frac_src = String::new();
                 if external {
                     tex_frac_src.push_str("#define EXTERNAL\n");
                 }
-                if alpha_multiplier {
+                if copy_type != TexCopyType::Identity {
                     tex_frac_src.push_str("#define ALPHA_MULTIPLIER\n");
                 }
+                if copy_type == TexCopyType::Rounded {
+                    tex_frac_src.push_str(ROUNDED);
+                }
                 if alpha {
                     tex_frac_src.push_str("#define ALPHA\n");
                 }
                 tex_frac_src.push_str(tex_frag);
+                let tex_vert = match copy_type {
+                    TexCopyType::Rounded => &tex_vert_rounded,
+                    _ => tex_vert,
+                };
                 unsafe {
                     let prog = GlProgram::from_shaders(ctx, tex_vert, &tex_frac_src)?;
-                    Ok::<_, RenderError>(TexProg::from(prog, alpha_multiplier))
+                    Ok::<_, RenderError>(TexProg::from(prog, copy_type))
                 }
             };
             Ok::<_, RenderError>(enum_map! {
                 TexCopyType::Identity => enum_map! {
-                    TexSourceType::Opaque => create_program(false, false)?,
-                    TexSourceType::HasAlpha => create_program(false, true)?,
+                    TexSourceType::Opaque => create_program(TexCopyType::Identity, false)?,
+                    TexSourceType::HasAlpha => create_program(TexCopyType::Identity, true)?,
                 },
                 TexCopyType::Multiply => enum_map! {
-                    TexSourceType::Opaque => create_program(true, false)?,
-                    TexSourceType::HasAlpha => create_program(true, true)?,
+                    TexSourceType::Opaque => create_program(TexCopyType::Multiply, false)?,
+                    TexSourceType::HasAlpha => create_program(TexCopyType::Multiply, true)?,
+                },
+                TexCopyType::Rounded => enum_map! {
+                    TexSourceType::Opaque => create_program(TexCopyType::Rounded, false)?,
+                    TexSourceType::HasAlpha => create_program(TexCopyType::Rounded, true)?,
                 },
             })
         };
@@ -153,12 +203,22 @@ impl GlRenderContext {
         } else {
             None
         };
-        let fill_prog = unsafe {
-            GlProgram::from_shaders(
+        let fill_vert = include_str!("../shaders/fill.vert.glsl");
+        let fill_frag = include_str!("../shaders/fill.frag.glsl");
+        let fill_prog = unsafe { GlProgram::from_shaders(ctx, fill_vert, fill_frag)? };
+        let fill_rounded_prog = unsafe {
+            let prog = GlProgram::from_shaders(
                 ctx,
-                include_str!("../shaders/fill.vert.glsl"),
-                include_str!("../shaders/fill.frag.glsl"),
-            )?
+                &format!("{ROUNDED}{fill_vert}"),
+                &format!("{ROUNDED}{fill_frag}"),
+            )?;
+            RoundedFillProg {
+                pos: prog.get_attrib_location(c"pos"),
+                color: prog.get_uniform_location(c"color"),
+                blur: prog.get_uniform_location(c"blur"),
+                rounded: RoundedLocations::from(&prog),
+                prog,
+            }
         };
         Ok(Self {
             ctx: ctx.clone(),
@@ -174,6 +234,8 @@ impl GlRenderContext {
             fill_prog_color: unsafe { fill_prog.get_uniform_location(c"color") },
             fill_prog,
 
+            fill_rounded_prog,
+
             gl_state: Default::default(),
 
             buffer_resv_user: Default::default(),
@@ -1,6 +1,29 @@
+#if defined(ROUNDED) && defined(GL_FRAGMENT_PRECISION_HIGH)
+precision highp float;
+#else
 precision mediump float;
+#endif
 uniform vec4 color;
+#ifdef ROUNDED
+varying vec2 v_local;
+uniform vec4 clip;
+uniform float radius;
+uniform float blur;
+
+float rounded_coverage(vec2 p, vec4 rect, float radius, float blur) {
+	vec2 center = (rect.xy + rect.zw) * 0.5;
+	vec2 half_size = (rect.zw - rect.xy) * 0.5;
+	float r = min(radius, min(half_size.x, half_size.y));
+	vec2 q = abs(p - center) - half_size + r;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - r;
+	return 1.0 - smoothstep(-blur * 0.5, blur * 0.5, d);
+}
+#endif
 
 void main() {
+#ifdef ROUNDED
+	gl_FragColor = color * rounded_coverage(v_local, clip, radius, blur);
+#else
 	gl_FragColor = color;
+#endif
 }
@@ -1,5 +1,12 @@
 attribute vec2 pos;
+#ifdef ROUNDED
+attribute vec2 local;
+varying vec2 v_local;
+#endif
 
 void main() {
 	gl_Position = vec4(pos, 0.0, 1.0);
+#ifdef ROUNDED
+	v_local = local;
+#endif
 }
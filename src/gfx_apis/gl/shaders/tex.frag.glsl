@@ -2,7 +2,11 @@
 #extension GL_OES_EGL_image_external : require
 #endif
 
+#if defined(ROUNDED) && defined(GL_FRAGMENT_PRECISION_HIGH)
+precision highp float;
+#else
 precision mediump float;
+#endif
 varying vec2 v_texcoord;
 #ifdef EXTERNAL
 uniform samplerExternalOES tex;
@@ -12,12 +16,32 @@ uniform sampler2D tex;
 #ifdef ALPHA_MULTIPLIER
 uniform float alpha;
 #endif
+#ifdef ROUNDED
+varying vec2 v_local;
+uniform vec4 clip;
+uniform float radius;
+
+float rounded_coverage(vec2 p, vec4 rect, float radius, float blur) {
+	vec2 center = (rect.xy + rect.zw) * 0.5;
+	vec2 half_size = (rect.zw - rect.xy) * 0.5;
+	float r = min(radius, min(half_size.x, half_size.y));
+	vec2 q = abs(p - center) - half_size + r;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - r;
+	return 1.0 - smoothstep(-blur * 0.5, blur * 0.5, d);
+}
+#endif
 
 void main() {
+#ifdef ROUNDED
+	float mul = alpha * rounded_coverage(v_local, clip, radius, 1.0);
+#elif defined(ALPHA_MULTIPLIER)
+	float mul = alpha;
+#endif
+
 #ifdef ALPHA
 
 #ifdef ALPHA_MULTIPLIER
-	gl_FragColor = texture2D(tex, v_texcoord) * alpha;
+	gl_FragColor = texture2D(tex, v_texcoord) * mul;
 #else // !ALPHA_MULTIPLIER
 	gl_FragColor = texture2D(tex, v_texcoord);
 #endif // ALPHA_MULTIPLIER
@@ -25,7 +49,7 @@ void main() {
 #else // !ALPHA
 
 #ifdef ALPHA_MULTIPLIER
-	gl_FragColor = vec4(texture2D(tex, v_texcoord).rgb * alpha, alpha);
+	gl_FragColor = vec4(texture2D(tex, v_texcoord).rgb * mul, mul);
 #else // !ALPHA_MULTIPLIER
 	gl_FragColor = vec4(texture2D(tex, v_texcoord).rgb, 1.0);
 #endif // ALPHA_MULTIPLIER
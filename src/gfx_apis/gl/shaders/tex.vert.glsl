@@ -1,8 +1,15 @@
 attribute vec2 pos;
 attribute vec2 texcoord;
 varying vec2 v_texcoord;
+#ifdef ROUNDED
+attribute vec2 local;
+varying vec2 v_local;
+#endif
 
 void main() {
 	gl_Position = vec4(pos, 0.0, 1.0);
 	v_texcoord = texcoord;
+#ifdef ROUNDED
+	v_local = local;
+#endif
 }
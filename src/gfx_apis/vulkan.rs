@@ -1,4 +1,5 @@
 mod allocator;
+mod blur;
 mod bo_allocator;
 mod command;
 mod descriptor;
@@ -0,0 +1,478 @@
+use {
+    crate::{
+        gfx_api::Blur,
+        gfx_apis::vulkan::{
+            allocator::VulkanAllocation,
+            device::VulkanDevice,
+            image::VulkanImage,
+            pipeline::VulkanPipeline,
+            renderer::{image_barrier, VulkanFormatPipelines, VulkanRenderer},
+            shaders::{
+                BlurFragPushConstants, BlurFragRoundedPushConstants, TexVertPushConstants,
+                TexVertRoundedPushConstants,
+            },
+            VulkanError,
+        },
+        utils::on_drop::OnDrop,
+    },
+    ash::vk::{
+        self, AccessFlags2, AttachmentLoadOp, AttachmentStoreOp, CommandBuffer, CopyImageInfo2,
+        DependencyInfoKHR, DescriptorImageInfo, DescriptorType, Extent2D, Extent3D, Image,
+        ImageAspectFlags, ImageCopy2, ImageCreateInfo, ImageLayout, ImageMemoryBarrier2,
+        ImageSubresourceLayers, ImageSubresourceRange, ImageTiling, ImageType, ImageUsageFlags,
+        ImageView, ImageViewCreateInfo, ImageViewType, Offset3D, PipelineBindPoint,
+        PipelineStageFlags2, Rect2D, RenderingAttachmentInfo, RenderingInfo, SampleCountFlags,
+        ShaderStageFlags, SharingMode, Viewport, WriteDescriptorSet,
+    },
+    gpu_alloc::UsageFlags,
+    std::{cell::Cell, rc::Rc, slice},
+    uapi::Packed,
+};
+
+/// The maximum number of times the contents are downsampled.
+pub const MAX_BLUR_PASSES: u32 = 8;
+
+/// The number of allocation points after which unused blur buffers are freed.
+const BLUR_BUFFERS_TTL: u64 = 1000;
+
+/// The intermediate images of the blur of a framebuffer.
+///
+/// Level 0 has the size of the framebuffer and every further level has half the size of
+/// the previous level.
+pub(super) struct VulkanBlurBuffers {
+    levels: Vec<VulkanBlurLevel>,
+    last_used: Cell<u64>,
+}
+
+struct VulkanBlurLevel {
+    device: Rc<VulkanDevice>,
+    image: Image,
+    view: ImageView,
+    width: u32,
+    height: u32,
+    _allocation: VulkanAllocation,
+}
+
+impl Drop for VulkanBlurLevel {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.device.destroy_image_view(self.view, None);
+            self.device.device.destroy_image(self.image, None);
+        }
+    }
+}
+
+impl VulkanRenderer {
+    fn get_blur_buffers(
+        &self,
+        fb: &VulkanImage,
+        passes: u32,
+    ) -> Result<Rc<VulkanBlurBuffers>, VulkanError> {
+        let now = self.last_point.get();
+        self.blur_buffers
+            .lock()
+            .retain(|_, b| b.last_used.get() + BLUR_BUFFERS_TTL >= now);
+        let key = (fb.format.vk_format, fb.width, fb.height);
+        if let Some(buffers) = self.blur_buffers.get(&key) {
+            if buffers.levels.len() > passes as usize {
+                buffers.last_used.set(now);
+                return Ok(buffers);
+            }
+        }
+        let mut levels = vec![];
+        let (mut width, mut height) = (fb.width, fb.height);
+        for _ in 0..=passes {
+            levels.push(self.create_blur_level(fb.format.vk_format, width, height)?);
+            width = width.div_ceil(2);
+            height = height.div_ceil(2);
+        }
+        let buffers = Rc::new(VulkanBlurBuffers {
+            levels,
+            last_used: Cell::new(now),
+        });
+        self.blur_buffers.set(key, buffers.clone());
+        Ok(buffers)
+    }
+
+    fn create_blur_level(
+        &self,
+        format: vk::Format,
+        width: u32,
+        height: u32,
+    ) -> Result<VulkanBlurLevel, VulkanError> {
+        let dev = &self.device.device;
+        let create_info = ImageCreateInfo::default()
+            .image_type(ImageType::TYPE_2D)
+            .format(format)
+            .mip_levels(1)
+            .array_layers(1)
+            .tiling(ImageTiling::OPTIMAL)
+            .samples(SampleCountFlags::TYPE_1)
+            .sharing_mode(SharingMode::EXCLUSIVE)
+            .initial_layout(ImageLayout::UNDEFINED)
+            .extent(Extent3D {
+                width,
+                height,
+                depth: 1,
+            })
+            .usage(
+                ImageUsageFlags::COLOR_ATTACHMENT
+                    | ImageUsageFlags::SAMPLED
+                    | ImageUsageFlags::TRANSFER_DST,
+            );
+        let image = unsafe { dev.create_image(&create_info, None) };
+        let image = image.map_err(VulkanError::CreateImage)?;
+        let destroy_image = OnDrop(|| unsafe { dev.destroy_image(image, None) });
+        let memory_requirements = unsafe { dev.get_image_memory_requirements(image) };
+        let allocation =
+            self.allocator
+                .alloc(&memory_requirements, UsageFlags::FAST_DEVICE_ACCESS, false)?;
+        let res = unsafe { dev.bind_image_memory(image, allocation.memory, allocation.offset) };
+        res.map_err(VulkanError::BindImageMemory)?;
+        let create_info = ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(ImageSubresourceRange {
+                aspect_mask: ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+        let view = unsafe { dev.create_image_view(&create_info, None) };
+        let view = view.map_err(VulkanError::CreateImageView)?;
+        destroy_image.forget();
+        Ok(VulkanBlurLevel {
+            device: self.device.clone(),
+            image,
+            view,
+            width,
+            height,
+            _allocation: allocation,
+        })
+    }
+
+    /// Blurs the contents of the framebuffer below `blur.rect`.
+    ///
+    /// This must be called while rendering to `fb`. Rendering to `fb` has been resumed
+    /// when this function returns.
+    pub(super) fn record_blur(
+        &self,
+        buf: CommandBuffer,
+        fb: &VulkanImage,
+        pipelines: &VulkanFormatPipelines,
+        blur: &Blur,
+    ) -> Result<(), VulkanError> {
+        zone!("record_blur");
+        let passes = blur.passes.min(MAX_BLUR_PASSES);
+        if passes == 0 {
+            return Ok(());
+        }
+        let (fb_width, fb_height) = (fb.width as f32, fb.height as f32);
+        let pos = blur.rect.to_points();
+        let px = pos.map(|[x, y]| [(x + 1.0) / 2.0 * fb_width, (y + 1.0) / 2.0 * fb_height]);
+        let [mut x1, mut y1, mut x2, mut y2] = [f32::MAX, f32::MAX, f32::MIN, f32::MIN];
+        for [x, y] in px {
+            x1 = x1.min(x);
+            y1 = y1.min(y);
+            x2 = x2.max(x);
+            y2 = y2.max(y);
+        }
+        // The samples of the downsampling and upsampling passes reach at most this far beyond
+        // the blurred rectangle.
+        let margin = (blur.offset + 1.0) * (2u32 << passes) as f32;
+        let region = [
+            (x1 - margin).floor().max(0.0),
+            (y1 - margin).floor().max(0.0),
+            (x2 + margin).ceil().min(fb_width),
+            (y2 + margin).ceil().min(fb_height),
+        ];
+        if region[0] >= region[2] || region[1] >= region[3] {
+            return Ok(());
+        }
+        let buffers = self.get_blur_buffers(fb, passes)?;
+        self.memory.borrow_mut().blur_buffers.push(buffers.clone());
+        let levels = &buffers.levels[..=passes as usize];
+        self.end_rendering(buf);
+        self.copy_to_blur_buffers(buf, fb, &levels[0], region);
+        for i in 1..levels.len() {
+            self.blur_level(
+                buf,
+                &pipelines.blur_down,
+                (&levels[i - 1], i - 1),
+                (&levels[i], i),
+                region,
+                blur.offset,
+                ImageLayout::UNDEFINED,
+            );
+        }
+        for i in (1..levels.len() - 1).rev() {
+            self.blur_level(
+                buf,
+                &pipelines.blur_up,
+                (&levels[i + 1], i + 1),
+                (&levels[i], i),
+                region,
+                blur.offset,
+                ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            );
+        }
+        self.begin_rendering(buf, fb, None);
+        self.set_viewport(buf, fb);
+        let src = &levels[1];
+        let tex_pos = px.map(|[x, y]| [x / 2.0 / src.width as f32, y / 2.0 / src.height as f32]);
+        let half_pixel = [0.5 / src.width as f32, 0.5 / src.height as f32];
+        match &blur.clip {
+            None => {
+                let vert = TexVertPushConstants { pos, tex_pos };
+                let frag = BlurFragPushConstants {
+                    half_pixel,
+                    offset: blur.offset,
+                };
+                self.blur_draw(buf, &pipelines.blur_final, src, &vert, &frag);
+            }
+            Some(clip) => {
+                let vert = TexVertRoundedPushConstants {
+                    pos,
+                    tex_pos,
+                    local: clip.target,
+                };
+                let frag = BlurFragRoundedPushConstants {
+                    clip: clip.rect,
+                    half_pixel,
+                    radius: clip.radius,
+                    offset: blur.offset,
+                };
+                self.blur_draw(buf, &pipelines.blur_final_rounded, src, &vert, &frag);
+            }
+        }
+        Ok(())
+    }
+
+    fn copy_to_blur_buffers(
+        &self,
+        buf: CommandBuffer,
+        fb: &VulkanImage,
+        dst: &VulkanBlurLevel,
+        region: [f32; 4],
+    ) {
+        let [x1, y1, x2, y2] = region.map(|v| v as u32);
+        self.blur_barriers(
+            buf,
+            &[
+                image_barrier()
+                    .image(fb.image)
+                    .old_layout(ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                    .new_layout(ImageLayout::TRANSFER_SRC_OPTIMAL)
+                    .src_access_mask(AccessFlags2::COLOR_ATTACHMENT_WRITE)
+                    .src_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+                    .dst_access_mask(AccessFlags2::TRANSFER_READ)
+                    .dst_stage_mask(PipelineStageFlags2::TRANSFER),
+                image_barrier()
+                    .image(dst.image)
+                    .old_layout(ImageLayout::UNDEFINED)
+                    .new_layout(ImageLayout::TRANSFER_DST_OPTIMAL)
+                    .src_access_mask(AccessFlags2::SHADER_SAMPLED_READ)
+                    .src_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+                    .dst_access_mask(AccessFlags2::TRANSFER_WRITE)
+                    .dst_stage_mask(PipelineStageFlags2::TRANSFER),
+            ],
+        );
+        let image_subresource_layers = ImageSubresourceLayers::default()
+            .aspect_mask(ImageAspectFlags::COLOR)
+            .layer_count(1)
+            .base_array_layer(0)
+            .mip_level(0);
+        let offset = Offset3D {
+            x: x1 as i32,
+            y: y1 as i32,
+            z: 0,
+        };
+        let image_copy = ImageCopy2::default()
+            .src_subresource(image_subresource_layers)
+            .src_offset(offset)
+            .dst_subresource(image_subresource_layers)
+            .dst_offset(offset)
+            .extent(Extent3D {
+                width: x2 - x1,
+                height: y2 - y1,
+                depth: 1,
+            });
+        let copy_image_info = CopyImageInfo2::default()
+            .src_image(fb.image)
+            .src_image_layout(ImageLayout::TRANSFER_SRC_OPTIMAL)
+            .dst_image(dst.image)
+            .dst_image_layout(ImageLayout::TRANSFER_DST_OPTIMAL)
+            .regions(slice::from_ref(&image_copy));
+        unsafe {
+            self.device.device.cmd_copy_image2(buf, &copy_image_info);
+        }
+        self.blur_barriers(
+            buf,
+            &[
+                image_barrier()
+                    .image(fb.image)
+                    .old_layout(ImageLayout::TRANSFER_SRC_OPTIMAL)
+                    .new_layout(ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                    .src_stage_mask(PipelineStageFlags2::TRANSFER)
+                    .dst_access_mask(
+                        AccessFlags2::COLOR_ATTACHMENT_WRITE | AccessFlags2::COLOR_ATTACHMENT_READ,
+                    )
+                    .dst_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT),
+                image_barrier()
+                    .image(dst.image)
+                    .old_layout(ImageLayout::TRANSFER_DST_OPTIMAL)
+                    .new_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                    .src_access_mask(AccessFlags2::TRANSFER_WRITE)
+                    .src_stage_mask(PipelineStageFlags2::TRANSFER)
+                    .dst_access_mask(AccessFlags2::SHADER_SAMPLED_READ)
+                    .dst_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER),
+            ],
+        );
+    }
+
+    /// Renders the region of one level of the blur buffers into another level.
+    ///
+    /// `region` is given in framebuffer pixels.
+    fn blur_level(
+        &self,
+        buf: CommandBuffer,
+        pipeline: &VulkanPipeline,
+        (src, src_level): (&VulkanBlurLevel, usize),
+        (dst, dst_level): (&VulkanBlurLevel, usize),
+        region: [f32; 4],
+        offset: f32,
+        old_layout: ImageLayout,
+    ) {
+        let dev = &self.device.device;
+        self.blur_barriers(
+            buf,
+            &[image_barrier()
+                .image(dst.image)
+                .old_layout(old_layout)
+                .new_layout(ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(AccessFlags2::SHADER_SAMPLED_READ)
+                .src_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+                .dst_access_mask(
+                    AccessFlags2::COLOR_ATTACHMENT_WRITE | AccessFlags2::COLOR_ATTACHMENT_READ,
+                )
+                .dst_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)],
+        );
+        let extent = Extent2D {
+            width: dst.width,
+            height: dst.height,
+        };
+        let rendering_attachment_info = RenderingAttachmentInfo::default()
+            .image_view(dst.view)
+            .image_layout(ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+            .load_op(AttachmentLoadOp::LOAD)
+            .store_op(AttachmentStoreOp::STORE);
+        let rendering_info = RenderingInfo::default()
+            .render_area(Rect2D {
+                offset: Default::default(),
+                extent,
+            })
+            .layer_count(1)
+            .color_attachments(slice::from_ref(&rendering_attachment_info));
+        let viewport = Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: dst.width as _,
+            height: dst.height as _,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+        let scissor = Rect2D {
+            offset: Default::default(),
+            extent,
+        };
+        unsafe {
+            dev.cmd_begin_rendering(buf, &rendering_info);
+            dev.cmd_set_viewport(buf, 0, slice::from_ref(&viewport));
+            dev.cmd_set_scissor(buf, 0, slice::from_ref(&scissor));
+        }
+        let dst_scale = (1u32 << dst_level) as f32;
+        let src_scale = (1u32 << src_level) as f32;
+        let [x1, y1, x2, y2] = region;
+        let points = |x1: f32, y1: f32, x2: f32, y2: f32| [[x2, y1], [x1, y1], [x2, y2], [x1, y2]];
+        let (w, h) = (dst_scale * dst.width as f32, dst_scale * dst.height as f32);
+        let pos = points(
+            2.0 * x1 / w - 1.0,
+            2.0 * y1 / h - 1.0,
+            2.0 * x2 / w - 1.0,
+            2.0 * y2 / h - 1.0,
+        );
+        let (w, h) = (src_scale * src.width as f32, src_scale * src.height as f32);
+        let tex_pos = points(x1 / w, y1 / h, x2 / w, y2 / h);
+        let vert = TexVertPushConstants { pos, tex_pos };
+        let frag = BlurFragPushConstants {
+            half_pixel: [0.5 / src.width as f32, 0.5 / src.height as f32],
+            offset,
+        };
+        self.blur_draw(buf, pipeline, src, &vert, &frag);
+        unsafe {
+            dev.cmd_end_rendering(buf);
+        }
+        self.blur_barriers(
+            buf,
+            &[image_barrier()
+                .image(dst.image)
+                .old_layout(ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .new_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_access_mask(AccessFlags2::COLOR_ATTACHMENT_WRITE)
+                .src_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(AccessFlags2::SHADER_SAMPLED_READ)
+                .dst_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)],
+        );
+    }
+
+    fn blur_draw<V: Packed, F: Packed>(
+        &self,
+        buf: CommandBuffer,
+        pipeline: &VulkanPipeline,
+        src: &VulkanBlurLevel,
+        vert: &V,
+        frag: &F,
+    ) {
+        let dev = &self.device.device;
+        let image_info = DescriptorImageInfo::default()
+            .image_view(src.view)
+            .image_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+        let write_descriptor_set = WriteDescriptorSet::default()
+            .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(slice::from_ref(&image_info));
+        unsafe {
+            dev.cmd_bind_pipeline(buf, PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            self.device.push_descriptor.cmd_push_descriptor_set(
+                buf,
+                PipelineBindPoint::GRAPHICS,
+                pipeline.pipeline_layout,
+                0,
+                slice::from_ref(&write_descriptor_set),
+            );
+            dev.cmd_push_constants(
+                buf,
+                pipeline.pipeline_layout,
+                ShaderStageFlags::VERTEX,
+                0,
+                uapi::as_bytes(vert),
+            );
+            dev.cmd_push_constants(
+                buf,
+                pipeline.pipeline_layout,
+                ShaderStageFlags::FRAGMENT,
+                pipeline.frag_push_offset,
+                uapi::as_bytes(frag),
+            );
+            dev.cmd_draw(buf, 4, 1, 0, 0);
+        }
+    }
+
+    fn blur_barriers(&self, buf: CommandBuffer, barriers: &[ImageMemoryBarrier2<'_>]) {
+        let dep_info = DependencyInfoKHR::default().image_memory_barriers(barriers);
+        unsafe {
+            self.device.device.cmd_pipeline_barrier2(buf, &dep_info);
+        }
+    }
+}
@@ -9,6 +9,7 @@ use {
         },
         gfx_apis::vulkan::{
             allocator::{VulkanAllocator, VulkanThreadedAllocator},
+            blur::VulkanBlurBuffers,
             command::{VulkanCommandBuffer, VulkanCommandPool},
             descriptor::VulkanDescriptorSetLayout,
             device::VulkanDevice,
@@ -17,9 +18,13 @@ use {
             pipeline::{PipelineCreateInfo, VulkanPipeline},
             semaphore::VulkanSemaphore,
             shaders::{
-                FillFragPushConstants, FillVertPushConstants, TexFragPushConstants,
-                TexVertPushConstants, VulkanShader, FILL_FRAG, FILL_VERT, TEX_FRAG,
-                TEX_FRAG_MULT_ALPHA, TEX_FRAG_MULT_OPAQUE, TEX_VERT,
+                BlurFragPushConstants, BlurFragRoundedPushConstants, FillFragPushConstants,
+                FillFragRoundedPushConstants, FillVertPushConstants, FillVertRoundedPushConstants,
+                TexFragPushConstants, TexFragRoundedPushConstants, TexVertPushConstants,
+                TexVertRoundedPushConstants, VulkanShader, BLUR_DOWN_FRAG, BLUR_UP_FRAG,
+                BLUR_UP_FRAG_ROUNDED, FILL_FRAG, FILL_FRAG_ROUNDED, FILL_VERT, FILL_VERT_ROUNDED,
+                TEX_FRAG, TEX_FRAG_MULT_ALPHA, TEX_FRAG_MULT_OPAQUE, TEX_FRAG_ROUNDED_ALPHA,
+                TEX_FRAG_ROUNDED_OPAQUE, TEX_VERT, TEX_VERT_ROUNDED,
             },
             VulkanError,
         },
@@ -76,6 +81,15 @@ pub struct VulkanRenderer {
     pub(super) tex_frag_shader: Rc<VulkanShader>,
     pub(super) tex_frag_mult_opaque_shader: Rc<VulkanShader>,
     pub(super) tex_frag_mult_alpha_shader: Rc<VulkanShader>,
+    pub(super) fill_vert_rounded_shader: Rc<VulkanShader>,
+    pub(super) fill_frag_rounded_shader: Rc<VulkanShader>,
+    pub(super) tex_vert_rounded_shader: Rc<VulkanShader>,
+    pub(super) tex_frag_rounded_opaque_shader: Rc<VulkanShader>,
+    pub(super) tex_frag_rounded_alpha_shader: Rc<VulkanShader>,
+    pub(super) blur_down_frag_shader: Rc<VulkanShader>,
+    pub(super) blur_up_frag_shader: Rc<VulkanShader>,
+    pub(super) blur_up_frag_rounded_shader: Rc<VulkanShader>,
+    pub(super) blur_buffers: CopyHashMap<(vk::Format, u32, u32), Rc<VulkanBlurBuffers>>,
     pub(super) tex_descriptor_set_layout: Rc<VulkanDescriptorSetLayout>,
     pub(super) defunct: Cell<bool>,
     pub(super) pending_cpu_jobs: CopyHashMap<u64, PendingJob>,
@@ -113,6 +127,7 @@ pub(super) struct UsedTexture {
 pub(super) enum TexCopyType {
     Identity,
     Multiply,
+    Rounded,
 }
 
 #[derive(Enum)]
@@ -131,6 +146,7 @@ pub(super) struct Memory {
     wait_semaphore_infos: Vec<SemaphoreSubmitInfo<'static>>,
     release_fence: Option<Rc<VulkanFence>>,
     release_sync_file: Option<SyncFile>,
+    pub(super) blur_buffers: Vec<Rc<VulkanBlurBuffers>>,
 }
 
 pub(super) struct PendingFrame {
@@ -141,11 +157,17 @@ pub(super) struct PendingFrame {
     wait_semaphores: Cell<Vec<Rc<VulkanSemaphore>>>,
     waiter: Cell<Option<SpawnedFuture<()>>>,
     _release_fence: Option<Rc<VulkanFence>>,
+    _blur_buffers: Vec<Rc<VulkanBlurBuffers>>,
 }
 
 pub(super) struct VulkanFormatPipelines {
     pub(super) fill: Rc<VulkanPipeline>,
+    pub(super) fill_rounded: Rc<VulkanPipeline>,
     pub(super) tex: EnumMap<TexCopyType, EnumMap<TexSourceType, Rc<VulkanPipeline>>>,
+    pub(super) blur_down: Rc<VulkanPipeline>,
+    pub(super) blur_up: Rc<VulkanPipeline>,
+    pub(super) blur_final: Rc<VulkanPipeline>,
+    pub(super) blur_final_rounded: Rc<VulkanPipeline>,
 }
 
 impl VulkanDevice {
@@ -162,6 +184,14 @@ impl VulkanDevice {
         let tex_frag_shader = self.create_shader(TEX_FRAG)?;
         let tex_frag_mult_opaque_shader = self.create_shader(TEX_FRAG_MULT_OPAQUE)?;
         let tex_frag_mult_alpha_shader = self.create_shader(TEX_FRAG_MULT_ALPHA)?;
+        let fill_vert_rounded_shader = self.create_shader(FILL_VERT_ROUNDED)?;
+        let fill_frag_rounded_shader = self.create_shader(FILL_FRAG_ROUNDED)?;
+        let tex_vert_rounded_shader = self.create_shader(TEX_VERT_ROUNDED)?;
+        let tex_frag_rounded_opaque_shader = self.create_shader(TEX_FRAG_ROUNDED_OPAQUE)?;
+        let tex_frag_rounded_alpha_shader = self.create_shader(TEX_FRAG_ROUNDED_ALPHA)?;
+        let blur_down_frag_shader = self.create_shader(BLUR_DOWN_FRAG)?;
+        let blur_up_frag_shader = self.create_shader(BLUR_UP_FRAG)?;
+        let blur_up_frag_rounded_shader = self.create_shader(BLUR_UP_FRAG_ROUNDED)?;
         let gfx_command_buffers = self.create_command_pool(self.graphics_queue_idx)?;
         let transfer_command_buffers = self
             .distinct_transfer_queue_family_idx
@@ -221,6 +251,15 @@ impl VulkanDevice {
             tex_frag_shader,
             tex_frag_mult_opaque_shader,
             tex_frag_mult_alpha_shader,
+            fill_vert_rounded_shader,
+            fill_frag_rounded_shader,
+            tex_vert_rounded_shader,
+            tex_frag_rounded_opaque_shader,
+            tex_frag_rounded_alpha_shader,
+            blur_down_frag_shader,
+            blur_up_frag_shader,
+            blur_up_frag_rounded_shader,
+            blur_buffers: Default::default(),
             tex_descriptor_set_layout,
             defunct: Cell::new(false),
             pending_cpu_jobs: Default::default(),
@@ -250,6 +289,17 @@ impl VulkanRenderer {
                     frag_descriptor_set_layout: None,
                 },
             )?;
+        let fill_rounded = self
+            .device
+            .create_pipeline::<FillVertRoundedPushConstants, FillFragRoundedPushConstants>(
+                PipelineCreateInfo {
+                    format,
+                    vert: self.fill_vert_rounded_shader.clone(),
+                    frag: self.fill_frag_rounded_shader.clone(),
+                    alpha: true,
+                    frag_descriptor_set_layout: None,
+                },
+            )?;
         let create_tex_pipeline = |alpha| {
             self.device
                 .create_pipeline::<TexVertPushConstants, ()>(PipelineCreateInfo {
@@ -270,12 +320,53 @@ impl VulkanRenderer {
                     frag_descriptor_set_layout: Some(self.tex_descriptor_set_layout.clone()),
                 })
         };
+        let create_tex_rounded_pipeline = |frag: &Rc<VulkanShader>| {
+            self.device
+                .create_pipeline::<TexVertRoundedPushConstants, TexFragRoundedPushConstants>(
+                    PipelineCreateInfo {
+                        format,
+                        vert: self.tex_vert_rounded_shader.clone(),
+                        frag: frag.clone(),
+                        alpha: true,
+                        frag_descriptor_set_layout: Some(self.tex_descriptor_set_layout.clone()),
+                    },
+                )
+        };
+        let create_blur_pipeline = |frag: &Rc<VulkanShader>, alpha| {
+            self.device
+                .create_pipeline::<TexVertPushConstants, BlurFragPushConstants>(
+                    PipelineCreateInfo {
+                        format,
+                        vert: self.tex_vert_shader.clone(),
+                        frag: frag.clone(),
+                        alpha,
+                        frag_descriptor_set_layout: Some(self.tex_descriptor_set_layout.clone()),
+                    },
+                )
+        };
+        let blur_down = create_blur_pipeline(&self.blur_down_frag_shader, false)?;
+        let blur_up = create_blur_pipeline(&self.blur_up_frag_shader, false)?;
+        let blur_final = create_blur_pipeline(&self.blur_up_frag_shader, true)?;
+        let blur_final_rounded = self
+            .device
+            .create_pipeline::<TexVertRoundedPushConstants, BlurFragRoundedPushConstants>(
+                PipelineCreateInfo {
+                    format,
+                    vert: self.tex_vert_rounded_shader.clone(),
+                    frag: self.blur_up_frag_rounded_shader.clone(),
+                    alpha: true,
+                    frag_descriptor_set_layout: Some(self.tex_descriptor_set_layout.clone()),
+                },
+            )?;
         let tex_opaque = create_tex_pipeline(false)?;
         let tex_alpha = create_tex_pipeline(true)?;
         let tex_mult_opaque = create_tex_mult_pipeline(&self.tex_frag_mult_opaque_shader)?;
         let tex_mult_alpha = create_tex_mult_pipeline(&self.tex_frag_mult_alpha_shader)?;
+        let tex_rounded_opaque = create_tex_rounded_pipeline(&self.tex_frag_rounded_opaque_shader)?;
+        let tex_rounded_alpha = create_tex_rounded_pipeline(&self.tex_frag_rounded_alpha_shader)?;
         let pipelines = Rc::new(VulkanFormatPipelines {
             fill,
+            fill_rounded,
             tex: enum_map! {
                 TexCopyType::Identity => enum_map! {
                     TexSourceType::HasAlpha => tex_alpha.clone(),
@@ -285,7 +376,15 @@ impl VulkanRenderer {
                     TexSourceType::HasAlpha => tex_mult_alpha.clone(),
                     TexSourceType::Opaque => tex_mult_opaque.clone(),
                 },
+                TexCopyType::Rounded => enum_map! {
+                    TexSourceType::HasAlpha => tex_rounded_alpha.clone(),
+                    TexSourceType::Opaque => tex_rounded_opaque.clone(),
+                },
             },
+            blur_down,
+            blur_up,
+            blur_final,
+            blur_final_rounded,
         });
         self.pipelines.set(format, pipelines.clone());
         Ok(pipelines)
@@ -430,7 +529,12 @@ impl VulkanRenderer {
         Ok(())
     }
 
-    fn begin_rendering(&self, buf: CommandBuffer, fb: &VulkanImage, clear: Option<&Color>) {
+    pub(super) fn begin_rendering(
+        &self,
+        buf: CommandBuffer,
+        fb: &VulkanImage,
+        clear: Option<&Color>,
+    ) {
         zone!("begin_rendering");
         let rendering_attachment_info = {
             let mut rai = RenderingAttachmentInfo::default()
@@ -464,7 +568,7 @@ impl VulkanRenderer {
         }
     }
 
-    fn set_viewport(&self, buf: CommandBuffer, fb: &VulkanImage) {
+    pub(super) fn set_viewport(&self, buf: CommandBuffer, fb: &VulkanImage) {
         zone!("set_viewport");
         let viewport = Viewport {
             x: 0.0,
@@ -500,10 +604,10 @@ impl VulkanRenderer {
         zone!("record_draws");
         let pipelines = self.get_or_create_pipelines(fb.format.vk_format)?;
         let dev = &self.device.device;
-        let mut current_pipeline = None;
-        let mut bind = |pipeline: &VulkanPipeline| {
-            if current_pipeline != Some(pipeline.pipeline) {
-                current_pipeline = Some(pipeline.pipeline);
+        let current_pipeline = Cell::new(None);
+        let bind = |pipeline: &VulkanPipeline| {
+            if current_pipeline.get() != Some(pipeline.pipeline) {
+                current_pipeline.set(Some(pipeline.pipeline));
                 unsafe {
                     dev.cmd_bind_pipeline(buf, PipelineBindPoint::GRAPHICS, pipeline.pipeline);
                 }
@@ -512,30 +616,66 @@ impl VulkanRenderer {
         for opt in opts {
             match opt {
                 GfxApiOpt::Sync => {}
+                GfxApiOpt::Blur(b) => {
+                    self.record_blur(buf, fb, &pipelines, b)?;
+                    current_pipeline.set(None);
+                }
                 GfxApiOpt::FillRect(r) => {
-                    bind(&pipelines.fill);
-                    let vert = FillVertPushConstants {
-                        pos: r.rect.to_points(),
-                    };
-                    let frag = FillFragPushConstants {
-                        color: r.color.to_array_srgb(),
-                    };
-                    unsafe {
-                        dev.cmd_push_constants(
-                            buf,
-                            pipelines.fill.pipeline_layout,
-                            ShaderStageFlags::VERTEX,
-                            0,
-                            uapi::as_bytes(&vert),
-                        );
-                        dev.cmd_push_constants(
-                            buf,
-                            pipelines.fill.pipeline_layout,
-                            ShaderStageFlags::FRAGMENT,
-                            pipelines.fill.frag_push_offset,
-                            uapi::as_bytes(&frag),
-                        );
-                        dev.cmd_draw(buf, 4, 1, 0, 0);
+                    if let Some(clip) = &r.clip {
+                        let pipeline = &pipelines.fill_rounded;
+                        bind(pipeline);
+                        let vert = FillVertRoundedPushConstants {
+                            pos: r.rect.to_points(),
+                            local: clip.target,
+                        };
+                        let frag = FillFragRoundedPushConstants {
+                            color: r.color.to_array_srgb(),
+                            clip: clip.rect,
+                            radius: clip.radius,
+                            blur: clip.blur,
+                        };
+                        unsafe {
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::VERTEX,
+                                0,
+                                uapi::as_bytes(&vert),
+                            );
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::FRAGMENT,
+                                pipeline.frag_push_offset,
+                                uapi::as_bytes(&frag),
+                            );
+                            dev.cmd_draw(buf, 4, 1, 0, 0);
+                        }
+                    } else {
+                        bind(&pipelines.fill);
+                        let vert = FillVertPushConstants {
+                            pos: r.rect.to_points(),
+                        };
+                        let frag = FillFragPushConstants {
+                            color: r.color.to_array_srgb(),
+                        };
+                        unsafe {
+                            dev.cmd_push_constants(
+                                buf,
+                                pipelines.fill.pipeline_layout,
+                                ShaderStageFlags::VERTEX,
+                                0,
+                                uapi::as_bytes(&vert),
+                            );
+                            dev.cmd_push_constants(
+                                buf,
+                                pipelines.fill.pipeline_layout,
+                                ShaderStageFlags::FRAGMENT,
+                                pipelines.fill.frag_push_offset,
+                                uapi::as_bytes(&frag),
+                            );
+                            dev.cmd_draw(buf, 4, 1, 0, 0);
+                        }
                     }
                 }
                 GfxApiOpt::CopyTexture(c) => {
@@ -549,9 +689,10 @@ impl VulkanRenderer {
                         log::warn!("Ignoring texture owned by different queue");
                         continue;
                     }
-                    let copy_type = match c.alpha.is_some() {
-                        true => TexCopyType::Multiply,
-                        false => TexCopyType::Identity,
+                    let copy_type = match (c.clip.is_some(), c.alpha.is_some()) {
+                        (true, _) => TexCopyType::Rounded,
+                        (false, true) => TexCopyType::Multiply,
+                        (false, false) => TexCopyType::Identity,
                     };
                     let source_type = match tex.format.has_alpha {
                         true => TexSourceType::HasAlpha,
@@ -559,10 +700,8 @@ impl VulkanRenderer {
                     };
                     let pipeline = &pipelines.tex[copy_type][source_type];
                     bind(pipeline);
-                    let vert = TexVertPushConstants {
-                        pos: c.target.to_points(),
-                        tex_pos: c.source.to_points(),
-                    };
+                    let pos = c.target.to_points();
+                    let tex_pos = c.source.to_points();
                     let image_info = DescriptorImageInfo::default()
                         .image_view(tex.texture_view)
                         .image_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL);
@@ -577,22 +716,50 @@ impl VulkanRenderer {
                             0,
                             slice::from_ref(&write_descriptor_set),
                         );
-                        dev.cmd_push_constants(
-                            buf,
-                            pipeline.pipeline_layout,
-                            ShaderStageFlags::VERTEX,
-                            0,
-                            uapi::as_bytes(&vert),
-                        );
-                        if let Some(alpha) = c.alpha {
-                            let frag = TexFragPushConstants { alpha };
+                        if let Some(clip) = &c.clip {
+                            let vert = TexVertRoundedPushConstants {
+                                pos,
+                                tex_pos,
+                                local: clip.target,
+                            };
+                            let frag = TexFragRoundedPushConstants {
+                                clip: clip.rect,
+                                radius: clip.radius,
+                                alpha: c.alpha.unwrap_or(1.0),
+                            };
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::VERTEX,
+                                0,
+                                uapi::as_bytes(&vert),
+                            );
                             dev.cmd_push_constants(
                                 buf,
                                 pipeline.pipeline_layout,
                                 ShaderStageFlags::FRAGMENT,
-                                size_of_val(&vert) as _,
+                                pipeline.frag_push_offset,
                                 uapi::as_bytes(&frag),
                             );
+                        } else {
+                            let vert = TexVertPushConstants { pos, tex_pos };
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::VERTEX,
+                                0,
+                                uapi::as_bytes(&vert),
+                            );
+                            if let Some(alpha) = c.alpha {
+                                let frag = TexFragPushConstants { alpha };
+                                dev.cmd_push_constants(
+                                    buf,
+                                    pipeline.pipeline_layout,
+                                    ShaderStageFlags::FRAGMENT,
+                                    size_of_val(&vert) as _,
+                                    uapi::as_bytes(&frag),
+                                );
+                            }
                         }
                         dev.cmd_draw(buf, 4, 1, 0, 0);
                     }
@@ -602,7 +769,7 @@ impl VulkanRenderer {
         Ok(())
     }
 
-    fn end_rendering(&self, buf: CommandBuffer) {
+    pub(super) fn end_rendering(&self, buf: CommandBuffer) {
         zone!("end_rendering");
         unsafe {
             self.device.device.cmd_end_rendering(buf);
@@ -897,6 +1064,7 @@ impl VulkanRenderer {
             wait_semaphores: Cell::new(mem::take(&mut memory.wait_semaphores)),
             waiter: Cell::new(None),
             _release_fence: memory.release_fence.take(),
+            _blur_buffers: mem::take(&mut memory.blur_buffers),
         });
         self.pending_frames.set(frame.point, frame.clone());
         let future = self.eng.spawn(
@@ -928,6 +1096,7 @@ impl VulkanRenderer {
             memory.queue_transfer.clear();
             memory.wait_semaphores.clear();
             memory.release_fence.take();
+            memory.blur_buffers.clear();
             memory.release_sync_file.take()
         };
         res.map(|_| sync_file)
@@ -13,6 +13,20 @@ pub const TEX_FRAG_MULT_OPAQUE: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+opaque.spv"));
 pub const TEX_FRAG_MULT_ALPHA: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+alpha.spv"));
+pub const FILL_VERT_ROUNDED: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/fill.vert.rounded.spv"));
+pub const FILL_FRAG_ROUNDED: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/fill.frag.rounded.spv"));
+pub const TEX_VERT_ROUNDED: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.vert.rounded.spv"));
+pub const TEX_FRAG_ROUNDED_OPAQUE: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.rounded+opaque.spv"));
+pub const TEX_FRAG_ROUNDED_ALPHA: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.rounded+alpha.spv"));
+pub const BLUR_DOWN_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/blur_down.frag.spv"));
+pub const BLUR_UP_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/blur_up.frag.spv"));
+pub const BLUR_UP_FRAG_ROUNDED: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/blur_up.frag.rounded.spv"));
 
 pub struct VulkanShader {
     pub(super) device: Rc<VulkanDevice>,
@@ -35,6 +49,25 @@ pub struct FillFragPushConstants {
 
 unsafe impl Packed for FillFragPushConstants {}
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct FillVertRoundedPushConstants {
+    pub pos: [[f32; 2]; 4],
+    pub local: [f32; 4],
+}
+
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct FillFragRoundedPushConstants {
+    pub color: [f32; 4],
+    pub clip: [f32; 4],
+    pub radius: f32,
+    pub blur: f32,
+}
+
+unsafe impl Packed for FillVertRoundedPushConstants {}
+unsafe impl Packed for FillFragRoundedPushConstants {}
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)]
 pub struct TexVertPushConstants {
@@ -48,8 +81,45 @@ pub struct TexFragPushConstants {
     pub alpha: f32,
 }
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct TexVertRoundedPushConstants {
+    pub pos: [[f32; 2]; 4],
+    pub tex_pos: [[f32; 2]; 4],
+    pub local: [f32; 4],
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct TexFragRoundedPushConstants {
+    pub clip: [f32; 4],
+    pub radius: f32,
+    pub alpha: f32,
+}
+
 unsafe impl Packed for TexVertPushConstants {}
 unsafe impl Packed for TexFragPushConstants {}
+unsafe impl Packed for TexVertRoundedPushConstants {}
+unsafe impl Packed for TexFragRoundedPushConstants {}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct BlurFragPushConstants {
+    pub half_pixel: [f32; 2],
+    pub offset: f32,
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct BlurFragRoundedPushConstants {
+    pub clip: [f32; 4],
+    pub half_pixel: [f32; 2],
+    pub radius: f32,
+    pub offset: f32,
+}
+
+unsafe impl Packed for BlurFragPushConstants {}
+unsafe impl Packed for BlurFragRoundedPushConstants {}
 
 impl VulkanDevice {
     pub(super) fn create_shader(
@@ -0,0 +1,20 @@
+#version 450
+
+layout(push_constant, std430) uniform Data {
+	layout(offset = 64) vec2 half_pixel;
+	layout(offset = 72) float offset;
+} data;
+
+layout(set = 0, binding = 0) uniform sampler2D tex;
+layout(location = 0) in vec2 tex_pos;
+layout(location = 0) out vec4 out_color;
+
+void main() {
+	vec2 o = data.half_pixel * data.offset;
+	vec4 sum = textureLod(tex, tex_pos, 0) * 4.0;
+	sum += textureLod(tex, tex_pos - o, 0);
+	sum += textureLod(tex, tex_pos + o, 0);
+	sum += textureLod(tex, tex_pos + vec2(o.x, -o.y), 0);
+	sum += textureLod(tex, tex_pos - vec2(o.x, -o.y), 0);
+	out_color = sum / 8.0;
+}
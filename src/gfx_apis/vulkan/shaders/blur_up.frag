@@ -0,0 +1,38 @@
+#version 450
+
+#ifdef ROUNDED
+#include "rounded.glsl"
+
+layout(push_constant, std430) uniform Data {
+	layout(offset = 80) vec4 clip;
+	layout(offset = 96) vec2 half_pixel;
+	layout(offset = 104) float radius;
+	layout(offset = 108) float offset;
+} data;
+layout(location = 1) in vec2 local_pos;
+#else
+layout(push_constant, std430) uniform Data {
+	layout(offset = 64) vec2 half_pixel;
+	layout(offset = 72) float offset;
+} data;
+#endif
+
+layout(set = 0, binding = 0) uniform sampler2D tex;
+layout(location = 0) in vec2 tex_pos;
+layout(location = 0) out vec4 out_color;
+
+void main() {
+	vec2 o = data.half_pixel * data.offset;
+	vec4 sum = textureLod(tex, tex_pos + vec2(-o.x * 2.0, 0.0), 0);
+	sum += textureLod(tex, tex_pos + vec2(-o.x, o.y), 0) * 2.0;
+	sum += textureLod(tex, tex_pos + vec2(0.0, o.y * 2.0), 0);
+	sum += textureLod(tex, tex_pos + vec2(o.x, o.y), 0) * 2.0;
+	sum += textureLod(tex, tex_pos + vec2(o.x * 2.0, 0.0), 0);
+	sum += textureLod(tex, tex_pos + vec2(o.x, -o.y), 0) * 2.0;
+	sum += textureLod(tex, tex_pos + vec2(0.0, -o.y * 2.0), 0);
+	sum += textureLod(tex, tex_pos + vec2(-o.x, -o.y), 0) * 2.0;
+	out_color = sum / 12.0;
+#ifdef ROUNDED
+	out_color *= rounded_coverage(local_pos, data.clip, data.radius, 1.0);
+#endif
+}
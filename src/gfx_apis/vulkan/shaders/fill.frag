@@ -1,11 +1,28 @@
 #version 450
 
+#ifdef ROUNDED
+#include "rounded.glsl"
+
+layout(push_constant, std430) uniform Data {
+	layout(offset = 48) vec4 color;
+	layout(offset = 64) vec4 clip;
+	layout(offset = 80) float radius;
+	layout(offset = 84) float blur;
+} data;
+
+layout(location = 0) in vec2 local_pos;
+#else
 layout(push_constant, std430) uniform Data {
 	layout(offset = 32) vec4 color;
 } data;
+#endif
 
 layout(location = 0) out vec4 out_color;
 
 void main() {
+#ifdef ROUNDED
+	out_color = data.color * rounded_coverage(local_pos, data.clip, data.radius, data.blur);
+#else
 	out_color = data.color;
+#endif
 }
@@ -0,0 +1,12 @@
+// Returns the coverage of the point `p` by the rectangle `rect` with rounded corners.
+//
+// `rect` is given as `(x1, y1, x2, y2)`. `blur` is the width of the transition at the
+// edges of the rectangle.
+float rounded_coverage(vec2 p, vec4 rect, float radius, float blur) {
+	vec2 center = (rect.xy + rect.zw) * 0.5;
+	vec2 half_size = (rect.zw - rect.xy) * 0.5;
+	float r = min(radius, min(half_size.x, half_size.y));
+	vec2 q = abs(p - center) - half_size + r;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - r;
+	return 1.0 - smoothstep(-blur * 0.5, blur * 0.5, d);
+}
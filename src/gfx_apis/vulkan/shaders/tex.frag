@@ -1,6 +1,15 @@
 #version 450
 
-#ifdef ALPHA_MULTIPLIER
+#ifdef ROUNDED
+#include "rounded.glsl"
+
+layout(push_constant, std430) uniform Data {
+	layout(offset = 80) vec4 clip;
+	layout(offset = 96) float radius;
+	layout(offset = 100) float mul;
+} data;
+layout(location = 1) in vec2 local_pos;
+#elif defined(ALPHA_MULTIPLIER)
 layout(push_constant, std430) uniform Data {
 	layout(offset = 64) float mul;
 } data;
@@ -10,7 +19,14 @@ layout(location = 0) in vec2 tex_pos;
 layout(location = 0) out vec4 out_color;
 
 void main() {
-#ifdef ALPHA_MULTIPLIER
+#ifdef ROUNDED
+	float mul = data.mul * rounded_coverage(local_pos, data.clip, data.radius, 1.0);
+#ifdef ALPHA
+	out_color = textureLod(tex, tex_pos, 0) * mul;
+#else // !ALPHA
+	out_color = vec4(textureLod(tex, tex_pos, 0).rgb * mul, mul);
+#endif // ALPHA
+#elif defined(ALPHA_MULTIPLIER)
 #ifdef ALPHA
 	out_color = textureLod(tex, tex_pos, 0) * data.mul;
 #endif // !ALPHA
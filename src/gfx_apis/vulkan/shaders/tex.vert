@@ -4,9 +4,15 @@
 layout(push_constant, std430) uniform Data {
 	layout(offset = 0) vec2 pos[4];
 	layout(offset = 32) vec2 tex_pos[4];
+#ifdef ROUNDED
+	layout(offset = 64) vec4 local;
+#endif
 } data;
 
 layout(location = 0) out vec2 tex_pos;
+#ifdef ROUNDED
+layout(location = 1) out vec2 local_pos;
+#endif
 
 void main() {
 	vec2 pos;
@@ -16,6 +22,14 @@ void main() {
 		case 2: pos = data.pos[2]; tex_pos = data.tex_pos[2]; break;
 		case 3: pos = data.pos[3]; tex_pos = data.tex_pos[3]; break;
 	}
+#ifdef ROUNDED
+	switch (gl_VertexIndex) {
+		case 0: local_pos = data.local.zy; break;
+		case 1: local_pos = data.local.xy; break;
+		case 2: local_pos = data.local.zw; break;
+		case 3: local_pos = data.local.xw; break;
+	}
+#endif
 	gl_Position = vec4(pos, 0.0, 1.0);
 //	debugPrintfEXT("gl_Position = %v4f, tex_pos = %v2f", gl_Position, tex_pos);
 }
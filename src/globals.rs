@@ -3,6 +3,7 @@ use {
         backend::Backend,
         client::{Client, ClientCaps},
         ifs::{
+            ext_background_effect_manager_v1::ExtBackgroundEffectManagerV1Global,
             ext_foreign_toplevel_image_capture_source_manager_v1::ExtForeignToplevelImageCaptureSourceManagerV1Global,
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1Global,
             ext_idle_notifier_v1::ExtIdleNotifierV1Global,
@@ -211,6 +212,7 @@ impl Globals {
         add_singleton!(WpFifoManagerV1Global);
         add_singleton!(WpCommitTimingManagerV1Global);
         add_singleton!(ExtDataControlManagerV1Global);
+        add_singleton!(ExtBackgroundEffectManagerV1Global);
     }
 
     pub fn add_backend_singletons(&self, backend: &Rc<dyn Backend>) {
@@ -1,3 +1,4 @@
+pub mod ext_background_effect_manager_v1;
 pub mod ext_foreign_toplevel_handle_v1;
 pub mod ext_foreign_toplevel_image_capture_source_manager_v1;
 pub mod ext_foreign_toplevel_list_v1;
@@ -13,8 +14,10 @@ pub mod jay_compositor;
 pub mod jay_damage_tracking;
 pub mod jay_ei_session;
 pub mod jay_ei_session_builder;
+pub mod jay_global_shortcuts;
 pub mod jay_idle;
 pub mod jay_input;
+pub mod jay_input_capture;
 pub mod jay_log_file;
 pub mod jay_output;
 pub mod jay_pointer;
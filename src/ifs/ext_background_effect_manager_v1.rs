@@ -0,0 +1,154 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_surface::ext_background_effect_surface_v1::{
+            ExtBackgroundEffectSurfaceV1, ExtBackgroundEffectSurfaceV1Error,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{ext_background_effect_manager_v1::*, ExtBackgroundEffectManagerV1Id},
+    },
+    jay_config::video::GfxApi,
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const CAPABILITY_BLUR: u32 = 1;
+
+pub struct ExtBackgroundEffectManagerV1Global {
+    name: GlobalName,
+}
+
+pub struct ExtBackgroundEffectManagerV1 {
+    id: ExtBackgroundEffectManagerV1Id,
+    client: Rc<Client>,
+    version: Version,
+    capabilities: Cell<Option<u32>>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ExtBackgroundEffectManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ExtBackgroundEffectManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ExtBackgroundEffectManagerV1Error> {
+        let obj = Rc::new(ExtBackgroundEffectManagerV1 {
+            id,
+            client: client.clone(),
+            version,
+            capabilities: Default::default(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        client
+            .state
+            .background_effect_managers
+            .set((client.id, id), obj.clone());
+        obj.send_capabilities();
+        Ok(())
+    }
+}
+
+impl ExtBackgroundEffectManagerV1 {
+    /// Sends the capabilities if they differ from the ones last sent.
+    pub fn send_capabilities(&self) {
+        let state = &self.client.state;
+        let mut flags = 0;
+        if let Some(ctx) = state.render_ctx.get() {
+            // The GL renderer does not support blurring.
+            if ctx.gfx_api() == GfxApi::Vulkan && state.theme.sizes.blur_passes.get() > 0 {
+                flags |= CAPABILITY_BLUR;
+            }
+        }
+        if self.capabilities.replace(Some(flags)) == Some(flags) {
+            return;
+        }
+        self.client.event(Capabilities {
+            self_id: self.id,
+            flags,
+        });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .background_effect_managers
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl ExtBackgroundEffectManagerV1RequestHandler for ExtBackgroundEffectManagerV1 {
+    type Error = ExtBackgroundEffectManagerV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_background_effect(
+        &self,
+        req: GetBackgroundEffect,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let effect = Rc::new(ExtBackgroundEffectSurfaceV1::new(
+            req.id,
+            &surface,
+            self.version,
+        ));
+        track!(self.client, effect);
+        self.client.add_client_obj(&effect)?;
+        effect.install()?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ExtBackgroundEffectManagerV1Global,
+    ExtBackgroundEffectManagerV1,
+    ExtBackgroundEffectManagerV1Error
+);
+
+impl Global for ExtBackgroundEffectManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ExtBackgroundEffectManagerV1Global);
+
+object_base! {
+    self = ExtBackgroundEffectManagerV1;
+    version = self.version;
+}
+
+impl Object for ExtBackgroundEffectManagerV1 {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(ExtBackgroundEffectManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ExtBackgroundEffectManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    ExtBackgroundEffectSurfaceV1Error(#[from] ExtBackgroundEffectSurfaceV1Error),
+}
+
+efrom!(ExtBackgroundEffectManagerV1Error, ClientError);
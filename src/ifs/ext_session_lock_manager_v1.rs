@@ -71,6 +71,9 @@ impl ExtSessionLockManagerV1RequestHandler for ExtSessionLockManagerV1 {
             for seat in state.globals.seats.lock().values() {
                 seat.prepare_for_lock();
             }
+            for capture in state.input_captures.lock().values() {
+                capture.release_by_compositor();
+            }
             state.lock.locked.set(true);
             state.lock.lock.set(Some(new.clone()));
             state.tree_changed();
@@ -5,8 +5,10 @@ use {
         globals::{Global, GlobalName},
         ifs::{
             jay_ei_session_builder::JayEiSessionBuilder,
+            jay_global_shortcuts::JayGlobalShortcuts,
             jay_idle::JayIdle,
             jay_input::JayInput,
+            jay_input_capture::JayInputCapture,
             jay_log_file::JayLogFile,
             jay_output::JayOutput,
             jay_pointer::JayPointer,
@@ -35,6 +37,8 @@ use {
 pub const CREATE_EI_SESSION_SINCE: Version = Version(5);
 pub const SCREENSHOT_SPLITUP_SINCE: Version = Version(6);
 pub const GET_TOPLEVEL_SINCE: Version = Version(12);
+pub const CREATE_GLOBAL_SHORTCUTS_SINCE: Version = Version(13);
+pub const CREATE_INPUT_CAPTURE_SINCE: Version = Version(14);
 
 pub struct JayCompositorGlobal {
     name: GlobalName,
@@ -72,7 +76,7 @@ impl Global for JayCompositorGlobal {
     }
 
     fn version(&self) -> u32 {
-        12
+        14
     }
 
     fn required_caps(&self) -> ClientCaps {
@@ -438,6 +442,37 @@ impl JayCompositorRequestHandler for JayCompositor {
         obj.done(tl);
         Ok(())
     }
+
+    fn create_global_shortcuts(
+        &self,
+        req: CreateGlobalShortcuts,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayGlobalShortcuts::new(req.id, &self.client, self.version));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.client
+            .state
+            .global_shortcuts
+            .set((self.client.id, req.id), obj);
+        Ok(())
+    }
+
+    fn create_input_capture(
+        &self,
+        req: CreateInputCapture,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayInputCapture::new(req.id, &self.client, self.version));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.client
+            .state
+            .input_captures
+            .set((self.client.id, req.id), obj.clone());
+        obj.send_zones();
+        Ok(())
+    }
 }
 
 object_base! {
@@ -0,0 +1,151 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::SeatId,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+    },
+    ahash::AHashMap,
+    jay_config::keyboard::mods::{CAPS, NUM, RELEASE},
+    smallvec::SmallVec,
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct JayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    bindings: RefCell<AHashMap<u32, Binding>>,
+    active: RefCell<AHashMap<(SeatId, u32), SmallVec<[u32; 1]>>>,
+}
+
+struct Binding {
+    mods: u32,
+    keysym: u32,
+}
+
+impl JayGlobalShortcuts {
+    pub fn new(id: JayGlobalShortcutsId, client: &Rc<Client>, version: Version) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            bindings: Default::default(),
+            active: Default::default(),
+        }
+    }
+
+    pub fn key_pressed(
+        &self,
+        seat: SeatId,
+        time_usec: u64,
+        key: u32,
+        mods: u32,
+        keysym: u32,
+    ) -> bool {
+        let mut ids = SmallVec::<[u32; 1]>::new();
+        for (&id, binding) in &*self.bindings.borrow() {
+            if binding.keysym == keysym && binding.mods == mods {
+                ids.push(id);
+            }
+        }
+        if ids.is_empty() {
+            return false;
+        }
+        let mut active = self.active.borrow_mut();
+        let active = active.entry((seat, key)).or_default();
+        // The same key can produce several keysyms that all match a binding.
+        for id in ids {
+            if !active.contains(&id) {
+                active.push(id);
+                self.send_activated(id, time_usec);
+            }
+        }
+        true
+    }
+
+    pub fn key_released(&self, seat: SeatId, time_usec: u64, key: u32) -> bool {
+        let Some(ids) = self.active.borrow_mut().remove(&(seat, key)) else {
+            return false;
+        };
+        for id in ids {
+            self.send_deactivated(id, time_usec);
+        }
+        true
+    }
+
+    fn send_activated(&self, id: u32, time_usec: u64) {
+        self.client.event(Activated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    fn send_deactivated(&self, id: u32, time_usec: u64) {
+        self.client.event(Deactivated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .global_shortcuts
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayGlobalShortcutsRequestHandler for JayGlobalShortcuts {
+    type Error = JayGlobalShortcutsError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn bind(&self, req: Bind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let binding = Binding {
+            mods: req.mods & !(CAPS.0 | NUM.0 | RELEASE.0),
+            keysym: req.keysym,
+        };
+        self.bindings.borrow_mut().insert(req.id, binding);
+        Ok(())
+    }
+
+    fn unbind(&self, req: Unbind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.bindings.borrow_mut().remove(&req.id);
+        self.active.borrow_mut().retain(|_, ids| {
+            ids.retain(|id| *id != req.id);
+            !ids.is_empty()
+        });
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayGlobalShortcuts;
+    version = self.version;
+}
+
+impl Object for JayGlobalShortcuts {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayGlobalShortcuts);
+
+#[derive(Debug, Error)]
+pub enum JayGlobalShortcutsError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayGlobalShortcutsError, ClientError);
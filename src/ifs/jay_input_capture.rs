@@ -0,0 +1,304 @@
+use {
+    crate::{
+        client::{Client, ClientError, ClientId},
+        ei::ei_client::EiClientError,
+        fixed::Fixed,
+        ifs::wl_seat::WlSeatGlobal,
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::{
+            cell_ext::CellExt, clonecell::CloneCell, errorfmt::ErrorFmt, numcell::NumCell,
+            oserror::OsError,
+        },
+        wire::{jay_input_capture::*, JayInputCaptureId},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+    uapi::{c, OwnedFd},
+};
+
+pub struct JayInputCapture {
+    pub id: JayInputCaptureId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub ei_client_id: Cell<Option<ClientId>>,
+    barriers: RefCell<Vec<PointerBarrier>>,
+    enabled: Cell<bool>,
+    seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
+    activation_id: NumCell<u32>,
+}
+
+struct PointerBarrier {
+    id: u32,
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+}
+
+impl PointerBarrier {
+    fn crossed(&self, x1: f64, y1: f64, x2: f64, y2: f64) -> bool {
+        // Barriers are either vertical or horizontal. Both cases are handled by
+        // swapping the coordinates of horizontal barriers.
+        let (bx, by1, by2, x1, y1, x2, y2) = if self.x1 == self.x2 {
+            (self.x1, self.y1, self.y2, x1, y1, x2, y2)
+        } else if self.y1 == self.y2 {
+            (self.y1, self.x1, self.x2, y1, x1, y2, x2)
+        } else {
+            return false;
+        };
+        let bx = bx as f64;
+        if (x1 < bx) == (x2 < bx) {
+            return false;
+        }
+        let y = y1 + (bx - x1) / (x2 - x1) * (y2 - y1);
+        y >= by1.min(by2) as f64 && y <= by1.max(by2) as f64
+    }
+}
+
+impl JayInputCapture {
+    pub fn new(id: JayInputCaptureId, client: &Rc<Client>, version: Version) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            ei_client_id: Default::default(),
+            barriers: Default::default(),
+            enabled: Cell::new(false),
+            seat: Default::default(),
+            activation_id: Default::default(),
+        }
+    }
+
+    pub fn send_zones(&self) {
+        for output in self.client.state.root.outputs.lock().values() {
+            let pos = output.global.pos.get();
+            self.client.event(Zone {
+                self_id: self.id,
+                x: pos.x1(),
+                y: pos.y1(),
+                width: pos.width(),
+                height: pos.height(),
+            });
+        }
+        self.client.event(ZonesDone { self_id: self.id });
+    }
+
+    pub fn zones_changed(&self) {
+        self.deactivate(None);
+        self.barriers.borrow_mut().clear();
+        self.send_zones();
+    }
+
+    fn send_ei_fd(&self, fd: &Rc<OwnedFd>) {
+        self.client.event(EiFd {
+            self_id: self.id,
+            fd: fd.clone(),
+        });
+    }
+
+    fn send_ei_failed(&self, reason: &str) {
+        self.client.event(EiFailed {
+            self_id: self.id,
+            reason,
+        });
+    }
+
+    fn send_activated(&self, activation_id: u32, barrier_id: u32, x: Fixed, y: Fixed) {
+        self.client.event(Activated {
+            self_id: self.id,
+            activation_id,
+            barrier_id,
+            x,
+            y,
+        });
+    }
+
+    fn send_deactivated(&self, activation_id: u32, x: Fixed, y: Fixed) {
+        self.client.event(Deactivated {
+            self_id: self.id,
+            activation_id,
+            x,
+            y,
+        });
+    }
+
+    /// Activates the capture if the pointer motion from (x1, y1) to (x2, y2) crosses
+    /// one of the barriers.
+    pub fn handle_motion(
+        self: &Rc<Self>,
+        seat: &Rc<WlSeatGlobal>,
+        x1: Fixed,
+        y1: Fixed,
+        x2: Fixed,
+        y2: Fixed,
+    ) -> bool {
+        if !self.enabled.get() || self.seat.is_some() {
+            return false;
+        }
+        let barrier_id = {
+            let (x1, y1, x2, y2) = (x1.to_f64(), y1.to_f64(), x2.to_f64(), y2.to_f64());
+            let barriers = self.barriers.borrow();
+            match barriers.iter().find(|b| b.crossed(x1, y1, x2, y2)) {
+                Some(b) => b.id,
+                _ => return false,
+            }
+        };
+        let activation_id = self.activation_id.fetch_add(1) + 1;
+        self.seat.set(Some(seat.clone()));
+        seat.set_input_capture(Some(self.clone()));
+        self.send_activated(activation_id, barrier_id, x1, y1);
+        if let Some(ei_client_id) = self.ei_client_id.get() {
+            seat.set_ei_client_emulating(ei_client_id, Some(activation_id));
+        }
+        true
+    }
+
+    /// Ends the capture regardless of what the client wants, e.g. because the user pressed
+    /// the release chord or the session was locked.
+    pub fn release_by_compositor(&self) {
+        self.deactivate(None);
+    }
+
+    fn deactivate(&self, warp: Option<(Fixed, Fixed)>) {
+        let Some(seat) = self.seat.take() else {
+            return;
+        };
+        seat.set_input_capture(None);
+        if let Some(ei_client_id) = self.ei_client_id.get() {
+            seat.set_ei_client_emulating(ei_client_id, None);
+        }
+        if let Some((x, y)) = warp {
+            seat.motion_event_abs(self.client.state.now_usec(), x, y);
+        }
+        let (x, y) = seat.pointer_cursor().position();
+        self.send_deactivated(self.activation_id.get(), x, y);
+    }
+
+    fn spawn_ei_client(&self, app_id: &str) -> Result<Rc<OwnedFd>, JayInputCaptureError> {
+        let (server, client) = uapi::socketpair(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+            .map_err(|e| JayInputCaptureError::SocketPair(e.into()))?;
+        let ei_client = self
+            .client
+            .state
+            .ei_clients
+            .spawn2(
+                &self.client.state,
+                Rc::new(server),
+                None,
+                Some(app_id.to_string()),
+            )
+            .map_err(JayInputCaptureError::SpawnClient)?;
+        ei_client.input_capture.set(true);
+        self.ei_client_id.set(Some(ei_client.id));
+        Ok(Rc::new(client))
+    }
+
+    fn kill(&self) {
+        self.deactivate(None);
+        self.client
+            .state
+            .input_captures
+            .remove(&(self.client.id, self.id));
+        if let Some(id) = self.ei_client_id.take() {
+            self.client.state.ei_clients.shutdown(id);
+        }
+    }
+}
+
+impl JayInputCaptureRequestHandler for JayInputCapture {
+    type Error = JayInputCaptureError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.kill();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn connect_ei(&self, req: ConnectEi<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.ei_client_id.is_some() {
+            return Err(JayInputCaptureError::AlreadyConnected);
+        }
+        match self.spawn_ei_client(req.app_id) {
+            Ok(fd) => self.send_ei_fd(&fd),
+            Err(e) => {
+                let e = format!("Could not spawn client: {}", ErrorFmt(e));
+                log::error!("{}", e);
+                self.send_ei_failed(&e);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_barrier(&self, req: AddBarrier, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.x1 != req.x2 && req.y1 != req.y2 {
+            return Err(JayInputCaptureError::DiagonalBarrier);
+        }
+        self.barriers.borrow_mut().push(PointerBarrier {
+            id: req.id,
+            x1: req.x1,
+            y1: req.y1,
+            x2: req.x2,
+            y2: req.y2,
+        });
+        Ok(())
+    }
+
+    fn clear_barriers(&self, _req: ClearBarriers, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.barriers.borrow_mut().clear();
+        Ok(())
+    }
+
+    fn enable(&self, _req: Enable, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.enabled.set(true);
+        Ok(())
+    }
+
+    fn disable(&self, _req: Disable, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.enabled.set(false);
+        self.deactivate(None);
+        Ok(())
+    }
+
+    fn release(&self, req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.activation_id != self.activation_id.get() {
+            return Ok(());
+        }
+        let warp = (req.warp != 0).then_some((req.x, req.y));
+        self.deactivate(warp);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayInputCapture;
+    version = self.version;
+}
+
+impl Object for JayInputCapture {
+    fn break_loops(&self) {
+        self.kill();
+    }
+}
+
+simple_add_obj!(JayInputCapture);
+
+#[derive(Debug, Error)]
+pub enum JayInputCaptureError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Could not create a socketpair")]
+    SocketPair(#[source] OsError),
+    #[error("Could not spawn a new client")]
+    SpawnClient(#[source] EiClientError),
+    #[error("The EI client has already been created")]
+    AlreadyConnected,
+    #[error("Barriers must be horizontal or vertical")]
+    DiagonalBarrier,
+}
+efrom!(JayInputCaptureError, ClientError);
@@ -16,6 +16,7 @@ use {
             cell_ext::CellExt, clonecell::CloneCell, copyhashmap::CopyHashMap,
             transform_ext::TransformExt,
         },
+        wallpaper::WallpaperConfig,
         wire::{wl_output::*, WlOutputId, ZxdgOutputV1Id},
     },
     ahash::AHashMap,
@@ -101,6 +102,7 @@ pub struct PersistentOutputState {
     pub vrr_mode: Cell<&'static VrrMode>,
     pub vrr_cursor_hz: Cell<Option<f64>>,
     pub tearing_mode: Cell<&'static TearingMode>,
+    pub wallpaper: RefCell<Option<WallpaperConfig>>,
 }
 
 #[derive(Eq, PartialEq, Hash, Debug)]
@@ -1,6 +1,7 @@
 mod event_handling;
 pub mod ext_transient_seat_manager_v1;
 pub mod ext_transient_seat_v1;
+mod focus_history;
 mod gesture_owner;
 mod kb_owner;
 mod pointer_owner;
@@ -44,8 +45,10 @@ use {
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
                 DynDataSource, IpcError, IpcLocation,
             },
+            jay_input_capture::JayInputCapture,
             wl_output::WlOutputGlobal,
             wl_seat::{
+                focus_history::WindowSwitcher,
                 gesture_owner::GestureOwnerHolder,
                 kb_owner::KbOwnerHolder,
                 pointer_owner::PointerOwnerHolder,
@@ -77,13 +80,19 @@ use {
         rect::Rect,
         state::{DeviceHandlerData, State},
         tree::{
-            generic_node_visitor, ContainerNode, ContainerSplit, Direction, FoundNode, Node,
-            OutputNode, ToplevelNode, WorkspaceNode,
+            generic_node_visitor, ContainerNode, ContainerSplit, ContainingNode, Direction,
+            FoundNode, Node, OutputNode, ToplevelNode, WindowBorder, WorkspaceNode,
         },
         utils::{
-            asyncevent::AsyncEvent, bindings::PerClientBindings, clonecell::CloneCell,
-            copyhashmap::CopyHashMap, errorfmt::ErrorFmt, linkedlist::LinkedNode, numcell::NumCell,
-            rc_eq::rc_eq, smallmap::SmallMap,
+            asyncevent::AsyncEvent,
+            bindings::PerClientBindings,
+            clonecell::CloneCell,
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            linkedlist::{LinkedList, LinkedNode},
+            numcell::NumCell,
+            rc_eq::rc_eq,
+            smallmap::SmallMap,
         },
         wire::{
             wl_seat::*, ExtIdleNotificationV1Id, WlDataDeviceId, WlKeyboardId, WlPointerId,
@@ -94,6 +103,7 @@ use {
         xkbcommon::{DynKeyboardState, KeyboardState, KeymapId, XkbKeymap, XkbState},
     },
     ahash::AHashMap,
+    jay_config::ResizeAmount,
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -119,6 +129,9 @@ const MISSING_CAPABILITY: u32 = 0;
 
 pub const BTN_LEFT: u32 = 0x110;
 pub const BTN_RIGHT: u32 = 0x111;
+pub const BTN_MIDDLE: u32 = 0x112;
+pub const BTN_SIDE: u32 = 0x113;
+pub const BTN_EXTRA: u32 = 0x114;
 
 pub const SEAT_NAME_SINCE: Version = Version(2);
 
@@ -203,9 +216,14 @@ pub struct WlSeatGlobal {
     hold_bindings: PerClientBindings<ZwpPointerGestureHoldV1>,
     tablet: TabletSeatData,
     ei_seats: CopyHashMap<(ClientId, EiSeatId), Rc<EiSeat>>,
+    input_capture: CloneCell<Option<Rc<JayInputCapture>>>,
     ui_drag_highlight: Cell<Option<Rect>>,
     keyboard_node_serial: Cell<u64>,
     tray_popups: CopyHashMap<(TrayItemId, XdgPopupId), Rc<dyn DynTrayItem>>,
+    focus_history: LinkedList<Weak<dyn ToplevelNode>>,
+    focus_history_cursor: CloneCell<Option<Weak<dyn ToplevelNode>>>,
+    focus_history_navigating: Cell<bool>,
+    window_switcher: RefCell<Option<WindowSwitcher>>,
 }
 
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
@@ -277,8 +295,13 @@ impl WlSeatGlobal {
             hold_bindings: Default::default(),
             tablet: Default::default(),
             ei_seats: Default::default(),
+            input_capture: Default::default(),
             ui_drag_highlight: Default::default(),
             tray_popups: Default::default(),
+            focus_history: Default::default(),
+            focus_history_cursor: Default::default(),
+            focus_history_navigating: Cell::new(false),
+            window_switcher: Default::default(),
         });
         slf.pointer_cursor.set_owner(slf.clone());
         let seat = slf.clone();
@@ -561,7 +584,13 @@ impl WlSeatGlobal {
     }
 
     pub fn get_mono(&self) -> Option<bool> {
-        self.kb_parent_container().map(|c| c.mono_child.is_some())
+        self.kb_parent_container()
+            .map(|c| c.mono_child.is_some() && !c.stacked.get())
+    }
+
+    pub fn get_stacked(&self) -> Option<bool> {
+        self.kb_parent_container()
+            .map(|c| c.mono_child.is_some() && c.stacked.get())
     }
 
     pub fn get_split(&self) -> Option<ContainerSplit> {
@@ -573,12 +602,49 @@ impl WlSeatGlobal {
             if let Some(parent) = tl.tl_data().parent.get() {
                 if let Some(container) = parent.node_into_container() {
                     let node = if mono { Some(tl.deref()) } else { None };
+                    if mono {
+                        container.set_stacked(false);
+                    }
                     container.set_mono(node);
                 }
             }
         }
     }
 
+    pub fn set_stacked(&self, stacked: bool) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            if let Some(parent) = tl.tl_data().parent.get() {
+                if let Some(container) = parent.node_into_container() {
+                    if stacked {
+                        container.set_stacked(true);
+                        container.set_mono(Some(tl.deref()));
+                    } else if container.stacked.get() {
+                        container.set_mono(None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Cycles the parent container of the focused window between the split, tabbed,
+    /// and stacked layouts.
+    pub fn toggle_layout(&self) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            if let Some(parent) = tl.tl_data().parent.get() {
+                if let Some(container) = parent.node_into_container() {
+                    match (container.mono_child.is_some(), container.stacked.get()) {
+                        (false, _) => {
+                            container.set_stacked(false);
+                            container.set_mono(Some(tl.deref()));
+                        }
+                        (true, false) => container.set_stacked(true),
+                        (true, true) => container.set_mono(None),
+                    }
+                }
+            }
+        }
+    }
+
     pub fn set_split(&self, axis: ContainerSplit) {
         if let Some(c) = self.kb_parent_container() {
             c.set_split(axis);
@@ -632,6 +698,198 @@ impl WlSeatGlobal {
         self.set_tl_floating(tl, floating);
     }
 
+    pub fn minimize(&self) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            self.state.minimize(tl);
+        }
+    }
+
+    /// Restores the most recently minimized window.
+    pub fn unminimize_last(self: &Rc<Self>) {
+        let Some(tl) = self.state.minimized.last().and_then(|tl| tl.upgrade()) else {
+            return;
+        };
+        self.state.unminimize(self, tl);
+    }
+
+    pub fn get_sticky(&self) -> Option<bool> {
+        match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => Some(tl.tl_data().is_sticky()),
+            _ => None,
+        }
+    }
+
+    /// Sets whether the focused window stays visible when the workspace of its output
+    /// changes.
+    ///
+    /// This only has an effect while the window is floating.
+    pub fn set_sticky(&self, sticky: bool) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let data = tl.tl_data();
+        let old = data.is_sticky();
+        data.sticky.set(Some(sticky));
+        if old == sticky || !sticky {
+            return;
+        }
+        if let Some(float) = tl.tl_data().parent.get().and_then(|p| p.node_into_float()) {
+            float.restack();
+        }
+    }
+
+    /// Sets the opacity of the focused window.
+    ///
+    /// If `opacity` is `None`, the opacity configured for the app-id of the window is
+    /// used.
+    pub fn set_window_opacity(&self, opacity: Option<f32>) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        if tl.tl_data().opacity.replace(opacity) != opacity {
+            self.state.damage(tl.node_absolute_position());
+        }
+    }
+
+    /// Overrides the border of the focused window.
+    pub fn set_window_border(&self, border: WindowBorder) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            tl.tl_data().set_border(border);
+        }
+    }
+
+    /// Toggles whether the title bar of the focused window is hidden when it is tiled.
+    pub fn toggle_title_hidden(&self) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            let data = tl.tl_data();
+            data.set_title_hidden(!data.title_hidden());
+        }
+    }
+
+    pub fn mark(&self, mark: &str) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            tl.tl_data().add_mark(mark);
+        }
+    }
+
+    pub fn unmark(&self) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            tl.tl_data().clear_marks();
+        }
+    }
+
+    pub fn get_marks(&self) -> Vec<String> {
+        match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl.tl_data().marks.borrow().clone(),
+            _ => vec![],
+        }
+    }
+
+    /// Makes `tl` visible and gives it the keyboard focus.
+    ///
+    /// This switches to the workspace of `tl` and activates `tl` in all mono containers
+    /// that contain it.
+    pub fn reveal_toplevel(self: &Rc<Self>, mut tl: Rc<dyn ToplevelNode>) {
+        if let Some(swallower) = tl.tl_data().swallowed_by.get().and_then(|s| s.upgrade()) {
+            tl = swallower;
+        }
+        if tl.tl_data().minimized.borrow().is_some() {
+            self.state.unminimize(self, tl);
+            return;
+        }
+        if let Some(ws) = tl.tl_data().workspace.get() {
+            let output = ws.output.get();
+            if output.show_workspace(&ws) {
+                ws.flush_jay_workspaces();
+                output.schedule_update_render_data();
+                self.state.tree_changed();
+            }
+        }
+        let mut node = tl.clone();
+        while let Some(parent) = node.tl_data().parent.get() {
+            let Some(container) = parent.node_into_container() else {
+                break;
+            };
+            container.show_child(node.tl_as_node());
+            node = container;
+        }
+        self.focus_toplevel(tl);
+    }
+
+    pub fn focus_mark(self: &Rc<Self>, mark: &str) {
+        if let Some(tl) = self.state.find_mark(mark) {
+            self.reveal_toplevel(tl);
+        }
+    }
+
+    pub fn swap_with_mark(&self, mark: &str) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let Some(target) = self.state.find_mark(mark) else {
+            return;
+        };
+        if is_ancestor(&*tl, &*target) || is_ancestor(&*target, &*tl) {
+            return;
+        }
+        if tl.tl_data().is_fullscreen.get() || target.tl_data().is_fullscreen.get() {
+            return;
+        }
+        let parent_container =
+            |tl: &Rc<dyn ToplevelNode>| tl.tl_data().parent.get()?.node_into_container();
+        let (Some(a), Some(b)) = (parent_container(&tl), parent_container(&target)) else {
+            log::warn!("Only tiled windows can be swapped");
+            return;
+        };
+        if a.node_id() == b.node_id() {
+            a.swap_children(tl.tl_as_node(), target.tl_as_node());
+            return;
+        }
+        let tl_visible = tl.node_visible();
+        let target_visible = target.node_visible();
+        a.cnode_replace_child(tl.tl_as_node(), target.clone());
+        b.cnode_replace_child(target.tl_as_node(), tl.clone());
+        target.tl_set_visible(tl_visible);
+        tl.tl_set_visible(target_visible);
+    }
+
+    pub fn move_to_mark(&self, mark: &str) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let Some(target) = self.state.find_mark(mark) else {
+            return;
+        };
+        if tl.tl_data().is_fullscreen.get() || is_ancestor(&*tl, &*target) {
+            return;
+        }
+        let Some(container) = target
+            .tl_data()
+            .parent
+            .get()
+            .and_then(|p| p.node_into_container())
+        else {
+            if let Some(ws) = target.tl_data().workspace.get() {
+                self.set_workspace(&ws);
+            }
+            return;
+        };
+        let Some(parent) = tl.tl_data().parent.get() else {
+            return;
+        };
+        let old_ws = tl.tl_data().workspace.get();
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        parent.cnode_remove_child2(tl.tl_as_node(), true);
+        container.add_child_after(target.tl_as_node(), tl.clone());
+        if !tl.node_visible() {
+            if let Some(old_ws) = old_ws {
+                for focus in kb_foci {
+                    old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
+                }
+            }
+        }
+    }
+
     pub fn set_tl_floating(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, floating: bool) {
         let data = tl.tl_data();
         if data.is_fullscreen.get() {
@@ -691,8 +949,10 @@ impl WlSeatGlobal {
         if direction == Direction::Down && tl.node_is_container() {
             tl.node_do_focus(self, direction);
         } else if let Some(p) = tl.tl_data().parent.get() {
-            if let Some(c) = p.node_into_container() {
+            if let Some(c) = p.clone().node_into_container() {
                 c.move_focus_from_child(self, tl.deref(), direction);
+            } else if let Some(s) = p.node_into_scrolling() {
+                s.move_focus_from_column(self, tl.tl_as_node(), direction);
             }
         }
     }
@@ -701,13 +961,71 @@ impl WlSeatGlobal {
         let kb_node = self.keyboard_node.get();
         if let Some(tl) = kb_node.node_toplevel() {
             if let Some(parent) = tl.tl_data().parent.get() {
-                if let Some(c) = parent.node_into_container() {
+                if let Some(c) = parent.clone().node_into_container() {
                     c.move_child(tl, direction);
+                } else if let Some(s) = parent.node_into_scrolling() {
+                    s.move_column(tl.tl_as_node(), direction);
                 }
             }
         }
     }
 
+    pub fn resize_focused(self: &Rc<Self>, split: ContainerSplit, amount: ResizeAmount) {
+        let kb_node = self.keyboard_node.get();
+        let Some(tl) = kb_node.node_toplevel() else {
+            return;
+        };
+        if tl.tl_data().is_fullscreen.get() {
+            return;
+        }
+        let Some(parent) = tl.tl_data().parent.get() else {
+            return;
+        };
+        if let Some(c) = parent.clone().node_into_container() {
+            c.resize_child(&*tl, split, amount);
+        } else if let Some(s) = parent.clone().node_into_scrolling() {
+            s.resize_column(tl.tl_as_node(), split, amount);
+        } else if let Some(f) = parent.node_into_float() {
+            f.resize_child(split, amount);
+        }
+    }
+
+    pub fn cycle_column_width(self: &Rc<Self>) {
+        let kb_node = self.keyboard_node.get();
+        let Some(mut tl) = kb_node.node_toplevel() else {
+            return;
+        };
+        while let Some(parent) = tl.tl_data().parent.get() {
+            if let Some(s) = parent.clone().node_into_scrolling() {
+                s.cycle_column_width(tl.tl_as_node());
+                return;
+            }
+            match parent.node_into_container() {
+                Some(c) => tl = c,
+                _ => return,
+            }
+        }
+    }
+
+    pub fn promote_to_master(self: &Rc<Self>) {
+        let kb_node = self.keyboard_node.get();
+        let Some(tl) = kb_node.node_toplevel() else {
+            return;
+        };
+        if tl.tl_data().is_fullscreen.get() {
+            return;
+        }
+        let Some(parent) = tl.tl_data().parent.get() else {
+            return;
+        };
+        if parent.node_into_container().is_none() {
+            return;
+        }
+        if let Some(ws) = tl.tl_data().workspace.get() {
+            ws.promote_to_master(&*tl);
+        }
+    }
+
     fn set_selection_<T, X, S>(
         self: &Rc<Self>,
         field: &CloneCell<Option<Rc<dyn DynDataSource>>>,
@@ -922,6 +1240,7 @@ impl WlSeatGlobal {
         self.constraint.take();
         self.text_inputs.borrow_mut().clear();
         self.text_input.take();
+        self.clear_focus_history();
         self.input_method.take();
         self.input_method_grab.take();
         self.swipe_bindings.clear();
@@ -930,6 +1249,7 @@ impl WlSeatGlobal {
         self.cursor_user_group.detach();
         self.tablet_clear();
         self.ei_seats.clear();
+        self.input_capture.take();
     }
 
     pub fn id(&self) -> SeatId {
@@ -1035,6 +1355,22 @@ impl WlSeatGlobal {
         self.update_capabilities();
     }
 
+    pub fn input_capture(&self) -> Option<Rc<JayInputCapture>> {
+        self.input_capture.get()
+    }
+
+    pub fn set_input_capture(&self, capture: Option<Rc<JayInputCapture>>) {
+        self.input_capture.set(capture);
+    }
+
+    pub fn set_ei_client_emulating(&self, client: ClientId, sequence: Option<u32>) {
+        self.for_each_ei_seat(|ei_seat| {
+            if ei_seat.client.id == client {
+                ei_seat.set_emulating(sequence);
+            }
+        });
+    }
+
     pub fn seat_xkb_state(&self) -> Rc<dyn DynKeyboardState> {
         self.seat_xkb_state.get()
     }
@@ -1043,6 +1379,12 @@ impl WlSeatGlobal {
         self.latest_kb_state.get()
     }
 
+    pub fn bar_modifiers_held(&self) -> bool {
+        let mask = self.state.bar_modifiers.get().0;
+        let mods = self.latest_kb_state.get().borrow().mods.mods_effective;
+        mask != 0 && mods & mask == mask
+    }
+
     pub fn output_extents_changed(&self) {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.regions_changed();
@@ -1262,6 +1604,21 @@ pub fn collect_kb_foci(node: Rc<dyn Node>) -> SmallVec<[Rc<WlSeatGlobal>; 3]> {
     res
 }
 
+/// Returns whether `node` is `ancestor` or is contained in `ancestor`.
+fn is_ancestor(ancestor: &dyn ToplevelNode, node: &dyn ToplevelNode) -> bool {
+    if ancestor.node_id() == node.node_id() {
+        return true;
+    }
+    let mut parent = node.tl_data().parent.get();
+    while let Some(p) = parent {
+        if p.node_id() == ancestor.node_id() {
+            return true;
+        }
+        parent = p.node_toplevel().and_then(|tl| tl.tl_data().parent.get());
+    }
+    false
+}
+
 impl DeviceHandlerData {
     pub fn set_seat(&self, seat: Option<Rc<WlSeatGlobal>>) {
         let old = self.seat.set(seat.clone());
@@ -36,7 +36,7 @@ use {
         object::Version,
         rect::Rect,
         state::DeviceHandlerData,
-        tree::{Direction, Node, ToplevelNode},
+        tree::{BarMode, Direction, Node, ToplevelNode},
         utils::{bitflags::BitflagsExt, hash_map_ext::HashMapExt, smallmap::SmallMap},
         wire::WlDataOfferId,
         xkbcommon::{KeyboardState, XkbState, XKB_KEY_DOWN, XKB_KEY_UP},
@@ -45,7 +45,7 @@ use {
     jay_config::{
         input::SwitchEvent,
         keyboard::{
-            mods::{Modifiers, CAPS, NUM, RELEASE},
+            mods::{Modifiers, CAPS, LOGO, NUM, RELEASE, SHIFT},
             syms::{KeySym, SYM_Escape},
         },
     },
@@ -145,6 +145,10 @@ impl NodeSeatState {
         self.kb_foci.len() > 0
     }
 
+    pub fn has_pointer_grab(&self) -> bool {
+        self.pointer_grabs.is_not_empty()
+    }
+
     pub fn release_kb_grab(&self) {
         for (_, seat) in &self.kb_foci {
             seat.ungrab_kb();
@@ -505,6 +509,9 @@ impl WlSeatGlobal {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_motion_abs(time_usec, x, y);
         });
+        if self.input_capture.is_some() {
+            return;
+        }
         let (x, y) = self.set_pointer_cursor_position(x, y);
         if let Some(c) = self.constraint.get() {
             if c.ty == ConstraintType::Lock || !c.contains(x.round_down(), y.round_down()) {
@@ -528,6 +535,9 @@ impl WlSeatGlobal {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_motion(time_usec, dx, dy);
         });
+        if self.input_capture.is_some() || self.check_pointer_barriers(dx, dy) {
+            return;
+        }
         self.pointer_owner.relative_motion(
             self,
             time_usec,
@@ -572,10 +582,26 @@ impl WlSeatGlobal {
         self.cursor_moved(time_usec);
     }
 
+    fn check_pointer_barriers(self: &Rc<Self>, dx: Fixed, dy: Fixed) -> bool {
+        if self.state.input_captures.is_empty() || self.state.lock.locked.get() {
+            return false;
+        }
+        let (x, y) = self.pointer_cursor.position();
+        for capture in self.state.input_captures.lock().values() {
+            if capture.handle_motion(self, x, y, x + dx, y + dy) {
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn button_event(self: &Rc<Self>, time_usec: u64, button: u32, state: KeyState) {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_button(time_usec, button, state);
         });
+        if self.input_capture.is_some() {
+            return;
+        }
         self.state.for_each_seat_tester(|t| {
             t.send_button(self.id, time_usec, button, state);
         });
@@ -815,11 +841,18 @@ impl WlSeatGlobal {
             }
         };
         let mut shortcuts = SmallVec::<[_; 1]>::new();
+        let mut global_shortcut = false;
+        let mut captured = self.input_capture.is_some();
+        let mut release_capture = false;
         let new_mods;
         {
             let mut mods = xkb_state.mods().mods_effective & !(CAPS.0 | NUM.0);
+            let global_mods = mods;
             if state == wl_keyboard::RELEASED {
                 mods |= RELEASE.0;
+                self.state.for_each_global_shortcuts(|gs| {
+                    global_shortcut |= gs.key_released(self.id, time_usec, key);
+                });
             }
             let scs = &*self.shortcuts.borrow();
             let keysyms = xkb_state.unmodified_keysyms(key);
@@ -828,7 +861,14 @@ impl WlSeatGlobal {
                 if sym == SYM_Escape.0 && mods == 0 {
                     revert_pointer_to_default = true;
                 }
-                if !self.state.lock.locked.get() {
+                if captured
+                    && state == wl_keyboard::PRESSED
+                    && sym == SYM_Escape.0
+                    && mods == (LOGO | SHIFT).0
+                {
+                    release_capture = true;
+                }
+                if !self.state.lock.locked.get() && !captured {
                     if let Some(key_mods) = scs.get(&sym) {
                         for (key_mods, mask) in key_mods {
                             if mods & mask == key_mods {
@@ -840,6 +880,12 @@ impl WlSeatGlobal {
                             }
                         }
                     }
+                    if state == wl_keyboard::PRESSED {
+                        self.state.for_each_global_shortcuts(|gs| {
+                            global_shortcut |=
+                                gs.key_pressed(self.id, time_usec, key, global_mods, sym);
+                        });
+                    }
                 }
             }
             if revert_pointer_to_default {
@@ -849,6 +895,14 @@ impl WlSeatGlobal {
             }
             new_mods = xkb_state.update(key, xkb_dir);
         }
+        if release_capture {
+            if let Some(capture) = self.input_capture.get() {
+                drop(xkb_state);
+                capture.release_by_compositor();
+                xkb_state = xkb_state_rc.borrow_mut();
+            }
+            captured = false;
+        }
         self.state.for_each_seat_tester(|t| {
             t.send_key(self.id, time_usec, key, key_state);
         });
@@ -871,11 +925,16 @@ impl WlSeatGlobal {
             }
             forward = self.forward.get();
         }
-        if forward {
+        if global_shortcut || release_capture {
+            forward = false;
+        }
+        if forward && !captured {
             match &input_method_grab {
                 Some(g) => g.on_key(time_usec, key, state, &xkb_state.kb_state),
                 _ => node.node_on_key(self, time_usec, key, state, &xkb_state.kb_state),
             }
+        }
+        if forward || captured {
             self.for_each_ei_seat(|ei_seat| {
                 ei_seat.handle_key(time_usec, key, state, &xkb_state.kb_state);
             });
@@ -900,8 +959,15 @@ impl WlSeatGlobal {
                 xkb_state.kb_state.pressed_keys.insert(key);
             }
         }
+        let mods_released = new_mods && xkb_state.mods().mods_effective & !(CAPS.0 | NUM.0) == 0;
         drop(xkb_state);
         self.latest_kb_state.set(xkb_state_rc);
+        if new_mods && self.state.bar_mode.get() == BarMode::OnModifier {
+            self.state.update_bar_revealed();
+        }
+        if mods_released {
+            self.commit_window_switcher();
+        }
     }
 
     pub(super) fn for_each_ei_seat(&self, mut f: impl FnMut(&Rc<EiSeat>)) {
@@ -0,0 +1,156 @@
+use {
+    crate::{
+        ifs::wl_seat::WlSeatGlobal,
+        tree::{OutputNode, OutputWindowSwitcher, ToplevelNode},
+        utils::linkedlist::NodeRef,
+    },
+    jay_config::keyboard::mods::{CAPS, NUM},
+    std::rc::{Rc, Weak},
+};
+
+pub struct WindowSwitcher {
+    output: Rc<OutputNode>,
+    windows: Vec<Weak<dyn ToplevelNode>>,
+    selected: usize,
+}
+
+impl WlSeatGlobal {
+    /// Moves `tl` to the front of the focus history.
+    pub fn add_to_focus_history(&self, tl: &Rc<dyn ToplevelNode>) {
+        if self.focus_history_navigating.get() {
+            return;
+        }
+        self.focus_history_cursor.take();
+        let data = tl.tl_data();
+        let mut entries = data.focus_history.borrow_mut();
+        match entries.get(&self.id) {
+            Some(node) => self.focus_history.add_first_existing(&node.to_ref()),
+            _ => {
+                let node = self.focus_history.add_first(data.slf.clone());
+                entries.insert(self.id, node);
+            }
+        }
+    }
+
+    pub(super) fn clear_focus_history(&self) {
+        self.focus_history_cursor.take();
+        self.window_switcher.take();
+        let windows: Vec<_> = self.focus_history.iter().collect();
+        for tl in windows {
+            if let Some(tl) = tl.upgrade() {
+                tl.tl_data().focus_history.borrow_mut().remove(&self.id);
+            }
+        }
+    }
+
+    /// Returns the windows in the focus history, most recently focused first.
+    pub fn focus_history(&self) -> Vec<Rc<dyn ToplevelNode>> {
+        self.focus_history
+            .iter()
+            .filter_map(|n| n.upgrade())
+            .collect()
+    }
+
+    /// Returns the entry of the window at the current position in the focus history.
+    fn focus_history_position(&self) -> Option<NodeRef<Weak<dyn ToplevelNode>>> {
+        let tl = match self.focus_history_cursor.get().and_then(|c| c.upgrade()) {
+            Some(tl) => tl,
+            _ => self.keyboard_node.get().node_toplevel()?,
+        };
+        let entries = tl.tl_data().focus_history.borrow();
+        entries.get(&self.id).map(|node| node.to_ref())
+    }
+
+    fn navigate_focus_history(self: &Rc<Self>, older: bool) {
+        let next = match (self.focus_history_position(), older) {
+            (Some(entry), true) => entry.next(),
+            (Some(entry), false) => entry.prev(),
+            (None, true) => self.focus_history.first(),
+            (None, false) => None,
+        };
+        let Some(tl) = next.and_then(|n| n.upgrade()) else {
+            return;
+        };
+        self.focus_history_cursor
+            .set(Some(tl.tl_data().slf.clone()));
+        self.focus_history_navigating.set(true);
+        self.reveal_toplevel(tl);
+        self.focus_history_navigating.set(false);
+    }
+
+    /// Focuses the window that was focused before the current position in the focus
+    /// history.
+    pub fn focus_previous(self: &Rc<Self>) {
+        self.navigate_focus_history(true);
+    }
+
+    /// Focuses the window that was focused after the current position in the focus
+    /// history.
+    pub fn focus_next_in_history(self: &Rc<Self>) {
+        self.navigate_focus_history(false);
+    }
+
+    /// Opens the window switcher or moves its selection.
+    ///
+    /// The selected window is focused once all modifiers have been released.
+    pub fn window_switcher(self: &Rc<Self>, reverse: bool) {
+        let mut guard = self.window_switcher.borrow_mut();
+        match &mut *guard {
+            Some(s) => {
+                let len = s.windows.len();
+                s.selected = match reverse {
+                    true => (s.selected + len - 1) % len,
+                    false => (s.selected + 1) % len,
+                };
+            }
+            _ => {
+                let windows = self.focus_history();
+                if windows.is_empty() {
+                    return;
+                }
+                let selected = match reverse {
+                    true => windows.len() - 1,
+                    false => 1 % windows.len(),
+                };
+                *guard = Some(WindowSwitcher {
+                    output: self.get_output(),
+                    windows: windows.iter().map(|w| w.tl_data().slf.clone()).collect(),
+                    selected,
+                });
+            }
+        }
+        let Some(switcher) = &*guard else {
+            return;
+        };
+        let titles = switcher
+            .windows
+            .iter()
+            .map(|w| match w.upgrade() {
+                Some(w) => w.tl_data().title.borrow().clone(),
+                _ => String::new(),
+            })
+            .collect();
+        *switcher.output.window_switcher.borrow_mut() = Some(OutputWindowSwitcher {
+            titles,
+            selected: switcher.selected,
+        });
+        switcher.output.schedule_update_render_data();
+        let mods = self.latest_kb_state.get().borrow().mods.mods_effective & !(CAPS.0 | NUM.0);
+        if mods == 0 {
+            drop(guard);
+            self.commit_window_switcher();
+        }
+    }
+
+    /// Closes the window switcher and focuses the selected window.
+    pub(super) fn commit_window_switcher(self: &Rc<Self>) {
+        let Some(switcher) = self.window_switcher.borrow_mut().take() else {
+            return;
+        };
+        switcher.output.window_switcher.take();
+        switcher.output.schedule_update_render_data();
+        if let Some(tl) = switcher.windows[switcher.selected].upgrade() {
+            self.reveal_toplevel(tl);
+        }
+    }
+}
@@ -89,6 +89,9 @@ impl PointerOwnerHolder {
         seat.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_pending_scroll(time_usec, &pending);
         });
+        if seat.input_capture.is_some() {
+            return;
+        }
         seat.state.for_each_seat_tester(|t| {
             t.send_axis(seat.id, time_usec, &pending);
         });
@@ -1,6 +1,7 @@
 pub mod commit_timeline;
 pub mod cursor;
 pub mod dnd_icon;
+pub mod ext_background_effect_surface_v1;
 pub mod ext_session_lock_surface_v1;
 pub mod tray;
 pub mod wl_subsurface;
@@ -47,6 +48,7 @@ use {
                 commit_timeline::{ClearReason, CommitTimeline, CommitTimelineError},
                 cursor::CursorSurface,
                 dnd_icon::DndIcon,
+                ext_background_effect_surface_v1::ExtBackgroundEffectSurfaceV1,
                 tray::TrayItemId,
                 wl_subsurface::{PendingSubsurfaceData, SubsurfaceId, WlSubsurface},
                 wp_alpha_modifier_surface_v1::WpAlphaModifierSurfaceV1,
@@ -319,6 +321,8 @@ pub struct WlSurface {
     destroyed: Cell<bool>,
     commit_timeline: CommitTimeline,
     alpha_modifier: CloneCell<Option<Rc<WpAlphaModifierSurfaceV1>>>,
+    background_effect: CloneCell<Option<Rc<ExtBackgroundEffectSurfaceV1>>>,
+    blur_region: CloneCell<Option<Rc<Region>>>,
     alpha: Cell<Option<f32>>,
     pub text_input_connections: SmallMap<SeatId, Rc<TextInputConnection>, 1>,
     vblank_listener: EventListener<dyn VblankListener>,
@@ -454,6 +458,7 @@ struct PendingState {
     acquire_point: Option<(Rc<SyncObj>, SyncObjPoint)>,
     release_point: Option<(Rc<SyncObj>, SyncObjPoint)>,
     alpha_multiplier: Option<Option<f32>>,
+    blur_region: Option<Option<Rc<Region>>>,
     explicit_sync: bool,
     fifo_barrier_set: bool,
     fifo_barrier_wait: bool,
@@ -509,6 +514,7 @@ impl PendingState {
         opt!(tearing);
         opt!(content_type);
         opt!(alpha_multiplier);
+        opt!(blur_region);
         opt!(commit_time);
         opt!(tray_item_ack_serial);
         {
@@ -656,6 +662,8 @@ impl WlSurface {
             destroyed: Cell::new(false),
             commit_timeline: client.commit_timelines.create_timeline(),
             alpha_modifier: Default::default(),
+            background_effect: Default::default(),
+            blur_region: Default::default(),
             alpha: Default::default(),
             text_input_connections: Default::default(),
             vblank_listener: EventListener::new(slf.clone()),
@@ -822,6 +830,15 @@ impl WlSurface {
         }
     }
 
+    fn animate_unmap(&self, buffer: &Rc<SurfaceBuffer>) {
+        let Some(tl) = self.toplevel.get() else {
+            return;
+        };
+        if tl.tl_scanout_surface().is_some_and(|s| s.id == self.id) {
+            tl.tl_data().animate_close(self, buffer);
+        }
+    }
+
     fn set_toplevel(&self, tl: Option<Rc<dyn ToplevelNode>>) {
         let ch = self.children.borrow();
         if let Some(ch) = &*ch {
@@ -1140,10 +1157,18 @@ impl WlSurface {
             alpha_changed = true;
             self.alpha.set(alpha);
         }
+        let mut blur_changed = false;
+        if let Some(region) = pending.blur_region.take() {
+            blur_changed = true;
+            self.blur_region.set(region);
+        }
         let buffer_abs_pos = self.buffer_abs_pos.get();
         let mut max_surface_size = buffer_abs_pos.size();
-        let mut damage_full =
-            scale_changed || buffer_transform_changed || viewport_changed || alpha_changed;
+        let mut damage_full = scale_changed
+            || buffer_transform_changed
+            || viewport_changed
+            || alpha_changed
+            || blur_changed;
         let mut buffer_changed = false;
         let mut old_raw_size = None;
         let (mut dx, mut dy) = mem::take(&mut pending.offset);
@@ -1151,6 +1176,9 @@ impl WlSurface {
             buffer_changed = true;
             if let Some(buffer) = self.buffer.take() {
                 old_raw_size = Some(buffer.buffer.rect);
+                if buffer_change.is_none() {
+                    self.animate_unmap(&buffer);
+                }
             }
             if let Some(buffer) = buffer_change {
                 if buffer.is_shm() {
@@ -1646,6 +1674,10 @@ impl WlSurface {
     pub fn alpha(&self) -> Option<f32> {
         self.alpha.get()
     }
+
+    pub fn blur_region(&self) -> Option<Rc<Region>> {
+        self.blur_region.get()
+    }
 }
 
 object_base! {
@@ -1674,6 +1706,7 @@ impl Object for WlSurface {
         self.drm_feedback.clear();
         self.commit_timeline.clear(ClearReason::BreakLoops);
         self.alpha_modifier.take();
+        self.background_effect.take();
         self.text_input_connections.clear();
         self.fifo.take();
         self.commit_timer.take();
@@ -1803,6 +1836,7 @@ impl Node for WlSurface {
         if let Some(tl) = self.toplevel.get() {
             tl.tl_data().focus_node.insert(seat.id(), self.clone());
             tl.tl_on_activate();
+            seat.add_to_focus_history(&tl);
         }
         seat.focus_surface(&self);
     }
@@ -0,0 +1,82 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_surface::WlSurface,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{ext_background_effect_surface_v1::*, ExtBackgroundEffectSurfaceV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ExtBackgroundEffectSurfaceV1 {
+    pub id: ExtBackgroundEffectSurfaceV1Id,
+    pub version: Version,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ExtBackgroundEffectSurfaceV1 {
+    pub fn new(
+        id: ExtBackgroundEffectSurfaceV1Id,
+        surface: &Rc<WlSurface>,
+        version: Version,
+    ) -> Self {
+        Self {
+            id,
+            version,
+            client: surface.client.clone(),
+            surface: surface.clone(),
+            tracker: Default::default(),
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) -> Result<(), ExtBackgroundEffectSurfaceV1Error> {
+        if self.surface.background_effect.is_some() {
+            return Err(ExtBackgroundEffectSurfaceV1Error::Exists);
+        }
+        self.surface.background_effect.set(Some(self.clone()));
+        Ok(())
+    }
+}
+
+impl ExtBackgroundEffectSurfaceV1RequestHandler for ExtBackgroundEffectSurfaceV1 {
+    type Error = ExtBackgroundEffectSurfaceV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.surface.background_effect.take();
+        self.surface.pending.borrow_mut().blur_region = Some(None);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_blur_region(&self, req: SetBlurRegion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let region = if req.region.is_some() {
+            Some(self.client.lookup(req.region)?.region())
+        } else {
+            None
+        };
+        self.surface.pending.borrow_mut().blur_region = Some(region);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ExtBackgroundEffectSurfaceV1;
+    version = self.version;
+}
+
+impl Object for ExtBackgroundEffectSurfaceV1 {}
+
+simple_add_obj!(ExtBackgroundEffectSurfaceV1);
+
+#[derive(Debug, Error)]
+pub enum ExtBackgroundEffectSurfaceV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The surface already has a background effect extension attached")]
+    Exists,
+}
+efrom!(ExtBackgroundEffectSurfaceV1Error, ClientError);
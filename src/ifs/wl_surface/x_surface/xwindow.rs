@@ -13,7 +13,8 @@ use {
         tree::{
             default_tile_drag_destination, ContainerSplit, Direction, FindTreeResult,
             FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitor, StackedNode,
-            TileDragDestination, ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
+            TileDragDestination, ToplevelData, ToplevelNode, ToplevelNodeBase, ToplevelSizeHints,
+            WorkspaceNode,
         },
         utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, linkedlist::LinkedNode},
         wire::WlSurfaceId,
@@ -27,6 +28,7 @@ use {
         rc::Rc,
     },
     thiserror::Error,
+    uapi::c,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -290,6 +292,8 @@ impl Xwindow {
             Change::Map => {
                 if override_redirect {
                     self.tl_set_visible(true);
+                } else {
+                    self.toplevel_data.animate_open();
                 }
                 self.toplevel_data.broadcast(self.clone());
             }
@@ -414,6 +418,21 @@ impl ToplevelNodeBase for Xwindow {
         self.x.surface.set_output(&ws.output.get());
     }
 
+    fn tl_pid(&self) -> Option<c::pid_t> {
+        self.data.info.pid.get().map(|pid| pid as c::pid_t)
+    }
+
+    fn tl_size_hints(&self) -> ToplevelSizeHints {
+        let hints = &self.data.info.normal_hints;
+        let positive = |v: i32| (v > 0).then_some(v);
+        ToplevelSizeHints {
+            min_width: positive(hints.min_width.get()),
+            min_height: positive(hints.min_height.get()),
+            max_width: positive(hints.max_width.get()),
+            max_height: positive(hints.max_height.get()),
+        }
+    }
+
     fn tl_change_extents_impl(self: Rc<Self>, rect: &Rect) {
         // log::info!("xwin {} change_extents {:?}", self.data.window_id, rect);
         let old = self.data.info.extents.replace(*rect);
@@ -27,7 +27,8 @@ use {
         tree::{
             default_tile_drag_destination, ContainerSplit, Direction, FindTreeResult,
             FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitor, OutputNode, TileDragDestination,
-            ToplevelData, ToplevelNode, ToplevelNodeBase, ToplevelNodeId, WorkspaceNode,
+            ToplevelData, ToplevelNode, ToplevelNodeBase, ToplevelNodeId, ToplevelSizeHints,
+            WorkspaceNode,
         },
         utils::{clonecell::CloneCell, hash_map_ext::HashMapExt},
         wire::{xdg_toplevel::*, XdgToplevelId},
@@ -350,7 +351,10 @@ impl XdgToplevelRequestHandler for XdgToplevel {
         Ok(())
     }
 
-    fn set_minimized(&self, _req: SetMinimized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_minimized(&self, _req: SetMinimized, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.is_mapped.get() {
+            self.state.minimize(slf.clone());
+        }
         Ok(())
     }
 }
@@ -459,6 +463,7 @@ impl XdgToplevel {
             //     }
             // }
             self.state.tree_changed();
+            self.toplevel_data.animate_open();
             self.toplevel_data.broadcast(self.clone());
         }
     }
@@ -590,6 +595,15 @@ impl ToplevelNodeBase for XdgToplevel {
         self.xdg.set_workspace(ws);
     }
 
+    fn tl_size_hints(&self) -> ToplevelSizeHints {
+        ToplevelSizeHints {
+            min_width: self.min_width.get(),
+            min_height: self.min_height.get(),
+            max_width: self.max_width.get(),
+            max_height: self.max_height.get(),
+        }
+    }
+
     fn tl_change_extents_impl(self: Rc<Self>, rect: &Rect) {
         self.extents_set.set(true);
         let nw = rect.width();
@@ -519,7 +519,7 @@ impl GfxFramebuffer for TestGfxFb {
             copy_to_staging(staging);
             for op in ops {
                 match op {
-                    GfxApiOpt::Sync => {}
+                    GfxApiOpt::Sync | GfxApiOpt::Blur(_) => {}
                     GfxApiOpt::FillRect(f) => fill_rect(&f, staging),
                     GfxApiOpt::CopyTexture(c) => copy_texture(&c, staging)?,
                 }
@@ -47,6 +47,7 @@ mod leaks;
 mod tracy;
 mod acceptor;
 mod allocator;
+mod animation;
 mod async_engine;
 mod backend;
 mod backends;
@@ -101,6 +102,7 @@ mod user_session;
 mod utils;
 mod version;
 mod video;
+mod wallpaper;
 mod wheel;
 mod wire;
 mod wire_dbus;
@@ -47,6 +47,8 @@ pub struct OutputSchedule {
     software_cursor_change: Cell<Change>,
 
     iteration: NumCell<u64>,
+
+    animation_end_nsec: Cell<u64>,
 }
 
 impl OutputSchedule {
@@ -70,6 +72,7 @@ impl OutputSchedule {
             last_present_nsec: Default::default(),
             cursor_delta_nsec: Default::default(),
             iteration: Default::default(),
+            animation_end_nsec: Default::default(),
         };
         if let Some(hz) = persistent.vrr_cursor_hz.get() {
             slf.set_cursor_hz(hz);
@@ -106,9 +109,36 @@ impl OutputSchedule {
             self.hardware_cursor_change.set(Change::None);
         }
         self.iteration.fetch_add(1);
+        self.animation_frame();
         self.trigger();
     }
 
+    /// Requests a frame after every latch until `end_nsec` has passed.
+    pub fn animate_until(&self, end_nsec: u64) {
+        if end_nsec > self.animation_end_nsec.get() {
+            self.animation_end_nsec.set(end_nsec);
+        }
+        self.connector.damage();
+    }
+
+    /// Returns whether an animation is in progress.
+    pub fn animating(&self) -> bool {
+        self.animation_end_nsec.get() != 0
+    }
+
+    fn animation_frame(&self) {
+        let end = self.animation_end_nsec.get();
+        if end == 0 {
+            return;
+        }
+        if self.last_present_nsec.get() >= end {
+            self.animation_end_nsec.set(0);
+        }
+        // Render one more frame after the animations have finished so that the final
+        // state becomes visible.
+        self.connector.damage();
+    }
+
     pub fn vrr_enabled(&self) -> bool {
         self.vrr_enabled.get()
     }
@@ -5,7 +5,7 @@ use {
         pango::consts::{CairoFormat, CairoOperator, PangoEllipsizeMode},
         rect::Rect,
     },
-    std::{cell::Cell, ptr, rc::Rc},
+    std::{cell::Cell, ffi::CStr, ptr, rc::Rc},
     thiserror::Error,
     uapi::{c, IntoUstr},
 };
@@ -60,6 +60,11 @@ unsafe extern "C" {
     fn g_object_unref(object: *mut GObject);
 }
 
+#[link(name = "glib-2.0")]
+unsafe extern "C" {
+    fn g_free(mem: *mut c::c_void);
+}
+
 #[repr(transparent)]
 struct PangoFontDescription_(u8);
 #[repr(transparent)]
@@ -71,6 +76,12 @@ unsafe extern "C" {
     fn pango_font_description_free(desc: *mut PangoFontDescription_);
     fn pango_font_description_get_size(desc: *mut PangoFontDescription_) -> c::c_int;
     fn pango_font_description_set_size(desc: *mut PangoFontDescription_, size: c::c_int);
+    fn pango_font_description_get_family(desc: *mut PangoFontDescription_) -> *const c::c_char;
+    fn pango_font_description_set_family(
+        desc: *mut PangoFontDescription_,
+        family: *const c::c_char,
+    );
+    fn pango_font_description_to_string(desc: *mut PangoFontDescription_) -> *mut c::c_char;
 
     fn pango_layout_new(context: *mut PangoContext_) -> *mut PangoLayout_;
     fn pango_layout_set_width(layout: *mut PangoLayout_, width: c::c_int);
@@ -278,6 +289,32 @@ impl PangoFontDescription {
             pango_font_description_set_size(self.s, size);
         }
     }
+
+    pub fn family(&self) -> Option<String> {
+        unsafe {
+            let family = pango_font_description_get_family(self.s);
+            if family.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(family).to_string_lossy().into_owned())
+        }
+    }
+
+    pub fn set_family<'a>(&mut self, family: impl IntoUstr<'a>) {
+        let family = family.into_ustr();
+        unsafe {
+            pango_font_description_set_family(self.s, family.as_ptr());
+        }
+    }
+
+    pub fn description(&self) -> String {
+        unsafe {
+            let s = pango_font_description_to_string(self.s);
+            let res = CStr::from_ptr(s).to_string_lossy().into_owned();
+            g_free(s as _);
+            res
+        }
+    }
 }
 
 impl Drop for PangoFontDescription {
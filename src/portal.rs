@@ -1,7 +1,11 @@
+mod ptl_clipboard;
 mod ptl_display;
+mod ptl_global_shortcuts;
+mod ptl_input_capture;
 mod ptl_remote_desktop;
 mod ptl_render_ctx;
 mod ptl_screencast;
+mod ptl_screenshot;
 mod ptl_session;
 mod ptl_text;
 mod ptr_gui;
@@ -10,6 +14,7 @@ use {
     crate::{
         async_engine::AsyncEngine,
         cli::GlobalArgs,
+        cpu_worker::CpuWorker,
         dbus::{
             Dbus, DbusSocket, BUS_DEST, BUS_PATH, DBUS_NAME_FLAG_DO_NOT_QUEUE,
             DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER,
@@ -19,10 +24,14 @@ use {
         logger::Logger,
         pipewire::pw_con::{PwCon, PwConHolder, PwConOwner},
         portal::{
+            ptl_clipboard::add_clipboard_dbus_members,
             ptl_display::{watch_displays, PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::add_global_shortcuts_dbus_members,
+            ptl_input_capture::add_input_capture_dbus_members,
             ptl_remote_desktop::add_remote_desktop_dbus_members,
             ptl_render_ctx::PortalRenderCtx,
             ptl_screencast::add_screencast_dbus_members,
+            ptl_screenshot::add_screenshot_dbus_members,
             ptl_session::PortalSession,
         },
         utils::{
@@ -52,8 +61,9 @@ use {
     uapi::{c, getpid, OwnedFd},
 };
 
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+
 const PORTAL_SUCCESS: u32 = 0;
-#[expect(dead_code)]
 const PORTAL_CANCELLED: u32 = 1;
 #[expect(dead_code)]
 const PORTAL_ENDED: u32 = 2;
@@ -188,6 +198,12 @@ async fn run_async(
             fatal!("Could not create a timer wheel: {}", ErrorFmt(e));
         }
     };
+    let cpu_worker = match CpuWorker::new(&ring, &eng) {
+        Ok(w) => Rc::new(w),
+        Err(e) => {
+            fatal!("Could not create a CPU worker: {}", ErrorFmt(e));
+        }
+    };
     let pw_con = match PwConHolder::new(&eng, &ring).await {
         Ok(p) => Some(p),
         Err(e) => {
@@ -200,6 +216,7 @@ async fn run_async(
         ring,
         eng,
         wheel,
+        cpu_worker,
         displays: Default::default(),
         dbus,
         sessions: Default::default(),
@@ -212,14 +229,15 @@ async fn run_async(
         pw_con.con.owner.set(Some(state.clone()));
     }
     let _root = {
-        let obj = state
-            .dbus
-            .add_object("/org/freedesktop/portal/desktop")
-            .unwrap();
+        let obj = state.dbus.add_object(PORTAL_PATH).unwrap();
         if let Some(pw_con) = &pw_con {
             add_screencast_dbus_members(&state, &pw_con.con, &obj);
         }
         add_remote_desktop_dbus_members(&state, &obj);
+        add_clipboard_dbus_members(&state, &obj);
+        add_screenshot_dbus_members(&state, &obj);
+        add_global_shortcuts_dbus_members(&state, &obj);
+        add_input_capture_dbus_members(&state, &obj);
         obj
     };
     watch_displays(state.clone()).await;
@@ -295,6 +313,7 @@ struct PortalState {
     ring: Rc<IoUring>,
     eng: Rc<AsyncEngine>,
     wheel: Rc<Wheel>,
+    cpu_worker: Rc<CpuWorker>,
     displays: CopyHashMap<PortalDisplayId, Rc<PortalDisplay>>,
     dbus: Rc<DbusSocket>,
     sessions: CopyHashMap<String, Rc<PortalSession>>,
@@ -0,0 +1,341 @@
+use {
+    crate::{
+        dbus::{
+            prelude::{ObjectPath, Variant},
+            DbusObject, DictEntry, DynamicType, PendingReply, FALSE, TRUE,
+        },
+        portal::{
+            ptl_display::{PortalDisplay, PortalSeat},
+            ptl_remote_desktop::RemoteDesktopPhase,
+            ptl_session::PortalSession,
+            PortalState, PORTAL_PATH,
+        },
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, numcell::NumCell},
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::clipboard::{
+                RequestClipboard, RequestClipboardReply, SelectionOwnerChanged, SelectionRead,
+                SelectionReadReply, SelectionTransfer, SelectionWrite, SelectionWriteDone,
+                SelectionWriteDoneReply, SelectionWriteReply, SetSelection, SetSelectionReply,
+            },
+        },
+        wl_usr::usr_ifs::{
+            usr_ext_data_control_device::UsrExtDataControlDevice,
+            usr_ext_data_control_manager::UsrExtDataControlManager,
+            usr_ext_data_control_offer::UsrExtDataControlOffer,
+            usr_ext_data_control_source::{UsrExtDataControlSource, UsrExtDataControlSourceOwner},
+        },
+    },
+    std::{borrow::Cow, ops::Deref, rc::Rc},
+    uapi::{c, OwnedFd},
+};
+
+pub struct PortalClipboard {
+    pub session: Rc<PortalSession>,
+    pub seat: Rc<PortalSeat>,
+    pub data_control: Rc<UsrExtDataControlManager>,
+    pub device: Rc<UsrExtDataControlDevice>,
+    pub source: CloneCell<Option<Rc<UsrExtDataControlSource>>>,
+    pub next_serial: NumCell<u32>,
+    pub transfers: CopyHashMap<u32, Rc<OwnedFd>>,
+}
+
+impl PortalClipboard {
+    pub fn new(session: &Rc<PortalSession>, dpy: &Rc<PortalDisplay>) -> Option<Rc<Self>> {
+        let data_control = dpy.data_control.clone()?;
+        let seats = dpy.seats.lock();
+        let seat = seats
+            .values()
+            .find(|s| s.name.borrow().deref() == "default")
+            .or_else(|| seats.values().next())?;
+        let device = seat.data_device.clone()?;
+        let clipboard = Rc::new(Self {
+            session: session.clone(),
+            seat: seat.clone(),
+            data_control,
+            device,
+            source: Default::default(),
+            next_serial: Default::default(),
+            transfers: Default::default(),
+        });
+        seat.clipboards
+            .set(session.session_obj.path().to_owned(), clipboard.clone());
+        Some(clipboard)
+    }
+
+    pub fn announce_selection(&self) {
+        self.selection_changed(self.device.selection.get().as_ref());
+    }
+
+    pub fn kill(&self) {
+        self.seat.clipboards.remove(self.session.session_obj.path());
+        if let Some(source) = self.source.take() {
+            source.con.remove_obj(source.deref());
+        }
+        self.transfers.clear();
+    }
+
+    pub fn selection_changed(&self, offer: Option<&Rc<UsrExtDataControlOffer>>) {
+        let mime_types = match offer {
+            Some(offer) => offer
+                .mime_types
+                .borrow()
+                .iter()
+                .map(|m| Variant::String(m.clone().into()))
+                .collect(),
+            None => vec![],
+        };
+        let session_is_owner = match self.source.is_some() {
+            true => TRUE,
+            false => FALSE,
+        };
+        let options = [
+            DictEntry {
+                key: "mime_types".into(),
+                value: Variant::Array(DynamicType::String, mime_types),
+            },
+            DictEntry {
+                key: "session_is_owner".into(),
+                value: Variant::Bool(session_is_owner),
+            },
+        ];
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &SelectionOwnerChanged {
+                session_handle: ObjectPath(self.session.session_obj.path().into()),
+                options: Cow::Borrowed(&options),
+            },
+        );
+    }
+}
+
+impl UsrExtDataControlSourceOwner for PortalClipboard {
+    fn send(&self, mime_type: &str, fd: Rc<OwnedFd>) {
+        let serial = self.next_serial.fetch_add(1);
+        self.transfers.set(serial, fd);
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &SelectionTransfer {
+                session_handle: ObjectPath(self.session.session_obj.path().into()),
+                mime_type: mime_type.into(),
+                serial,
+            },
+        );
+    }
+
+    fn cancelled(&self) {
+        if let Some(source) = self.source.take() {
+            source.con.remove_obj(source.deref());
+        }
+    }
+}
+
+impl PortalSession {
+    fn clipboard<T>(&self, reply: &PendingReply<T>) -> Option<Rc<PortalClipboard>> {
+        if let RemoteDesktopPhase::Started(s) = self.rd_phase.get() {
+            if let Some(clipboard) = &s.clipboard {
+                return Some(clipboard.clone());
+            }
+        }
+        reply.err("Clipboard is not enabled for this session");
+        None
+    }
+
+    fn dbus_request_clipboard(
+        self: &Rc<Self>,
+        _req: RequestClipboard,
+        reply: PendingReply<RequestClipboardReply>,
+    ) {
+        match self.rd_phase.get() {
+            RemoteDesktopPhase::Init | RemoteDesktopPhase::DevicesSelected => {}
+            _ => {
+                reply.err("Clipboard must be requested before the session is started");
+                return;
+            }
+        }
+        self.clipboard_requested.set(true);
+        reply.ok(&RequestClipboardReply);
+    }
+
+    fn dbus_set_selection(
+        self: &Rc<Self>,
+        req: SetSelection,
+        reply: PendingReply<SetSelectionReply>,
+    ) {
+        let Some(clipboard) = self.clipboard(&reply) else {
+            return;
+        };
+        let mut mime_types = vec![];
+        for option in req.options.iter() {
+            if let ("mime_types", Variant::Array(_, types)) = (&*option.key, &option.value) {
+                for ty in types {
+                    if let Variant::String(ty) = ty {
+                        mime_types.push(ty.deref());
+                    }
+                }
+            }
+        }
+        let source = match mime_types.is_empty() {
+            true => None,
+            false => {
+                let source = clipboard.data_control.create_data_source();
+                for mime_type in mime_types {
+                    source.offer(mime_type);
+                }
+                source.owner.set(Some(clipboard.clone()));
+                Some(source)
+            }
+        };
+        clipboard.device.set_selection(source.as_deref());
+        if let Some(old) = clipboard.source.set(source) {
+            old.con.remove_obj(old.deref());
+        }
+        reply.ok(&SetSelectionReply);
+    }
+
+    fn dbus_selection_write(
+        self: &Rc<Self>,
+        req: SelectionWrite,
+        reply: PendingReply<SelectionWriteReply>,
+    ) {
+        let Some(clipboard) = self.clipboard(&reply) else {
+            return;
+        };
+        match clipboard.transfers.get(&req.serial) {
+            Some(fd) => reply.ok(&SelectionWriteReply { fd }),
+            None => reply.err("Unknown serial"),
+        }
+    }
+
+    fn dbus_selection_write_done(
+        self: &Rc<Self>,
+        req: SelectionWriteDone,
+        reply: PendingReply<SelectionWriteDoneReply>,
+    ) {
+        let Some(clipboard) = self.clipboard(&reply) else {
+            return;
+        };
+        clipboard.transfers.remove(&req.serial);
+        reply.ok(&SelectionWriteDoneReply);
+    }
+
+    fn dbus_selection_read(
+        self: &Rc<Self>,
+        req: SelectionRead,
+        reply: PendingReply<SelectionReadReply>,
+    ) {
+        let Some(clipboard) = self.clipboard(&reply) else {
+            return;
+        };
+        let Some(offer) = clipboard.device.selection.get() else {
+            reply.err("The selection is empty");
+            return;
+        };
+        if !offer
+            .mime_types
+            .borrow()
+            .iter()
+            .any(|m| m == &*req.mime_type)
+        {
+            reply.err("The selection does not support this mime type");
+            return;
+        }
+        let (read, write) = match uapi::pipe2(c::O_CLOEXEC) {
+            Ok(p) => p,
+            Err(_) => {
+                reply.err("Could not create a pipe");
+                return;
+            }
+        };
+        offer.receive(&req.mime_type, &Rc::new(write));
+        reply.ok(&SelectionReadReply { fd: Rc::new(read) });
+    }
+}
+
+pub(super) fn add_clipboard_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::clipboard::*;
+    let state = state_.clone();
+    object.add_method::<RequestClipboard, _>(move |req, pr| {
+        dbus_request_clipboard(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<SetSelection, _>(move |req, pr| {
+        dbus_set_selection(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<SelectionWrite, _>(move |req, pr| {
+        dbus_selection_write(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<SelectionWriteDone, _>(move |req, pr| {
+        dbus_selection_write_done(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<SelectionRead, _>(move |req, pr| {
+        dbus_selection_read(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_request_clipboard(
+    state: &Rc<PortalState>,
+    req: RequestClipboard,
+    reply: PendingReply<RequestClipboardReply>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_request_clipboard(req, reply);
+    }
+}
+
+fn dbus_set_selection(
+    state: &Rc<PortalState>,
+    req: SetSelection,
+    reply: PendingReply<SetSelectionReply>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_set_selection(req, reply);
+    }
+}
+
+fn dbus_selection_write(
+    state: &Rc<PortalState>,
+    req: SelectionWrite,
+    reply: PendingReply<SelectionWriteReply>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_selection_write(req, reply);
+    }
+}
+
+fn dbus_selection_write_done(
+    state: &Rc<PortalState>,
+    req: SelectionWriteDone,
+    reply: PendingReply<SelectionWriteDoneReply>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_selection_write_done(req, reply);
+    }
+}
+
+fn dbus_selection_read(
+    state: &Rc<PortalState>,
+    req: SelectionRead,
+    reply: PendingReply<SelectionReadReply>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_selection_read(req, reply);
+    }
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<PortalSession>> {
+    let res = state.sessions.get(handle);
+    if res.is_none() {
+        let msg = format!("Clipboard session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
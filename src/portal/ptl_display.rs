@@ -5,6 +5,7 @@ use {
         ifs::wl_seat::POINTER,
         object::Version,
         portal::{
+            ptl_clipboard::PortalClipboard,
             ptl_render_ctx::{PortalRenderCtx, PortalServerRenderCtx},
             ptl_session::PortalSession,
             ptr_gui::WindowData,
@@ -21,11 +22,17 @@ use {
         },
         video::drm::Drm,
         wire::{
-            wl_pointer, JayCompositor, WlCompositor, WlOutput, WlSeat, WlSurfaceId,
-            WpFractionalScaleManagerV1, WpViewporter, ZwlrLayerShellV1, ZwpLinuxDmabufV1,
+            wl_output, wl_pointer, ExtDataControlManagerV1, JayCompositor, WlCompositor, WlOutput,
+            WlSeat, WlSurfaceId, WpFractionalScaleManagerV1, WpViewporter, ZwlrLayerShellV1,
+            ZwpLinuxDmabufV1,
         },
         wl_usr::{
             usr_ifs::{
+                usr_ext_data_control_device::{
+                    UsrExtDataControlDevice, UsrExtDataControlDeviceOwner,
+                },
+                usr_ext_data_control_manager::UsrExtDataControlManager,
+                usr_ext_data_control_offer::UsrExtDataControlOffer,
                 usr_jay_compositor::UsrJayCompositor,
                 usr_jay_output::{UsrJayOutput, UsrJayOutputOwner},
                 usr_jay_pointer::UsrJayPointer,
@@ -79,6 +86,7 @@ pub struct PortalDisplay {
     pub comp: Rc<UsrWlCompositor>,
     pub fsm: Rc<UsrWpFractionalScaleManager>,
     pub vp: Rc<UsrWpViewporter>,
+    pub data_control: Option<Rc<UsrExtDataControlManager>>,
     pub render_ctx: CloneCell<Option<Rc<PortalServerRenderCtx>>>,
 
     pub outputs: CopyHashMap<u32, Rc<PortalOutput>>,
@@ -94,6 +102,7 @@ pub struct PortalOutput {
     pub dpy: Rc<PortalDisplay>,
     pub wl: Rc<UsrWlOutput>,
     pub jay: Rc<UsrJayOutput>,
+    pub pos: Cell<(i32, i32)>,
 }
 
 pub struct PortalSeat {
@@ -105,6 +114,8 @@ pub struct PortalSeat {
     pub name: RefCell<String>,
     pub capabilities: Cell<u32>,
     pub pointer_focus: CloneCell<Option<Rc<WindowData>>>,
+    pub data_device: Option<Rc<UsrExtDataControlDevice>>,
+    pub clipboards: CopyHashMap<String, Rc<PortalClipboard>>,
 }
 
 impl UsrWlSeatOwner for PortalSeat {
@@ -128,6 +139,14 @@ impl UsrWlSeatOwner for PortalSeat {
     }
 }
 
+impl UsrExtDataControlDeviceOwner for PortalSeat {
+    fn selection(&self, offer: Option<&Rc<UsrExtDataControlOffer>>) {
+        for clipboard in self.clipboards.lock().values() {
+            clipboard.selection_changed(offer);
+        }
+    }
+}
+
 impl UsrWlPointerOwner for PortalSeat {
     fn enter(&self, ev: &wl_pointer::Enter) {
         if let Some(window) = self.dpy.windows.get(&ev.surface) {
@@ -278,7 +297,11 @@ impl UsrJayOutputOwner for PortalOutput {
     }
 }
 
-impl UsrWlOutputOwner for PortalOutput {}
+impl UsrWlOutputOwner for PortalOutput {
+    fn geometry(&self, ev: &wl_output::Geometry) {
+        self.pos.set((ev.x, ev.y));
+    }
+}
 
 async fn maybe_add_display(state: &Rc<PortalState>, name: &str) {
     let tail = match name.strip_prefix("wayland-") {
@@ -335,6 +358,7 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
     let mut comp_opt = None;
     let mut vp_opt = None;
     let mut dmabuf_opt = None;
+    let mut data_control_opt = None;
     let mut outputs = vec![];
     let mut seats = vec![];
     for (interface, instances) in dpy.globals.borrow_mut().deref() {
@@ -345,7 +369,7 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
                     con: dpy.con.clone(),
                     owner: Default::default(),
                     caps: Default::default(),
-                    version: Version(version.min(12)),
+                    version: Version(version.min(14)),
                 });
                 dpy.con.add_object(jc.clone());
                 dpy.registry.request_bind(name, jc.version.0, jc.deref());
@@ -396,6 +420,15 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
                 dpy.con.add_object(ls.clone());
                 dpy.registry.request_bind(name, ls.version.0, ls.deref());
                 dmabuf_opt = Some(ls);
+            } else if interface == ExtDataControlManagerV1.name() {
+                let ls = Rc::new(UsrExtDataControlManager {
+                    id: dpy.con.id(),
+                    con: dpy.con.clone(),
+                    version: Version(version.min(1)),
+                });
+                dpy.con.add_object(ls.clone());
+                dpy.registry.request_bind(name, ls.version.0, ls.deref());
+                data_control_opt = Some(ls);
             } else if interface == WlOutput.name() {
                 outputs.push((name, version));
             } else if interface == WlSeat.name() {
@@ -438,6 +471,7 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
         comp,
         fsm,
         vp,
+        data_control: data_control_opt,
         windows: Default::default(),
         sessions: Default::default(),
         workspaces: Default::default(),
@@ -470,6 +504,7 @@ fn add_seat(dpy: &Rc<PortalDisplay>, name: u32, version: u32) {
     dpy.con.add_object(wl.clone());
     dpy.registry.request_bind(name, wl.version.0, wl.deref());
     let jay_pointer = dpy.jc.get_pointer(&wl);
+    let data_device = dpy.data_control.as_ref().map(|dc| dc.get_data_device(&wl));
     let js = Rc::new(PortalSeat {
         global_id: name,
         dpy: dpy.clone(),
@@ -479,8 +514,13 @@ fn add_seat(dpy: &Rc<PortalDisplay>, name: u32, version: u32) {
         name: RefCell::new("".to_string()),
         capabilities: Cell::new(0),
         pointer_focus: Default::default(),
+        data_device,
+        clipboards: Default::default(),
     });
     js.wl.owner.set(Some(js.clone()));
+    if let Some(dd) = &js.data_device {
+        dd.owner.set(Some(js.clone()));
+    }
     dpy.seats.set(name, js);
 }
 
@@ -500,6 +540,7 @@ fn add_output(dpy: &Rc<PortalDisplay>, name: u32, version: u32) {
         dpy: dpy.clone(),
         wl: wl.clone(),
         jay: jo.clone(),
+        pos: Default::default(),
     });
     po.wl.owner.set(Some(po.clone()));
     po.jay.owner.set(Some(po.clone()));
@@ -0,0 +1,450 @@
+mod global_shortcuts_gui;
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        dbus::{
+            prelude::{ObjectPath, Variant},
+            DbusObject, DictEntry, DynamicType, PendingReply,
+        },
+        ifs::jay_compositor::CREATE_GLOBAL_SHORTCUTS_SINCE,
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::global_shortcuts_gui::SelectionGui,
+            ptl_input_capture::InputCapturePhase,
+            ptl_remote_desktop::RemoteDesktopPhase,
+            ptl_screencast::ScreencastPhase,
+            ptl_session::PortalSession,
+            PortalState, PORTAL_CANCELLED, PORTAL_PATH, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::{
+                global_shortcuts::{
+                    Activated, BindShortcuts, BindShortcutsReply, CreateSession,
+                    CreateSessionReply, Deactivated, ListShortcuts, ListShortcutsReply,
+                },
+                session::CloseReply as SessionCloseReply,
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_global_shortcuts::{
+            UsrJayGlobalShortcuts, UsrJayGlobalShortcutsOwner,
+        },
+        xkbcommon::keysym_from_name,
+    },
+    jay_config::keyboard::mods::{Modifiers, ALT, CTRL, LOGO, SHIFT},
+    std::{borrow::Cow, cell::Cell, ops::Deref, rc::Rc},
+};
+
+#[derive(Clone)]
+pub enum GlobalShortcutsPhase {
+    Init,
+    Selecting(Rc<SelectingShortcuts>),
+    Bound(Rc<BoundShortcuts>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for GlobalShortcutsPhase {}
+
+pub struct PortalShortcut {
+    pub id: String,
+    pub description: String,
+    pub trigger: Option<ShortcutTrigger>,
+}
+
+pub struct ShortcutTrigger {
+    pub mods: Modifiers,
+    pub keysym: u32,
+    pub description: String,
+}
+
+pub struct SelectingShortcuts {
+    pub session: Rc<PortalSession>,
+    pub _request_obj: DbusObject,
+    pub shortcuts: Rc<Vec<PortalShortcut>>,
+    pub reply: Cell<Option<PendingReply<BindShortcutsReply<'static>>>>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<SelectionGui>>,
+}
+
+pub struct BoundShortcuts {
+    pub session: Rc<PortalSession>,
+    pub dpy: Rc<PortalDisplay>,
+    pub shortcuts: CloneCell<Rc<Vec<PortalShortcut>>>,
+    pub jay_shortcuts: Rc<UsrJayGlobalShortcuts>,
+}
+
+impl SelectingShortcuts {
+    pub fn reject(&self) {
+        if let Some(reply) = self.reply.take() {
+            reply.ok(&BindShortcutsReply {
+                response: PORTAL_CANCELLED,
+                results: Default::default(),
+            });
+        }
+        self.session.kill();
+    }
+
+    pub fn bind(&self, dpy: &Rc<PortalDisplay>) {
+        let bound = Rc::new(BoundShortcuts {
+            session: self.session.clone(),
+            dpy: dpy.clone(),
+            shortcuts: CloneCell::new(Rc::new(vec![])),
+            jay_shortcuts: dpy.jc.create_global_shortcuts(),
+        });
+        bound.rebind(&self.shortcuts);
+        bound.jay_shortcuts.owner.set(Some(bound.clone()));
+        self.session
+            .gs_phase
+            .set(GlobalShortcutsPhase::Bound(bound));
+        dpy.sessions.set(
+            self.session.session_obj.path().to_owned(),
+            self.session.clone(),
+        );
+        if let Some(reply) = self.reply.take() {
+            let results = shortcuts_results(&self.shortcuts);
+            reply.ok(&BindShortcutsReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Borrowed(&results),
+            });
+        }
+    }
+}
+
+impl BoundShortcuts {
+    /// Replaces all bindings of this session.
+    fn rebind(&self, shortcuts: &Rc<Vec<PortalShortcut>>) {
+        for id in 0..self.shortcuts.get().len() {
+            self.jay_shortcuts.unbind(id as u32);
+        }
+        for (id, shortcut) in shortcuts.iter().enumerate() {
+            if let Some(trigger) = &shortcut.trigger {
+                self.jay_shortcuts
+                    .bind(id as u32, trigger.mods.0, trigger.keysym);
+            }
+        }
+        self.shortcuts.set(shortcuts.clone());
+    }
+
+    fn emit(&self, id: u32, time_usec: u64, activated: bool) {
+        let shortcuts = self.shortcuts.get();
+        let Some(shortcut) = shortcuts.get(id as usize) else {
+            return;
+        };
+        let session_handle = || ObjectPath(self.session.session_obj.path().into());
+        let timestamp = time_usec / 1000;
+        if activated {
+            self.session.state.dbus.emit_signal(
+                PORTAL_PATH,
+                &Activated {
+                    session_handle: session_handle(),
+                    shortcut_id: shortcut.id.as_str().into(),
+                    timestamp,
+                    options: Default::default(),
+                },
+            );
+        } else {
+            self.session.state.dbus.emit_signal(
+                PORTAL_PATH,
+                &Deactivated {
+                    session_handle: session_handle(),
+                    shortcut_id: shortcut.id.as_str().into(),
+                    timestamp,
+                    options: Default::default(),
+                },
+            );
+        }
+    }
+}
+
+impl UsrJayGlobalShortcutsOwner for BoundShortcuts {
+    fn activated(&self, id: u32, time_usec: u64) {
+        self.emit(id, time_usec, true);
+    }
+
+    fn deactivated(&self, id: u32, time_usec: u64) {
+        self.emit(id, time_usec, false);
+    }
+}
+
+fn parse_trigger(trigger: &str) -> Option<ShortcutTrigger> {
+    let mut mods = Modifiers(0);
+    let mut keysym = None;
+    for part in trigger.split('+') {
+        let part = part.trim();
+        let m = match part.to_ascii_lowercase().as_str() {
+            "shift" => SHIFT,
+            "ctrl" | "control" => CTRL,
+            "alt" => ALT,
+            "logo" | "super" | "meta" => LOGO,
+            _ => {
+                if keysym.is_some() {
+                    return None;
+                }
+                keysym = Some(keysym_from_name(part)?);
+                continue;
+            }
+        };
+        mods |= m;
+    }
+    Some(ShortcutTrigger {
+        mods,
+        keysym: keysym?,
+        description: trigger.to_string(),
+    })
+}
+
+fn shortcuts_results(
+    shortcuts: &[PortalShortcut],
+) -> Vec<DictEntry<Cow<'static, str>, Variant<'static>>> {
+    let entry_type = DynamicType::DictEntry(
+        Box::new(DynamicType::String),
+        Box::new(DynamicType::Variant),
+    );
+    let shortcut_type = DynamicType::Struct(vec![
+        DynamicType::String,
+        DynamicType::Array(Box::new(entry_type.clone())),
+    ]);
+    let entry = |key: &'static str, value: &str| {
+        Variant::DictEntry(
+            Box::new(Variant::String(key.into())),
+            Box::new(Variant::Variant(Box::new(Variant::String(
+                value.to_string().into(),
+            )))),
+        )
+    };
+    let mut list = vec![];
+    for shortcut in shortcuts {
+        let mut properties = vec![entry("description", &shortcut.description)];
+        if let Some(trigger) = &shortcut.trigger {
+            properties.push(entry("trigger_description", &trigger.description));
+        }
+        list.push(Variant::Struct(vec![
+            Variant::String(shortcut.id.clone().into()),
+            Variant::Array(entry_type.clone(), properties),
+        ]));
+    }
+    vec![DictEntry {
+        key: "shortcuts".into(),
+        value: Variant::Array(shortcut_type, list),
+    }]
+}
+
+impl PortalSession {
+    fn dbus_bind_shortcuts(
+        self: &Rc<Self>,
+        req: BindShortcuts<'_>,
+        reply: PendingReply<BindShortcutsReply<'static>>,
+    ) {
+        let bound = match self.gs_phase.get() {
+            GlobalShortcutsPhase::Init => None,
+            GlobalShortcutsPhase::Bound(b) => Some(b),
+            _ => {
+                self.kill();
+                reply.err("Shortcuts are already being bound");
+                return;
+            }
+        };
+        let mut shortcuts = vec![];
+        for (id, options) in req.shortcuts.iter() {
+            let mut description = String::new();
+            let mut trigger = None;
+            for option in options.iter() {
+                let key = &*option.key;
+                if !matches!(key, "description" | "preferred_trigger") {
+                    log::warn!(
+                        "Ignoring unsupported property `{}` of shortcut `{}`",
+                        key,
+                        id
+                    );
+                    continue;
+                }
+                let Variant::String(value) = &option.value else {
+                    log::warn!("Property `{}` of shortcut `{}` is not a string", key, id);
+                    continue;
+                };
+                if key == "description" {
+                    description = value.to_string();
+                } else {
+                    trigger = parse_trigger(value);
+                    if trigger.is_none() {
+                        log::warn!("Could not parse shortcut trigger `{}`", value);
+                    }
+                }
+            }
+            shortcuts.push(PortalShortcut {
+                id: id.to_string(),
+                description,
+                trigger,
+            });
+        }
+        if let Some(bound) = bound {
+            bound.rebind(&Rc::new(shortcuts));
+            let results = shortcuts_results(&bound.shortcuts.get());
+            reply.ok(&BindShortcutsReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Borrowed(&results),
+            });
+            return;
+        }
+        let request_obj = match self.state.dbus.add_object(req.handle.to_string()) {
+            Ok(r) => r,
+            Err(_) => {
+                self.kill();
+                reply.err("Request handle is not unique");
+                return;
+            }
+        };
+        {
+            use org::freedesktop::impl_::portal::request::*;
+            request_obj.add_method::<Close, _>({
+                let slf = self.clone();
+                move |_, pr| {
+                    slf.kill();
+                    pr.ok(&CloseReply);
+                }
+            });
+        }
+        let selecting = Rc::new(SelectingShortcuts {
+            session: self.clone(),
+            _request_obj: request_obj,
+            shortcuts: Rc::new(shortcuts),
+            reply: Cell::new(Some(reply)),
+            guis: Default::default(),
+        });
+        for dpy in self.state.displays.lock().values() {
+            if dpy.outputs.len() > 0 && dpy.jc.version >= CREATE_GLOBAL_SHORTCUTS_SINCE {
+                selecting
+                    .guis
+                    .set(dpy.id, SelectionGui::new(&selecting, dpy));
+            }
+        }
+        if selecting.guis.is_empty() {
+            if let Some(reply) = selecting.reply.take() {
+                reply.err("There are no running displays");
+            }
+            self.kill();
+            return;
+        }
+        self.gs_phase
+            .set(GlobalShortcutsPhase::Selecting(selecting));
+    }
+
+    fn dbus_list_shortcuts(
+        self: &Rc<Self>,
+        _req: ListShortcuts<'_>,
+        reply: PendingReply<ListShortcutsReply<'static>>,
+    ) {
+        let results = match self.gs_phase.get() {
+            GlobalShortcutsPhase::Bound(b) => shortcuts_results(&b.shortcuts.get()),
+            _ => shortcuts_results(&[]),
+        };
+        reply.ok(&ListShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Borrowed(&results),
+        });
+    }
+}
+
+pub(super) fn add_global_shortcuts_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::global_shortcuts::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<BindShortcuts, _>(move |req, pr| {
+        dbus_bind_shortcuts(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<ListShortcuts, _>(move |req, pr| {
+        dbus_list_shortcuts(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create global shortcuts session {:#?}", req);
+    if state.sessions.contains(req.session_handle.0.deref()) {
+        reply.err("Session already exists");
+        return;
+    }
+    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(PortalSession {
+        _id: state.id(),
+        state: state.clone(),
+        pw_con: state.pw_con.clone(),
+        app: req.app_id.to_string(),
+        session_obj: obj,
+        sc_phase: CloneCell::new(ScreencastPhase::Init),
+        rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        clipboard_requested: Cell::new(false),
+        start_reply: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(1));
+    }
+    state
+        .sessions
+        .set(req.session_handle.0.to_string(), session);
+    reply.ok(&CreateSessionReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+fn dbus_bind_shortcuts(
+    state: &Rc<PortalState>,
+    req: BindShortcuts,
+    reply: PendingReply<BindShortcutsReply<'static>>,
+) {
+    log::info!("Bind shortcuts {:#?}", req);
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_bind_shortcuts(req, reply);
+    }
+}
+
+fn dbus_list_shortcuts(
+    state: &Rc<PortalState>,
+    req: ListShortcuts,
+    reply: PendingReply<ListShortcutsReply<'static>>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_list_shortcuts(req, reply);
+    }
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<PortalSession>> {
+    let res = state.sessions.get(handle);
+    if res.is_none() {
+        let msg = format!("Global shortcuts session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
@@ -0,0 +1,30 @@
+use {
+    crate::portal::ptl_global_shortcuts::parse_trigger,
+    jay_config::keyboard::{
+        mods::{Modifiers, ALT, CTRL, LOGO, SHIFT},
+        syms::{SYM_Return, SYM_a},
+    },
+};
+
+#[test]
+fn key_only() {
+    let trigger = parse_trigger("a").unwrap();
+    assert_eq!(trigger.mods, Modifiers(0));
+    assert_eq!(trigger.keysym, SYM_a.0);
+    assert_eq!(trigger.description, "a");
+}
+
+#[test]
+fn modifiers() {
+    let trigger = parse_trigger("CTRL + Shift+alt+super+Return").unwrap();
+    assert_eq!(trigger.mods, CTRL | SHIFT | ALT | LOGO);
+    assert_eq!(trigger.keysym, SYM_Return.0);
+}
+
+#[test]
+fn invalid() {
+    assert!(parse_trigger("").is_none());
+    assert!(parse_trigger("ctrl").is_none());
+    assert!(parse_trigger("ctrl+a+b").is_none());
+    assert!(parse_trigger("ctrl+not_a_key").is_none());
+}
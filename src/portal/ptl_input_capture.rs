@@ -0,0 +1,613 @@
+mod input_capture_gui;
+
+use {
+    crate::{
+        dbus::{
+            prelude::{ObjectPath, Variant},
+            DbusObject, DictEntry, DynamicType, PendingReply,
+        },
+        fixed::Fixed,
+        ifs::jay_compositor::CREATE_INPUT_CAPTURE_SINCE,
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::input_capture_gui::SelectionGui,
+            ptl_remote_desktop::RemoteDesktopPhase,
+            ptl_screencast::ScreencastPhase,
+            ptl_session::PortalSession,
+            PortalState, PORTAL_CANCELLED, PORTAL_PATH, PORTAL_SUCCESS,
+        },
+        rect::Rect,
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            numcell::NumCell,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::{
+                input_capture::{
+                    Activated, ConnectToEIS, ConnectToEISReply, CreateSession, CreateSessionReply,
+                    Deactivated, Disable, DisableReply, Enable, EnableReply, GetZones,
+                    GetZonesReply, Release, ReleaseReply, SetPointerBarriers,
+                    SetPointerBarriersReply, ZonesChanged,
+                },
+                session::CloseReply as SessionCloseReply,
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_input_capture::{UsrJayInputCapture, UsrJayInputCaptureOwner},
+    },
+    std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        ops::Deref,
+        rc::Rc,
+    },
+    uapi::OwnedFd,
+};
+
+const CAP_KEYBOARD: u32 = 1;
+const CAP_POINTER: u32 = 2;
+
+const SUPPORTED_CAPABILITIES: u32 = CAP_KEYBOARD | CAP_POINTER;
+
+#[derive(Clone)]
+pub enum InputCapturePhase {
+    Init,
+    Selecting(Rc<SelectingInputCapture>),
+    Started(Rc<StartedInputCapture>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for InputCapturePhase {}
+
+pub struct SelectingInputCapture {
+    pub session: Rc<PortalSession>,
+    pub _request_obj: DbusObject,
+    pub capabilities: u32,
+    pub reply: Cell<Option<PendingReply<CreateSessionReply<'static>>>>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<SelectionGui>>,
+}
+
+pub struct StartedInputCapture {
+    pub session: Rc<PortalSession>,
+    pub dpy: Rc<PortalDisplay>,
+    pub capture: Rc<UsrJayInputCapture>,
+    pub capabilities: u32,
+    pub reply: Cell<Option<PendingReply<CreateSessionReply<'static>>>>,
+    pub ei_fd: Cell<Option<Rc<OwnedFd>>>,
+    pub zones: RefCell<Vec<Rect>>,
+    pub zone_set: NumCell<u32>,
+}
+
+impl SelectingInputCapture {
+    pub fn reject(&self) {
+        if let Some(reply) = self.reply.take() {
+            reply.ok(&CreateSessionReply {
+                response: PORTAL_CANCELLED,
+                results: Default::default(),
+            });
+        }
+        self.session.kill();
+    }
+
+    pub fn start(&self, dpy: &Rc<PortalDisplay>) {
+        let started = Rc::new(StartedInputCapture {
+            session: self.session.clone(),
+            dpy: dpy.clone(),
+            capture: dpy.jc.create_input_capture(),
+            capabilities: self.capabilities,
+            reply: Cell::new(self.reply.take()),
+            ei_fd: Default::default(),
+            zones: Default::default(),
+            zone_set: Default::default(),
+        });
+        started.capture.owner.set(Some(started.clone()));
+        started.capture.connect_ei(&self.session.app);
+        self.session
+            .ic_phase
+            .set(InputCapturePhase::Started(started));
+        dpy.sessions.set(
+            self.session.session_obj.path().to_owned(),
+            self.session.clone(),
+        );
+    }
+}
+
+impl StartedInputCapture {
+    fn emit_signal<'a, S: crate::dbus::Signal<'a>>(&self, signal: &S) {
+        self.session.state.dbus.emit_signal(PORTAL_PATH, signal);
+    }
+
+    fn session_handle(&self) -> ObjectPath<'_> {
+        ObjectPath(self.session.session_obj.path().into())
+    }
+
+    /// Returns whether the barrier lies on an edge of a zone that is not shared with
+    /// another zone.
+    fn barrier_is_valid(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+        let zones = self.zones.borrow();
+        // Vertical barriers are handled by swapping the coordinates.
+        let (v, a1, a2, swap) = if x1 == x2 {
+            (x1, y1.min(y2), y1.max(y2), false)
+        } else if y1 == y2 {
+            (y1, x1.min(x2), x1.max(x2), true)
+        } else {
+            return false;
+        };
+        let edges = |zone: &Rect| {
+            let (lo, hi, s1, s2) = match swap {
+                false => (zone.x1(), zone.x2(), zone.y1(), zone.y2()),
+                true => (zone.y1(), zone.y2(), zone.x1(), zone.x2()),
+            };
+            (lo, hi, s1, s2)
+        };
+        for zone in zones.iter() {
+            let (lo, hi, s1, s2) = edges(zone);
+            let outer = if v == lo {
+                hi
+            } else if v == hi {
+                lo
+            } else {
+                continue;
+            };
+            // The barrier must lie within the edge. The bottom/right pixel is inclusive.
+            if a1 < s1 || a2 > s2 {
+                continue;
+            }
+            let shared = zones.iter().any(|other| {
+                let (olo, ohi, os1, os2) = edges(other);
+                let touches = match outer > v {
+                    true => ohi == v,
+                    false => olo == v,
+                };
+                touches && os1 <= a2 && os2 > a1
+            });
+            if !shared {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn set_pointer_barriers(
+        &self,
+        barriers: &[Cow<'_, [DictEntry<Cow<'_, str>, Variant<'_>>]>],
+    ) -> Vec<u32> {
+        let mut failed = vec![];
+        self.capture.clear_barriers();
+        for barrier in barriers {
+            let mut id = None;
+            let mut position = None;
+            for entry in barrier.iter() {
+                match (&*entry.key, &entry.value) {
+                    ("barrier_id", Variant::U32(v)) => id = Some(*v),
+                    ("position", Variant::Struct(v)) => {
+                        if let [Variant::I32(x1), Variant::I32(y1), Variant::I32(x2), Variant::I32(y2)] =
+                            &v[..]
+                        {
+                            position = Some((*x1, *y1, *x2, *y2));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let Some(id) = id else {
+                continue;
+            };
+            match position {
+                Some((x1, y1, x2, y2)) if id != 0 && self.barrier_is_valid(x1, y1, x2, y2) => {
+                    self.capture.add_barrier(id, x1, y1, x2, y2);
+                }
+                _ => failed.push(id),
+            }
+        }
+        failed
+    }
+}
+
+fn cursor_position(x: Fixed, y: Fixed) -> Variant<'static> {
+    Variant::Struct(vec![Variant::F64(x.to_f64()), Variant::F64(y.to_f64())])
+}
+
+impl UsrJayInputCaptureOwner for StartedInputCapture {
+    fn ei_fd(&self, fd: Rc<OwnedFd>) {
+        self.ei_fd.set(Some(fd));
+        if let Some(reply) = self.reply.take() {
+            let results = [DictEntry {
+                key: "capabilities".into(),
+                value: Variant::U32(self.capabilities),
+            }];
+            reply.ok(&CreateSessionReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Borrowed(&results),
+            });
+        }
+    }
+
+    fn ei_failed(&self, reason: &str) {
+        log::error!("Could not create EI client: {}", reason);
+        if let Some(reply) = self.reply.take() {
+            reply.err(reason);
+        }
+        self.session.kill();
+    }
+
+    fn zones(&self, zones: Vec<Rect>) {
+        *self.zones.borrow_mut() = zones;
+        let zone_set = self.zone_set.fetch_add(1) + 1;
+        if zone_set > 1 {
+            let options = [DictEntry {
+                key: "zone_set".into(),
+                value: Variant::U32(zone_set),
+            }];
+            self.emit_signal(&ZonesChanged {
+                session_handle: self.session_handle(),
+                options: Cow::Borrowed(&options),
+            });
+        }
+    }
+
+    fn activated(&self, activation_id: u32, barrier_id: u32, x: Fixed, y: Fixed) {
+        let options = [
+            DictEntry {
+                key: "activation_id".into(),
+                value: Variant::U32(activation_id),
+            },
+            DictEntry {
+                key: "cursor_position".into(),
+                value: cursor_position(x, y),
+            },
+            DictEntry {
+                key: "barrier_id".into(),
+                value: Variant::U32(barrier_id),
+            },
+        ];
+        self.emit_signal(&Activated {
+            session_handle: self.session_handle(),
+            options: Cow::Borrowed(&options),
+        });
+    }
+
+    fn deactivated(&self, activation_id: u32, x: Fixed, y: Fixed) {
+        let options = [
+            DictEntry {
+                key: "activation_id".into(),
+                value: Variant::U32(activation_id),
+            },
+            DictEntry {
+                key: "cursor_position".into(),
+                value: cursor_position(x, y),
+            },
+        ];
+        self.emit_signal(&Deactivated {
+            session_handle: self.session_handle(),
+            options: Cow::Borrowed(&options),
+        });
+    }
+}
+
+impl PortalSession {
+    fn started_input_capture<T>(
+        self: &Rc<Self>,
+        reply: &PendingReply<T>,
+    ) -> Option<Rc<StartedInputCapture>> {
+        match self.ic_phase.get() {
+            InputCapturePhase::Started(s) => Some(s),
+            _ => {
+                reply.err("Input capture session has not been started");
+                None
+            }
+        }
+    }
+
+    fn dbus_get_zones(
+        self: &Rc<Self>,
+        _req: GetZones<'_>,
+        reply: PendingReply<GetZonesReply<'static>>,
+    ) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        let zone_type = DynamicType::Struct(vec![
+            DynamicType::U32,
+            DynamicType::U32,
+            DynamicType::I32,
+            DynamicType::I32,
+        ]);
+        let zones = started
+            .zones
+            .borrow()
+            .iter()
+            .map(|zone| {
+                Variant::Struct(vec![
+                    Variant::U32(zone.width() as u32),
+                    Variant::U32(zone.height() as u32),
+                    Variant::I32(zone.x1()),
+                    Variant::I32(zone.y1()),
+                ])
+            })
+            .collect();
+        let results = [
+            DictEntry {
+                key: "zones".into(),
+                value: Variant::Array(zone_type, zones),
+            },
+            DictEntry {
+                key: "zone_set".into(),
+                value: Variant::U32(started.zone_set.get()),
+            },
+        ];
+        reply.ok(&GetZonesReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Borrowed(&results),
+        });
+    }
+
+    fn dbus_set_pointer_barriers(
+        self: &Rc<Self>,
+        req: SetPointerBarriers<'_>,
+        reply: PendingReply<SetPointerBarriersReply<'static>>,
+    ) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        if req.zone_set != started.zone_set.get() {
+            reply.err("The zone set is out of date");
+            return;
+        }
+        let failed = started.set_pointer_barriers(&req.barriers);
+        let failed = failed.into_iter().map(Variant::U32).collect();
+        let results = [DictEntry {
+            key: "failed_barriers".into(),
+            value: Variant::Array(DynamicType::U32, failed),
+        }];
+        reply.ok(&SetPointerBarriersReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Borrowed(&results),
+        });
+    }
+
+    fn dbus_enable(self: &Rc<Self>, _req: Enable<'_>, reply: PendingReply<EnableReply>) {
+        if let Some(started) = self.started_input_capture(&reply) {
+            started.capture.enable();
+            reply.ok(&EnableReply);
+        }
+    }
+
+    fn dbus_disable(self: &Rc<Self>, _req: Disable<'_>, reply: PendingReply<DisableReply>) {
+        if let Some(started) = self.started_input_capture(&reply) {
+            started.capture.disable();
+            reply.ok(&DisableReply);
+        }
+    }
+
+    fn dbus_release(self: &Rc<Self>, req: Release<'_>, reply: PendingReply<ReleaseReply>) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        let mut activation_id = None;
+        let mut position = None;
+        for option in req.options.iter() {
+            match (&*option.key, &option.value) {
+                ("activation_id", Variant::U32(v)) => activation_id = Some(*v),
+                ("cursor_position", Variant::Struct(v)) => {
+                    if let [Variant::F64(x), Variant::F64(y)] = &v[..] {
+                        position = Some((Fixed::from_f64(*x), Fixed::from_f64(*y)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        match activation_id {
+            Some(id) => started.capture.release(id, position),
+            _ => log::warn!("Release called without activation id"),
+        }
+        reply.ok(&ReleaseReply);
+    }
+
+    fn dbus_connect_to_eis_input_capture(
+        self: &Rc<Self>,
+        _req: ConnectToEIS<'_>,
+        reply: PendingReply<ConnectToEISReply>,
+    ) {
+        let Some(started) = self.started_input_capture(&reply) else {
+            return;
+        };
+        let Some(fd) = started.ei_fd.take() else {
+            reply.err("EI file descriptor has already been consumed");
+            return;
+        };
+        reply.ok(&ConnectToEISReply { fd });
+    }
+}
+
+pub(super) fn add_input_capture_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::input_capture::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<GetZones, _>(move |req, pr| {
+        dbus_get_zones(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<SetPointerBarriers, _>(move |req, pr| {
+        dbus_set_pointer_barriers(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<Enable, _>(move |req, pr| {
+        dbus_enable(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<Disable, _>(move |req, pr| {
+        dbus_disable(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<Release, _>(move |req, pr| {
+        dbus_release(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<ConnectToEIS, _>(move |req, pr| {
+        dbus_connect_to_eis(&state, req, pr);
+    });
+    object.set_property::<SupportedCapabilities>(Variant::U32(SUPPORTED_CAPABILITIES));
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create input capture session {:#?}", req);
+    if state.sessions.contains(req.session_handle.0.deref()) {
+        reply.err("Session already exists");
+        return;
+    }
+    let mut capabilities = 0;
+    for option in req.options.iter() {
+        if let ("capabilities", Variant::U32(caps)) = (&*option.key, &option.value) {
+            capabilities = *caps;
+        }
+    }
+    let capabilities = capabilities & SUPPORTED_CAPABILITIES;
+    if capabilities == 0 {
+        reply.err("None of the requested capabilities are supported");
+        return;
+    }
+    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let request_obj = match state.dbus.add_object(req.handle.0.to_string()) {
+        Ok(r) => r,
+        Err(_) => {
+            reply.err("Request handle is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(PortalSession {
+        _id: state.id(),
+        state: state.clone(),
+        pw_con: state.pw_con.clone(),
+        app: req.app_id.to_string(),
+        session_obj: obj,
+        sc_phase: CloneCell::new(ScreencastPhase::Init),
+        rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        clipboard_requested: Cell::new(false),
+        start_reply: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(1));
+    }
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        request_obj.add_method::<Close, _>({
+            let slf = session.clone();
+            move |_, pr| {
+                slf.kill();
+                pr.ok(&CloseReply);
+            }
+        });
+    }
+    let selecting = Rc::new(SelectingInputCapture {
+        session: session.clone(),
+        _request_obj: request_obj,
+        capabilities,
+        reply: Cell::new(Some(reply)),
+        guis: Default::default(),
+    });
+    for dpy in state.displays.lock().values() {
+        if dpy.outputs.len() > 0 && dpy.jc.version >= CREATE_INPUT_CAPTURE_SINCE {
+            selecting
+                .guis
+                .set(dpy.id, SelectionGui::new(&selecting, dpy));
+        }
+    }
+    if selecting.guis.is_empty() {
+        if let Some(reply) = selecting.reply.take() {
+            reply.err("There are no running displays");
+        }
+        return;
+    }
+    session
+        .ic_phase
+        .set(InputCapturePhase::Selecting(selecting));
+    state
+        .sessions
+        .set(req.session_handle.0.to_string(), session);
+}
+
+fn dbus_get_zones(
+    state: &Rc<PortalState>,
+    req: GetZones,
+    reply: PendingReply<GetZonesReply<'static>>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_get_zones(req, reply);
+    }
+}
+
+fn dbus_set_pointer_barriers(
+    state: &Rc<PortalState>,
+    req: SetPointerBarriers,
+    reply: PendingReply<SetPointerBarriersReply<'static>>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_set_pointer_barriers(req, reply);
+    }
+}
+
+fn dbus_enable(state: &Rc<PortalState>, req: Enable, reply: PendingReply<EnableReply>) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_enable(req, reply);
+    }
+}
+
+fn dbus_disable(state: &Rc<PortalState>, req: Disable, reply: PendingReply<DisableReply>) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_disable(req, reply);
+    }
+}
+
+fn dbus_release(state: &Rc<PortalState>, req: Release, reply: PendingReply<ReleaseReply>) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_release(req, reply);
+    }
+}
+
+fn dbus_connect_to_eis(
+    state: &Rc<PortalState>,
+    req: ConnectToEIS,
+    reply: PendingReply<ConnectToEISReply>,
+) {
+    if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
+        s.dbus_connect_to_eis_input_capture(req, reply);
+    }
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<PortalSession>> {
+    let res = state.sessions.get(handle);
+    if res.is_none() {
+        let msg = format!("Input capture session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
@@ -5,7 +5,10 @@ use {
         dbus::{prelude::Variant, DbusObject, PendingReply},
         ifs::jay_compositor::CREATE_EI_SESSION_SINCE,
         portal::{
+            ptl_clipboard::PortalClipboard,
             ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::InputCapturePhase,
             ptl_remote_desktop::remote_desktop_gui::SelectionGui,
             ptl_screencast::ScreencastPhase,
             ptl_session::{PortalSession, PortalSessionReply},
@@ -61,6 +64,7 @@ pub struct StartedRemoteDesktop {
     pub dpy: Rc<PortalDisplay>,
     pub ei_session: Rc<UsrJayEiSession>,
     pub ei_fd: Cell<Option<Rc<OwnedFd>>>,
+    pub clipboard: Option<Rc<PortalClipboard>>,
 }
 
 bitflags! {
@@ -73,11 +77,19 @@ bitflags! {
 
 impl UsrJayEiSessionOwner for StartingRemoteDesktop {
     fn created(&self, fd: &Rc<OwnedFd>) {
+        let mut clipboard = None;
+        if self.session.clipboard_requested.get() {
+            clipboard = PortalClipboard::new(&self.session, &self.dpy);
+            if clipboard.is_none() {
+                log::warn!("Display does not support clipboard access");
+            }
+        }
         let started = Rc::new(StartedRemoteDesktop {
             session: self.session.clone(),
             dpy: self.dpy.clone(),
             ei_session: self.ei_session.clone(),
             ei_fd: Cell::new(Some(fd.clone())),
+            clipboard,
         });
         self.session
             .rd_phase
@@ -92,6 +104,9 @@ impl UsrJayEiSessionOwner for StartingRemoteDesktop {
         } else {
             self.session.send_start_reply(None, None, None);
         }
+        if let Some(clipboard) = &started.clipboard {
+            clipboard.announce_selection();
+        }
     }
 
     fn failed(&self, reason: &str) {
@@ -268,6 +283,9 @@ fn dbus_create_session(
         session_obj: obj,
         sc_phase: CloneCell::new(ScreencastPhase::Init),
         rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        clipboard_requested: Cell::new(false),
         start_reply: Default::default(),
     });
     {
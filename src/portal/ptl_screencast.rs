@@ -21,6 +21,8 @@ use {
         },
         portal::{
             ptl_display::{PortalDisplay, PortalDisplayId, PortalOutput},
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::InputCapturePhase,
             ptl_remote_desktop::RemoteDesktopPhase,
             ptl_screencast::screencast_gui::SelectionGui,
             ptl_session::{PortalSession, PortalSessionReply},
@@ -783,6 +785,9 @@ fn dbus_create_session(
         session_obj: obj,
         sc_phase: CloneCell::new(ScreencastPhase::Init),
         rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        clipboard_requested: Cell::new(false),
         start_reply: Default::default(),
     });
     {
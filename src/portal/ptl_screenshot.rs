@@ -0,0 +1,459 @@
+mod color_picker_gui;
+mod screenshot_gui;
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        cli::screenshot::{buf_to_xrgb8888, xrgb8888_encode_png},
+        cpu_worker::{AsyncCpuWork, CpuJob, CpuWork, PendingJob},
+        dbus::{prelude::Variant, DbusObject, DictEntry, PendingReply, TRUE},
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_screenshot::{color_picker_gui::ColorPickerGui, screenshot_gui::SelectionGui},
+            PortalState, PORTAL_CANCELLED, PORTAL_SUCCESS,
+        },
+        rect::Rect,
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+        },
+        video::dmabuf::DmaBuf,
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::screenshot::{
+                PickColor, PickColorReply, Screenshot, ScreenshotReply,
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_screenshot::{UsrJayScreenshot, UsrJayScreenshotOwner},
+    },
+    chrono::Local,
+    std::{
+        borrow::Cow,
+        cell::Cell,
+        fmt::Write,
+        rc::Rc,
+        time::{Duration, SystemTime},
+    },
+    uapi::OwnedFd,
+};
+
+#[derive(Clone)]
+pub enum ScreenshotPhase {
+    Init,
+    Selecting(Rc<SelectingScreenshot>),
+    Capturing(Rc<CapturingScreenshot>),
+    Saving(Rc<SavingScreenshot>),
+    Picking(Rc<PickingColor>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for ScreenshotPhase {}
+
+pub struct SelectingScreenshot {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<SelectionGui>>,
+}
+
+pub struct CapturingScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub screenshot: Rc<UsrJayScreenshot>,
+    pub region: Option<Rect>,
+}
+
+pub struct SavingScreenshot {
+    pub job: Cell<Option<PendingJob>>,
+}
+
+pub struct PickingColor {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ColorPickerGui>>,
+}
+
+pub struct ScreenshotRequest {
+    pub state: Rc<PortalState>,
+    pub app: String,
+    pub phase: CloneCell<ScreenshotPhase>,
+    request_obj: Cell<Option<DbusObject>>,
+    reply: Cell<Option<PortalScreenshotReply>>,
+}
+
+enum PortalScreenshotReply {
+    Screenshot(PendingReply<ScreenshotReply<'static>>),
+    PickColor(PendingReply<PickColorReply<'static>>),
+}
+
+impl ScreenshotRequest {
+    pub fn kill(&self) {
+        self.request_obj.take();
+        match self.phase.set(ScreenshotPhase::Terminated) {
+            ScreenshotPhase::Init => {}
+            ScreenshotPhase::Terminated => {}
+            ScreenshotPhase::Selecting(s) => {
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            ScreenshotPhase::Capturing(s) => {
+                s.screenshot.owner.take();
+            }
+            ScreenshotPhase::Saving(s) => {
+                if let Some(job) = s.job.take() {
+                    job.detach();
+                }
+            }
+            ScreenshotPhase::Picking(s) => {
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.send_reply(PORTAL_CANCELLED, &[]);
+        self.kill();
+    }
+
+    fn fail(&self, msg: &str) {
+        if let Some(reply) = self.reply.take() {
+            match reply {
+                PortalScreenshotReply::Screenshot(r) => r.err(msg),
+                PortalScreenshotReply::PickColor(r) => r.err(msg),
+            }
+        }
+        self.kill();
+    }
+
+    fn send_reply(
+        &self,
+        response: u32,
+        results: &[DictEntry<Cow<'static, str>, Variant<'static>>],
+    ) {
+        if let Some(reply) = self.reply.take() {
+            match reply {
+                PortalScreenshotReply::Screenshot(r) => r.ok(&ScreenshotReply {
+                    response,
+                    results: Cow::Borrowed(results),
+                }),
+                PortalScreenshotReply::PickColor(r) => r.ok(&PickColorReply {
+                    response,
+                    results: Cow::Borrowed(results),
+                }),
+            }
+        }
+    }
+
+    fn displays(&self) -> Vec<Rc<PortalDisplay>> {
+        self.state
+            .displays
+            .lock()
+            .values()
+            .filter(|dpy| dpy.outputs.len() > 0)
+            .cloned()
+            .collect()
+    }
+
+    fn start_selection(self: &Rc<Self>) {
+        let guis = CopyHashMap::new();
+        for dpy in self.displays() {
+            guis.set(dpy.id, SelectionGui::new(self, &dpy));
+        }
+        if guis.is_empty() {
+            self.fail("There are no running displays");
+            return;
+        }
+        self.phase
+            .set(ScreenshotPhase::Selecting(Rc::new(SelectingScreenshot {
+                guis,
+            })));
+    }
+
+    fn start_picking(self: &Rc<Self>) {
+        let guis = CopyHashMap::new();
+        for dpy in self.displays() {
+            guis.set(dpy.id, ColorPickerGui::new(self, &dpy));
+        }
+        if guis.is_empty() {
+            self.fail("There are no running displays");
+            return;
+        }
+        self.phase
+            .set(ScreenshotPhase::Picking(Rc::new(PickingColor { guis })));
+    }
+
+    /// Takes a screenshot of the display. If a region is given, the screenshot is cropped
+    /// to it.
+    pub fn capture(self: &Rc<Self>, dpy: &Rc<PortalDisplay>, region: Option<Rect>) {
+        let capturing = Rc::new(CapturingScreenshot {
+            request: self.clone(),
+            screenshot: dpy.jc.take_screenshot(false),
+            region,
+        });
+        capturing.screenshot.owner.set(Some(capturing.clone()));
+        self.phase.set(ScreenshotPhase::Capturing(capturing));
+    }
+
+    fn capture_first_display(self: &Rc<Self>) {
+        match self.displays().first() {
+            Some(dpy) => self.capture(dpy, None),
+            _ => self.fail("There are no running displays"),
+        }
+    }
+
+    fn save(self: &Rc<Self>, pixels: Vec<u8>, width: i32, height: i32) {
+        let job = Box::new(SaveJob {
+            work: SaveWork {
+                dir: format!("{}/jay-screenshots", self.state.xrd),
+                pixels,
+                width,
+                height,
+                result: None,
+            },
+            request: self.clone(),
+        });
+        let job = self.state.cpu_worker.submit(job);
+        self.phase
+            .set(ScreenshotPhase::Saving(Rc::new(SavingScreenshot {
+                job: Cell::new(Some(job)),
+            })));
+    }
+
+    pub fn send_color(&self, r: f64, g: f64, b: f64) {
+        log::info!("Picked color ({r}, {g}, {b})");
+        let results = [DictEntry {
+            key: "color".into(),
+            value: Variant::Struct(vec![Variant::F64(r), Variant::F64(g), Variant::F64(b)]),
+        }];
+        self.send_reply(PORTAL_SUCCESS, &results);
+        self.kill();
+    }
+}
+
+impl UsrJayScreenshotOwner for CapturingScreenshot {
+    fn done(self: Rc<Self>, drm_dev: Option<Rc<OwnedFd>>, buf: DmaBuf) {
+        self.screenshot.owner.take();
+        let (pixels, width, height) = match buf_to_xrgb8888(drm_dev.as_ref(), &buf, self.region) {
+            Ok(p) => p,
+            Err(e) => {
+                let msg = format!("Could not read the screenshot: {}", ErrorFmt(e));
+                log::error!("{}", msg);
+                self.request.fail(&msg);
+                return;
+            }
+        };
+        if width == 0 || height == 0 {
+            self.request.fail("The selected region is empty");
+            return;
+        }
+        self.request.save(pixels, width, height);
+    }
+
+    fn error(self: Rc<Self>, msg: &str) {
+        log::error!("Could not take a screenshot: {}", msg);
+        self.request.fail(msg);
+    }
+}
+
+struct SaveWork {
+    dir: String,
+    pixels: Vec<u8>,
+    width: i32,
+    height: i32,
+    result: Option<Result<String, String>>,
+}
+
+impl SaveWork {
+    fn save(&self) -> Result<String, String> {
+        if let Err(e) = std::fs::create_dir_all(&self.dir) {
+            return Err(format!("Could not create `{}`: {}", self.dir, ErrorFmt(e)));
+        }
+        prune_screenshots(&self.dir);
+        let data = xrgb8888_encode_png(&self.pixels, self.width, self.height, self.width * 4);
+        let file = Local::now().format("%Y-%m-%d-%H%M%S%.3f_jay.png");
+        let path = format!("{}/{}", self.dir, file);
+        if let Err(e) = std::fs::write(&path, data) {
+            return Err(format!("Could not write `{}`: {}", path, ErrorFmt(e)));
+        }
+        Ok(path)
+    }
+}
+
+impl CpuWork for SaveWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        self.result = Some(self.save());
+        None
+    }
+}
+
+struct SaveJob {
+    work: SaveWork,
+    request: Rc<ScreenshotRequest>,
+}
+
+impl CpuJob for SaveJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(mut self: Box<Self>) {
+        let path = match self.work.result.take().unwrap() {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("{}", e);
+                self.request.fail(&e);
+                return;
+            }
+        };
+        log::info!("Saved screenshot to `{}`", path);
+        let results = [DictEntry {
+            key: "uri".into(),
+            value: Variant::String(file_uri(&path).into()),
+        }];
+        self.request.send_reply(PORTAL_SUCCESS, &results);
+        self.request.kill();
+    }
+}
+
+/// Screenshots are stored in `$XDG_RUNTIME_DIR` and deleted after this time. This gives
+/// applications enough time to read them without filling up the tmpfs.
+const SCREENSHOT_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+fn prune_screenshots(dir: &str) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) => {
+            log::warn!("Could not read `{}`: {}", dir, ErrorFmt(e));
+            return;
+        }
+    };
+    let now = SystemTime::now();
+    for entry in entries.flatten() {
+        if !entry.file_name().to_string_lossy().ends_with("_jay.png") {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().and_then(|m| m.modified()) else {
+            continue;
+        };
+        let expired = match now.duration_since(modified) {
+            Ok(age) => age > SCREENSHOT_MAX_AGE,
+            _ => false,
+        };
+        if expired {
+            let path = entry.path();
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Could not delete `{}`: {}", path.display(), ErrorFmt(e));
+            }
+        }
+    }
+}
+
+/// Creates a `file://` URI from an absolute path, percent-encoding all bytes that are
+/// not unreserved in URIs.
+fn file_uri(path: &str) -> String {
+    let mut uri = "file://".to_string();
+    for &b in path.as_bytes() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(b as char)
+            }
+            _ => {
+                let _ = write!(uri, "%{:02X}", b);
+            }
+        }
+    }
+    uri
+}
+
+pub(super) fn add_screenshot_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::screenshot::*;
+    let state = state_.clone();
+    object.add_method::<Screenshot, _>(move |req, pr| {
+        dbus_screenshot(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<PickColor, _>(move |req, pr| {
+        dbus_pick_color(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(2));
+}
+
+fn create_request(
+    state: &Rc<PortalState>,
+    handle: &str,
+    app_id: &str,
+) -> Result<Rc<ScreenshotRequest>, &'static str> {
+    let request_obj = match state.dbus.add_object(handle.to_string()) {
+        Ok(r) => r,
+        Err(_) => return Err("Request handle is not unique"),
+    };
+    let request = Rc::new(ScreenshotRequest {
+        state: state.clone(),
+        app: app_id.to_string(),
+        phase: CloneCell::new(ScreenshotPhase::Init),
+        request_obj: Cell::new(None),
+        reply: Cell::new(None),
+    });
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        request_obj.add_method::<Close, _>({
+            let slf = request.clone();
+            move |_, pr| {
+                slf.cancel();
+                pr.ok(&CloseReply);
+            }
+        });
+    }
+    request.request_obj.set(Some(request_obj));
+    Ok(request)
+}
+
+fn get_bool_option(options: &[DictEntry<Cow<str>, Variant>], name: &str) -> bool {
+    options
+        .iter()
+        .any(|o| o.key == name && matches!(o.value, Variant::Bool(TRUE)))
+}
+
+fn dbus_screenshot(
+    state: &Rc<PortalState>,
+    req: Screenshot,
+    reply: PendingReply<ScreenshotReply<'static>>,
+) {
+    log::info!("Screenshot {:#?}", req);
+    let request = match create_request(state, &req.handle.0, &req.app_id) {
+        Ok(r) => r,
+        Err(e) => {
+            reply.err(e);
+            return;
+        }
+    };
+    request
+        .reply
+        .set(Some(PortalScreenshotReply::Screenshot(reply)));
+    let interactive = get_bool_option(&req.options, "interactive");
+    let permission_store_checked = get_bool_option(&req.options, "permission_store_checked");
+    if permission_store_checked && !interactive {
+        request.capture_first_display();
+    } else {
+        request.start_selection();
+    }
+}
+
+fn dbus_pick_color(
+    state: &Rc<PortalState>,
+    req: PickColor,
+    reply: PendingReply<PickColorReply<'static>>,
+) {
+    log::info!("Pick color {:#?}", req);
+    let request = match create_request(state, &req.handle.0, &req.app_id) {
+        Ok(r) => r,
+        Err(e) => {
+            reply.err(e);
+            return;
+        }
+    };
+    request
+        .reply
+        .set(Some(PortalScreenshotReply::PickColor(reply)));
+    request.start_picking();
+}
@@ -0,0 +1,175 @@
+use {
+    crate::{
+        allocator::MappedBuffer,
+        cli::screenshot::import_dmabuf,
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT, BTN_RIGHT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screenshot::{ScreenshotPhase, ScreenshotRequest},
+            ptr_gui::{ClickArea, ClickAreaOwner, FullscreenWindow, Label},
+        },
+        theme::Color,
+        utils::{
+            clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+        },
+        video::dmabuf::DmaBuf,
+        wl_usr::usr_ifs::usr_jay_screenshot::{UsrJayScreenshot, UsrJayScreenshotOwner},
+    },
+    std::{cell::RefCell, rc::Rc},
+    uapi::OwnedFd,
+};
+
+const MARGIN: f32 = 30.0;
+const PADDING: f32 = 10.0;
+
+pub struct ColorPickerGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    screenshot: CloneCell<Option<Rc<UsrJayScreenshot>>>,
+    image: RefCell<Option<PickerImage>>,
+    surfaces: CopyHashMap<u32, Rc<ColorPickerSurface>>,
+}
+
+struct PickerImage {
+    map: Box<dyn MappedBuffer>,
+    width: i32,
+    height: i32,
+    x1: i32,
+    y1: i32,
+}
+
+pub struct ColorPickerSurface {
+    gui: Rc<ColorPickerGui>,
+    output: Rc<PortalOutput>,
+    window: Rc<FullscreenWindow>,
+}
+
+impl ColorPickerGui {
+    pub fn new(request: &Rc<ScreenshotRequest>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(ColorPickerGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            screenshot: Default::default(),
+            image: Default::default(),
+            surfaces: Default::default(),
+        });
+        let screenshot = dpy.jc.take_screenshot(false);
+        screenshot.owner.set(Some(gui.clone()));
+        gui.screenshot.set(Some(screenshot));
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        if let Some(screenshot) = self.screenshot.take() {
+            screenshot.owner.take();
+        }
+        for surface in self.surfaces.lock().drain_values() {
+            self.dpy.windows.remove(&surface.window.data.surface.id);
+            surface.window.data.kill(false);
+        }
+        self.image.take();
+        if let ScreenshotPhase::Picking(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.cancel();
+            }
+        }
+    }
+
+    fn show(self: &Rc<Self>) {
+        for output in self.dpy.outputs.lock().values() {
+            let surface = Rc::new(ColorPickerSurface {
+                gui: self.clone(),
+                output: output.clone(),
+                window: FullscreenWindow::new(output),
+            });
+            let label = Rc::new(Label::default());
+            *label.text.borrow_mut() = "Click to pick a color. Right-click to cancel.".to_string();
+            let area = Rc::new(ClickArea::default());
+            area.margin.set(MARGIN);
+            area.padding.set(PADDING);
+            area.bg_color.set(Color::from_rgba_straight(0, 0, 0, 200));
+            area.content.set(Some(label));
+            area.owner.set(Some(surface.clone()));
+            surface.window.data.content.set(Some(area));
+            self.dpy
+                .windows
+                .set(surface.window.data.surface.id, surface.window.data.clone());
+            self.surfaces.set(output.global_id, surface);
+        }
+    }
+}
+
+impl UsrJayScreenshotOwner for ColorPickerGui {
+    fn done(self: Rc<Self>, drm_dev: Option<Rc<OwnedFd>>, buf: DmaBuf) {
+        self.screenshot.take();
+        let map = match import_dmabuf(drm_dev.as_ref(), &buf) {
+            Ok((_, m)) => m,
+            Err(e) => {
+                log::error!("Could not map the screenshot: {}", ErrorFmt(e));
+                self.kill(true);
+                return;
+            }
+        };
+        let mut x1 = i32::MAX;
+        let mut y1 = i32::MAX;
+        for output in self.dpy.outputs.lock().values() {
+            let (x, y) = output.pos.get();
+            x1 = x1.min(x);
+            y1 = y1.min(y);
+        }
+        *self.image.borrow_mut() = Some(PickerImage {
+            map,
+            width: buf.width,
+            height: buf.height,
+            x1,
+            y1,
+        });
+        self.show();
+    }
+
+    fn error(self: Rc<Self>, msg: &str) {
+        log::error!("Could not take a screenshot: {}", msg);
+        self.screenshot.take();
+        self.kill(true);
+    }
+}
+
+impl ColorPickerSurface {
+    fn pick(&self, seat: &PortalSeat) -> Option<(f64, f64, f64)> {
+        let seat = self.window.data.seats.get(&seat.global_id)?;
+        let image = self.gui.image.borrow();
+        let image = image.as_ref()?;
+        let (ox, oy) = self.output.pos.get();
+        let x = ox + seat.x.get().floor() as i32 - image.x1;
+        let y = oy + seat.y.get().floor() as i32 - image.y1;
+        if x < 0 || y < 0 || x >= image.width || y >= image.height {
+            return None;
+        }
+        let data = unsafe { image.map.data() };
+        let offset = y as usize * image.map.stride() as usize + x as usize * 4;
+        let pixel = data.get(offset..offset + 4)?;
+        let channel = |c: u8| c as f64 / 255.0;
+        Some((channel(pixel[2]), channel(pixel[1]), channel(pixel[0])))
+    }
+}
+
+impl ClickAreaOwner for ColorPickerSurface {
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if state != PRESSED {
+            return;
+        }
+        match button {
+            BTN_LEFT => match self.pick(seat) {
+                Some((r, g, b)) => self.gui.request.send_color(r, g, b),
+                _ => log::warn!("Selected position is outside of the screenshot"),
+            },
+            BTN_RIGHT => {
+                log::info!("User has aborted the color selection");
+                self.gui.request.cancel();
+            }
+            _ => {}
+        }
+    }
+}
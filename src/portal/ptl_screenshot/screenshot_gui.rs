@@ -0,0 +1,186 @@
+use {
+    crate::{
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screenshot::{ScreenshotPhase, ScreenshotRequest},
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
+                OverlayWindowOwner,
+            },
+        },
+        rect::Rect,
+        theme::Color,
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+    },
+    std::rc::Rc,
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+pub struct SelectionGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<SelectionGuiSurface>>,
+}
+
+pub struct SelectionGuiSurface {
+    gui: Rc<SelectionGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    surface: Rc<SelectionGuiSurface>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    AcceptOutput,
+    AcceptAll,
+    Reject,
+}
+
+impl SelectionGui {
+    pub fn kill(&self, upwards: bool) {
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let ScreenshotPhase::Selecting(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.cancel();
+            }
+        }
+    }
+}
+
+fn create_accept_gui(surface: &Rc<SelectionGuiSurface>) -> Rc<dyn GuiElement> {
+    let app = &surface.gui.request.app;
+    let text = if app.is_empty() {
+        "An application wants to take a screenshot".to_string()
+    } else {
+        format!("`{}` wants to take a screenshot", app)
+    };
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let output_button = static_button(surface, ButtonRole::AcceptOutput, "Capture This Output");
+    let all_button = static_button(surface, ButtonRole::AcceptAll, "Capture All Outputs");
+    let reject_button = static_button(surface, ButtonRole::Reject, "Reject");
+    for button in [&output_button, &all_button, &reject_button] {
+        button.border_color.set(Color::from_gray(100));
+        button.border.set(2.0);
+        button.padding.set(5.0);
+    }
+    for button in [&output_button, &all_button] {
+        button.bg_color.set(Color::from_rgb(170, 200, 170));
+        button.bg_hover_color.set(Color::from_rgb(170, 255, 170));
+    }
+    reject_button.bg_color.set(Color::from_rgb(200, 170, 170));
+    reject_button
+        .bg_hover_color
+        .set(Color::from_rgb(255, 170, 170));
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label, output_button];
+    if surface.gui.dpy.outputs.len() > 1 {
+        elements.push(all_button);
+    }
+    elements.push(reject_button);
+    *flow.elements.borrow_mut() = elements;
+    flow
+}
+
+impl SelectionGuiSurface {
+    /// Returns the area of the output within a screenshot of the whole display.
+    fn output_rect(&self) -> Rect {
+        let (x, y) = self.output.pos.get();
+        let (mut x1, mut y1) = (x, y);
+        for output in self.gui.dpy.outputs.lock().values() {
+            let (ox, oy) = output.pos.get();
+            x1 = x1.min(ox);
+            y1 = y1.min(oy);
+        }
+        let data = &self.overlay.data;
+        Rect::new_sized_unchecked(x - x1, y - y1, data.width.get(), data.height.get())
+    }
+}
+
+impl OverlayWindowOwner for SelectionGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl SelectionGui {
+    pub fn new(request: &Rc<ScreenshotRequest>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(SelectionGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let sgs = Rc::new(SelectionGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new(output),
+            });
+            let element = create_accept_gui(&sgs);
+            sgs.overlay.data.content.set(Some(element));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, _seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        let region = match self.role {
+            ButtonRole::AcceptOutput => Some(self.surface.output_rect()),
+            ButtonRole::AcceptAll => None,
+            ButtonRole::Reject => {
+                log::info!("User has rejected the screenshot request");
+                self.surface.gui.request.cancel();
+                return;
+            }
+        };
+        log::info!("User has accepted the screenshot request");
+        let selecting = match self.surface.gui.request.phase.get() {
+            ScreenshotPhase::Selecting(selecting) => selecting,
+            _ => return,
+        };
+        for gui in selecting.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        self.surface
+            .gui
+            .request
+            .capture(&self.surface.output.dpy, region);
+    }
+}
+
+fn static_button(surface: &Rc<SelectionGuiSurface>, role: ButtonRole, text: &str) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        surface: surface.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button
+}
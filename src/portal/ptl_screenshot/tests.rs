@@ -0,0 +1,19 @@
+use crate::portal::ptl_screenshot::file_uri;
+
+#[test]
+fn plain_path() {
+    assert_eq!(
+        file_uri("/run/user/1000/jay-screenshots/2024-01-01-120000.000_jay.png"),
+        "file:///run/user/1000/jay-screenshots/2024-01-01-120000.000_jay.png",
+    );
+}
+
+#[test]
+fn reserved_characters() {
+    assert_eq!(file_uri("/tmp/a b#c?d%e"), "file:///tmp/a%20b%23c%3Fd%25e",);
+}
+
+#[test]
+fn non_ascii() {
+    assert_eq!(file_uri("/tmp/ä"), "file:///tmp/%C3%A4");
+}
@@ -1,8 +1,10 @@
 use {
     crate::{
-        dbus::{prelude::Variant, DbusObject, DictEntry, DynamicType, PendingReply, FALSE},
+        dbus::{prelude::Variant, DbusObject, DictEntry, DynamicType, PendingReply, FALSE, TRUE},
         pipewire::pw_con::PwCon,
         portal::{
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::InputCapturePhase,
             ptl_remote_desktop::{DeviceTypes, RemoteDesktopPhase},
             ptl_screencast::{ScreencastPhase, ScreencastTarget},
             PortalState, PORTAL_SUCCESS,
@@ -25,6 +27,9 @@ pub struct PortalSession {
     pub session_obj: DbusObject,
     pub sc_phase: CloneCell<ScreencastPhase>,
     pub rd_phase: CloneCell<RemoteDesktopPhase>,
+    pub gs_phase: CloneCell<GlobalShortcutsPhase>,
+    pub ic_phase: CloneCell<InputCapturePhase>,
+    pub clipboard_requested: Cell<bool>,
     pub start_reply: Cell<Option<PortalSessionReply>>,
 }
 
@@ -52,10 +57,48 @@ impl PortalSession {
                 s.dpy.sessions.remove(self.session_obj.path());
             }
             RemoteDesktopPhase::Started(s) => {
+                if let Some(clipboard) = &s.clipboard {
+                    clipboard.kill();
+                }
                 s.ei_session.con.remove_obj(s.ei_session.deref());
                 s.dpy.sessions.remove(self.session_obj.path());
             }
         }
+        match self.gs_phase.set(GlobalShortcutsPhase::Terminated) {
+            GlobalShortcutsPhase::Init => {}
+            GlobalShortcutsPhase::Terminated => {}
+            GlobalShortcutsPhase::Selecting(s) => {
+                if let Some(reply) = s.reply.take() {
+                    reply.err("Session has been terminated");
+                }
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            GlobalShortcutsPhase::Bound(s) => {
+                s.jay_shortcuts.con.remove_obj(s.jay_shortcuts.deref());
+                s.dpy.sessions.remove(self.session_obj.path());
+            }
+        }
+        match self.ic_phase.set(InputCapturePhase::Terminated) {
+            InputCapturePhase::Init => {}
+            InputCapturePhase::Terminated => {}
+            InputCapturePhase::Selecting(s) => {
+                if let Some(reply) = s.reply.take() {
+                    reply.err("Session has been terminated");
+                }
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            InputCapturePhase::Started(s) => {
+                if let Some(reply) = s.reply.take() {
+                    reply.err("Session has been terminated");
+                }
+                s.capture.con.remove_obj(s.capture.deref());
+                s.dpy.sessions.remove(self.session_obj.path());
+            }
+        }
         match self.sc_phase.set(ScreencastPhase::Terminated) {
             ScreencastPhase::Init => {}
             ScreencastPhase::SourcesSelected(_) => {}
@@ -110,6 +153,10 @@ impl PortalSession {
             DynamicType::U32,
             DynamicType::Array(Box::new(inner_type.clone())),
         ]);
+        let clipboard_enabled = match self.rd_phase.get() {
+            RemoteDesktopPhase::Started(s) if s.clipboard.is_some() => TRUE,
+            _ => FALSE,
+        };
         let mut streams = vec![];
         if let Some(node_id) = pw_node_id {
             streams = vec![Variant::U32(node_id), Variant::Array(inner_type, vec![])];
@@ -121,7 +168,7 @@ impl PortalSession {
             },
             DictEntry {
                 key: "clipboard_enabled".into(),
-                value: Variant::Bool(FALSE),
+                value: Variant::Bool(clipboard_enabled),
             },
             DictEntry {
                 key: "streams".into(),
@@ -8,7 +8,13 @@ use {
         gfx_api::{
             needs_render_usage, AcquireSync, GfxContext, GfxFramebuffer, GfxTexture, ReleaseSync,
         },
-        ifs::zwlr_layer_shell_v1::OVERLAY,
+        ifs::{
+            wl_surface::zwlr_layer_surface_v1::{
+                BOTTOM as ANCHOR_BOTTOM, LEFT as ANCHOR_LEFT, RIGHT as ANCHOR_RIGHT,
+                TOP as ANCHOR_TOP,
+            },
+            zwlr_layer_shell_v1::OVERLAY,
+        },
         portal::{
             ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
             ptl_text::{self, TextMeasurement},
@@ -462,6 +468,95 @@ impl GuiElement for Flow {
     }
 }
 
+pub struct ClickArea {
+    pub data: GuiElementData,
+    pub margin: Cell<f32>,
+    pub padding: Cell<f32>,
+    pub bg_color: Cell<Color>,
+    pub content: CloneCell<Option<Rc<dyn GuiElement>>>,
+    pub owner: CloneCell<Option<Rc<dyn ClickAreaOwner>>>,
+}
+
+pub trait ClickAreaOwner {
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32);
+}
+
+impl Default for ClickArea {
+    fn default() -> Self {
+        Self {
+            data: Default::default(),
+            margin: Default::default(),
+            padding: Default::default(),
+            bg_color: Cell::new(Color::from_gray(0)),
+            content: Default::default(),
+            owner: Default::default(),
+        }
+    }
+}
+
+impl GuiElement for ClickArea {
+    fn data(&self) -> &GuiElementData {
+        &self.data
+    }
+
+    fn layout(
+        &self,
+        ctx: &Rc<dyn GfxContext>,
+        scale: f32,
+        max_width: f32,
+        max_height: f32,
+    ) -> (f32, f32) {
+        if let Some(content) = self.content.get() {
+            let (w, h) = content.layout(ctx, scale, max_width, max_height);
+            let data = content.data();
+            data.x.set(((max_width - w) / 2.0).round());
+            data.y.set(self.margin.get() + self.padding.get());
+            data.width.set(w);
+            data.height.set(h);
+        }
+        (max_width, max_height)
+    }
+
+    fn render_at(&self, r: &mut RendererBase, x: f32, y: f32) {
+        let Some(content) = self.content.get() else {
+            return;
+        };
+        let data = content.data();
+        let x1 = x + data.x.get();
+        let y1 = y + data.y.get();
+        let padding = self.padding.get();
+        let rects = [(
+            x1 - padding,
+            y1 - padding,
+            x1 + data.width.get() + padding,
+            y1 + data.height.get() + padding,
+        )];
+        r.fill_boxes_f(&rects, &self.bg_color.get());
+        content.render_at(r, x1, y1);
+    }
+
+    fn child_at(&self, _x: f32, _y: f32) -> Option<Rc<dyn GuiElement>> {
+        None
+    }
+
+    fn hover_cursor(&self) -> KnownCursor {
+        KnownCursor::Crosshair
+    }
+
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if let Some(owner) = self.owner.get() {
+            owner.button(seat, button, state);
+        }
+    }
+
+    fn destroy(&self) {
+        self.owner.take();
+        if let Some(content) = self.content.take() {
+            content.destroy();
+        }
+    }
+}
+
 pub struct OverlayWindow {
     pub layer_surface: Rc<UsrWlrLayerSurface>,
     pub data: Rc<WindowData>,
@@ -472,6 +567,12 @@ pub trait OverlayWindowOwner {
     fn kill(&self, upwards: bool);
 }
 
+pub struct FullscreenWindow {
+    pub layer_surface: Rc<UsrWlrLayerSurface>,
+    pub data: Rc<WindowData>,
+    pub owner: CloneCell<Option<Rc<dyn OverlayWindowOwner>>>,
+}
+
 pub struct WindowData {
     pub frame_missed: Cell<bool>,
     pub first_scale: Cell<bool>,
@@ -488,6 +589,8 @@ pub struct WindowData {
     pending_bufs: CopyHashMap<ZwpLinuxBufferParamsV1Id, Rc<GuiBufferPending>>,
     pub width: Cell<i32>,
     pub height: Cell<i32>,
+    pub fixed_size: Cell<Option<(i32, i32)>>,
+    pub clear_color: Cell<Color>,
     pub owner: CloneCell<Option<Rc<dyn WindowDataOwner>>>,
     pub seats: CopyHashMap<u32, Rc<GuiWindowSeatState>>,
 }
@@ -522,6 +625,21 @@ impl WindowDataOwner for OverlayWindow {
     }
 }
 
+impl WindowDataOwner for FullscreenWindow {
+    fn post_layout(&self) {
+        self.data.surface.commit();
+    }
+
+    fn kill(&self, upwards: bool) {
+        if let Some(owner) = self.owner.take() {
+            owner.kill(upwards);
+        }
+        self.layer_surface
+            .con
+            .remove_obj(self.layer_surface.deref());
+    }
+}
+
 const NUM_BUFFERS: usize = 2;
 
 impl OverlayWindow {
@@ -544,6 +662,29 @@ impl OverlayWindow {
     }
 }
 
+impl FullscreenWindow {
+    pub fn new(output: &Rc<PortalOutput>) -> Rc<Self> {
+        let data = WindowData::new(&output.dpy);
+        data.clear_color.set(Color::TRANSPARENT);
+        let layer_surface = output
+            .dpy
+            .ls
+            .get_layer_surface(&data.surface, &output.wl, OVERLAY);
+        layer_surface.set_anchor(ANCHOR_TOP | ANCHOR_BOTTOM | ANCHOR_LEFT | ANCHOR_RIGHT);
+        layer_surface.set_exclusive_zone(-1);
+        layer_surface.set_size(0, 0);
+        let slf = Rc::new(Self {
+            layer_surface,
+            data,
+            owner: Default::default(),
+        });
+        slf.data.owner.set(Some(slf.clone()));
+        slf.layer_surface.owner.set(Some(slf.clone()));
+        slf.data.surface.commit();
+        slf
+    }
+}
+
 impl WindowData {
     pub fn schedule_render(&self) {
         self.render_trigger.trigger();
@@ -562,6 +703,8 @@ impl WindowData {
             pending_bufs: Default::default(),
             width: Cell::new(0),
             height: Cell::new(0),
+            fixed_size: Default::default(),
+            clear_color: Cell::new(Color::from_gray(0)),
             owner: Default::default(),
             render_trigger: Default::default(),
             render_task: Cell::new(None),
@@ -592,8 +735,11 @@ impl WindowData {
             Some(c) => c,
             _ => return,
         };
-        let (mut width, mut height) =
-            content.layout(&ctx.ctx.ctx, scale, f32::INFINITY, f32::INFINITY);
+        let (max_width, max_height) = match self.fixed_size.get() {
+            Some((w, h)) => (w as f32, h as f32),
+            _ => (f32::INFINITY, f32::INFINITY),
+        };
+        let (mut width, mut height) = content.layout(&ctx.ctx.ctx, scale, max_width, max_height);
         content.data().width.set(width);
         content.data().height.set(height);
         width = width.max(1.0);
@@ -633,7 +779,7 @@ impl WindowData {
             AcquireSync::Implicit,
             ReleaseSync::Implicit,
             self.scale.get(),
-            Some(&Color::from_gray(0)),
+            Some(&self.clear_color.get()),
             &mut |r| {
                 if let Some(content) = self.content.get() {
                     content.render_at(r, 0.0, 0.0)
@@ -892,6 +1038,21 @@ impl UsrWlrLayerSurfaceOwner for OverlayWindow {
     }
 }
 
+impl UsrWlrLayerSurfaceOwner for FullscreenWindow {
+    fn configure(&self, ev: &Configure) {
+        let size = (ev.width as i32, ev.height as i32);
+        if self.data.fixed_size.replace(Some(size)) != Some(size) {
+            self.data.layout();
+            self.data.allocate_buffers();
+        }
+        self.data.schedule_render();
+    }
+
+    fn closed(&self) {
+        self.data.kill(true);
+    }
+}
+
 impl UsrLinuxBufferParamsOwner for GuiBufferPending {
     fn created(&self, buffer: Rc<UsrWlBuffer>) {
         buffer.con.add_object(buffer.clone());
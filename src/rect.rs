@@ -127,6 +127,42 @@ impl Rect {
         self.raw.x1 <= x && self.raw.y1 <= y && self.raw.x2 > x && self.raw.y2 > y
     }
 
+    /// Returns whether the rectangle contains the point if its corners are rounded with
+    /// the given radius.
+    pub fn contains_rounded(&self, x: i32, y: i32, radius: i32) -> bool {
+        if !self.contains(x, y) {
+            return false;
+        }
+        let radius = radius.min(self.width() / 2).min(self.height() / 2);
+        if radius <= 0 {
+            return true;
+        }
+        // All values are doubled so that the center of the pixel can be used.
+        let (x1, y1, x2, y2) = (
+            2 * (self.raw.x1 + radius) as i64,
+            2 * (self.raw.y1 + radius) as i64,
+            2 * (self.raw.x2 - radius) as i64,
+            2 * (self.raw.y2 - radius) as i64,
+        );
+        let (x, y) = (2 * x as i64 + 1, 2 * y as i64 + 1);
+        let dx = if x < x1 {
+            x1 - x
+        } else if x > x2 {
+            x - x2
+        } else {
+            return true;
+        };
+        let dy = if y < y1 {
+            y1 - y
+        } else if y > y2 {
+            y - y2
+        } else {
+            return true;
+        };
+        let radius = 2 * radius as i64;
+        dx * dx + dy * dy <= radius * radius
+    }
+
     pub fn dist_squared(&self, x: i32, y: i32) -> i32 {
         let mut dx = 0;
         if self.raw.x1 > x {
@@ -90,7 +90,6 @@ impl Region {
         })
     }
 
-    #[cfg_attr(not(feature = "it"), expect(dead_code))]
     pub fn extents(&self) -> Rect {
         self.extents
     }
@@ -111,3 +111,51 @@ fn rects_to_bands2() {
     // println!("{:#?}", r.rects);
     assert_eq!(&r.rects[..], &[Rect::new(0, 0, 10, 20).unwrap().raw,]);
 }
+
+#[test]
+fn contains_rounded_corners() {
+    let r = Rect::new(0, 0, 100, 100).unwrap();
+    assert!(r.contains_rounded(50, 50, 10));
+    assert!(r.contains_rounded(10, 0, 10));
+    assert!(r.contains_rounded(0, 10, 10));
+    assert!(!r.contains_rounded(0, 0, 10));
+    assert!(!r.contains_rounded(2, 2, 10));
+    assert!(r.contains_rounded(3, 3, 10));
+    assert!(!r.contains_rounded(99, 0, 10));
+    assert!(!r.contains_rounded(0, 99, 10));
+    assert!(!r.contains_rounded(99, 99, 10));
+    assert!(r.contains_rounded(96, 96, 10));
+    assert!(r.contains_rounded(97, 96, 10));
+    assert!(!r.contains_rounded(97, 97, 10));
+    assert!(!r.contains_rounded(100, 50, 10));
+    assert!(!r.contains_rounded(-1, 50, 10));
+}
+
+#[test]
+fn contains_rounded_without_radius() {
+    let r = Rect::new(0, 0, 100, 100).unwrap();
+    assert!(r.contains_rounded(0, 0, 0));
+    assert!(r.contains_rounded(99, 99, -5));
+    assert!(!r.contains_rounded(100, 100, 0));
+}
+
+#[test]
+fn contains_rounded_large_radius() {
+    // The radius is clamped to half of the smaller dimension.
+    let r = Rect::new(0, 0, 20, 10).unwrap();
+    assert!(r.contains_rounded(10, 5, 100));
+    assert!(r.contains_rounded(0, 5, 100));
+    assert!(r.contains_rounded(0, 4, 100));
+    assert!(r.contains_rounded(1, 1, 100));
+    assert!(!r.contains_rounded(0, 1, 100));
+    assert!(!r.contains_rounded(0, 0, 100));
+    assert!(!r.contains_rounded(19, 9, 100));
+    assert_eq!(r.contains_rounded(3, 2, 5), r.contains_rounded(3, 2, 100));
+
+    let circle = Rect::new(0, 0, 10, 10).unwrap();
+    assert!(circle.contains_rounded(5, 5, 5));
+    assert!(circle.contains_rounded(0, 5, 5));
+    assert!(circle.contains_rounded(5, 0, 5));
+    assert!(!circle.contains_rounded(0, 0, 5));
+    assert!(!circle.contains_rounded(9, 0, 5));
+}
@@ -7,16 +7,19 @@ use {
             zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
             SurfaceBuffer, WlSurface,
         },
-        rect::Rect,
-        renderer::renderer_base::RendererBase,
+        rect::{Rect, Region},
+        renderer::renderer_base::{RendererBase, RoundedRect},
         scale::Scale,
         state::State,
         theme::Color,
         tree::{
-            ContainerNode, DisplayNode, FloatNode, OutputNode, PlaceholderNode, ToplevelData,
-            ToplevelNodeBase, WorkspaceNode,
+            BarMode, ContainerNode, DisplayNode, FloatNode, OutputNode, PlaceholderNode,
+            ScrollingNode, StackedNode, TitleButton, ToplevelData, ToplevelNode, ToplevelNodeBase,
+            WorkspaceNode,
         },
+        wallpaper::WallpaperMode,
     },
+    jay_config::video::Transform,
     std::{ops::Deref, rc::Rc, slice},
 };
 
@@ -27,6 +30,8 @@ pub struct Renderer<'a> {
     pub state: &'a State,
     pub logical_extents: Rect,
     pub pixel_extents: Rect,
+    /// The absolute logical areas whose backgrounds have been blurred.
+    pub blurred_areas: Vec<Rect>,
 }
 
 impl Renderer<'_> {
@@ -53,6 +58,15 @@ impl Renderer<'_> {
     }
 
     pub fn render_output(&mut self, output: &OutputNode, x: i32, y: i32) {
+        self.blurred_areas.clear();
+        self.render_output2(output, x, y);
+        output
+            .blurred_areas
+            .borrow_mut()
+            .clone_from(&self.blurred_areas);
+    }
+
+    fn render_output2(&mut self, output: &OutputNode, x: i32, y: i32) {
         if self.state.lock.locked.get() {
             if let Some(surface) = output.lock_surface.get() {
                 if surface.surface.buffer.is_some() {
@@ -62,6 +76,7 @@ impl Renderer<'_> {
             return;
         }
         let opos = output.global.pos.get();
+        let (output_x, output_y) = (x, y);
         macro_rules! render_layer {
             ($layer:expr) => {
                 for ls in $layer.iter() {
@@ -76,97 +91,46 @@ impl Renderer<'_> {
             fullscreen = ws.fullscreen.get();
         }
         let theme = &self.state.theme;
-        let th = theme.sizes.title_height.get();
+        let slide = match fullscreen {
+            Some(_) => None,
+            None => self.workspace_slide(output),
+        };
         if let Some(fs) = fullscreen {
             fs.tl_as_node().node_render(self, x, y, None);
         } else {
+            self.render_wallpaper(output, x, y);
             render_layer!(output.layers[0]);
             render_layer!(output.layers[1]);
-            let non_exclusive_rect = output.non_exclusive_rect_rel.get();
-            let (x, y) = non_exclusive_rect.translate_inv(x, y);
-            {
-                let c = theme.colors.bar_background.get();
-                self.base.fill_boxes2(
-                    slice::from_ref(
-                        &Rect::new_sized(0, 0, non_exclusive_rect.width(), th).unwrap(),
-                    ),
-                    &c,
-                    x,
-                    y,
-                );
-                let rd = output.render_data.borrow_mut();
-                if let Some(aw) = &rd.active_workspace {
-                    let c = match aw.captured {
-                        true => theme.colors.captured_focused_title_background.get(),
-                        false => theme.colors.focused_title_background.get(),
-                    };
-                    self.base.fill_boxes2(slice::from_ref(&aw.rect), &c, x, y);
-                }
-                let c = theme.colors.separator.get();
-                self.base
-                    .fill_boxes2(slice::from_ref(&rd.underline), &c, x, y);
-                let c = theme.colors.unfocused_title_background.get();
-                self.base.fill_boxes2(&rd.inactive_workspaces, &c, x, y);
-                let c = theme.colors.captured_unfocused_title_background.get();
-                self.base
-                    .fill_boxes2(&rd.captured_inactive_workspaces, &c, x, y);
-                let c = theme.colors.attention_requested_background.get();
-                self.base
-                    .fill_boxes2(&rd.attention_requested_workspaces, &c, x, y);
-                let scale = output.global.persistent.scale.get();
-                for title in &rd.titles {
-                    let (x, y) = self.base.scale_point(x + title.tex_x, y + title.tex_y);
-                    self.base.render_texture(
-                        &title.tex,
-                        None,
-                        x,
-                        y,
-                        None,
-                        None,
-                        scale,
-                        None,
-                        None,
-                        AcquireSync::None,
-                        ReleaseSync::None,
-                    );
-                }
-                if let Some(status) = &rd.status {
-                    if let Some(texture) = status.tex.texture() {
-                        let (x, y) = self.base.scale_point(x + status.tex_x, y);
-                        self.base.render_texture(
-                            &texture,
-                            None,
-                            x,
-                            y,
-                            None,
-                            None,
-                            scale,
-                            None,
-                            None,
-                            AcquireSync::None,
-                            ReleaseSync::None,
-                        );
-                    }
-                }
-                for item in output.tray_items.iter() {
-                    let data = item.data();
-                    if data.surface.buffer.is_some() {
-                        let rect = data.rel_pos.get().move_(x, y);
-                        let bounds = self.base.scale_rect(rect);
-                        self.render_surface(&data.surface, rect.x1(), rect.y1(), Some(&bounds));
-                    }
-                }
+            let bar_shown = output.bar_shown();
+            let bar_overlay = output.bar_mode.get() == BarMode::OnModifier;
+            if bar_shown && !bar_overlay {
+                self.render_bar(output, x, y);
             }
             if let Some(ws) = output.workspace.get() {
-                self.render_workspace(&ws, x, y + th + 1);
+                let (x, y) = output.workspace_rect_rel().translate_inv(x, y);
+                self.render_workspace_switch(&ws, slide.as_ref(), x, y);
+            }
+            if bar_shown && bar_overlay {
+                self.base.ops.push(GfxApiOpt::Sync);
+                self.render_bar(output, x, y);
             }
+            self.render_closing_windows(output, output_x, output_y);
         }
+        let incoming = match (&slide, output.workspace.get()) {
+            (Some(slide), Some(ws)) => Some((ws, slide.offset)),
+            _ => None,
+        };
         macro_rules! render_stacked {
             ($stack:expr) => {
                 for stacked in $stack.iter() {
                     if stacked.node_visible() {
                         self.base.ops.push(GfxApiOpt::Sync);
-                        let pos = stacked.node_absolute_position();
+                        let mut pos = stacked.node_absolute_position();
+                        if let Some((ws, offset)) = &incoming {
+                            if slides_with_workspace(stacked.deref(), ws) {
+                                pos = pos.move_(*offset, 0);
+                            }
+                        }
                         if pos.intersects(&opos) {
                             let (x, y) = opos.translate(pos.x1(), pos.y1());
                             stacked.node_render(self, x, y, None);
@@ -182,16 +146,360 @@ impl Renderer<'_> {
         if let Some(ws) = output.workspace.get() {
             if ws.render_highlight.get() > 0 {
                 let color = self.state.theme.colors.highlight.get();
-                let bounds = ws.position.get().at_point(x, y + th + 1);
+                let (x, y) = output.workspace_rect_rel().translate_inv(x, y);
+                let bounds = ws.position.get().at_point(x, y);
                 self.base.fill_boxes(&[bounds], &color);
             }
         }
+        if let Some(sw) = &output.render_data.borrow().window_switcher {
+            let c = theme.colors.bar_background.get();
+            self.base.fill_boxes2(slice::from_ref(&sw.rect), &c, x, y);
+            if let Some(selected) = &sw.selected {
+                let c = theme.colors.focused_title_background.get();
+                self.base.fill_boxes2(slice::from_ref(selected), &c, x, y);
+            }
+            let scale = output.global.persistent.scale.get();
+            for title in &sw.titles {
+                let (x, y) = self.base.scale_point(x + title.tex_x, y + title.tex_y);
+                self.base.render_texture(
+                    &title.tex,
+                    None,
+                    x,
+                    y,
+                    None,
+                    None,
+                    scale,
+                    None,
+                    None,
+                    AcquireSync::None,
+                    ReleaseSync::None,
+                );
+            }
+        }
+    }
+
+    fn render_wallpaper(&mut self, output: &OutputNode, x: i32, y: i32) {
+        /// The largest number of copies of the image drawn in the tile mode.
+        const MAX_TILES: i32 = 4096;
+        let Some(wallpaper) = output.wallpaper.get() else {
+            return;
+        };
+        let pos = output.global.pos.get();
+        let (x1, y1) = self.base.scale_point(x, y);
+        let (x2, y2) = self.base.scale_point(x + pos.width(), y + pos.height());
+        let (width, height) = (x2 - x1, y2 - y1);
+        let (tex_width, tex_height) = wallpaper.texture.size();
+        if width <= 0 || height <= 0 || tex_width <= 0 || tex_height <= 0 {
+            return;
+        }
+        let bounds = Rect::new_unchecked(x1, y1, x2, y2);
+        let scale = self.base.scale;
+        let mut render = |x: i32, y: i32, size: (i32, i32), tpoints: Option<SampleRect>| {
+            self.base.render_texture(
+                &wallpaper.texture,
+                None,
+                x,
+                y,
+                tpoints,
+                Some(size),
+                scale,
+                Some(&bounds),
+                None,
+                AcquireSync::Unnecessary,
+                ReleaseSync::None,
+            );
+        };
+        let scale_x = width as f64 / tex_width as f64;
+        let scale_y = height as f64 / tex_height as f64;
+        let tiles_x = (width + tex_width - 1) / tex_width;
+        let tiles_y = (height + tex_height - 1) / tex_height;
+        let mut mode = wallpaper.mode;
+        // Tiny images would require an excessive number of draw calls. This fallback is
+        // documented in the configuration APIs.
+        if mode == WallpaperMode::Tile && tiles_x.saturating_mul(tiles_y) > MAX_TILES {
+            mode = WallpaperMode::Fill;
+        }
+        match mode {
+            WallpaperMode::Fill => {
+                let scale = scale_x.max(scale_y);
+                let visible_x = (scale_x / scale) as f32;
+                let visible_y = (scale_y / scale) as f32;
+                let tpoints = SampleRect {
+                    x1: (1.0 - visible_x) / 2.0,
+                    y1: (1.0 - visible_y) / 2.0,
+                    x2: (1.0 + visible_x) / 2.0,
+                    y2: (1.0 + visible_y) / 2.0,
+                    buffer_transform: Transform::None,
+                };
+                render(x1, y1, (width, height), Some(tpoints));
+            }
+            WallpaperMode::Fit => {
+                let scale = scale_x.min(scale_y);
+                let w = (tex_width as f64 * scale).round() as i32;
+                let h = (tex_height as f64 * scale).round() as i32;
+                render(x1 + (width - w) / 2, y1 + (height - h) / 2, (w, h), None);
+            }
+            WallpaperMode::Center => {
+                let x = x1 + (width - tex_width) / 2;
+                let y = y1 + (height - tex_height) / 2;
+                render(x, y, (tex_width, tex_height), None);
+            }
+            WallpaperMode::Tile => {
+                for ty in 0..tiles_y {
+                    for tx in 0..tiles_x {
+                        let x = x1 + tx * tex_width;
+                        let y = y1 + ty * tex_height;
+                        render(x, y, (tex_width, tex_height), None);
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_bar(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let theme = &self.state.theme;
+        let th = theme.sizes.title_height.get();
+        let bar_rect = output.bar_rect_rel.get();
+        let (x, y) = bar_rect.translate_inv(x, y);
+        let c = theme.colors.bar_background.get();
+        self.base.fill_boxes2(
+            slice::from_ref(&Rect::new_sized(0, 0, bar_rect.width(), th).unwrap()),
+            &c,
+            x,
+            y,
+        );
+        let rd = output.render_data.borrow_mut();
+        if let Some(aw) = &rd.active_workspace {
+            let c = match aw.captured {
+                true => theme.colors.captured_focused_title_background.get(),
+                false => theme.colors.focused_title_background.get(),
+            };
+            self.base.fill_boxes2(slice::from_ref(&aw.rect), &c, x, y);
+        }
+        let c = theme.colors.separator.get();
+        self.base
+            .fill_boxes2(slice::from_ref(&rd.underline), &c, x, y);
+        let c = theme.colors.unfocused_title_background.get();
+        self.base.fill_boxes2(&rd.inactive_workspaces, &c, x, y);
+        let c = theme.colors.captured_unfocused_title_background.get();
+        self.base
+            .fill_boxes2(&rd.captured_inactive_workspaces, &c, x, y);
+        let c = theme.colors.attention_requested_background.get();
+        self.base
+            .fill_boxes2(&rd.attention_requested_workspaces, &c, x, y);
+        let scale = output.global.persistent.scale.get();
+        for title in &rd.titles {
+            let (x, y) = self.base.scale_point(x + title.tex_x, y + title.tex_y);
+            self.base.render_texture(
+                &title.tex,
+                None,
+                x,
+                y,
+                None,
+                None,
+                scale,
+                None,
+                None,
+                AcquireSync::None,
+                ReleaseSync::None,
+            );
+        }
+        if let Some(status) = &rd.status {
+            if let Some(texture) = status.tex.texture() {
+                let (x, y) = self.base.scale_point(x + status.tex_x, y);
+                self.base.render_texture(
+                    &texture,
+                    None,
+                    x,
+                    y,
+                    None,
+                    None,
+                    scale,
+                    None,
+                    None,
+                    AcquireSync::None,
+                    ReleaseSync::None,
+                );
+            }
+        }
+        for block in &rd.status_blocks {
+            if let Some(c) = &block.background {
+                self.base.fill_boxes2(slice::from_ref(&block.rect), c, x, y);
+            }
+            self.base
+                .fill_boxes2(&block.borders, &block.border_color, x, y);
+            if let Some(texture) = block.tex.texture() {
+                let (x, y) = self.base.scale_point(x + block.tex_x, y);
+                self.base.render_texture(
+                    &texture,
+                    None,
+                    x,
+                    y,
+                    None,
+                    None,
+                    scale,
+                    None,
+                    None,
+                    AcquireSync::None,
+                    ReleaseSync::None,
+                );
+            }
+        }
+        if let Some(sep) = &rd.status_separator {
+            if let Some(texture) = sep.tex.texture() {
+                for tex_x in &sep.tex_x {
+                    let (x, y) = self.base.scale_point(x + tex_x, y);
+                    self.base.render_texture(
+                        &texture,
+                        None,
+                        x,
+                        y,
+                        None,
+                        None,
+                        scale,
+                        None,
+                        None,
+                        AcquireSync::None,
+                        ReleaseSync::None,
+                    );
+                }
+            }
+        }
+        for item in output.tray_items.iter() {
+            let data = item.data();
+            if data.surface.buffer.is_some() {
+                let rect = data.rel_pos.get().move_(x, y);
+                let bounds = self.base.scale_rect(rect);
+                self.render_surface(&data.surface, rect.x1(), rect.y1(), Some(&bounds));
+            }
+        }
+    }
+
+    /// Returns the state of the workspace switch animation of the output.
+    fn workspace_slide(&self, output: &OutputNode) -> Option<WorkspaceSlide> {
+        let now = self.state.now_nsec();
+        let wa = output.workspace_animation.borrow();
+        let wa = wa.as_ref()?;
+        let progress = wa.animation.progress(now)?;
+        let width = output.workspace.get()?.position.get().width();
+        let offset = wa.direction * ((1.0 - progress) * width as f64).round() as i32;
+        Some(WorkspaceSlide {
+            old: wa.old.clone(),
+            old_offset: offset - wa.direction * width,
+            offset,
+        })
+    }
+
+    fn render_workspace_switch(
+        &mut self,
+        ws: &WorkspaceNode,
+        slide: Option<&WorkspaceSlide>,
+        x: i32,
+        y: i32,
+    ) {
+        let Some(slide) = slide else {
+            self.render_workspace(ws, x, y);
+            return;
+        };
+        let old = &slide.old;
+        let old_x = x + slide.old_offset;
+        self.render_workspace(old, old_x, y);
+        let old_pos = old.position.get();
+        for stacked in old.stacked.iter() {
+            if let Some(float) = stacked.deref().clone().node_into_float() {
+                let pos = float.position.get();
+                let (fx, fy) = old_pos.translate(pos.x1(), pos.y1());
+                self.render_floating(&float, old_x + fx, y + fy);
+            }
+        }
+        self.render_workspace(ws, x + slide.offset, y);
+    }
+
+    fn render_closing_windows(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let now = self.state.now_nsec();
+        let opos = output.global.pos.get();
+        for window in output.closing_windows.borrow().iter() {
+            let Some(progress) = window.animation.progress(now) else {
+                continue;
+            };
+            let rect = window.rect.move_(x - opos.x1(), y - opos.y1());
+            let bounds = window.bounds.move_(x - opos.x1(), y - opos.y1());
+            let (x1, y1) = self.base.scale_point(rect.x1(), rect.y1());
+            let (x2, y2) = self.base.scale_point(rect.x2(), rect.y2());
+            let bounds = self.base.scale_rect(bounds);
+            self.base.ops.push(GfxApiOpt::Sync);
+            self.base.render_texture(
+                &window.texture,
+                Some(1.0 - progress.clamp(0.0, 1.0) as f32),
+                x1,
+                y1,
+                Some(window.tpoints),
+                Some((x2 - x1, y2 - y1)),
+                self.base.scale,
+                Some(&bounds),
+                Some(window.buffer.clone()),
+                AcquireSync::Unnecessary,
+                window.buffer.release_sync,
+            );
+        }
+    }
+
+    /// Renders a toplevel with the running animations applied to its position and opacity.
+    fn render_animated(
+        &mut self,
+        tl_data: &ToplevelData,
+        x: i32,
+        y: i32,
+        body: Rect,
+        f: impl FnOnce(&mut Self, i32, i32, Rect),
+    ) {
+        let Some(state) = tl_data.animated_state(self.state.now_nsec()) else {
+            f(self, x, y, body);
+            return;
+        };
+        let body = Rect::new_sized(
+            body.x1() + state.dx,
+            body.y1() + state.dy,
+            body.width() + state.dwidth,
+            body.height() + state.dheight,
+        )
+        .unwrap_or(body);
+        let prev_alpha = self.base.alpha;
+        if let Some(alpha) = state.alpha {
+            self.base.alpha = Some(alpha * prev_alpha.unwrap_or(1.0));
+        }
+        f(self, x + state.dx, y + state.dy, body);
+        self.base.alpha = prev_alpha;
     }
 
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
         if let Some(node) = workspace.container.get() {
             self.render_container(&node, x, y)
         }
+        if let Some(node) = workspace.scrolling.get() {
+            self.render_scrolling(&node, x, y)
+        }
+    }
+
+    pub fn render_scrolling(&mut self, scrolling: &ScrollingNode, x: i32, y: i32) {
+        let pos = scrolling.position.get();
+        let width = pos.width();
+        let scroll = scrolling.scroll.get();
+        // Columns that are partially visible must not be drawn outside of the workspace.
+        let prev_scissor = self.base.scissor;
+        let scissor = self.base.scale_rect(pos.at_point(x, y));
+        self.base.scissor = Some(match prev_scissor {
+            Some(prev) => prev.intersect(scissor),
+            None => scissor,
+        });
+        for column in scrolling.columns.iter() {
+            let body = column.body.get().move_(-scroll, 0);
+            if body.x2() <= 0 || body.x1() >= width {
+                continue;
+            }
+            self.render_container(&column.node, x + body.x1(), y + body.y1());
+        }
+        self.base.scissor = prev_scissor;
     }
 
     pub fn render_placeholder(
@@ -240,8 +548,9 @@ impl Renderer<'_> {
             self.base.fill_boxes2(&rd.attention_title_rects, &c, x, y);
             let c = self.state.theme.colors.separator.get();
             self.base.fill_boxes2(&rd.underline_rects, &c, x, y);
-            let c = self.state.theme.colors.border.get();
-            self.base.fill_boxes2(&rd.border_rects, &c, x, y);
+            for (rect, c) in &rd.border_rects {
+                self.base.fill_boxes2(slice::from_ref(rect), c, x, y);
+            }
             if let Some(lar) = &rd.last_active_rect {
                 let c = self
                     .state
@@ -272,11 +581,14 @@ impl Renderer<'_> {
         }
         if let Some(child) = container.mono_child.get() {
             let body = container.mono_body.get().move_(x, y);
-            let body = self.base.scale_rect(body);
             let content = container.mono_content.get();
-            child
-                .node
-                .node_render(self, x + content.x1(), y + content.y1(), Some(&body));
+            let (cx, cy) = (x + content.x1(), y + content.y1());
+            self.render_animated(child.node.tl_data(), cx, cy, body, |r, x, y, body| {
+                let body = r.base.scale_rect(body);
+                r.render_tiled_child(&*child.node, body, |r| {
+                    child.node.node_render(r, x, y, Some(&body));
+                });
+            });
         } else {
             for child in container.children.iter() {
                 let body = child.body.get();
@@ -284,26 +596,87 @@ impl Renderer<'_> {
                     break;
                 }
                 let body = body.move_(x, y);
-                let body = self.base.scale_rect(body);
                 let content = child.content.get();
-                child
-                    .node
-                    .node_render(self, x + content.x1(), y + content.y1(), Some(&body));
+                let (cx, cy) = (x + content.x1(), y + content.y1());
+                self.render_animated(child.node.tl_data(), cx, cy, body, |r, x, y, body| {
+                    let body = r.base.scale_rect(body);
+                    r.render_tiled_child(&*child.node, body, |r| {
+                        child.node.node_render(r, x, y, Some(&body));
+                    });
+                });
             }
         }
         self.render_tl_aux(container.tl_data(), None, false);
     }
 
+    /// Renders a tiled window with its contents clipped to the rounded corners of its body.
+    ///
+    /// `body` must be in framebuffer pixels. Nested containers are not clipped since their
+    /// children are clipped individually.
+    fn render_tiled_child(
+        &mut self,
+        node: &dyn ToplevelNode,
+        body: Rect,
+        f: impl FnOnce(&mut Self),
+    ) {
+        let radius = self.state.theme.sizes.corner_radius.get();
+        let prev_clip = self.base.clip;
+        if radius > 0 && prev_clip.is_none() && !node.node_is_container() {
+            self.base.clip = Some(self.rounded_rect(body, radius));
+        }
+        f(self);
+        self.base.clip = prev_clip;
+    }
+
+    fn rounded_rect(&self, rect: Rect, radius: i32) -> RoundedRect {
+        let (radius, _) = self.base.scale_point(radius, radius);
+        RoundedRect { rect, radius }
+    }
+
     pub fn render_xwindow(&mut self, tl: &Xwindow, x: i32, y: i32, bounds: Option<&Rect>) {
-        self.render_surface(&tl.x.surface, x, y, bounds);
+        self.render_tl_contents(tl.tl_data(), bounds, |r| {
+            r.render_surface(&tl.x.surface, x, y, bounds)
+        });
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
     pub fn render_xdg_toplevel(&mut self, tl: &XdgToplevel, x: i32, y: i32, bounds: Option<&Rect>) {
-        self.render_xdg_surface(&tl.xdg, x, y, bounds);
+        self.render_tl_contents(tl.tl_data(), bounds, |r| {
+            r.render_xdg_surface(&tl.xdg, x, y, bounds)
+        });
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
+    fn render_tl_contents(
+        &mut self,
+        tl_data: &ToplevelData,
+        bounds: Option<&Rect>,
+        f: impl FnOnce(&mut Self),
+    ) {
+        self.render_tl_blur(tl_data, bounds);
+        let prev_alpha = self.base.alpha;
+        if let Some(opacity) = self.tl_opacity(tl_data) {
+            self.base.alpha = Some(opacity * prev_alpha.unwrap_or(1.0));
+        }
+        f(self);
+        self.base.alpha = prev_alpha;
+        if let Some(bounds) = bounds {
+            let color = self.state.theme.colors.inactive_dim.get();
+            if color != Color::TRANSPARENT && !tl_data.active() {
+                self.base.ops.push(GfxApiOpt::Sync);
+                self.base.fill_scaled_boxes(slice::from_ref(bounds), &color);
+            }
+        }
+    }
+
+    fn tl_opacity(&self, tl_data: &ToplevelData) -> Option<f32> {
+        if let Some(opacity) = tl_data.opacity.get() {
+            return Some(opacity);
+        }
+        let app_id = tl_data.app_id.borrow();
+        self.state.window_opacities.borrow().get(&*app_id).copied()
+    }
+
     pub fn render_xdg_surface(
         &mut self,
         xdg: &XdgSurface,
@@ -318,6 +691,25 @@ impl Renderer<'_> {
         self.render_surface(surface, x, y, bounds);
     }
 
+    fn render_tl_blur(&mut self, tl_data: &ToplevelData, bounds: Option<&Rect>) {
+        let Some(bounds) = bounds else {
+            return;
+        };
+        let app_id = tl_data.app_id.borrow();
+        if self.state.blur_app_ids.borrow().contains(&*app_id) {
+            self.blur(*bounds);
+            self.blurred_areas.push(tl_data.pos.get());
+        }
+    }
+
+    /// Blurs the contents below a rectangle in framebuffer pixels.
+    fn blur(&mut self, rect: Rect) {
+        let theme = &self.state.theme;
+        let passes = theme.sizes.blur_passes.get();
+        let offset = theme.sizes.blur_offset.get();
+        self.base.blur(rect, passes, offset);
+    }
+
     fn render_tl_aux(
         &mut self,
         tl_data: &ToplevelData,
@@ -381,6 +773,12 @@ impl Renderer<'_> {
             size = self.base.scale_point(size.0, size.1);
         }
         let alpha = surface.alpha();
+        if let Some(region) = surface.blur_region() {
+            self.render_surface_blur(&region, x, y, pos_rel, size, bounds);
+            let pos = surface.buffer_abs_pos.get();
+            self.blurred_areas
+                .push(region.extents().move_(pos.x1(), pos.y1()));
+        }
         if let Some(children) = children.deref() {
             macro_rules! render {
                 ($children:expr) => {
@@ -409,6 +807,33 @@ impl Renderer<'_> {
         }
     }
 
+    fn render_surface_blur(
+        &mut self,
+        region: &Region,
+        x: i32,
+        y: i32,
+        pos_rel: Option<(i32, i32)>,
+        size: (i32, i32),
+        bounds: Option<&Rect>,
+    ) {
+        let Some(surface_rect) = Rect::new_sized(x, y, size.0, size.1) else {
+            return;
+        };
+        let (x_rel, y_rel) = pos_rel.unwrap_or_default();
+        let (dx, dy) = self.base.scale_point(x_rel, y_rel);
+        for rect in region.rects() {
+            let rect = self
+                .base
+                .scale_rect(rect.move_(x_rel, y_rel))
+                .move_(x - dx, y - dy);
+            let mut rect = rect.intersect(surface_rect);
+            if let Some(bounds) = bounds {
+                rect = rect.intersect(*bounds);
+            }
+            self.blur(rect);
+        }
+    }
+
     pub fn render_buffer(
         &mut self,
         surface: &WlSurface,
@@ -460,10 +885,24 @@ impl Renderer<'_> {
             _ => return,
         };
         let pos = floating.position.get();
+        let frame = pos.at_point(x, y);
+        self.render_animated(child.tl_data(), x, y, frame, |r, x, y, frame| {
+            r.render_floating_frame(floating, &child, x, y, frame)
+        });
+    }
+
+    fn render_floating_frame(
+        &mut self,
+        floating: &FloatNode,
+        child: &Rc<dyn ToplevelNode>,
+        x: i32,
+        y: i32,
+        pos: Rect,
+    ) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
-        let bc = theme.colors.border.get();
+        let bw = floating.border_width();
+        let bc = child.tl_data().border_color(floating.active.get());
         let tc = if floating.active.get() {
             theme.colors.focused_title_background.get()
         } else if floating.attention_requested.get() {
@@ -472,6 +911,28 @@ impl Renderer<'_> {
             theme.colors.unfocused_title_background.get()
         };
         let uc = theme.colors.separator.get();
+        let radius = theme.sizes.corner_radius.get();
+        let frame = self
+            .base
+            .scale_rect(Rect::new_sized(x, y, pos.width(), pos.height()).unwrap());
+        let blur = theme.sizes.shadow_blur.get();
+        let (dx, dy) = (
+            theme.sizes.shadow_offset_x.get(),
+            theme.sizes.shadow_offset_y.get(),
+        );
+        if blur > 0 || dx != 0 || dy != 0 {
+            let shadow = self.rounded_rect(frame, radius);
+            let (dx, dy) = self.base.scale_point(dx, dy);
+            let (blur, _) = self.base.scale_point(blur, blur);
+            let sc = theme.colors.shadow.get();
+            self.base
+                .fill_shadow(shadow.rect.move_(dx, dy), shadow.radius, blur, &sc);
+            self.base.ops.push(GfxApiOpt::Sync);
+        }
+        let prev_clip = self.base.clip;
+        if radius > 0 {
+            self.base.clip = Some(self.rounded_rect(frame, radius));
+        }
         let borders = [
             Rect::new_sized(x, y, pos.width(), bw).unwrap(),
             Rect::new_sized(x, y + bw, bw, pos.height() - bw).unwrap(),
@@ -502,6 +963,7 @@ impl Renderer<'_> {
                 );
             }
         }
+        self.render_title_buttons(floating, x, y);
         let body = Rect::new_sized(
             x + bw,
             y + bw + th + 1,
@@ -511,10 +973,87 @@ impl Renderer<'_> {
         .unwrap();
         let scissor_body = self.base.scale_rect(body);
         child.node_render(self, body.x1(), body.y1(), Some(&scissor_body));
+        self.base.clip = prev_clip;
+    }
+
+    fn render_title_buttons(&mut self, floating: &FloatNode, x: i32, y: i32) {
+        let Some(buttons) = floating.title_buttons() else {
+            return;
+        };
+        let theme = &self.state.theme;
+        let ic = match floating.active.get() {
+            true => theme.colors.focused_title_text.get(),
+            false => theme.colors.unfocused_title_text.get(),
+        };
+        let hc = theme.colors.title_button_hover.get();
+        let mut icons = vec![];
+        for (button, rect) in buttons {
+            let rect = rect.move_(x, y);
+            if floating.title_button_hovered(button) {
+                self.base.ops.push(GfxApiOpt::Sync);
+                self.base.fill_boxes(&[rect], &hc);
+            }
+            let size = rect.height() / 2;
+            let x1 = rect.x1() + (rect.width() - size) / 2;
+            let y1 = rect.y1() + (rect.height() - size) / 2;
+            let (x2, y2) = (x1 + size - 1, y1 + size - 1);
+            let outline = [
+                Rect::new_sized(x1, y1, size, 1),
+                Rect::new_sized(x1, y2, size, 1),
+                Rect::new_sized(x1, y1, 1, size),
+                Rect::new_sized(x2, y1, 1, size),
+            ];
+            match button {
+                TitleButton::Tile => {
+                    icons.extend(outline);
+                    icons.push(Rect::new_sized(x1 + size / 2, y1, 1, size));
+                }
+                TitleButton::Fullscreen => icons.extend(outline),
+                TitleButton::Close => {
+                    for i in 0..size {
+                        icons.push(Rect::new_sized(x1 + i, y1 + i, 1, 1));
+                        icons.push(Rect::new_sized(x2 - i, y1 + i, 1, 1));
+                    }
+                }
+            }
+        }
+        let icons: Vec<_> = icons.into_iter().flatten().collect();
+        self.base.ops.push(GfxApiOpt::Sync);
+        self.base.fill_boxes(&icons, &ic);
     }
 
     pub fn render_layer_surface(&mut self, surface: &ZwlrLayerSurfaceV1, x: i32, y: i32) {
-        let (dx, dy) = surface.surface.extents.get().position();
+        let extents = surface.surface.extents.get();
+        let namespaces = self.state.blur_layer_namespaces.borrow();
+        if namespaces.contains(&surface.namespace) {
+            if let Some(rect) = Rect::new_sized(x, y, extents.width(), extents.height()) {
+                self.blur(self.base.scale_rect(rect));
+                let pos = surface.surface.buffer_abs_pos.get();
+                self.blurred_areas.push(extents.move_(pos.x1(), pos.y1()));
+            }
+        }
+        let (dx, dy) = extents.position();
         self.render_surface(&surface.surface, x - dx, y - dy, None);
     }
 }
+
+/// A workspace switch animation that is in progress.
+struct WorkspaceSlide {
+    /// The workspace that slides out of view.
+    old: Rc<WorkspaceNode>,
+    /// The horizontal offset of the workspace that slides out of view.
+    old_offset: i32,
+    /// The horizontal offset of the workspace that slides into view.
+    offset: i32,
+}
+
+/// Returns whether the node is a floating window of `ws` that moves with the workspace
+/// while it slides into view.
+///
+/// Sticky windows are not moved since they are shown on both workspaces.
+fn slides_with_workspace(node: &Rc<dyn StackedNode>, ws: &WorkspaceNode) -> bool {
+    let Some(float) = node.clone().node_into_float() else {
+        return false;
+    };
+    float.workspace.get().id == ws.id && !float.is_sticky()
+}
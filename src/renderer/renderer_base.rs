@@ -1,8 +1,8 @@
 use {
     crate::{
         gfx_api::{
-            AcquireSync, BufferResv, CopyTexture, FillRect, FramebufferRect, GfxApiOpt, GfxTexture,
-            ReleaseSync, SampleRect,
+            AcquireSync, Blur, BufferResv, CopyTexture, FillRect, FramebufferRect, GfxApiOpt,
+            GfxTexture, ReleaseSync, RoundedClip, SampleRect,
         },
         rect::Rect,
         scale::Scale,
@@ -21,6 +21,19 @@ pub struct RendererBase<'a> {
     pub transform: Transform,
     pub fb_width: f32,
     pub fb_height: f32,
+    /// An opacity that is applied to everything that is rendered.
+    pub alpha: Option<f32>,
+    /// A rectangle with rounded corners that everything that is rendered is clipped to.
+    pub clip: Option<RoundedRect>,
+    /// A rectangle in framebuffer pixels that everything that is rendered is cut to.
+    pub scissor: Option<Rect>,
+}
+
+/// A rectangle with rounded corners in framebuffer pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RoundedRect {
+    pub rect: Rect,
+    pub radius: i32,
 }
 
 impl RendererBase<'_> {
@@ -64,6 +77,92 @@ impl RendererBase<'_> {
         rect
     }
 
+    fn apply_alpha(&self, color: &Color) -> Color {
+        match self.alpha {
+            Some(alpha) => *color * alpha,
+            None => *color,
+        }
+    }
+
+    /// Returns the clip of an operation with the given target.
+    ///
+    /// Returns `None` if the target is not affected by the rounded corners.
+    fn rounded_clip(&self, x1: f32, y1: f32, x2: f32, y2: f32) -> Option<RoundedClip> {
+        let clip = self.clip?;
+        let rect = [
+            clip.rect.x1() as f32,
+            clip.rect.y1() as f32,
+            clip.rect.x2() as f32,
+            clip.rect.y2() as f32,
+        ];
+        let [cx1, cy1, cx2, cy2] = rect;
+        let radius = clip.radius as f32;
+        let inside = x1 >= cx1 && y1 >= cy1 && x2 <= cx2 && y2 <= cy2;
+        let avoids_corners = (x1 >= cx1 + radius && x2 <= cx2 - radius)
+            || (y1 >= cy1 + radius && y2 <= cy2 - radius);
+        if inside && avoids_corners {
+            return None;
+        }
+        Some(RoundedClip {
+            target: [x1, y1, x2, y2],
+            rect,
+            radius,
+            blur: 1.0,
+        })
+    }
+
+    /// Cuts the target of an operation to the scissor.
+    ///
+    /// Returns `None` if nothing of the target remains.
+    fn scissor(&self, x1: f32, y1: f32, x2: f32, y2: f32) -> Option<[f32; 4]> {
+        let Some(scissor) = self.scissor else {
+            return Some([x1, y1, x2, y2]);
+        };
+        let x1 = x1.max(scissor.x1() as f32);
+        let y1 = y1.max(scissor.y1() as f32);
+        let x2 = x2.min(scissor.x2() as f32);
+        let y2 = y2.min(scissor.y2() as f32);
+        (x1 < x2 && y1 < y2).then_some([x1, y1, x2, y2])
+    }
+
+    /// Renders the shadow of a rectangle with rounded corners.
+    ///
+    /// `rect` must be in framebuffer pixels.
+    pub fn fill_shadow(&mut self, rect: Rect, radius: i32, blur: i32, color: &Color) {
+        let color = self.apply_alpha(color);
+        if rect.is_empty() || color == Color::TRANSPARENT {
+            return;
+        }
+        let blur = blur.max(1) as f32;
+        let [x1, y1, x2, y2] = [
+            rect.x1() as f32,
+            rect.y1() as f32,
+            rect.x2() as f32,
+            rect.y2() as f32,
+        ];
+        let Some(target) = self.scissor(x1 - blur, y1 - blur, x2 + blur, y2 + blur) else {
+            return;
+        };
+        self.ops.push(GfxApiOpt::FillRect(FillRect {
+            rect: FramebufferRect::new(
+                target[0],
+                target[1],
+                target[2],
+                target[3],
+                self.transform,
+                self.fb_width,
+                self.fb_height,
+            ),
+            color,
+            clip: Some(RoundedClip {
+                target,
+                rect: [x1, y1, x2, y2],
+                radius: radius as f32,
+                blur,
+            }),
+        }));
+    }
+
     pub fn fill_scaled_boxes(&mut self, boxes: &[Rect], color: &Color) {
         self.fill_boxes3(boxes, color, 0, 0, true);
     }
@@ -77,6 +176,7 @@ impl RendererBase<'_> {
     }
 
     fn fill_boxes3(&mut self, boxes: &[Rect], color: &Color, dx: i32, dy: i32, scaled: bool) {
+        let color = &self.apply_alpha(color);
         if boxes.is_empty() || *color == Color::TRANSPARENT {
             return;
         }
@@ -86,17 +186,26 @@ impl RendererBase<'_> {
                 false => self.scale_rect(*bx),
                 true => *bx,
             };
+            let Some([x1, y1, x2, y2]) = self.scissor(
+                (bx.x1() + dx) as f32,
+                (bx.y1() + dy) as f32,
+                (bx.x2() + dx) as f32,
+                (bx.y2() + dy) as f32,
+            ) else {
+                continue;
+            };
             self.ops.push(GfxApiOpt::FillRect(FillRect {
                 rect: FramebufferRect::new(
-                    (bx.x1() + dx) as f32,
-                    (bx.y1() + dy) as f32,
-                    (bx.x2() + dx) as f32,
-                    (bx.y2() + dy) as f32,
+                    x1,
+                    y1,
+                    x2,
+                    y2,
                     self.transform,
                     self.fb_width,
                     self.fb_height,
                 ),
                 color: *color,
+                clip: self.rounded_clip(x1, y1, x2, y2),
             }));
         }
     }
@@ -112,27 +221,63 @@ impl RendererBase<'_> {
         dx: f32,
         dy: f32,
     ) {
+        let color = &self.apply_alpha(color);
         if boxes.is_empty() || *color == Color::TRANSPARENT {
             return;
         }
         let (dx, dy) = self.scale_point_f(dx, dy);
         for bx in boxes {
             let (x1, y1, x2, y2) = self.scale_rect_f(*bx);
+            let Some([x1, y1, x2, y2]) = self.scissor(x1 + dx, y1 + dy, x2 + dx, y2 + dy) else {
+                continue;
+            };
             self.ops.push(GfxApiOpt::FillRect(FillRect {
                 rect: FramebufferRect::new(
-                    x1 + dx,
-                    y1 + dy,
-                    x2 + dx,
-                    y2 + dy,
+                    x1,
+                    y1,
+                    x2,
+                    y2,
                     self.transform,
                     self.fb_width,
                     self.fb_height,
                 ),
                 color: *color,
+                clip: self.rounded_clip(x1, y1, x2, y2),
             }));
         }
     }
 
+    /// Blurs the contents of a rectangle that have been rendered so far.
+    ///
+    /// `rect` must be in framebuffer pixels.
+    pub fn blur(&mut self, rect: Rect, passes: i32, offset: i32) {
+        if rect.is_empty() || passes <= 0 {
+            return;
+        }
+        let Some([x1, y1, x2, y2]) = self.scissor(
+            rect.x1() as f32,
+            rect.y1() as f32,
+            rect.x2() as f32,
+            rect.y2() as f32,
+        ) else {
+            return;
+        };
+        self.ops.push(GfxApiOpt::Blur(Blur {
+            rect: FramebufferRect::new(
+                x1,
+                y1,
+                x2,
+                y2,
+                self.transform,
+                self.fb_width,
+                self.fb_height,
+            ),
+            clip: self.rounded_clip(x1, y1, x2, y2),
+            passes: passes as u32,
+            offset: offset as f32,
+        }));
+    }
+
     pub fn render_texture(
         &mut self,
         texture: &Rc<dyn GfxTexture>,
@@ -147,6 +292,10 @@ impl RendererBase<'_> {
         acquire_sync: AcquireSync,
         release_sync: ReleaseSync,
     ) {
+        let alpha = match (alpha, self.alpha) {
+            (Some(a), Some(b)) => Some(a * b),
+            (a, b) => a.or(b),
+        };
         let mut texcoord = tpoints.unwrap_or_else(SampleRect::identity);
 
         let (twidth, theight) = if let Some(size) = tsize {
@@ -164,17 +313,23 @@ impl RendererBase<'_> {
         let mut target_x = [x, x + twidth];
         let mut target_y = [y, y + theight];
 
-        if let Some(bounds) = bounds {
+        let bounds = match (bounds.copied(), self.scissor) {
+            (Some(bounds), Some(scissor)) => Some(bounds.intersect(scissor)),
+            (bounds, scissor) => bounds.or(scissor),
+        };
+        if let Some(bounds) = &bounds {
             if bound_target(&mut target_x, &mut target_y, &mut texcoord, bounds) {
                 return;
             }
         }
 
+        let [x1, x2] = target_x.map(|x| x as f32);
+        let [y1, y2] = target_y.map(|y| y as f32);
         let target = FramebufferRect::new(
-            target_x[0] as f32,
-            target_y[0] as f32,
-            target_x[1] as f32,
-            target_y[1] as f32,
+            x1,
+            y1,
+            x2,
+            y2,
             self.transform,
             self.fb_width,
             self.fb_height,
@@ -188,6 +343,7 @@ impl RendererBase<'_> {
             buffer_resv,
             acquire_sync,
             release_sync,
+            clip: self.rounded_clip(x1, y1, x2, y2),
         }));
     }
 }
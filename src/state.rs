@@ -1,6 +1,7 @@
 use {
     crate::{
         acceptor::Acceptor,
+        animation::Animations,
         async_engine::{AsyncEngine, SpawnedFuture},
         backend::{
             Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorId, ConnectorIds,
@@ -16,7 +17,7 @@ use {
         cpu_worker::CpuWorker,
         cursor::{Cursor, ServerCursors},
         cursor_user::{CursorUserGroup, CursorUserGroupId, CursorUserGroupIds, CursorUserIds},
-        damage::DamageVisualizer,
+        damage::{expand_damage, DamageVisualizer},
         dbus::Dbus,
         drm_feedback::{DrmFeedback, DrmFeedbackIds},
         ei::{
@@ -33,12 +34,15 @@ use {
         gfx_apis::create_gfx_context,
         globals::{Globals, GlobalsError, RemovableWaylandGlobal, WaylandGlobal},
         ifs::{
+            ext_background_effect_manager_v1::ExtBackgroundEffectManagerV1,
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
             ext_session_lock_v1::ExtSessionLockV1,
             ipc::{
                 data_control::DataControlDeviceIds, x_data_device::XIpcDeviceIds, DataOfferIds,
                 DataSourceIds,
             },
+            jay_global_shortcuts::JayGlobalShortcuts,
+            jay_input_capture::JayInputCapture,
             jay_render_ctx::JayRenderCtx,
             jay_screencast::JayScreencast,
             jay_seat_events::JaySeatEvents,
@@ -46,6 +50,7 @@ use {
             wl_drm::WlDrmGlobal,
             wl_output::{OutputGlobalOpt, OutputId, PersistentOutputState},
             wl_seat::{
+                collect_kb_foci,
                 tablet::{TabletIds, TabletInit, TabletPadIds, TabletPadInit, TabletToolIds},
                 SeatIds, WlSeatGlobal,
             },
@@ -73,16 +78,18 @@ use {
         theme::{Color, Theme},
         time::Time,
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, LatchListener, Node,
-            NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode, TearingMode, ToplevelNode,
-            ToplevelNodeBase, VrrMode, WorkspaceNode,
+            BarMode, BarPosition, ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode,
+            LatchListener, Node, NodeId, NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode,
+            ScrollingNode, StatusBlocks, TearingMode, ToplevelNode, ToplevelNodeBase, VrrMode,
+            WindowBorder, WorkspaceLayout, WorkspaceNode,
         },
         utils::{
             activation_token::ActivationToken, asyncevent::AsyncEvent, bindings::Bindings,
             clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt,
             event_listener::EventSource, fdcloser::FdCloser, hash_map_ext::HashMapExt,
-            linkedlist::LinkedList, numcell::NumCell, queue::AsyncQueue, refcounted::RefCounted,
-            run_toplevel::RunToplevel, toplevel_identifier::ToplevelIdentifier,
+            linkedlist::LinkedList, numcell::NumCell, pid_info::get_parent_pid, queue::AsyncQueue,
+            refcounted::RefCounted, run_toplevel::RunToplevel,
+            toplevel_identifier::ToplevelIdentifier,
         },
         video::{
             dmabuf::DmaBufIds,
@@ -94,7 +101,8 @@ use {
         },
         wheel::Wheel,
         wire::{
-            ExtForeignToplevelListV1Id, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
+            ExtBackgroundEffectManagerV1Id, ExtForeignToplevelListV1Id, JayGlobalShortcutsId,
+            JayInputCaptureId, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
             ZwpLinuxDmabufFeedbackV1Id,
         },
         xkbcommon::{KeyboardStateIds, XkbContext, XkbKeymap, XkbState},
@@ -103,6 +111,7 @@ use {
     ahash::{AHashMap, AHashSet},
     bstr::ByteSlice,
     jay_config::{
+        keyboard::mods::Modifiers,
         video::{GfxApi, Transform},
         PciId,
     },
@@ -152,6 +161,8 @@ pub struct State {
     pub config: CloneCell<Option<Rc<ConfigProxy>>>,
     pub theme: Theme,
     pub pending_container_layout: AsyncQueue<Rc<ContainerNode>>,
+    pub pending_workspace_layout: AsyncQueue<Rc<WorkspaceNode>>,
+    pub pending_scrolling_layout: AsyncQueue<Rc<ScrollingNode>>,
     pub pending_container_render_positions: AsyncQueue<Rc<ContainerNode>>,
     pub pending_container_render_title: AsyncQueue<Rc<ContainerNode>>,
     pub pending_output_render_data: AsyncQueue<Rc<OutputNode>>,
@@ -168,6 +179,7 @@ pub struct State {
     pub outputs: CopyHashMap<ConnectorId, Rc<OutputData>>,
     pub drm_devs: CopyHashMap<DrmDeviceId, Rc<DrmDevData>>,
     pub status: CloneCell<Rc<String>>,
+    pub status_blocks: CloneCell<Option<Rc<StatusBlocks>>>,
     pub idle: IdleState,
     pub run_args: RunArgs,
     pub xwayland: XWaylandState,
@@ -187,11 +199,35 @@ pub struct State {
     pub testers: RefCell<AHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
+    pub global_shortcuts: CopyHashMap<(ClientId, JayGlobalShortcutsId), Rc<JayGlobalShortcuts>>,
+    pub input_captures: CopyHashMap<(ClientId, JayInputCaptureId), Rc<JayInputCapture>>,
     pub default_workspace_capture: Cell<bool>,
+    pub default_workspace_layout: Cell<WorkspaceLayout>,
+    pub column_width_presets: RefCell<Vec<f64>>,
+    pub show_marks: Cell<bool>,
+    pub title_format: RefCell<Option<String>>,
+    pub hidden_title_app_ids: RefCell<Vec<String>>,
+    pub sticky_app_ids: RefCell<Vec<String>>,
+    pub show_title_buttons: Cell<bool>,
+    pub swallow_terminals: RefCell<Vec<String>>,
+    pub swallow_exceptions: RefCell<Vec<String>>,
+    pub blur_app_ids: RefCell<Vec<String>>,
+    pub blur_layer_namespaces: RefCell<Vec<String>>,
+    pub window_opacities: RefCell<AHashMap<String, f32>>,
+    pub window_borders: RefCell<AHashMap<String, WindowBorder>>,
+    pub bar_position: Cell<BarPosition>,
+    pub bar_mode: Cell<BarMode>,
+    pub bar_modifiers: Cell<Modifiers>,
+    pub bar_connectors: RefCell<Option<Vec<String>>>,
+    pub bar_revealed: Cell<bool>,
+    pub layout_placeholders: CopyHashMap<NodeId, Rc<PlaceholderNode>>,
+    pub animations: Animations,
     pub default_gfx_api: Cell<GfxApi>,
     pub activation_tokens: CopyHashMap<ActivationToken, ()>,
     pub toplevel_lists:
         CopyHashMap<(ClientId, ExtForeignToplevelListV1Id), Rc<ExtForeignToplevelListV1>>,
+    pub background_effect_managers:
+        CopyHashMap<(ClientId, ExtBackgroundEffectManagerV1Id), Rc<ExtBackgroundEffectManagerV1>>,
     pub dma_buf_ids: DmaBufIds,
     pub drm_feedback_ids: DrmFeedbackIds,
     pub direct_scanout_enabled: Cell<bool>,
@@ -225,6 +261,7 @@ pub struct State {
     pub ui_drag_enabled: Cell<bool>,
     pub ui_drag_threshold_squared: Cell<i32>,
     pub toplevels: CopyHashMap<ToplevelIdentifier, Weak<dyn ToplevelNode>>,
+    pub minimized: LinkedList<Weak<dyn ToplevelNode>>,
     pub const_40hz_latch: EventSource<dyn LatchListener>,
     pub tray_item_ids: TrayItemIds,
     pub data_control_device_ids: DataControlDeviceIds,
@@ -480,6 +517,8 @@ impl State {
             }
         }
 
+        self.update_blur_capabilities();
+
         {
             struct Walker;
             impl NodeVisitorBase for Walker {
@@ -497,7 +536,11 @@ impl State {
                 fn visit_output(&mut self, node: &Rc<OutputNode>) {
                     node.render_data.borrow_mut().titles.clear();
                     node.render_data.borrow_mut().status.take();
+                    node.render_data.borrow_mut().status_blocks.clear();
+                    node.render_data.borrow_mut().status_separator.take();
                     node.hardware_cursor.set(None);
+                    node.wallpaper.take();
+                    node.load_wallpaper();
                     node.node_visit_children(self);
                 }
                 fn visit_float(&mut self, node: &Rc<FloatNode>) {
@@ -612,7 +655,9 @@ impl State {
 
     pub fn map_tiled(self: &Rc<Self>, node: Rc<dyn ToplevelNode>) {
         let seat = self.seat_queue.last();
-        self.do_map_tiled(seat.as_deref(), node.clone());
+        if !self.fill_layout_placeholder(&node) && !self.swallow(&node) {
+            self.do_map_tiled(seat.as_deref(), node.clone());
+        }
         if node.node_visible() {
             if let Some(seat) = seat {
                 node.node_do_focus(&seat, Direction::Unspecified);
@@ -620,7 +665,95 @@ impl State {
         }
     }
 
-    fn do_map_tiled(self: &Rc<Self>, seat: Option<&Rc<WlSeatGlobal>>, node: Rc<dyn ToplevelNode>) {
+    /// Replaces the oldest placeholder created from a layout file that matches `node`
+    /// with `node`.
+    fn fill_layout_placeholder(&self, node: &Rc<dyn ToplevelNode>) -> bool {
+        let placeholder = self
+            .layout_placeholders
+            .lock()
+            .values()
+            .filter(|p| p.criteria.as_ref().is_some_and(|c| c.matches(&**node)))
+            .min_by_key(|p| p.node_id().raw())
+            .cloned();
+        let Some(placeholder) = placeholder else {
+            return false;
+        };
+        let Some(parent) = placeholder.tl_data().parent.take() else {
+            return false;
+        };
+        parent.cnode_replace_child(placeholder.tl_as_node(), node.clone());
+        placeholder.tl_destroy();
+        true
+    }
+
+    /// Replaces the terminal that `node` was launched from with `node`.
+    fn swallow(&self, node: &Rc<dyn ToplevelNode>) -> bool {
+        let Some(terminal) = self.find_swallower(node) else {
+            return false;
+        };
+        let data = terminal.tl_data();
+        let Some(parent) = data.parent.take() else {
+            return false;
+        };
+        parent.cnode_replace_child(terminal.tl_as_node(), node.clone());
+        terminal.tl_set_visible(false);
+        data.swallowed_by.set(Some(node.tl_data().slf.clone()));
+        node.tl_data().swallowed.set(Some(terminal));
+        true
+    }
+
+    fn find_swallower(&self, node: &Rc<dyn ToplevelNode>) -> Option<Rc<dyn ToplevelNode>> {
+        const MAX_DEPTH: usize = 32;
+        let terminals = self.swallow_terminals.borrow();
+        if terminals.is_empty() {
+            return None;
+        }
+        let data = node.tl_data();
+        if self
+            .swallow_exceptions
+            .borrow()
+            .contains(&*data.app_id.borrow())
+        {
+            return None;
+        }
+        let mut pid = data.client.as_ref()?.pid_info.pid;
+        for _ in 0..MAX_DEPTH {
+            pid = get_parent_pid(pid)?;
+            if pid <= 1 {
+                return None;
+            }
+            for tl in self.toplevels.lock().values() {
+                let Some(tl) = tl.upgrade() else {
+                    continue;
+                };
+                let tl_data = tl.tl_data();
+                if tl_data.client.as_ref().map(|c| c.pid_info.pid) != Some(pid)
+                    || tl.tl_as_node().node_id() == node.tl_as_node().node_id()
+                    || !terminals.contains(&*tl_data.app_id.borrow())
+                    || tl_data.swallowed.is_some()
+                    || tl_data.swallowed_by.is_some()
+                    || tl_data.is_fullscreen.get()
+                {
+                    continue;
+                }
+                let in_container = tl_data
+                    .parent
+                    .get()
+                    .and_then(|p| p.node_into_container())
+                    .is_some();
+                if in_container {
+                    return Some(tl);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn do_map_tiled(
+        self: &Rc<Self>,
+        seat: Option<&Rc<WlSeatGlobal>>,
+        node: Rc<dyn ToplevelNode>,
+    ) {
         let output = seat
             .map(|s| s.get_output())
             .or_else(|| self.root.outputs.lock().values().next().cloned())
@@ -643,6 +776,12 @@ impl State {
             } else {
                 c.append_child(node);
             }
+        } else if let Some(s) = ws.scrolling.get() {
+            s.add_window(node);
+        } else if ws.layout.get() == WorkspaceLayout::Scrolling {
+            let scrolling = ScrollingNode::new(self, ws);
+            ws.set_scrolling(&scrolling);
+            scrolling.add_window(node);
         } else {
             let container = ContainerNode::new(self, ws, node, ContainerSplit::Horizontal);
             ws.set_container(&container);
@@ -657,8 +796,9 @@ impl State {
         workspace: &Rc<WorkspaceNode>,
         abs_pos: Option<(i32, i32)>,
     ) {
-        width += 2 * self.theme.sizes.border_width.get();
-        height += 2 * self.theme.sizes.border_width.get() + self.theme.sizes.title_height.get() + 1;
+        let bw = node.tl_data().border_width();
+        width += 2 * bw;
+        height += 2 * bw + self.theme.sizes.title_height.get() + 1;
         let output = workspace.output.get();
         let output_rect = output.global.pos.get();
         let position = if let Some((mut x1, mut y1)) = abs_pos {
@@ -668,8 +808,8 @@ impl State {
             if y1 > output_rect.y2() {
                 y1 = output_rect.y2();
             }
-            y1 -= self.theme.sizes.border_width.get() + self.theme.sizes.title_height.get() + 1;
-            x1 -= self.theme.sizes.border_width.get();
+            y1 -= bw + self.theme.sizes.title_height.get() + 1;
+            x1 -= bw;
             Rect::new_sized(x1, y1, width, height).unwrap()
         } else {
             let mut x1 = output_rect.x1();
@@ -694,6 +834,75 @@ impl State {
         }
     }
 
+    /// Removes the window from the tree without destroying it.
+    ///
+    /// The window can be restored with [`Self::unminimize`].
+    pub fn minimize(&self, tl: Rc<dyn ToplevelNode>) {
+        if tl.tl_admits_children() || tl.node_is_placeholder() {
+            return;
+        }
+        let data = tl.tl_data();
+        if data.minimized.borrow().is_some() {
+            return;
+        }
+        if data.is_fullscreen.get() {
+            tl.clone().tl_set_fullscreen(false);
+        }
+        let Some(parent) = data.parent.take() else {
+            return;
+        };
+        let ws = data.workspace.get();
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        parent.cnode_remove_child2(tl.tl_as_node(), true);
+        tl.tl_set_visible(false);
+        if let Some(ws) = ws {
+            for seat in kb_foci {
+                ws.clone().node_do_focus(&seat, Direction::Unspecified);
+            }
+        }
+        *data.minimized.borrow_mut() = Some(self.minimized.add_last(data.slf.clone()));
+        self.tree_changed();
+    }
+
+    /// Restores a minimized window and focuses it.
+    ///
+    /// The window is restored on the workspace it was minimized from. If that workspace
+    /// no longer exists, it is restored on the workspace of the seat.
+    pub fn unminimize(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>, tl: Rc<dyn ToplevelNode>) {
+        let data = tl.tl_data();
+        if data.minimized.borrow_mut().take().is_none() {
+            return;
+        }
+        let ws = data
+            .workspace
+            .get()
+            .filter(|ws| match self.workspaces.get(&ws.name) {
+                Some(current) => current.id == ws.id,
+                _ => false,
+            })
+            .unwrap_or_else(|| seat.get_output().ensure_workspace());
+        if data.is_floating.get() {
+            let (width, height) = data.float_size(&ws);
+            self.map_floating(tl.clone(), width, height, &ws, None);
+        } else {
+            self.map_tiled_on(tl.clone(), &ws);
+        }
+        if tl.node_visible() {
+            seat.focus_toplevel(tl);
+        }
+    }
+
+    pub fn find_mark(&self, mark: &str) -> Option<Rc<dyn ToplevelNode>> {
+        for tl in self.toplevels.lock().values() {
+            if let Some(tl) = tl.upgrade() {
+                if tl.tl_data().marks.borrow().iter().any(|m| m == mark) {
+                    return Some(tl);
+                }
+            }
+        }
+        None
+    }
+
     pub fn show_workspace(&self, seat: &Rc<WlSeatGlobal>, name: &str) {
         let (output, ws) = match self.workspaces.get(name) {
             Some(ws) => {
@@ -741,12 +950,23 @@ impl State {
     pub fn set_status(&self, status: &str) {
         let status = Rc::new(status.to_owned());
         self.status.set(status.clone());
+        self.status_blocks.take();
         let outputs = self.root.outputs.lock();
         for output in outputs.values() {
             output.set_status(&status);
         }
     }
 
+    pub fn set_status_blocks(&self, blocks: StatusBlocks) {
+        let blocks = Rc::new(blocks);
+        self.status.set(Default::default());
+        self.status_blocks.set(Some(blocks.clone()));
+        let outputs = self.root.outputs.lock();
+        for output in outputs.values() {
+            output.set_status_blocks(&blocks);
+        }
+    }
+
     pub fn input_occurred(&self) {
         if !self.idle.input.replace(true) {
             self.idle.change.trigger();
@@ -794,6 +1014,11 @@ impl State {
         if rect.is_empty() {
             return;
         }
+        // The cursor is rendered on top of everything else and never blurred.
+        let rect = match cursor {
+            true => rect,
+            false => expand_damage(self, rect),
+        };
         self.damage_visualizer.add(rect);
         for output in self.root.outputs.lock().values() {
             if output.global.pos.get().intersects(&rect) {
@@ -806,6 +1031,34 @@ impl State {
         }
     }
 
+    pub fn bar_changed(&self) {
+        for output in self.root.outputs.lock().values() {
+            output.update_exclusive_zones();
+        }
+        self.update_bar_revealed();
+    }
+
+    pub fn update_bar_revealed(&self) {
+        let revealed = self.bar_mode.get() == BarMode::OnModifier
+            && self
+                .globals
+                .seats
+                .lock()
+                .values()
+                .any(|seat| seat.bar_modifiers_held());
+        if self.bar_revealed.replace(revealed) == revealed {
+            return;
+        }
+        for output in self.root.outputs.lock().values() {
+            if output.bar_mode.get() == BarMode::OnModifier {
+                output.update_visible();
+                if output.title_visible.get() {
+                    self.damage(output.with_bar_separator(output.bar_rect.get()));
+                }
+            }
+        }
+    }
+
     pub fn do_unlock(&self) {
         self.lock.locked.set(false);
         self.lock.lock.take();
@@ -849,6 +1102,8 @@ impl State {
         }
         self.dbus.clear();
         self.pending_container_layout.clear();
+        self.pending_workspace_layout.clear();
+        self.pending_scrolling_layout.clear();
         self.pending_container_render_positions.clear();
         self.pending_container_render_title.clear();
         self.pending_output_render_data.clear();
@@ -858,9 +1113,13 @@ impl State {
         self.pending_toplevel_screencasts.clear();
         self.pending_screencast_reallocs_or_reconfigures.clear();
         self.pending_placeholder_render_textures.clear();
+        self.layout_placeholders.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
+        self.global_shortcuts.clear();
+        self.input_captures.clear();
         self.toplevel_lists.clear();
+        self.background_effect_managers.clear();
         self.security_context_acceptors.clear();
         self.slow_clients.clear();
         for h in self.input_device_handlers.borrow_mut().drain_values() {
@@ -922,6 +1181,18 @@ impl State {
         g.present_hardware_cursor(output, hc);
     }
 
+    pub fn update_blur_capabilities(&self) {
+        for manager in self.background_effect_managers.lock().values() {
+            manager.send_capabilities();
+        }
+    }
+
+    pub fn for_each_global_shortcuts<F: FnMut(&JayGlobalShortcuts)>(&self, mut f: F) {
+        for gs in self.global_shortcuts.lock().values() {
+            f(gs);
+        }
+    }
+
     pub fn for_each_seat_tester<F: Fn(&JaySeatEvents)>(&self, f: F) {
         let testers = self.testers.borrow_mut();
         for tester in testers.values() {
@@ -988,6 +1259,7 @@ impl State {
                 let (width, height) = target.logical_size(target_transform);
                 Rect::new_sized(0, 0, width, height).unwrap()
             },
+            blurred_areas: Default::default(),
         };
         let mut sample_rect = SampleRect::identity();
         sample_rect.buffer_transform = transform;
@@ -1172,6 +1444,9 @@ impl State {
         for seat in self.globals.seats.lock().values() {
             seat.output_extents_changed();
         }
+        for capture in self.input_captures.lock().values() {
+            capture.zones_changed();
+        }
     }
 
     pub fn update_ei_acceptor(self: &Rc<Self>) {
@@ -121,6 +121,7 @@ impl ConnectorHandler {
                     vrr_mode: Cell::new(self.state.default_vrr_mode.get()),
                     vrr_cursor_hz: Cell::new(self.state.default_vrr_cursor_hz.get()),
                     tearing_mode: Cell::new(self.state.default_tearing_mode.get()),
+                    wallpaper: Default::default(),
                 });
                 self.state
                     .persistent_output_states
@@ -164,6 +165,10 @@ impl ConnectorHandler {
             workspace_rect: Default::default(),
             non_exclusive_rect: Default::default(),
             non_exclusive_rect_rel: Default::default(),
+            bar_rect: Default::default(),
+            bar_rect_rel: Default::default(),
+            bar_mode: Default::default(),
+            bar_position: Default::default(),
             render_data: RefCell::new(OutputRenderData {
                 active_workspace: None,
                 underline: Default::default(),
@@ -172,10 +177,16 @@ impl ConnectorHandler {
                 captured_inactive_workspaces: Default::default(),
                 titles: Default::default(),
                 status: None,
+                status_blocks: Default::default(),
+                status_separator: None,
+                window_switcher: None,
+                window_switcher_textures: Default::default(),
+                window_switcher_rows: None,
             }),
             state: self.state.clone(),
             is_dummy: false,
             status: self.state.status.clone(),
+            status_blocks: self.state.status_blocks.clone(),
             scroll: Default::default(),
             pointer_positions: Default::default(),
             pointer_down: Default::default(),
@@ -197,9 +208,17 @@ impl ConnectorHandler {
             before_latch_event: Default::default(),
             tray_start_rel: Default::default(),
             tray_items: Default::default(),
+            window_switcher: Default::default(),
+            wallpaper: Default::default(),
+            wallpaper_job: Default::default(),
+            workspace_animation: Default::default(),
+            closing_windows: Default::default(),
+            blurred_areas: Default::default(),
         });
+        on.update_exclusive_zones();
         on.update_visible();
         on.update_rects();
+        on.load_wallpaper();
         self.state
             .add_output_scale(on.global.persistent.scale.get());
         let output_data = Rc::new(OutputData {
@@ -337,6 +356,9 @@ impl ConnectorHandler {
         for item in on.tray_items.iter() {
             item.destroy_node();
         }
+        if let Some(job) = on.wallpaper_job.take() {
+            job.detach();
+        }
         self.state
             .remove_output_scale(on.global.persistent.scale.get());
         let _ = self.state.remove_global(&global);
@@ -1,6 +1,12 @@
 use {
-    crate::utils::clonecell::CloneCell,
-    std::{cell::Cell, cmp::Ordering, ops::Mul, sync::Arc},
+    crate::{pango::PangoFontDescription, utils::clonecell::CloneCell},
+    std::{
+        cell::{Cell, RefCell},
+        cmp::Ordering,
+        ops::Mul,
+        rc::Rc,
+        sync::Arc,
+    },
 };
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -155,11 +161,20 @@ impl From<jay_config::theme::Color> for Color {
 }
 
 macro_rules! colors {
-    ($($name:ident = $colors:tt,)*) => {
+    (
+        $($name:ident = $colors:tt,)*
+        ;
+        $($fallback_name:ident = $fallback:ident,)*
+    ) => {
         pub struct ThemeColors {
             $(
                 pub $name: Cell<Color>,
             )*
+            $(
+                /// `None` until the color is set explicitly. The method of the same name
+                /// returns the color that is used.
+                pub $fallback_name: Cell<Option<Color>>,
+            )*
         }
 
         impl ThemeColors {
@@ -168,7 +183,18 @@ macro_rules! colors {
                 $(
                     self.$name.set(default.$name.get());
                 )*
+                $(
+                    self.$fallback_name.set(None);
+                )*
             }
+
+            $(
+                pub fn $fallback_name(&self) -> Color {
+                    self.$fallback_name
+                        .get()
+                        .unwrap_or_else(|| self.$fallback.get())
+                }
+            )*
         }
 
         impl Default for ThemeColors {
@@ -177,6 +203,9 @@ macro_rules! colors {
                     $(
                         $name: Cell::new(colors!(@colors $colors)),
                     )*
+                    $(
+                        $fallback_name: Default::default(),
+                    )*
                 }
             }
         }
@@ -205,6 +234,11 @@ colors! {
     bar_text = (0xff, 0xff, 0xff),
     attention_requested_background = (0x23, 0x09, 0x2c),
     highlight = (0x9d, 0x28, 0xc6, 0x7f),
+    shadow = (0x00, 0x00, 0x00, 0x7f),
+    inactive_dim = (0x00, 0x00, 0x00, 0x00),
+    title_button_hover = (0xff, 0xff, 0xff, 0x33),
+    ;
+    focused_border = border,
 }
 
 macro_rules! sizes {
@@ -282,6 +316,12 @@ macro_rules! sizes {
 sizes! {
     title_height = (1, 1000, 17),
     border_width = (1, 1000, 4),
+    corner_radius = (0, 1000, 0),
+    shadow_blur = (0, 1000, 0),
+    shadow_offset_x = (-1000, 1000, 0),
+    shadow_offset_y = (-1000, 1000, 0),
+    blur_passes = (0, 8, 3),
+    blur_offset = (0, 100, 2),
 }
 
 pub const DEFAULT_FONT: &str = "monospace 8";
@@ -290,7 +330,18 @@ pub struct Theme {
     pub colors: ThemeColors,
     pub sizes: ThemeSizes,
     pub font: CloneCell<Arc<String>>,
+    pub title_font: CloneCell<Option<Arc<String>>>,
+    pub bar_font: CloneCell<Option<Arc<String>>>,
+    pub status_font: CloneCell<Option<Arc<String>>>,
+    pub font_fallbacks: RefCell<Vec<String>>,
     pub default_font: Arc<String>,
+    resolved_fonts: CloneCell<Option<Rc<ResolvedFonts>>>,
+}
+
+struct ResolvedFonts {
+    title: Arc<String>,
+    bar: Arc<String>,
+    status: Arc<String>,
 }
 
 impl Default for Theme {
@@ -300,7 +351,75 @@ impl Default for Theme {
             colors: Default::default(),
             sizes: Default::default(),
             font: CloneCell::new(default_font.clone()),
+            title_font: Default::default(),
+            bar_font: Default::default(),
+            status_font: Default::default(),
+            font_fallbacks: Default::default(),
             default_font,
+            resolved_fonts: Default::default(),
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the font used for window titles and the window switcher.
+    pub fn title_font(&self) -> Arc<String> {
+        self.resolved_fonts().title.clone()
+    }
+
+    /// Returns the font used for the workspace names in the bar.
+    pub fn bar_font(&self) -> Arc<String> {
+        self.resolved_fonts().bar.clone()
+    }
+
+    /// Returns the font used for the status text in the bar.
+    pub fn status_font(&self) -> Arc<String> {
+        self.resolved_fonts().status.clone()
+    }
+
+    /// Must be called after any of the fonts or the font fallbacks have been changed.
+    pub fn fonts_changed(&self) {
+        self.resolved_fonts.take();
+    }
+
+    pub fn reset_fonts(&self) {
+        self.font.set(self.default_font.clone());
+        self.title_font.take();
+        self.bar_font.take();
+        self.status_font.take();
+        self.font_fallbacks.take();
+        self.fonts_changed();
+    }
+
+    fn resolved_fonts(&self) -> Rc<ResolvedFonts> {
+        if let Some(fonts) = self.resolved_fonts.get() {
+            return fonts;
         }
+        let fallbacks = self.font_fallbacks.borrow();
+        let default = self.font.get();
+        let resolve = |font: Option<Arc<String>>| {
+            let font = font.unwrap_or_else(|| default.clone());
+            if fallbacks.is_empty() {
+                return font;
+            }
+            // Fallbacks are implemented as additional families in the family list of
+            // the font description. Pango tries these families in order when a glyph is
+            // not available in the primary family.
+            let mut fd = PangoFontDescription::from_string(&**font);
+            let mut families = fd.family().unwrap_or_else(|| "sans".to_string());
+            for fallback in &*fallbacks {
+                families.push(',');
+                families.push_str(fallback);
+            }
+            fd.set_family(&*families);
+            Arc::new(fd.description())
+        };
+        let fonts = Rc::new(ResolvedFonts {
+            title: resolve(self.title_font.get()),
+            bar: resolve(self.bar_font.get()),
+            status: resolve(self.status_font.get()),
+        });
+        self.resolved_fonts.set(Some(fonts.clone()));
+        fonts
     }
 }
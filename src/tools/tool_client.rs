@@ -326,16 +326,24 @@ impl ToolClient {
         if let Some(id) = self.jay_compositor.get() {
             return id;
         }
+        let id = self.bind_jay_compositor(11).await;
+        self.jay_compositor.set(Some(id));
+        id
+    }
+
+    /// Binds a new jay_compositor object with a version of at most `max_version`.
+    ///
+    /// Unlike the object returned by `jay_compositor`, this object is not shared.
+    pub async fn bind_jay_compositor(self: &Rc<Self>, max_version: u32) -> JayCompositorId {
         let s = self.singletons().await;
         let id: JayCompositorId = self.id();
         self.send(wl_registry::Bind {
             self_id: s.registry,
             name: s.jay_compositor.0,
             interface: JayCompositor.name(),
-            version: s.jay_compositor.1.min(11),
+            version: s.jay_compositor.1.min(max_version),
             id: id.into(),
         });
-        self.jay_compositor.set(Some(id));
         id
     }
 
@@ -27,16 +27,19 @@ use {
     },
 };
 pub use {
-    container::*, containing::*, display::*, float::*, output::*, placeholder::*, stacked::*,
-    toplevel::*, walker::*, workspace::*,
+    container::*, containing::*, display::*, float::*, layout::*, output::*, placeholder::*,
+    saved_layout::*, scrolling::*, stacked::*, toplevel::*, walker::*, workspace::*,
 };
 
 mod container;
 mod containing;
 mod display;
 mod float;
+mod layout;
 mod output;
 mod placeholder;
+mod saved_layout;
+mod scrolling;
 mod stacked;
 mod toplevel;
 mod walker;
@@ -84,7 +87,6 @@ impl NodeIds {
 pub struct NodeId(pub u32);
 
 impl NodeId {
-    #[expect(dead_code)]
     pub fn raw(&self) -> u32 {
         self.0
     }
@@ -524,6 +526,14 @@ pub trait Node: 'static {
         None
     }
 
+    fn node_into_scrolling(self: Rc<Self>) -> Option<Rc<ScrollingNode>> {
+        None
+    }
+
+    fn node_into_placeholder(self: Rc<Self>) -> Option<Rc<PlaceholderNode>> {
+        None
+    }
+
     fn node_into_surface(self: Rc<Self>) -> Option<Rc<WlSurface>> {
         None
     }
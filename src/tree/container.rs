@@ -16,10 +16,11 @@ use {
         scale::Scale,
         state::State,
         text::TextTexture,
+        theme::Color,
         tree::{
             default_tile_drag_bounds, walker::NodeVisitor, ContainingNode, Direction,
-            FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, TddType, TileDragDestination,
-            ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
+            FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, ScrollingNode, TddType,
+            TileDragDestination, ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -37,7 +38,7 @@ use {
         },
     },
     ahash::AHashMap,
-    jay_config::Axis,
+    jay_config::{Axis, ResizeAmount},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -103,7 +104,7 @@ pub struct ContainerRenderData {
     pub active_title_rects: Vec<Rect>,
     pub attention_title_rects: Vec<Rect>,
     pub last_active_rect: Option<Rect>,
-    pub border_rects: Vec<Rect>,
+    pub border_rects: Vec<(Rect, Color)>,
     pub underline_rects: Vec<Rect>,
     pub titles: SmallMapMut<Scale, Vec<ContainerTitle>, 2>,
 }
@@ -112,6 +113,9 @@ pub struct ContainerNode {
     pub id: ContainerNodeId,
     pub split: Cell<ContainerSplit>,
     pub mono_child: CloneCell<Option<NodeRef<ContainerChild>>>,
+    /// Whether the titles are stacked on top of each other instead of being placed
+    /// next to each other while in mono mode.
+    pub stacked: Cell<bool>,
     pub mono_body: Cell<Rect>,
     pub mono_content: Cell<Rect>,
     pub abs_x1: Cell<i32>,
@@ -174,6 +178,10 @@ struct CursorState {
 }
 
 impl ContainerChild {
+    pub fn factor(&self) -> f64 {
+        self.factor.get()
+    }
+
     fn position_content(&self) {
         let mut content = self.content.get();
         let body = self.body.get();
@@ -217,6 +225,7 @@ impl ContainerNode {
             id: state.node_ids.next(),
             split: Cell::new(split),
             mono_child: CloneCell::new(None),
+            stacked: Cell::new(false),
             mono_body: Cell::new(Default::default()),
             mono_content: Cell::new(Default::default()),
             abs_x1: Cell::new(0),
@@ -349,6 +358,23 @@ impl ContainerNode {
         // log::info!("add_child");
         self.schedule_layout();
         self.cancel_seat_ops();
+        self.schedule_auto_layout();
+    }
+
+    fn schedule_auto_layout(&self) {
+        if let Some(ws) = self.toplevel_data.workspace.get() {
+            ws.schedule_auto_layout();
+        }
+    }
+
+    pub fn set_child_factors(self: &Rc<Self>, factors: &[f64]) {
+        let mut sum_factors = 0.0;
+        for (child, factor) in self.children.iter().zip(factors) {
+            child.factor.set(*factor);
+            sum_factors += *factor;
+        }
+        self.sum_factors.set(sum_factors);
+        self.schedule_layout();
     }
 
     fn cancel_seat_ops(&self) {
@@ -417,6 +443,15 @@ impl ContainerNode {
             .set(child.content.get().at_point(mb.x1(), mb.y1()));
 
         let th = self.state.theme.sizes.title_height.get();
+        if self.stacked.get() {
+            for (i, child) in self.children.iter().enumerate() {
+                let y = i as i32 * (th + 1);
+                child
+                    .title_rect
+                    .set(Rect::new_sized(0, y, self.width.get(), th).unwrap());
+            }
+            return;
+        }
         let bw = self.state.theme.sizes.border_width.get();
         let num_children = self.num_children.get() as i32;
         let content_width = self.width.get().sub(bw * (num_children - 1)).max(0);
@@ -457,17 +492,21 @@ impl ContainerNode {
             let mut body_size = (content_size as f64 * factor).round() as i32;
             body_size = body_size.min(remaining_content_size);
             remaining_content_size -= body_size;
+            let title_size = self.child_title_size(&child);
             let (x1, y1, width, height) = match split {
-                ContainerSplit::Horizontal => {
-                    (pos, title_height + 1, body_size, other_content_size)
-                }
-                _ => (0, pos + title_height + 1, other_content_size, body_size),
+                ContainerSplit::Horizontal => (
+                    pos,
+                    title_size,
+                    body_size,
+                    other_content_size + title_height + 1 - title_size,
+                ),
+                _ => (0, pos + title_size, other_content_size, body_size),
             };
             let body = Rect::new_sized(x1, y1, width, height).unwrap();
             child.body.set(body);
             pos += body_size + border_width;
             if split == ContainerSplit::Vertical {
-                pos += title_height + 1;
+                pos += title_size;
             }
         }
         if remaining_content_size > 0 {
@@ -481,42 +520,38 @@ impl ContainerNode {
                     rem -= 1;
                     add += 1;
                 }
+                let title_size = self.child_title_size(&child);
                 let (x1, y1, width, height, size) = match split {
                     ContainerSplit::Horizontal => {
                         let width = body.width() + add;
-                        (pos, title_height + 1, width, other_content_size, width)
+                        (pos, title_size, width, body.height(), width)
                     }
                     _ => {
                         let height = body.height() + add;
-                        (
-                            0,
-                            pos + title_height + 1,
-                            other_content_size,
-                            height,
-                            height,
-                        )
+                        (0, pos + title_size, other_content_size, height, height)
                     }
                 };
                 body = Rect::new_sized(x1, y1, width, height).unwrap();
                 child.body.set(body);
                 pos += size + border_width;
                 if split == ContainerSplit::Vertical {
-                    pos += title_height + 1;
+                    pos += title_size;
                 }
             }
         }
         self.sum_factors.set(1.0);
         for child in self.children.iter() {
             let body = child.body.get();
-            child.title_rect.set(
-                Rect::new_sized(
+            let title_rect = match self.child_title_size(&child) {
+                0 => Rect::new_sized(body.x1(), body.y1(), body.width(), 0),
+                _ => Rect::new_sized(
                     body.x1(),
                     body.y1() - title_height - 1,
                     body.width(),
                     title_height,
-                )
-                .unwrap(),
-            );
+                ),
+            };
+            child.title_rect.set(title_rect.unwrap());
             let body = body.move_(self.abs_x1.get(), self.abs_y1.get());
             child.node.clone().tl_change_extents(&body);
             child.position_content();
@@ -535,26 +570,68 @@ impl ContainerNode {
                     .set(self.height.get().sub(title_height + 1).max(0));
             }
             ContainerSplit::Vertical => {
+                let titles_size: i32 = self
+                    .children
+                    .iter()
+                    .map(|c| self.child_title_size(&c))
+                    .sum();
                 let new_content_size = self
                     .height
                     .get()
-                    .sub(title_height + 1 + (nc - 1) as i32 * (border_width + title_height + 1))
+                    .sub(titles_size + (nc - 1) as i32 * border_width)
                     .max(0);
                 self.content_height.set(new_content_size);
                 self.content_width.set(self.width.get());
             }
         }
+        let titles_height = match self.stacked.get() {
+            true => nc as i32 * (title_height + 1),
+            false => title_height + 1,
+        };
         self.mono_body.set(
             Rect::new_sized(
                 0,
-                title_height + 1,
+                titles_height,
                 self.width.get(),
-                self.height.get().sub(title_height + 1).max(0),
+                self.height.get().sub(titles_height).max(0),
             )
             .unwrap(),
         );
     }
 
+    /// Returns the space taken by the title of a child in split mode.
+    fn child_title_size(&self, child: &ContainerChild) -> i32 {
+        match child.node.tl_data().title_hidden() {
+            true => 0,
+            false => self.state.theme.sizes.title_height.get() + 1,
+        }
+    }
+
+    /// Returns the color of the border between two adjacent children.
+    ///
+    /// The border of a focused child takes precedence over custom border colors which
+    /// take precedence over the theme.
+    fn border_color(&self, prev: &ContainerChild, next: &ContainerChild) -> Color {
+        for child in [prev, next] {
+            if child.active.get() {
+                return child.node.tl_data().border_color(true);
+            }
+        }
+        for child in [prev, next] {
+            if let Some(color) = child.node.tl_data().custom_border_color(false) {
+                return color;
+            }
+        }
+        self.state.theme.colors.border.get()
+    }
+
+    /// Returns whether the title of a child is shown.
+    ///
+    /// Titles can only be hidden in split mode.
+    fn child_title_visible(&self, child: &ContainerChild) -> bool {
+        self.mono_child.is_some() || !child.node.tl_data().title_hidden()
+    }
+
     fn pointer_move(
         self: &Rc<Self>,
         seat: &Rc<WlSeatGlobal>,
@@ -648,7 +725,7 @@ impl ContainerNode {
             for child in self.children.iter() {
                 let body = child.body.get();
                 if body.y1() > y {
-                    if body.y1() - y > title_height + 1 {
+                    if body.y1() - y > self.child_title_size(&child) {
                         cursor = KnownCursor::NsResize
                     }
                     break;
@@ -667,6 +744,7 @@ impl ContainerNode {
         let mut title = self.toplevel_data.title.borrow_mut();
         title.clear();
         let split = match (self.mono_child.is_some(), self.split.get()) {
+            (true, _) if self.stacked.get() => "S",
             (true, _) => "T",
             (_, ContainerSplit::Horizontal) => "H",
             (_, ContainerSplit::Vertical) => "V",
@@ -697,11 +775,14 @@ impl ContainerNode {
         };
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let font = theme.font.get();
+        let font = theme.title_font();
         let last_active = self.focus_history.last().map(|v| v.node.node_id());
         let have_active = self.children.iter().any(|c| c.active.get());
         let scales = self.state.scales.lock();
         for child in self.children.iter() {
+            if !self.child_title_visible(&child) {
+                continue;
+            }
             let rect = child.title_rect.get();
             let color = if child.active.get() {
                 theme.colors.focused_title_text.get()
@@ -713,6 +794,7 @@ impl ContainerNode {
                 theme.colors.unfocused_title_text.get()
             };
             let title = child.title.borrow_mut();
+            let title = child.node.tl_data().formatted_title(&title);
             let tt = &mut *child.title_tex.borrow_mut();
             for (scale, _) in scales.iter() {
                 let tex = tt
@@ -734,7 +816,7 @@ impl ContainerNode {
                     th,
                     1,
                     &font,
-                    title.deref(),
+                    &title,
                     color,
                     true,
                     false,
@@ -757,6 +839,9 @@ impl ContainerNode {
             if self.toplevel_data.visible.get() {
                 self.state.damage(rect.move_(abs_x, abs_y));
             }
+            if !self.child_title_visible(&child) {
+                continue;
+            }
             let title = child.title.borrow_mut();
             let tt = &*child.title_tex.borrow();
             for (scale, tex) in tt {
@@ -804,6 +889,7 @@ impl ContainerNode {
         rd.last_active_rect.take();
         let last_active = self.focus_history.last().map(|v| v.node.node_id());
         let mono = self.mono_child.is_some();
+        let tabbed = mono && !self.stacked.get();
         let split = self.split.get();
         let have_active = self.children.iter().any(|c| c.active.get());
         let abs_x = self.abs_x1.get();
@@ -813,15 +899,23 @@ impl ContainerNode {
             if self.toplevel_data.visible.get() {
                 self.state.damage(rect.move_(abs_x, abs_y));
             }
-            if i > 0 {
-                let rect = if mono {
+            if i > 0 && (tabbed || !mono) {
+                let rect = if tabbed {
                     Rect::new_sized(rect.x1() - bw, 0, bw, th)
                 } else if split == ContainerSplit::Horizontal {
                     Rect::new_sized(rect.x1() - bw, 0, bw, cheight)
                 } else {
                     Rect::new_sized(0, rect.y1() - bw, cwidth, bw)
                 };
-                rd.border_rects.push(rect.unwrap());
+                let rect = rect.unwrap();
+                if self.toplevel_data.visible.get() {
+                    self.state.damage(rect.move_(abs_x, abs_y));
+                }
+                let color = self.border_color(&child.prev().unwrap(), &child);
+                rd.border_rects.push((rect, color));
+            }
+            if !self.child_title_visible(&child) {
+                continue;
             }
             if child.active.get() {
                 rd.active_title_rects.push(rect);
@@ -832,7 +926,7 @@ impl ContainerNode {
             } else {
                 rd.title_rects.push(rect);
             }
-            if !mono {
+            if !tabbed {
                 let rect = Rect::new_sized(rect.x1(), rect.y2(), rect.width(), 1).unwrap();
                 rd.underline_rects.push(rect);
             }
@@ -848,7 +942,7 @@ impl ContainerNode {
                 }
             }
         }
-        if mono {
+        if tabbed {
             rd.underline_rects
                 .push(Rect::new_sized(0, th, cwidth, 1).unwrap());
         }
@@ -939,6 +1033,70 @@ impl ContainerNode {
         }
     }
 
+    pub fn set_stacked(self: &Rc<Self>, stacked: bool) {
+        if self.stacked.replace(stacked) != stacked {
+            self.update_content_size();
+            self.schedule_layout();
+            self.update_title();
+        }
+    }
+
+    /// Returns the axis along which the titles are placed.
+    fn title_split(&self) -> ContainerSplit {
+        match (self.mono_child.is_some(), self.stacked.get()) {
+            (true, true) => ContainerSplit::Vertical,
+            (true, false) => ContainerSplit::Horizontal,
+            _ => self.split.get(),
+        }
+    }
+
+    /// Makes `child` the visible child if the container is in mono mode.
+    pub fn show_child(self: &Rc<Self>, child: &dyn Node) {
+        let child = self
+            .child_nodes
+            .borrow()
+            .get(&child.node_id())
+            .map(|c| c.to_ref());
+        if let Some(child) = child {
+            self.activate_child(&child);
+        }
+    }
+
+    /// Swaps the positions and sizes of two children of this container.
+    pub fn swap_children(self: &Rc<Self>, a: &dyn Node, b: &dyn Node) {
+        let (a, b) = {
+            let children = self.child_nodes.borrow();
+            match (children.get(&a.node_id()), children.get(&b.node_id())) {
+                (Some(a), Some(b)) => (a.to_ref(), b.to_ref()),
+                _ => return,
+            }
+        };
+        let is = |n: Option<NodeRef<ContainerChild>>, m: &ContainerChild| {
+            n.is_some_and(|n| n.node.node_id() == m.node.node_id())
+        };
+        if is(a.next(), &b) {
+            b.append_existing(&a);
+        } else if is(b.next(), &a) {
+            a.append_existing(&b);
+        } else {
+            let a_next = a.next();
+            b.prepend_existing(&a);
+            match a_next {
+                Some(n) => n.prepend_existing(&b),
+                None => self.children.add_last_existing(&b),
+            }
+        }
+        let factor = a.factor.get();
+        a.factor.set(b.factor.get());
+        b.factor.set(factor);
+        self.schedule_layout();
+        self.update_title();
+    }
+
+    pub fn num_children(&self) -> usize {
+        self.num_children.get()
+    }
+
     fn parent_container(&self) -> Option<Rc<ContainerNode>> {
         self.toplevel_data
             .parent
@@ -946,6 +1104,21 @@ impl ContainerNode {
             .and_then(|p| p.node_into_container())
     }
 
+    fn parent_scrolling(&self) -> Option<Rc<ScrollingNode>> {
+        self.toplevel_data
+            .parent
+            .get()
+            .and_then(|p| p.node_into_scrolling())
+    }
+
+    fn move_focus_from_self(&self, seat: &Rc<WlSeatGlobal>, direction: Direction) {
+        if let Some(c) = self.parent_container() {
+            c.move_focus_from_child(seat, self, direction);
+        } else if let Some(s) = self.parent_scrolling() {
+            s.move_focus_from_column(seat, self, direction);
+        }
+    }
+
     pub fn move_focus_from_child(
         self: Rc<Self>,
         seat: &Rc<WlSeatGlobal>,
@@ -957,20 +1130,12 @@ impl ContainerNode {
             _ => return,
         };
         let mc = self.mono_child.get();
-        let in_line = if mc.is_some() {
-            matches!(direction, Direction::Left | Direction::Right)
-        } else {
-            match self.split.get() {
-                ContainerSplit::Horizontal => {
-                    matches!(direction, Direction::Left | Direction::Right)
-                }
-                ContainerSplit::Vertical => matches!(direction, Direction::Up | Direction::Down),
-            }
+        let in_line = match self.title_split() {
+            ContainerSplit::Horizontal => matches!(direction, Direction::Left | Direction::Right),
+            ContainerSplit::Vertical => matches!(direction, Direction::Up | Direction::Down),
         };
         if !in_line {
-            if let Some(c) = self.parent_container() {
-                c.move_focus_from_child(seat, self.deref(), direction);
-            }
+            self.move_focus_from_self(seat, direction);
             return;
         }
         let prev = match direction {
@@ -987,9 +1152,7 @@ impl ContainerNode {
         let sibling = match sibling {
             Some(s) => s,
             None => {
-                if let Some(c) = self.parent_container() {
-                    c.move_focus_from_child(seat, self.deref(), direction);
-                }
+                self.move_focus_from_self(seat, direction);
                 return;
             }
         };
@@ -1000,12 +1163,65 @@ impl ContainerNode {
         }
     }
 
-    //
+    /// Grows or shrinks a child along the axis.
+    ///
+    /// If the child cannot be resized within this container, the container itself is
+    /// resized within its parent.
+    pub fn resize_child(
+        self: Rc<Self>,
+        child: &dyn ToplevelNode,
+        split: ContainerSplit,
+        amount: ResizeAmount,
+    ) {
+        let mut neighbors = (false, false);
+        if split == self.split.get() && self.mono_child.is_none() {
+            if let Some(cc) = self.child_nodes.borrow().get(&child.node_id()) {
+                neighbors = (cc.prev().is_some(), cc.next().is_some());
+            }
+        }
+        let (has_prev, has_next) = neighbors;
+        if !has_prev && !has_next {
+            if let Some(parent) = self.parent_container() {
+                parent.resize_child(&*self, split, amount);
+            } else if let Some(parent) = self.parent_scrolling() {
+                parent.resize_column(&*self, split, amount);
+            }
+            return;
+        }
+        let delta = match amount {
+            ResizeAmount::Px(px) => px,
+            ResizeAmount::Ppt(ppt) => {
+                let size = match split {
+                    ContainerSplit::Horizontal => self.content_width.get(),
+                    ContainerSplit::Vertical => self.content_height.get(),
+                };
+                size * ppt / 100
+            }
+        };
+        let pos = child.node_absolute_position();
+        let (start, end) = match split {
+            ContainerSplit::Horizontal => (pos.x1(), pos.x2()),
+            ContainerSplit::Vertical => (pos.y1(), pos.y2()),
+        };
+        let size = child.tl_size_hints().resize(split, end - start, delta);
+        let (new_start, new_end) = match has_next {
+            true => (None, Some(start + size)),
+            false => (Some(end - size), None),
+        };
+        let (x1, y1, x2, y2) = match split {
+            ContainerSplit::Horizontal => (new_start, None, new_end, None),
+            ContainerSplit::Vertical => (None, new_start, None, new_end),
+        };
+        self.cnode_resize_child(child.tl_as_node(), x1, y1, x2, y2);
+    }
+
     pub fn move_child(self: Rc<Self>, child: Rc<dyn ToplevelNode>, direction: Direction) {
         // CASE 1: This is the only child of the container. Replace the container by the child.
         if self.num_children.get() == 1 {
             if let Some(parent) = self.toplevel_data.parent.get() {
-                if !self.toplevel_data.is_fullscreen.get()
+                if let Some(parent) = parent.clone().node_into_scrolling() {
+                    parent.move_child(self.deref(), child, direction);
+                } else if !self.toplevel_data.is_fullscreen.get()
                     && parent.cnode_accepts_child(child.tl_as_node())
                 {
                     parent.cnode_replace_child(self.deref(), child.clone());
@@ -1015,9 +1231,7 @@ impl ContainerNode {
         }
         let (split, prev) = direction_to_split(direction);
         // CASE 2: We're moving the child within the container.
-        if split == self.split.get()
-            || (split == ContainerSplit::Horizontal && self.mono_child.is_some())
-        {
+        if split == self.split.get() || split == self.title_split() {
             let cc = match self.child_nodes.borrow().get(&child.node_id()) {
                 Some(l) => l.to_ref(),
                 None => return,
@@ -1060,7 +1274,12 @@ impl ContainerNode {
         }
         let parent = match parent_opt {
             Some(p) => p,
-            _ => return,
+            _ => {
+                if let Some(s) = neighbor.parent_scrolling() {
+                    s.move_child(&*neighbor, child, direction);
+                }
+                return;
+            }
         };
         self.cnode_remove_child2(child.tl_as_node(), true);
         match prev {
@@ -1161,6 +1380,7 @@ impl ContainerNode {
     }
 
     fn toggle_mono(self: &Rc<Self>) {
+        self.set_stacked(false);
         if self.mono_child.is_some() {
             self.set_mono(None);
         } else if let Some(last) = self.focus_history.last() {
@@ -1182,7 +1402,7 @@ impl ContainerNode {
             _ => return,
         };
         if button == BTN_RIGHT && pressed {
-            if self.mono_child.is_some() || self.split.get() == ContainerSplit::Horizontal {
+            if self.title_split() == ContainerSplit::Horizontal {
                 if seat_data.y < self.state.theme.sizes.title_height.get() {
                     self.toggle_mono();
                 }
@@ -1269,6 +1489,7 @@ impl ContainerNode {
         abs_x: i32,
         abs_y: i32,
     ) -> Option<TileDragDestination> {
+        let stacked = self.stacked.get();
         let mut prev_is_source = false;
         let mut prev_center = 0;
         for child in self.children.iter() {
@@ -1277,9 +1498,16 @@ impl ContainerNode {
                 continue;
             }
             let rect = child.title_rect.get();
-            let center = (rect.x1() + rect.x2()) / 2;
+            let center = match stacked {
+                true => (rect.y1() + rect.y2()) / 2,
+                false => (rect.x1() + rect.x2()) / 2,
+            };
             if !prev_is_source {
-                let rect = Rect::new(prev_center, 0, center, rect.height())?
+                let rect = match stacked {
+                    true => Rect::new(0, prev_center, rect.width(), center)?,
+                    false => Rect::new(prev_center, 0, center, rect.height())?,
+                };
+                let rect = rect
                     .move_(self.abs_x1.get(), self.abs_y1.get())
                     .intersect(abs_bounds);
                 if rect.contains(abs_x, abs_y) {
@@ -1300,14 +1528,14 @@ impl ContainerNode {
             return None;
         }
         let last = self.children.last()?;
-        let rect = Rect::new(
-            prev_center,
-            0,
-            self.width.get(),
-            self.state.theme.sizes.title_height.get(),
-        )?
-        .move_(self.abs_x1.get(), self.abs_y1.get())
-        .intersect(abs_bounds);
+        let titles_end = self.mono_body.get().y1() - 1;
+        let rect = match stacked {
+            true => Rect::new(0, prev_center, self.width.get(), titles_end)?,
+            false => Rect::new(prev_center, 0, self.width.get(), titles_end)?,
+        };
+        let rect = rect
+            .move_(self.abs_x1.get(), self.abs_y1.get())
+            .intersect(abs_bounds);
         if rect.contains(abs_x, abs_y) {
             return Some(TileDragDestination {
                 highlight: rect,
@@ -1329,11 +1557,10 @@ impl ContainerNode {
         abs_x: i32,
         abs_y: i32,
     ) -> Option<TileDragDestination> {
-        let th = self.state.theme.sizes.title_height.get();
-        if abs_y < self.abs_y1.get() + th {
+        let body = self.mono_body.get();
+        if abs_y < self.abs_y1.get() + body.y1() - 1 {
             return self.tile_drag_destination_mono_titles(source, abs_bounds, abs_x, abs_y);
         }
-        let body = self.mono_body.get();
         let bounds = body
             .move_(self.abs_x1.get(), self.abs_y1.get())
             .intersect(abs_bounds);
@@ -1572,8 +1799,10 @@ impl Node for ContainerNode {
         tree: &mut Vec<FoundNode>,
         usecase: FindTreeUsecase,
     ) -> FindTreeResult {
-        let mut recurse = |content: Rect, child: NodeRef<ContainerChild>| {
-            if content.contains(x, y) {
+        let radius = self.state.theme.sizes.corner_radius.get();
+        let mut recurse = |body: Rect, content: Rect, child: NodeRef<ContainerChild>| {
+            let in_body = child.node.node_is_container() || body.contains_rounded(x, y, radius);
+            if in_body && content.contains(x, y) {
                 let (x, y) = content.translate(x, y);
                 tree.push(FoundNode {
                     node: child.node.clone().tl_into_node(),
@@ -1584,11 +1813,12 @@ impl Node for ContainerNode {
             }
         };
         if let Some(child) = self.mono_child.get() {
-            recurse(self.mono_content.get(), child);
+            recurse(self.mono_body.get(), self.mono_content.get(), child);
         } else {
             for child in self.children.iter() {
-                if child.body.get().contains(x, y) {
-                    recurse(child.content.get(), child);
+                let body = child.body.get();
+                if body.contains(x, y) {
+                    recurse(body, child.content.get(), child);
                     break;
                 }
             }
@@ -1636,7 +1866,7 @@ impl Node for ContainerNode {
             Some(s) => s,
             _ => return,
         };
-        if seat_data.y > self.state.theme.sizes.title_height.get() {
+        if seat_data.y >= self.mono_body.get().y1() {
             return;
         }
         let cur_mc = match self.mono_child.get() {
@@ -1832,6 +2062,7 @@ impl ContainingNode for ContainerNode {
         };
         node.focus_history.set(None);
         self.discard_child_properties(&node);
+        self.schedule_auto_layout();
         if let Some(mono) = self.mono_child.get() {
             if mono.node.node_id() == child.node_id() {
                 let mut new = self.focus_history.last().map(|n| n.deref().clone());
@@ -1904,9 +2135,9 @@ impl ContainingNode for ContainerNode {
         let Some(parent) = self.toplevel_data.parent.get() else {
             return;
         };
-        let th = self.state.theme.sizes.title_height.get();
         if self.mono_child.is_some() {
-            parent.cnode_set_child_position(&*self, x, y - th - 1);
+            let body = self.mono_body.get();
+            parent.cnode_set_child_position(&*self, x - body.x1(), y - body.y1());
         } else {
             let children = self.child_nodes.borrow();
             let Some(child) = children.get(&child.node_id()) else {
@@ -1933,6 +2164,7 @@ impl ContainingNode for ContainerNode {
         let mut right_outside = false;
         let mut top_outside = false;
         let mut bottom_outside = false;
+        let mut title_size = th + 1;
         if self.mono_child.is_some() {
             top_outside = true;
             right_outside = true;
@@ -1945,6 +2177,7 @@ impl ContainingNode for ContainerNode {
             };
             let pos = child.body.get();
             let split = self.split.get();
+            title_size = self.child_title_size(child);
             let mut changed_any = false;
             let (mut i1, mut i2, new_i1, new_i2, mut ci) = match split {
                 ContainerSplit::Horizontal => {
@@ -1969,7 +2202,7 @@ impl ContainingNode for ContainerNode {
             }
             let (new_delta, between) = match split {
                 ContainerSplit::Horizontal => (self.abs_x1.get(), bw),
-                ContainerSplit::Vertical => (self.abs_y1.get(), bw + th + 1),
+                ContainerSplit::Vertical => (self.abs_y1.get(), bw + title_size),
             };
             let new_i1 = new_i1.map(|v| v - new_delta);
             let new_i2 = new_i2.map(|v| v - new_delta);
@@ -2003,6 +2236,10 @@ impl ContainingNode for ContainerNode {
                         ContainerSplit::Horizontal => peer_pos.x2(),
                         ContainerSplit::Vertical => peer_pos.y2(),
                     };
+                    let between = match split {
+                        ContainerSplit::Horizontal => between,
+                        ContainerSplit::Vertical => bw + self.child_title_size(&peer),
+                    };
                     i2 = new_i2.min(peer_i2 - between).max(i1);
                     if i2 != orig_i2 {
                         let peer_factor = (peer_i2 - between - i2) as f64 / ci as f64;
@@ -2037,10 +2274,10 @@ impl ContainingNode for ContainerNode {
             x2 = new_x2.map(|v| v.max(x1.unwrap_or(pos.x1())));
         }
         if top_outside {
-            y1 = new_y1.map(|v| (v - th - 1).min(pos.y2() - th - 1));
+            y1 = new_y1.map(|v| (v - title_size).min(pos.y2() - title_size));
         }
         if bottom_outside {
-            y2 = new_y2.map(|v| v.max(y1.unwrap_or(pos.y1()) + th + 1));
+            y2 = new_y2.map(|v| v.max(y1.unwrap_or(pos.y1()) + title_size));
         }
         if (x1.is_some() && x1 != Some(pos.x1()))
             || (x2.is_some() && x2 != Some(pos.x2()))
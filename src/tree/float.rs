@@ -14,8 +14,9 @@ use {
         state::State,
         text::TextTexture,
         tree::{
-            walker::NodeVisitor, ContainingNode, Direction, FindTreeResult, FindTreeUsecase,
-            FoundNode, Node, NodeId, StackedNode, TileDragDestination, ToplevelNode, WorkspaceNode,
+            walker::NodeVisitor, ContainerSplit, ContainingNode, Direction, FindTreeResult,
+            FindTreeUsecase, FoundNode, Node, NodeId, StackedNode, TileDragDestination,
+            ToplevelNode, WorkspaceNode,
         },
         utils::{
             asyncevent::AsyncEvent, clonecell::CloneCell, double_click_state::DoubleClickState,
@@ -24,7 +25,9 @@ use {
         },
     },
     ahash::AHashMap,
+    jay_config::ResizeAmount,
     std::{
+        borrow::Cow,
         cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
         mem,
@@ -69,6 +72,14 @@ struct CursorState {
     dist_hor: i32,
     dist_ver: i32,
     double_click_state: DoubleClickState,
+    button: Option<TitleButton>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TitleButton {
+    Tile,
+    Fullscreen,
+    Close,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -158,6 +169,14 @@ impl FloatNode {
         }
     }
 
+    /// Returns the width of the border around the child.
+    pub fn border_width(&self) -> i32 {
+        match self.child.get() {
+            Some(c) => c.tl_data().border_width(),
+            _ => self.state.theme.sizes.border_width.get(),
+        }
+    }
+
     fn perform_layout(self: &Rc<Self>) {
         let child = match self.child.get() {
             Some(c) => c,
@@ -165,7 +184,7 @@ impl FloatNode {
         };
         let pos = self.position.get();
         let theme = &self.state.theme;
-        let bw = theme.sizes.border_width.get();
+        let bw = self.border_width();
         let th = theme.sizes.title_height.get();
         let cpos = Rect::new_sized(
             pos.x1() + bw,
@@ -179,6 +198,37 @@ impl FloatNode {
         self.schedule_render_titles();
     }
 
+    /// Grows or shrinks the window along the axis while keeping its top-left corner in
+    /// place.
+    pub fn resize_child(self: &Rc<Self>, split: ContainerSplit, amount: ResizeAmount) {
+        let Some(child) = self.child.get() else {
+            return;
+        };
+        let pos = self.position.get();
+        let theme = &self.state.theme;
+        let bw = self.border_width();
+        let th = theme.sizes.title_height.get();
+        let (x1, y1) = (pos.x1() + bw, pos.y1() + bw + th + 1);
+        let (size, ws_size) = {
+            let ws = self.workspace.get().position.get();
+            match split {
+                ContainerSplit::Horizontal => (pos.width() - 2 * bw, ws.width()),
+                ContainerSplit::Vertical => (pos.height() - 2 * bw - th - 1, ws.height()),
+            }
+        };
+        let delta = match amount {
+            ResizeAmount::Px(px) => px,
+            ResizeAmount::Ppt(ppt) => ws_size * ppt / 100,
+        };
+        let size = child.tl_size_hints().resize(split, size.max(0), delta);
+        let (x2, y2) = match split {
+            ContainerSplit::Horizontal => (Some(x1 + size), None),
+            ContainerSplit::Vertical => (None, Some(y1 + size)),
+        };
+        self.clone()
+            .cnode_resize_child(child.tl_as_node(), None, None, x2, y2);
+    }
+
     pub fn schedule_render_titles(self: &Rc<Self>) {
         if !self.render_titles_scheduled.replace(true) {
             self.state.pending_float_titles.push(self.clone());
@@ -193,9 +243,14 @@ impl FloatNode {
             true => theme.colors.focused_title_text.get(),
             false => theme.colors.unfocused_title_text.get(),
         };
-        let bw = theme.sizes.border_width.get();
-        let font = theme.font.get();
+        let bw = self.border_width();
+        let font = theme.title_font();
         let title = self.title.borrow_mut();
+        let child = self.child.get();
+        let title = match &child {
+            Some(c) => c.tl_data().formatted_title(&title),
+            None => Cow::Borrowed(title.as_str()),
+        };
         let pos = self.position.get();
         if pos.width() <= 2 * bw {
             return on_completed.event();
@@ -205,7 +260,11 @@ impl FloatNode {
             _ => return on_completed.event(),
         };
         let scales = self.state.scales.lock();
-        let tr = Rect::new_sized(pos.x1() + bw, pos.y1() + bw, pos.width() - 2 * bw, th).unwrap();
+        let mut tw = pos.width() - 2 * bw;
+        if self.title_buttons().is_some() {
+            tw -= 3 * th;
+        }
+        let tr = Rect::new_sized(pos.x1() + bw, pos.y1() + bw, tw, th).unwrap();
         let tt = &mut *self.title_textures.borrow_mut();
         for (scale, _) in scales.iter() {
             let tex =
@@ -243,7 +302,7 @@ impl FloatNode {
     fn render_title_phase2(&self) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
+        let bw = self.border_width();
         let title = self.title.borrow();
         let tt = &*self.title_textures.borrow();
         for (_, tt) in tt {
@@ -259,6 +318,43 @@ impl FloatNode {
         }
     }
 
+    /// Returns the buttons in the title bar and their positions relative to the node.
+    pub fn title_buttons(&self) -> Option<[(TitleButton, Rect); 3]> {
+        if !self.state.show_title_buttons.get() {
+            return None;
+        }
+        let theme = &self.state.theme;
+        let th = theme.sizes.title_height.get();
+        let bw = self.border_width();
+        let pos = self.position.get();
+        if th == 0 || pos.width() - 2 * bw < 4 * th {
+            return None;
+        }
+        let x2 = pos.width() - bw;
+        let button = |n: i32| Rect::new_sized(x2 - n * th, bw, th, th).unwrap();
+        Some([
+            (TitleButton::Tile, button(3)),
+            (TitleButton::Fullscreen, button(2)),
+            (TitleButton::Close, button(1)),
+        ])
+    }
+
+    pub fn title_button_hovered(&self, button: TitleButton) -> bool {
+        self.cursors
+            .borrow()
+            .values()
+            .any(|c| c.target && c.button == Some(button))
+    }
+
+    fn damage_title_buttons(&self) {
+        if let Some(buttons) = self.title_buttons() {
+            let pos = self.position.get();
+            for (_, rect) in buttons {
+                self.state.damage(rect.move_(pos.x1(), pos.y1()));
+            }
+        }
+    }
+
     fn pointer_move(
         self: &Rc<Self>,
         id: CursorType,
@@ -270,7 +366,7 @@ impl FloatNode {
         let x = x.round_down();
         let y = y.round_down();
         let theme = &self.state.theme;
-        let bw = theme.sizes.border_width.get();
+        let bw = self.border_width();
         let th = theme.sizes.title_height.get();
         let mut seats = self.cursors.borrow_mut();
         let seat_state = seats.entry(id).or_insert_with(|| CursorState {
@@ -283,6 +379,7 @@ impl FloatNode {
             dist_hor: 0,
             dist_ver: 0,
             double_click_state: Default::default(),
+            button: None,
         });
         seat_state.x = x;
         seat_state.y = y;
@@ -396,6 +493,19 @@ impl FloatNode {
                 cursor.set_known(new_cursor);
             }
         }
+        let mut button = None;
+        if op_type == OpType::Move {
+            if let Some(buttons) = self.title_buttons() {
+                button = buttons
+                    .into_iter()
+                    .find(|(_, rect)| rect.contains(x, y))
+                    .map(|(button, _)| button);
+            }
+        }
+        if mem::replace(&mut seat_state.button, button) != button {
+            drop(seats);
+            self.damage_title_buttons();
+        }
     }
 
     fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
@@ -408,6 +518,26 @@ impl FloatNode {
         self.stacked_set_visible(ws.container_visible());
     }
 
+    pub fn is_sticky(&self) -> bool {
+        match self.child.get() {
+            Some(c) => c.tl_data().is_sticky(),
+            _ => false,
+        }
+    }
+
+    /// Moves a sticky float to the workspace that is about to be shown on its output.
+    ///
+    /// The visibility is not changed so that the child keeps the keyboard focus.
+    pub fn follow_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
+        if let Some(c) = self.child.get() {
+            c.tl_set_workspace(ws);
+        }
+        self.workspace_link
+            .set(Some(ws.stacked.add_last(self.clone())));
+        self.workspace.set(ws.clone());
+        self.restack();
+    }
+
     fn update_child_title(self: &Rc<Self>, title: &str) {
         let mut t = self.title.borrow_mut();
         if t.deref() != title {
@@ -420,6 +550,12 @@ impl FloatNode {
     fn update_child_active(self: &Rc<Self>, active: bool) {
         if self.active.replace(active) != active {
             self.schedule_render_titles();
+            if let Some(child) = self.child.get() {
+                let data = child.tl_data();
+                if self.visible.get() && data.border_color(true) != data.border_color(false) {
+                    self.state.damage(self.position.get());
+                }
+            }
         }
     }
 
@@ -448,7 +584,7 @@ impl FloatNode {
         }
     }
 
-    fn restack(&self) {
+    pub fn restack(&self) {
         if let Some(dl) = &*self.display_link.borrow() {
             self.state.root.stacked.add_last_existing(&dl);
             if let Some(tl) = self.child.get() {
@@ -475,6 +611,17 @@ impl FloatNode {
             if !pressed {
                 return;
             }
+            if let Some(button) = cursor_data.button {
+                drop(cursors);
+                if let Some(tl) = self.child.get() {
+                    match button {
+                        TitleButton::Tile => seat.set_tl_floating(tl, false),
+                        TitleButton::Fullscreen => tl.tl_set_fullscreen(true),
+                        TitleButton::Close => tl.tl_close(),
+                    }
+                }
+                return;
+            }
             if cursor_data.op_type == OpType::Move {
                 if let Some(tl) = self.child.get() {
                     tl.node_do_focus(seat, Direction::Unspecified);
@@ -537,7 +684,7 @@ impl FloatNode {
     ) -> Option<TileDragDestination> {
         let child = self.child.get()?;
         let theme = &self.state.theme.sizes;
-        let bw = theme.border_width.get();
+        let bw = self.border_width();
         let th = theme.title_height.get();
         let pos = self.position.get();
         let body = Rect::new(
@@ -596,8 +743,12 @@ impl Node for FloatNode {
     ) -> FindTreeResult {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
+        let bw = self.border_width();
         let pos = self.position.get();
+        let radius = theme.sizes.corner_radius.get();
+        if radius > 0 && !pos.at_point(0, 0).contains_rounded(x, y, radius) {
+            return FindTreeResult::Other;
+        }
         if x < bw || x >= pos.width() - bw {
             return FindTreeResult::AcceptsInput;
         }
@@ -661,6 +812,10 @@ impl Node for FloatNode {
         let id = CursorType::Seat(seat.id());
         if let Some(seat_state) = cursors.get_mut(&id) {
             seat_state.target = false;
+            if seat_state.button.take().is_some() {
+                drop(cursors);
+                self.damage_title_buttons();
+            }
         }
     }
 
@@ -686,7 +841,10 @@ impl Node for FloatNode {
 
     fn node_on_tablet_tool_leave(&self, tool: &Rc<TabletTool>, _time_usec: u64) {
         let id = CursorType::TabletTool(tool.id);
-        self.cursors.borrow_mut().remove(&id);
+        let state = self.cursors.borrow_mut().remove(&id);
+        if state.is_some_and(|s| s.button.is_some()) {
+            self.damage_title_buttons();
+        }
     }
 
     fn node_on_tablet_tool_enter(
@@ -777,7 +935,7 @@ impl ContainingNode for FloatNode {
     fn cnode_set_child_position(self: Rc<Self>, _child: &dyn Node, x: i32, y: i32) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
+        let bw = self.border_width();
         let (x, y) = (x - bw, y - th - bw - 1);
         let pos = self.position.get();
         if pos.position() != (x, y) {
@@ -799,7 +957,7 @@ impl ContainingNode for FloatNode {
     ) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
+        let bw = self.border_width();
         let pos = self.position.get();
         let mut x1 = pos.x1();
         let mut x2 = pos.x2();
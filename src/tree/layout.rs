@@ -0,0 +1,306 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        state::State,
+        tree::{ContainerNode, ContainerSplit, ScrollingNode, ToplevelNode, WorkspaceNode},
+    },
+    jay_config::WorkspaceLayout as ConfigWorkspaceLayout,
+    std::rc::Rc,
+};
+
+pub const DEFAULT_MASTER_COUNT: u32 = 1;
+pub const DEFAULT_MASTER_RATIO: f64 = 0.5;
+pub const MIN_MASTER_RATIO: f64 = 0.1;
+pub const MAX_MASTER_RATIO: f64 = 0.9;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum WorkspaceLayout {
+    #[default]
+    Manual,
+    MasterStack,
+    Spiral,
+    Columns,
+    Rows,
+    Scrolling,
+}
+
+impl WorkspaceLayout {
+    pub fn from_config(layout: ConfigWorkspaceLayout) -> Option<Self> {
+        let res = match layout {
+            ConfigWorkspaceLayout::MANUAL => Self::Manual,
+            ConfigWorkspaceLayout::MASTER_STACK => Self::MasterStack,
+            ConfigWorkspaceLayout::SPIRAL => Self::Spiral,
+            ConfigWorkspaceLayout::COLUMNS => Self::Columns,
+            ConfigWorkspaceLayout::ROWS => Self::Rows,
+            ConfigWorkspaceLayout::SCROLLING => Self::Scrolling,
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    pub fn to_config(self) -> ConfigWorkspaceLayout {
+        match self {
+            Self::Manual => ConfigWorkspaceLayout::MANUAL,
+            Self::MasterStack => ConfigWorkspaceLayout::MASTER_STACK,
+            Self::Spiral => ConfigWorkspaceLayout::SPIRAL,
+            Self::Columns => ConfigWorkspaceLayout::COLUMNS,
+            Self::Rows => ConfigWorkspaceLayout::ROWS,
+            Self::Scrolling => ConfigWorkspaceLayout::SCROLLING,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Manual => Self::MasterStack,
+            Self::MasterStack => Self::Spiral,
+            Self::Spiral => Self::Columns,
+            Self::Columns => Self::Rows,
+            Self::Rows => Self::Scrolling,
+            Self::Scrolling => Self::Manual,
+        }
+    }
+}
+
+pub async fn workspace_layout(state: Rc<State>) {
+    loop {
+        let ws = state.pending_workspace_layout.pop().await;
+        if ws.layout_scheduled.get() {
+            ws.perform_auto_layout();
+        }
+    }
+}
+
+/// The desired arrangement of a subtree.
+#[derive(Debug, PartialEq)]
+enum Shape<L = Rc<dyn ToplevelNode>> {
+    Leaf(L),
+    Split(ContainerSplit, Vec<(Shape<L>, f64)>),
+}
+
+/// Returns the windows in the tree rooted at `container` in depth-first order.
+pub fn collect_tiled_toplevels(container: &ContainerNode, leaves: &mut Vec<Rc<dyn ToplevelNode>>) {
+    for child in container.children.iter() {
+        match child.node.clone().node_into_container() {
+            Some(c) => collect_tiled_toplevels(&c, leaves),
+            _ => leaves.push(child.node.clone()),
+        }
+    }
+}
+
+/// Arranges the windows of the workspace according to `layout`.
+///
+/// The existing containers are reused if they already have the desired structure. In
+/// that case the sizes of the windows are only reset if `reset_factors` is true so that
+/// manual resizes survive a relayout. Otherwise all windows are detached and a new tree
+/// is built.
+///
+/// Switching to or from the scrolling layout moves the windows between the container
+/// tree and the strip of columns.
+pub fn arrange_workspace(
+    state: &Rc<State>,
+    ws: &Rc<WorkspaceNode>,
+    layout: WorkspaceLayout,
+    master_count: u32,
+    master_ratio: f64,
+    reset_factors: bool,
+) {
+    if layout == WorkspaceLayout::Scrolling {
+        if let Some(root) = ws.container.get() {
+            convert_to_scrolling(state, ws, &root);
+        }
+        return;
+    }
+    if let Some(scrolling) = ws.scrolling.get() {
+        convert_from_scrolling(state, ws, &scrolling);
+    }
+    let Some(root) = ws.container.get() else {
+        return;
+    };
+    let mut leaves = vec![];
+    collect_tiled_toplevels(&root, &mut leaves);
+    if leaves.is_empty() {
+        return;
+    }
+    let (split, children) = match layout {
+        WorkspaceLayout::Manual | WorkspaceLayout::Scrolling => return,
+        WorkspaceLayout::MasterStack => master_stack(&leaves, master_count, master_ratio),
+        WorkspaceLayout::Spiral => match spiral(&leaves[0], &leaves[1..], 0) {
+            Shape::Split(split, children) => (split, children),
+            leaf => (ContainerSplit::Horizontal, vec![(leaf, 1.0)]),
+        },
+        WorkspaceLayout::Columns => linear(ContainerSplit::Horizontal, &leaves),
+        WorkspaceLayout::Rows => linear(ContainerSplit::Vertical, &leaves),
+    };
+    if container_matches(&root, split, &children) {
+        if reset_factors {
+            apply_factors(&root, &children);
+        }
+        return;
+    }
+    detach(&leaves);
+    let container = build_container(state, ws, split, children);
+    ws.set_container(&container);
+}
+
+fn detach(leaves: &[Rc<dyn ToplevelNode>]) {
+    for leaf in leaves {
+        if let Some(parent) = leaf.tl_data().parent.get() {
+            parent.cnode_remove_child2(leaf.tl_as_node(), true);
+        }
+    }
+}
+
+fn convert_to_scrolling(state: &Rc<State>, ws: &Rc<WorkspaceNode>, root: &ContainerNode) {
+    let mut leaves = vec![];
+    collect_tiled_toplevels(root, &mut leaves);
+    detach(&leaves);
+    let scrolling = match ws.scrolling.get() {
+        Some(s) => s,
+        _ => {
+            let scrolling = ScrollingNode::new(state, ws);
+            ws.set_scrolling(&scrolling);
+            scrolling
+        }
+    };
+    for leaf in leaves {
+        scrolling.add_window(leaf);
+    }
+}
+
+fn convert_from_scrolling(state: &Rc<State>, ws: &Rc<WorkspaceNode>, scrolling: &ScrollingNode) {
+    let mut leaves = vec![];
+    for column in scrolling.columns.iter() {
+        collect_tiled_toplevels(&column.node, &mut leaves);
+    }
+    detach(&leaves);
+    if leaves.is_empty() {
+        return;
+    }
+    let (split, children) = linear(ContainerSplit::Horizontal, &leaves);
+    let container = build_container(state, ws, split, children);
+    ws.set_container(&container);
+}
+
+fn linear<L: Clone>(split: ContainerSplit, leaves: &[L]) -> (ContainerSplit, Vec<(Shape<L>, f64)>) {
+    let factor = 1.0 / leaves.len() as f64;
+    let children = leaves
+        .iter()
+        .map(|l| (Shape::Leaf(l.clone()), factor))
+        .collect();
+    (split, children)
+}
+
+fn area<L: Clone>(split: ContainerSplit, leaves: &[L]) -> Shape<L> {
+    if let [leaf] = leaves {
+        return Shape::Leaf(leaf.clone());
+    }
+    let (split, children) = linear(split, leaves);
+    Shape::Split(split, children)
+}
+
+fn master_stack<L: Clone>(
+    leaves: &[L],
+    master_count: u32,
+    master_ratio: f64,
+) -> (ContainerSplit, Vec<(Shape<L>, f64)>) {
+    let master_count = (master_count as usize).min(leaves.len());
+    if master_count == 0 || master_count == leaves.len() {
+        return linear(ContainerSplit::Vertical, leaves);
+    }
+    let (master, stack) = leaves.split_at(master_count);
+    let children = vec![
+        (area(ContainerSplit::Vertical, master), master_ratio),
+        (area(ContainerSplit::Vertical, stack), 1.0 - master_ratio),
+    ];
+    (ContainerSplit::Horizontal, children)
+}
+
+fn spiral<L: Clone>(first: &L, rest: &[L], depth: usize) -> Shape<L> {
+    let [second, rest @ ..] = rest else {
+        return Shape::Leaf(first.clone());
+    };
+    let split = match depth % 2 {
+        0 => ContainerSplit::Horizontal,
+        _ => ContainerSplit::Vertical,
+    };
+    let first = (Shape::Leaf(first.clone()), 0.5);
+    let rest = (spiral(second, rest, depth + 1), 0.5);
+    let children = match depth % 4 {
+        0 | 1 => vec![first, rest],
+        _ => vec![rest, first],
+    };
+    Shape::Split(split, children)
+}
+
+fn shape_matches(node: &Rc<dyn ToplevelNode>, shape: &Shape) -> bool {
+    match shape {
+        Shape::Leaf(leaf) => leaf.node_id() == node.node_id(),
+        Shape::Split(split, children) => match node.clone().node_into_container() {
+            Some(c) => container_matches(&c, *split, children),
+            _ => false,
+        },
+    }
+}
+
+fn container_matches(
+    container: &ContainerNode,
+    split: ContainerSplit,
+    children: &[(Shape, f64)],
+) -> bool {
+    if container.split.get() != split || container.mono_child.is_some() {
+        return false;
+    }
+    let mut actual = container.children.iter();
+    for (shape, _) in children {
+        match actual.next() {
+            Some(child) if shape_matches(&child.node, shape) => {}
+            _ => return false,
+        }
+    }
+    actual.next().is_none()
+}
+
+fn apply_factors(container: &Rc<ContainerNode>, children: &[(Shape, f64)]) {
+    let factors: Vec<_> = children.iter().map(|(_, f)| *f).collect();
+    container.set_child_factors(&factors);
+    for (child, (shape, _)) in container.children.iter().zip(children) {
+        if let Shape::Split(_, children) = shape {
+            if let Some(c) = child.node.clone().node_into_container() {
+                apply_factors(&c, children);
+            }
+        }
+    }
+}
+
+fn build_node(state: &Rc<State>, ws: &Rc<WorkspaceNode>, shape: Shape) -> Rc<dyn ToplevelNode> {
+    match shape {
+        Shape::Leaf(leaf) => leaf,
+        Shape::Split(split, children) => build_container(state, ws, split, children),
+    }
+}
+
+fn build_container(
+    state: &Rc<State>,
+    ws: &Rc<WorkspaceNode>,
+    split: ContainerSplit,
+    children: Vec<(Shape, f64)>,
+) -> Rc<ContainerNode> {
+    let mut factors = vec![];
+    let mut nodes = vec![];
+    for (shape, factor) in children {
+        factors.push(factor);
+        nodes.push(build_node(state, ws, shape));
+    }
+    let mut nodes = nodes.into_iter();
+    let container = ContainerNode::new(state, ws, nodes.next().unwrap(), split);
+    // Make the container visible before adding the remaining children so that they
+    // don't lose the keyboard focus.
+    container.tl_set_visible(ws.container_visible());
+    for node in nodes {
+        container.append_child(node);
+    }
+    container.set_child_factors(&factors);
+    container
+}
@@ -0,0 +1,103 @@
+use crate::tree::{
+    layout::{linear, master_stack, spiral, Shape},
+    ContainerSplit::{Horizontal, Vertical},
+};
+
+fn leaf(n: u32) -> Shape<u32> {
+    Shape::Leaf(n)
+}
+
+#[test]
+fn linear_splits_evenly() {
+    let (split, children) = linear(Horizontal, &[1, 2, 3, 4]);
+    assert_eq!(split, Horizontal);
+    assert_eq!(
+        children,
+        vec![
+            (leaf(1), 0.25),
+            (leaf(2), 0.25),
+            (leaf(3), 0.25),
+            (leaf(4), 0.25)
+        ],
+    );
+}
+
+#[test]
+fn master_stack_single_master() {
+    let (split, children) = master_stack(&[1, 2, 3], 1, 0.6);
+    assert_eq!(split, Horizontal);
+    assert_eq!(
+        children,
+        vec![
+            (leaf(1), 0.6),
+            (
+                Shape::Split(Vertical, vec![(leaf(2), 0.5), (leaf(3), 0.5)]),
+                0.4
+            ),
+        ],
+    );
+}
+
+#[test]
+fn master_stack_multiple_masters() {
+    let (split, children) = master_stack(&[1, 2, 3], 2, 0.5);
+    assert_eq!(split, Horizontal);
+    assert_eq!(
+        children,
+        vec![
+            (
+                Shape::Split(Vertical, vec![(leaf(1), 0.5), (leaf(2), 0.5)]),
+                0.5
+            ),
+            (leaf(3), 0.5),
+        ],
+    );
+}
+
+#[test]
+fn master_stack_without_stack() {
+    assert_eq!(master_stack(&[1, 2], 0, 0.5), linear(Vertical, &[1, 2]));
+    assert_eq!(master_stack(&[1, 2], 5, 0.5), linear(Vertical, &[1, 2]));
+}
+
+#[test]
+fn spiral_single() {
+    assert_eq!(spiral(&1, &[], 0), leaf(1));
+}
+
+#[test]
+fn spiral_alternates() {
+    let shape = spiral(&1, &[2, 3, 4, 5], 0);
+    let expected = Shape::Split(
+        Horizontal,
+        vec![
+            (leaf(1), 0.5),
+            (
+                Shape::Split(
+                    Vertical,
+                    vec![
+                        (leaf(2), 0.5),
+                        (
+                            Shape::Split(
+                                Horizontal,
+                                vec![
+                                    (
+                                        Shape::Split(
+                                            Vertical,
+                                            vec![(leaf(5), 0.5), (leaf(4), 0.5)],
+                                        ),
+                                        0.5,
+                                    ),
+                                    (leaf(3), 0.5),
+                                ],
+                            ),
+                            0.5,
+                        ),
+                    ],
+                ),
+                0.5,
+            ),
+        ],
+    );
+    assert_eq!(shape, expected);
+}
@@ -1,7 +1,9 @@
 use {
     crate::{
+        animation::{Animation, AnimationKind, ClosingWindow, WorkspaceAnimation},
         backend::{HardwareCursor, KeyState, Mode},
         client::ClientId,
+        cpu_worker::PendingJob,
         cursor::KnownCursor,
         fixed::Fixed,
         gfx_api::{AcquireSync, BufferResv, GfxTexture, ReleaseSync},
@@ -15,7 +17,8 @@ use {
                 collect_kb_foci2,
                 tablet::{TabletTool, TabletToolChanges, TabletToolId},
                 wl_pointer::PendingScroll,
-                NodeSeatState, SeatId, WlSeatGlobal, BTN_LEFT,
+                NodeSeatState, SeatId, WlSeatGlobal, BTN_EXTRA, BTN_LEFT, BTN_MIDDLE, BTN_RIGHT,
+                BTN_SIDE,
             },
             wl_surface::{
                 ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
@@ -33,10 +36,11 @@ use {
         scale::Scale,
         state::State,
         text::TextTexture,
+        theme::Color,
         tree::{
             walker::NodeVisitor, Direction, FindTreeResult, FindTreeUsecase, FoundNode, Node,
             NodeId, StackedNode, TddType, TileDragDestination, WorkspaceDragDestination,
-            WorkspaceNode, WorkspaceNodeId,
+            WorkspaceNode, WorkspaceNodeId, DEFAULT_MASTER_COUNT, DEFAULT_MASTER_RATIO,
         },
         utils::{
             asyncevent::AsyncEvent, clonecell::CloneCell, copyhashmap::CopyHashMap,
@@ -44,21 +48,29 @@ use {
             linkedlist::LinkedList, on_drop_event::OnDropEvent, scroller::Scroller,
             transform_ext::TransformExt,
         },
+        wallpaper::{DecodeJob, Wallpaper},
         wire::{
             ExtImageCopyCaptureSessionV1Id, JayOutputId, JayScreencastId, ZwlrScreencopyFrameV1Id,
         },
     },
     ahash::AHashMap,
-    jay_config::video::{TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode},
+    jay_config::{
+        _private::ipc::StatusClick,
+        keyboard::mods::Modifiers,
+        video::{TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode},
+        BarMode as ConfigBarMode, BarPosition as ConfigBarPosition,
+    },
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
-        ops::{BitOrAssign, Deref},
+        ops::{BitOrAssign, Deref, Range},
         rc::Rc,
     },
 };
 
+const WINDOW_SWITCHER_PADDING: i32 = 5;
+
 tree_id!(OutputNodeId);
 pub struct OutputNode {
     pub id: OutputNodeId,
@@ -72,10 +84,15 @@ pub struct OutputNode {
     pub workspace_rect: Cell<Rect>,
     pub non_exclusive_rect: Cell<Rect>,
     pub non_exclusive_rect_rel: Cell<Rect>,
+    pub bar_rect: Cell<Rect>,
+    pub bar_rect_rel: Cell<Rect>,
+    pub bar_mode: Cell<BarMode>,
+    pub bar_position: Cell<BarPosition>,
     pub render_data: RefCell<OutputRenderData>,
     pub state: Rc<State>,
     pub is_dummy: bool,
     pub status: CloneCell<Rc<String>>,
+    pub status_blocks: CloneCell<Option<Rc<StatusBlocks>>>,
     pub scroll: Scroller,
     pub pointer_positions: CopyHashMap<PointerType, (i32, i32)>,
     pub pointer_down: CopyHashMap<SeatId, (i32, i32)>,
@@ -97,6 +114,13 @@ pub struct OutputNode {
     pub before_latch_event: EventSource<dyn BeforeLatchListener>,
     pub tray_start_rel: Cell<i32>,
     pub tray_items: LinkedList<Rc<dyn DynTrayItem>>,
+    pub window_switcher: RefCell<Option<OutputWindowSwitcher>>,
+    pub wallpaper: CloneCell<Option<Rc<Wallpaper>>>,
+    pub wallpaper_job: Cell<Option<PendingJob>>,
+    pub workspace_animation: RefCell<Option<WorkspaceAnimation>>,
+    pub closing_windows: RefCell<Vec<ClosingWindow>>,
+    /// The absolute areas whose backgrounds were blurred in the last frame.
+    pub blurred_areas: RefCell<Vec<Rect>>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -174,6 +198,17 @@ impl OutputNode {
         for listener in self.latch_event.iter() {
             listener.after_latch(self, tearing);
         }
+        self.animations_latched();
+    }
+
+    fn animations_latched(&self) {
+        if !self.schedule.animating() {
+            self.workspace_animation.take();
+        }
+        let now = self.state.now_nsec();
+        self.closing_windows
+            .borrow_mut()
+            .retain(|w| w.animation.end() > now);
     }
 
     pub fn vblank(&self) {
@@ -213,13 +248,23 @@ impl OutputNode {
                 exclusive = exclusive.max(&surface.exclusive_size());
             }
         }
-        if self.exclusive_zones.replace(exclusive) != exclusive {
+        let bar_mode = self.desired_bar_mode();
+        let bar_position = self.state.bar_position.get();
+        let exclusive_changed = self.exclusive_zones.replace(exclusive) != exclusive;
+        let mode_changed = self.bar_mode.replace(bar_mode) != bar_mode;
+        let position_changed = self.bar_position.replace(bar_position) != bar_position;
+        if exclusive_changed || mode_changed || position_changed {
             self.update_rects();
-            for layer in &self.layers {
-                for surface in layer.iter() {
-                    surface.exclusive_zones_changed();
+            if exclusive_changed {
+                for layer in &self.layers {
+                    for surface in layer.iter() {
+                        surface.exclusive_zones_changed();
+                    }
                 }
             }
+            if mode_changed {
+                self.update_visible();
+            }
             if let Some(c) = self.workspace.get() {
                 c.change_extents(&self.workspace_rect.get());
             }
@@ -229,6 +274,43 @@ impl OutputNode {
         }
     }
 
+    fn desired_bar_mode(&self) -> BarMode {
+        if let Some(connectors) = &*self.state.bar_connectors.borrow() {
+            if !connectors.contains(&self.global.connector.name) {
+                return BarMode::Hidden;
+            }
+        }
+        self.state.bar_mode.get()
+    }
+
+    /// Returns whether the bar is currently shown, ignoring fullscreen windows and the
+    /// screen lock.
+    pub fn bar_shown(&self) -> bool {
+        match self.bar_mode.get() {
+            BarMode::Visible => true,
+            BarMode::Hidden => false,
+            BarMode::OnModifier => self.state.bar_revealed.get(),
+        }
+    }
+
+    pub fn bar_visible(&self) -> bool {
+        self.title_visible.get() && self.bar_shown()
+    }
+
+    /// Extends a rect of the bar by the separator between the bar and the workspace.
+    pub fn with_bar_separator(&self, bar: Rect) -> Rect {
+        let y1 = match self.bar_position.get() {
+            BarPosition::Top => bar.y1(),
+            BarPosition::Bottom => bar.y1() - 1,
+        };
+        Rect::new_sized_unchecked(bar.x1(), y1, bar.width(), bar.height() + 1)
+    }
+
+    pub fn workspace_rect_rel(&self) -> Rect {
+        let pos = self.global.pos.get();
+        self.workspace_rect.get().move_(-pos.x1(), -pos.y1())
+    }
+
     pub fn add_screencast(&self, sc: &Rc<JayScreencast>) {
         self.screencasts.set((sc.client.id, sc.id), sc.clone());
         self.screencast_changed();
@@ -414,6 +496,82 @@ impl OutputNode {
         self.screencasts.clear();
         self.screencopies.clear();
         self.ext_copy_sessions.clear();
+        self.wallpaper.take();
+        if let Some(job) = self.wallpaper_job.take() {
+            job.detach();
+        }
+        self.workspace_animation.take();
+        self.closing_windows.take();
+    }
+
+    /// Returns whether animations are shown on this output.
+    pub fn animations_enabled(&self) -> bool {
+        let animations = &self.state.animations;
+        if !animations.enabled.get() || self.has_fullscreen() || self.tearing_enabled() {
+            return false;
+        }
+        animations.in_screencasts.get()
+            || (self.screencasts.is_empty() && self.ext_copy_sessions.is_empty())
+    }
+
+    /// Starts an animation on this output.
+    ///
+    /// The output is redrawn every frame until the animation has finished.
+    pub fn start_animation(&self, kind: AnimationKind) -> Option<Animation> {
+        if !self.animations_enabled() {
+            return None;
+        }
+        let animation = self.state.animations.create(kind, self.state.now_nsec())?;
+        self.schedule.animate_until(animation.end());
+        Some(animation)
+    }
+
+    fn animate_workspace_switch(&self, old: &Rc<WorkspaceNode>, new: &WorkspaceNode) {
+        if old.fullscreen.is_some() {
+            self.workspace_animation.take();
+            return;
+        }
+        let Some(animation) = self.start_animation(AnimationKind::Workspace) else {
+            self.workspace_animation.take();
+            return;
+        };
+        let mut direction = 1;
+        for ws in self.workspaces.iter() {
+            if ws.id == old.id {
+                break;
+            }
+            if ws.id == new.id {
+                direction = -1;
+                break;
+            }
+        }
+        *self.workspace_animation.borrow_mut() = Some(WorkspaceAnimation {
+            animation,
+            old: old.clone(),
+            direction,
+        });
+    }
+
+    /// Loads the wallpaper configured for this output.
+    ///
+    /// The current wallpaper stays visible until the new wallpaper has been loaded.
+    pub fn load_wallpaper(self: &Rc<Self>) {
+        if let Some(job) = self.wallpaper_job.take() {
+            job.detach();
+        }
+        let config = self.global.persistent.wallpaper.borrow().clone();
+        match config {
+            Some(config) => {
+                let job = self.state.cpu_worker.submit(DecodeJob::new(&config, self));
+                self.wallpaper_job.set(Some(job));
+            }
+            _ => self.set_wallpaper(None),
+        }
+    }
+
+    pub fn set_wallpaper(&self, wallpaper: Option<Rc<Wallpaper>>) {
+        self.wallpaper.set(wallpaper);
+        self.state.damage(self.global.pos.get());
     }
 
     pub fn on_spaces_changed(self: &Rc<Self>) {
@@ -460,8 +618,10 @@ impl OutputNode {
         let Some(ctx) = self.state.render_ctx.get() else {
             return on_completed.event();
         };
-        let font = self.state.theme.font.get();
         let theme = &self.state.theme;
+        let bar_font = theme.bar_font();
+        let status_font = theme.status_font();
+        let title_font = theme.title_font();
         let th = theme.sizes.title_height.get();
         let scale = self.global.persistent.scale.get();
         let scale = if scale != 1 {
@@ -484,7 +644,7 @@ impl OutputNode {
             tex.schedule_render_fitting(
                 on_completed.clone(),
                 Some(texture_height),
-                &font,
+                &bar_font,
                 &ws.name,
                 tc,
                 false,
@@ -501,15 +661,134 @@ impl OutputNode {
         tex.tex.schedule_render_fitting(
             on_completed.clone(),
             Some(texture_height),
-            &font,
+            &status_font,
             &status,
             tc,
             true,
             scale,
         );
+        let blocks = self.status_blocks.get();
+        let blocks = match &blocks {
+            Some(b) => &b.blocks[..],
+            None => &[],
+        };
+        rd.status_blocks.truncate(blocks.len());
+        while rd.status_blocks.len() < blocks.len() {
+            rd.status_blocks.push(OutputStatusBlock {
+                tex: TextTexture::new(&self.state.cpu_worker, &ctx),
+                min_width_tex: None,
+                tex_x: 0,
+                rect: Default::default(),
+                background: None,
+                borders: Default::default(),
+                border_color: Color::TRANSPARENT,
+            });
+        }
+        for (block, rd_block) in blocks.iter().zip(rd.status_blocks.iter_mut()) {
+            rd_block.tex.schedule_render_fitting(
+                on_completed.clone(),
+                Some(texture_height),
+                &status_font,
+                &block.text,
+                block.color.unwrap_or(tc),
+                block.markup,
+                scale,
+            );
+            match &block.min_width {
+                Some(StatusBlockMinWidth::Text(text)) => {
+                    let tex = rd_block
+                        .min_width_tex
+                        .get_or_insert_with(|| TextTexture::new(&self.state.cpu_worker, &ctx));
+                    tex.schedule_render_fitting(
+                        on_completed.clone(),
+                        Some(texture_height),
+                        &status_font,
+                        text,
+                        tc,
+                        block.markup,
+                        scale,
+                    );
+                }
+                _ => rd_block.min_width_tex = None,
+            }
+        }
+        match self.status_blocks.get() {
+            Some(blocks) if blocks.blocks.len() > 1 => {
+                let sep = rd
+                    .status_separator
+                    .get_or_insert_with(|| OutputStatusSeparator {
+                        tex: TextTexture::new(&self.state.cpu_worker, &ctx),
+                        tex_x: vec![],
+                    });
+                sep.tex.schedule_render_fitting(
+                    on_completed.clone(),
+                    Some(texture_height),
+                    &status_font,
+                    &blocks.separator,
+                    tc,
+                    true,
+                    scale,
+                );
+            }
+            _ => rd.status_separator = None,
+        }
+        rd.window_switcher_rows = None;
+        if let Some(sw) = &*self.window_switcher.borrow() {
+            let (rows, rect) = self.window_switcher_layout(sw);
+            let mut width = rect.width() - 4 * WINDOW_SWITCHER_PADDING;
+            if let Some(scale) = scale {
+                width = (width as f64 * scale).round() as _;
+            }
+            while rd.window_switcher_textures.len() < rows.len() {
+                let tex = TextTexture::new(&self.state.cpu_worker, &ctx);
+                rd.window_switcher_textures.push(tex);
+            }
+            for (tex, idx) in rd.window_switcher_textures.iter().zip(rows.clone()) {
+                let tc = match idx == sw.selected {
+                    true => theme.colors.focused_title_text.get(),
+                    false => theme.colors.unfocused_title_text.get(),
+                };
+                tex.schedule_render(
+                    on_completed.clone(),
+                    1,
+                    None,
+                    width,
+                    texture_height,
+                    1,
+                    &title_font,
+                    &sw.titles[idx],
+                    tc,
+                    true,
+                    false,
+                    scale,
+                );
+            }
+            rd.window_switcher_rows = Some((rows, sw.selected, rect));
+        }
         on_completed.event()
     }
 
+    /// Returns the visible rows of the window switcher and its position relative to
+    /// the output.
+    fn window_switcher_layout(&self, sw: &OutputWindowSwitcher) -> (Range<usize>, Rect) {
+        let th = self.state.theme.sizes.title_height.get();
+        let row_height = th + 2 * WINDOW_SWITCHER_PADDING;
+        let pos = self.global.pos.get();
+        let max_rows = ((pos.height() - 2 * WINDOW_SWITCHER_PADDING) / row_height).max(1) as usize;
+        let num_rows = sw.titles.len().min(max_rows);
+        let start = (sw.selected + 1).saturating_sub(num_rows);
+        let width = (pos.width() / 3).max(pos.width().min(400));
+        let height = num_rows as i32 * row_height + 2 * WINDOW_SWITCHER_PADDING;
+        let rect = Rect::new_sized(
+            (pos.width() - width) / 2,
+            (pos.height() - height) / 2,
+            width,
+            height,
+        )
+        .unwrap();
+        (start..start + num_rows, rect)
+    }
+
     fn update_render_data_phase2(&self) {
         let mut rd = self.render_data.borrow_mut();
         rd.titles.clear();
@@ -527,9 +806,12 @@ impl OutputNode {
             None
         };
         let active_id = self.workspace.get().map(|w| w.id);
-        let non_exclusive_rect = self.non_exclusive_rect.get();
-        let output_width = non_exclusive_rect.width();
-        rd.underline = Rect::new_sized(0, th, output_width, 1).unwrap();
+        let bar_rect = self.bar_rect.get();
+        let underline_y = match self.bar_position.get() {
+            BarPosition::Top => th,
+            BarPosition::Bottom => -1,
+        };
+        rd.underline = Rect::new_sized(0, underline_y, bar_rect.width(), 1).unwrap();
         for ws in self.workspaces.iter() {
             let mut title_width = th;
             let title = &*ws.title_texture.borrow();
@@ -589,15 +871,48 @@ impl OutputNode {
                 status.tex_x = pos;
             }
         }
-        if self.title_visible.get() {
-            let title_rect = Rect::new_sized(
-                non_exclusive_rect.x1(),
-                non_exclusive_rect.y1(),
-                non_exclusive_rect.width(),
-                th,
-            )
-            .unwrap();
-            self.state.damage(title_rect);
+        if let Some(blocks) = self.status_blocks.get() {
+            self.layout_status_blocks(&mut rd, &blocks, scale);
+        }
+        if self.bar_visible() {
+            self.state.damage(self.with_bar_separator(bar_rect));
+        }
+        let pos = self.global.pos.get();
+        if let Some(sw) = rd.window_switcher.take() {
+            self.state.damage(sw.rect.move_(pos.x1(), pos.y1()));
+        }
+        if let Some((rows, selected, rect)) = rd.window_switcher_rows.take() {
+            let mut titles = vec![];
+            let mut selected_rect = None;
+            let row_height = th + 2 * WINDOW_SWITCHER_PADDING;
+            for (i, (tex, idx)) in rd.window_switcher_textures.iter().zip(rows).enumerate() {
+                let row = Rect::new_sized(
+                    rect.x1() + WINDOW_SWITCHER_PADDING,
+                    rect.y1() + WINDOW_SWITCHER_PADDING + i as i32 * row_height,
+                    rect.width() - 2 * WINDOW_SWITCHER_PADDING,
+                    row_height,
+                )
+                .unwrap();
+                if idx == selected {
+                    selected_rect = Some(row);
+                }
+                if let Err(e) = tex.flip() {
+                    log::error!("Could not render window title: {}", ErrorFmt(e));
+                }
+                if let Some(texture) = tex.texture() {
+                    titles.push(OutputWindowSwitcherTitle {
+                        tex_x: row.x1() + WINDOW_SWITCHER_PADDING,
+                        tex_y: row.y1() + WINDOW_SWITCHER_PADDING,
+                        tex: texture,
+                    });
+                }
+            }
+            self.state.damage(rect.move_(pos.x1(), pos.y1()));
+            rd.window_switcher = Some(OutputWindowSwitcherRenderData {
+                rect,
+                selected: selected_rect,
+                titles,
+            });
         }
     }
 
@@ -625,10 +940,22 @@ impl OutputNode {
 
     pub fn show_workspace(&self, ws: &Rc<WorkspaceNode>) -> bool {
         let mut seats = SmallVec::new();
+        let mut sticky = vec![];
         if let Some(old) = self.workspace.set(Some(ws.clone())) {
             if old.id == ws.id {
                 return false;
             }
+            self.animate_workspace_switch(&old, ws);
+            for stacked in old.stacked.iter() {
+                if let Some(float) = stacked.deref().clone().node_into_float() {
+                    if float.is_sticky() {
+                        sticky.push(float);
+                    }
+                }
+            }
+            for float in &sticky {
+                float.follow_workspace(ws);
+            }
             collect_kb_foci2(old.clone(), &mut seats);
             if old.is_empty() {
                 for jw in old.jay_workspaces.lock().values() {
@@ -643,6 +970,9 @@ impl OutputNode {
             }
         }
         self.update_visible();
+        for float in sticky {
+            float.stacked_set_visible(ws.container_visible());
+        }
         if let Some(fs) = ws.fullscreen.get() {
             fs.tl_change_extents(&self.global.pos.get());
         }
@@ -664,6 +994,7 @@ impl OutputNode {
             output: CloneCell::new(self.clone()),
             position: Cell::new(Default::default()),
             container: Default::default(),
+            scrolling: Default::default(),
             stacked: Default::default(),
             seat_state: Default::default(),
             name: name.to_string(),
@@ -678,6 +1009,11 @@ impl OutputNode {
             title_texture: Default::default(),
             attention_requests: Default::default(),
             render_highlight: Default::default(),
+            layout: Cell::new(self.state.default_workspace_layout.get()),
+            master_count: Cell::new(DEFAULT_MASTER_COUNT),
+            master_ratio: Cell::new(DEFAULT_MASTER_RATIO),
+            layout_scheduled: Cell::new(false),
+            reset_layout_factors: Cell::new(false),
         });
         ws.update_has_captures();
         *ws.output_link.borrow_mut() = Some(self.workspaces.add_last(ws.clone()));
@@ -716,10 +1052,29 @@ impl OutputNode {
             width,
             height,
         ));
-        let y1 = y1 + th + 1;
-        let height = (y2 - y1).max(0);
+        let bar_y1 = match self.bar_position.get() {
+            BarPosition::Top => y1,
+            BarPosition::Bottom => y2 - th,
+        };
+        self.bar_rect
+            .set(Rect::new_sized_unchecked(x1, bar_y1, width, th));
+        self.bar_rect_rel.set(Rect::new_sized_unchecked(
+            exclusive.left,
+            bar_y1 - rect.y1(),
+            width,
+            th,
+        ));
+        let bar_height = match self.bar_mode.get() {
+            BarMode::Visible => th + 1,
+            BarMode::Hidden | BarMode::OnModifier => 0,
+        };
+        let ws_y1 = match self.bar_position.get() {
+            BarPosition::Top => y1 + bar_height,
+            BarPosition::Bottom => y1,
+        };
+        let height = (height - bar_height).max(0);
         self.workspace_rect
-            .set(Rect::new_sized_unchecked(x1, y1, width, height));
+            .set(Rect::new_sized_unchecked(x1, ws_y1, width, height));
         self.update_tray_positions();
         self.schedule_update_render_data();
     }
@@ -882,9 +1237,144 @@ impl OutputNode {
 
     pub fn set_status(self: &Rc<Self>, status: &Rc<String>) {
         self.status.set(status.clone());
+        self.status_blocks.take();
+        self.schedule_update_render_data();
+    }
+
+    pub fn set_status_blocks(self: &Rc<Self>, blocks: &Rc<StatusBlocks>) {
+        self.status.set(Default::default());
+        self.status_blocks.set(Some(blocks.clone()));
         self.schedule_update_render_data();
     }
 
+    fn layout_status_blocks(
+        &self,
+        rd: &mut OutputRenderData,
+        blocks: &StatusBlocks,
+        scale: Option<f64>,
+    ) {
+        let th = self.state.theme.sizes.title_height.get();
+        let logical_width = |tex: &TextTexture| {
+            if let Err(e) = tex.flip() {
+                log::error!("Could not render status: {}", ErrorFmt(e));
+            }
+            let (mut width, _) = tex.texture()?.size();
+            if let Some(scale) = scale {
+                width = (width as f64 / scale).round() as _;
+            }
+            Some(width)
+        };
+        let mut separator_width = 0;
+        if let Some(sep) = &mut rd.status_separator {
+            sep.tex_x.clear();
+            separator_width = logical_width(&sep.tex).unwrap_or(0);
+        }
+        let urgent = self.state.theme.colors.attention_requested_background.get();
+        let mut pos = self.tray_start_rel.get() - 1;
+        for (idx, (block, rd_block)) in blocks
+            .blocks
+            .iter()
+            .zip(rd.status_blocks.iter_mut())
+            .enumerate()
+            .rev()
+        {
+            let text_width = logical_width(&rd_block.tex).unwrap_or(0);
+            let min_width = match &block.min_width {
+                Some(StatusBlockMinWidth::Pixels(w)) => *w,
+                Some(StatusBlockMinWidth::Text(_)) => rd_block
+                    .min_width_tex
+                    .as_ref()
+                    .and_then(logical_width)
+                    .unwrap_or(0),
+                None => 0,
+            };
+            let [top, right, bottom, left] = match block.border {
+                Some(_) => block.border_widths,
+                None => [0; 4],
+            };
+            let content_width = text_width.max(min_width);
+            let x1 = pos - content_width - left - right;
+            let rect = Rect::new_sized(x1, 0, pos - x1, th).unwrap();
+            let mut tex_x = x1 + left;
+            match block.align {
+                StatusBlockAlign::Left => {}
+                StatusBlockAlign::Center => tex_x += (content_width - text_width) / 2,
+                StatusBlockAlign::Right => tex_x += content_width - text_width,
+            }
+            rd_block.tex_x = tex_x;
+            rd_block.rect = rect;
+            rd_block.background = match block.urgent {
+                true => Some(urgent),
+                false => block.background,
+            };
+            rd_block.border_color = block.border.unwrap_or(Color::TRANSPARENT);
+            rd_block.borders.clear();
+            let borders = [
+                Rect::new_sized(x1, 0, rect.width(), top),
+                Rect::new_sized(rect.x2() - right, 0, right, th),
+                Rect::new_sized(x1, th - bottom, rect.width(), bottom),
+                Rect::new_sized(x1, 0, left, th),
+            ];
+            rd_block
+                .borders
+                .extend(borders.into_iter().flatten().filter(|r| !r.is_empty()));
+            pos = x1;
+            if idx > 0 {
+                let prev = &blocks.blocks[idx - 1];
+                let mut gap = prev.separator_block_width;
+                if prev.separator {
+                    gap = gap.max(separator_width);
+                    if let Some(sep) = &mut rd.status_separator {
+                        sep.tex_x.push(pos - gap + (gap - separator_width) / 2);
+                    }
+                }
+                pos -= gap;
+            }
+        }
+    }
+
+    fn status_click(&self, seat: &Rc<WlSeatGlobal>, button: u32) -> bool {
+        let Some((x, y)) = self.pointer_positions.get(&PointerType::Seat(seat.id())) else {
+            return false;
+        };
+        if !self.bar_visible() {
+            return false;
+        }
+        let (bar_x, bar_y) = self.bar_rect_rel.get().translate(x, y);
+        let rect = 'rect: {
+            let rd = self.render_data.borrow();
+            for (idx, block) in rd.status_blocks.iter().enumerate() {
+                if block.rect.contains(bar_x, bar_y) {
+                    break 'rect Some((idx, block.rect));
+                }
+            }
+            None
+        };
+        let Some((block, rect)) = rect else {
+            return false;
+        };
+        let Some(config) = self.state.config.get() else {
+            return false;
+        };
+        let pos = self.global.pos.get();
+        let mods = seat.latest_xkb_state().borrow().mods.mods_effective;
+        config.status_clicked(StatusClick {
+            block,
+            button,
+            modifiers: Modifiers(mods),
+            x: pos.x1() + x,
+            y: pos.y1() + y,
+            relative_x: bar_x - rect.x1(),
+            relative_y: bar_y - rect.y1(),
+            output_x: x,
+            output_y: y,
+            width: rect.width(),
+            height: rect.height(),
+            scale: self.global.persistent.scale.get().to_f64(),
+        });
+        true
+    }
+
     fn pointer_move(self: &Rc<Self>, id: PointerType, x: Fixed, y: Fixed) {
         self.pointer_positions
             .set(id, (x.round_down(), y.round_down()));
@@ -936,8 +1426,9 @@ impl OutputNode {
         self.title_visible.set(lower_visible);
         set_layer_visible!(self.layers[0], lower_visible);
         set_layer_visible!(self.layers[1], lower_visible);
+        let bar_visible = lower_visible && self.bar_shown();
         for item in self.tray_items.iter() {
-            item.set_visible(lower_visible);
+            item.set_visible(bar_visible);
         }
         if let Some(ws) = self.workspace.get() {
             ws.set_visible(visible);
@@ -954,8 +1445,11 @@ impl OutputNode {
         if let PointerType::Seat(s) = id {
             self.pointer_down.set(s, (x, y));
         }
-        let (x, y) = self.non_exclusive_rect_rel.get().translate(x, y);
-        if y >= self.state.theme.sizes.title_height.get() {
+        if !self.bar_visible() {
+            return;
+        }
+        let (x, y) = self.bar_rect_rel.get().translate(x, y);
+        if y < 0 || y >= self.state.theme.sizes.title_height.get() {
             return;
         }
         let ws = 'ws: {
@@ -1012,7 +1506,12 @@ impl OutputNode {
     }
 
     fn update_tearing(&self) {
-        let enabled = match self.global.persistent.tearing_mode.get() {
+        let enabled = self.tearing_enabled();
+        self.global.connector.connector.set_tearing_enabled(enabled);
+    }
+
+    fn tearing_enabled(&self) -> bool {
+        match self.global.persistent.tearing_mode.get() {
             TearingMode::Never => false,
             TearingMode::Always => true,
             TearingMode::Fullscreen { surface } => 'get: {
@@ -1034,8 +1533,7 @@ impl OutputNode {
                 }
                 true
             }
-        };
-        self.global.connector.connector.set_tearing_enabled(enabled);
+        }
     }
 
     pub fn tile_drag_destination(
@@ -1076,7 +1574,9 @@ impl OutputNode {
             return None;
         }
         let th = self.state.theme.sizes.title_height.get();
-        if y_abs < rect.y1() + th + 1 {
+        let bar = self.bar_rect.get();
+        if self.bar_shown() && self.with_bar_separator(bar).contains(x_abs, y_abs) {
+            let rect = bar;
             let rd = &*self.render_data.borrow();
             let (x, _) = rect.translate(x_abs, y_abs);
             let mut last_x2 = 0;
@@ -1103,11 +1603,13 @@ impl OutputNode {
                 },
             });
         }
-        let thp1 = self.state.theme.sizes.title_height.get() + 1;
-        let rect = Rect::new(rect.x1(), rect.y1() + thp1, rect.x2(), rect.y2())?;
+        let rect = self.workspace_rect.get();
         if !rect.contains(x_abs, y_abs) {
             return None;
         }
+        if let Some(s) = ws.scrolling.get() {
+            return s.tile_drag_destination(source, x_abs, y_abs);
+        }
         let Some(c) = ws.container.get() else {
             return Some(TileDragDestination {
                 highlight: rect,
@@ -1123,14 +1625,14 @@ impl OutputNode {
         x_abs: i32,
         y_abs: i32,
     ) -> Option<WorkspaceDragDestination> {
-        let rect = self.non_exclusive_rect.get();
-        if !rect.contains(x_abs, y_abs) {
+        if !self.bar_shown() {
             return None;
         }
-        let th = self.state.theme.sizes.title_height.get();
-        if y_abs - rect.y1() > th + 1 {
+        let rect = self.bar_rect.get();
+        if !self.with_bar_separator(rect).contains(x_abs, y_abs) {
             return None;
         }
+        let th = self.state.theme.sizes.title_height.get();
         let rd = &*self.render_data.borrow();
         let (x, _) = rect.translate(x_abs, y_abs);
         let mut prev_is_source = false;
@@ -1177,7 +1679,7 @@ impl OutputNode {
 
     pub fn update_tray_positions(self: &Rc<Self>) {
         let th = self.state.theme.sizes.title_height.get();
-        let rect = self.non_exclusive_rect.get();
+        let rect = self.bar_rect.get();
         let output_width = rect.width();
         let mut right = output_width;
         let mut have_any = false;
@@ -1199,7 +1701,7 @@ impl OutputNode {
         if prev_right != right {
             {
                 let min = prev_right.min(right);
-                let rect = Rect::new_sized(rect.x1() + min, 0, output_width, th).unwrap();
+                let rect = Rect::new_sized(rect.x1() + min, rect.y1(), output_width, th).unwrap();
                 self.state.damage(rect);
             }
             self.schedule_update_render_data();
@@ -1216,11 +1718,77 @@ pub struct OutputTitle {
     pub ws: Rc<WorkspaceNode>,
 }
 
+/// The contents of the window switcher shown on an output.
+pub struct OutputWindowSwitcher {
+    pub titles: Vec<String>,
+    pub selected: usize,
+}
+
+pub struct OutputWindowSwitcherTitle {
+    pub tex_x: i32,
+    pub tex_y: i32,
+    pub tex: Rc<dyn GfxTexture>,
+}
+
+pub struct OutputWindowSwitcherRenderData {
+    pub rect: Rect,
+    pub selected: Option<Rect>,
+    pub titles: Vec<OutputWindowSwitcherTitle>,
+}
+
 pub struct OutputStatus {
     pub tex_x: i32,
     pub tex: TextTexture,
 }
 
+/// A status block set via the i3bar protocol.
+pub struct StatusBlock {
+    pub text: String,
+    pub markup: bool,
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub border: Option<Color>,
+    /// The widths of the top, right, bottom, and left borders.
+    pub border_widths: [i32; 4],
+    pub min_width: Option<StatusBlockMinWidth>,
+    pub align: StatusBlockAlign,
+    pub urgent: bool,
+    pub separator: bool,
+    pub separator_block_width: i32,
+}
+
+pub enum StatusBlockMinWidth {
+    Pixels(i32),
+    Text(String),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StatusBlockAlign {
+    Left,
+    Center,
+    Right,
+}
+
+pub struct StatusBlocks {
+    pub blocks: Vec<StatusBlock>,
+    pub separator: String,
+}
+
+pub struct OutputStatusBlock {
+    pub tex: TextTexture,
+    pub min_width_tex: Option<TextTexture>,
+    pub tex_x: i32,
+    pub rect: Rect,
+    pub background: Option<Color>,
+    pub borders: SmallVec<[Rect; 4]>,
+    pub border_color: Color,
+}
+
+pub struct OutputStatusSeparator {
+    pub tex: TextTexture,
+    pub tex_x: Vec<i32>,
+}
+
 #[derive(Copy, Clone)]
 pub struct OutputWorkspaceRenderData {
     pub rect: Rect,
@@ -1236,6 +1804,11 @@ pub struct OutputRenderData {
     pub captured_inactive_workspaces: Vec<Rect>,
     pub titles: Vec<OutputTitle>,
     pub status: Option<OutputStatus>,
+    pub status_blocks: Vec<OutputStatusBlock>,
+    pub status_separator: Option<OutputStatusSeparator>,
+    pub window_switcher: Option<OutputWindowSwitcherRenderData>,
+    pub window_switcher_textures: Vec<TextTexture>,
+    pub window_switcher_rows: Option<(Range<usize>, usize, Rect)>,
 }
 
 impl Debug for OutputNode {
@@ -1297,7 +1870,7 @@ impl Node for OutputNode {
     fn node_find_tree_at(
         &self,
         x: i32,
-        mut y: i32,
+        y: i32,
         tree: &mut Vec<FoundNode>,
         usecase: FindTreeUsecase,
     ) -> FindTreeResult {
@@ -1314,10 +1887,11 @@ impl Node for OutputNode {
             }
             return FindTreeResult::AcceptsInput;
         }
-        let bar_height = self.state.theme.sizes.title_height.get() + 1;
+        let bar_area = self.with_bar_separator(self.bar_rect_rel.get());
+        let in_bar = self.bar_shown() && bar_area.contains(x, y);
         if usecase == FindTreeUsecase::SelectWorkspace {
-            if y >= bar_height {
-                y -= bar_height;
+            if !in_bar {
+                let (x, y) = self.workspace_rect_rel().translate(x, y);
                 if let Some(ws) = self.workspace.get() {
                     tree.push(FoundNode {
                         node: ws.clone(),
@@ -1362,8 +1936,8 @@ impl Node for OutputNode {
             let mut search_layers = true;
             let non_exclusive_rect = self.non_exclusive_rect_rel.get();
             if non_exclusive_rect.contains(x, y) {
-                let (x, y) = non_exclusive_rect.translate(x, y);
-                if y < bar_height {
+                if in_bar {
+                    let (x, y) = self.bar_rect_rel.get().translate(x, y);
                     search_layers = false;
                     for item in self.tray_items.iter() {
                         let data = item.data();
@@ -1380,7 +1954,7 @@ impl Node for OutputNode {
                     }
                 } else {
                     if let Some(ws) = self.workspace.get() {
-                        let y = y - bar_height;
+                        let (x, y) = self.workspace_rect_rel().translate(x, y);
                         let len = tree.len();
                         tree.push(FoundNode {
                             node: ws.clone(),
@@ -1415,6 +1989,21 @@ impl Node for OutputNode {
         state: KeyState,
         _serial: u64,
     ) {
+        if state == KeyState::Pressed {
+            let status_button = match button {
+                BTN_LEFT => Some(1),
+                BTN_MIDDLE => Some(2),
+                BTN_RIGHT => Some(3),
+                BTN_SIDE => Some(8),
+                BTN_EXTRA => Some(9),
+                _ => None,
+            };
+            if let Some(status_button) = status_button {
+                if self.status_click(seat, status_button) {
+                    return;
+                }
+            }
+        }
         if button != BTN_LEFT {
             return;
         }
@@ -1433,6 +2022,16 @@ impl Node for OutputNode {
         if steps == 0 {
             return;
         }
+        let status_button = match steps < 0 {
+            true => 4,
+            false => 5,
+        };
+        if self.status_click(seat, status_button) {
+            for _ in 1..steps.abs() {
+                self.status_click(seat, status_button);
+            }
+            return;
+        }
         let ws = match self.workspace.get() {
             Some(ws) => ws,
             _ => return,
@@ -1660,3 +2259,41 @@ impl TearingMode {
         }
     }
 }
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum BarPosition {
+    #[default]
+    Top,
+    Bottom,
+}
+
+impl BarPosition {
+    pub fn from_config(position: ConfigBarPosition) -> Option<Self> {
+        let res = match position {
+            ConfigBarPosition::TOP => Self::Top,
+            ConfigBarPosition::BOTTOM => Self::Bottom,
+            _ => return None,
+        };
+        Some(res)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum BarMode {
+    #[default]
+    Visible,
+    Hidden,
+    OnModifier,
+}
+
+impl BarMode {
+    pub fn from_config(mode: ConfigBarMode) -> Option<Self> {
+        let res = match mode {
+            ConfigBarMode::VISIBLE => Self::Visible,
+            ConfigBarMode::HIDDEN => Self::Hidden,
+            ConfigBarMode::ON_MODIFIER => Self::OnModifier,
+            _ => return None,
+        };
+        Some(res)
+    }
+}
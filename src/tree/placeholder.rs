@@ -11,8 +11,8 @@ use {
         text::TextTexture,
         tree::{
             default_tile_drag_destination, ContainerSplit, Direction, FindTreeResult,
-            FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitor, TileDragDestination,
-            ToplevelData, ToplevelNode, ToplevelNodeBase,
+            FindTreeUsecase, FoundNode, LayoutCriteria, Node, NodeId, NodeVisitor,
+            TileDragDestination, ToplevelData, ToplevelNode, ToplevelNodeBase,
         },
         utils::{
             asyncevent::AsyncEvent, errorfmt::ErrorFmt, on_drop_event::OnDropEvent,
@@ -35,6 +35,9 @@ pub struct PlaceholderNode {
     destroyed: Cell<bool>,
     update_textures_scheduled: Cell<bool>,
     state: Rc<State>,
+    /// The windows that can replace this placeholder if it was created from a layout
+    /// file.
+    pub criteria: Option<LayoutCriteria>,
     pub textures: RefCell<SmallMapMut<Scale, TextTexture, 2>>,
 }
 
@@ -60,6 +63,7 @@ impl PlaceholderNode {
             destroyed: Default::default(),
             update_textures_scheduled: Cell::new(false),
             state: state.clone(),
+            criteria: None,
             textures: Default::default(),
         }
     }
@@ -71,6 +75,19 @@ impl PlaceholderNode {
             destroyed: Default::default(),
             update_textures_scheduled: Default::default(),
             state: state.clone(),
+            criteria: None,
+            textures: Default::default(),
+        }
+    }
+
+    pub fn new_for_layout(state: &Rc<State>, criteria: &LayoutCriteria, slf: &Weak<Self>) -> Self {
+        Self {
+            id: state.node_ids.next(),
+            toplevel: ToplevelData::new(state, criteria.describe(), None, slf),
+            destroyed: Default::default(),
+            update_textures_scheduled: Default::default(),
+            state: state.clone(),
+            criteria: Some(criteria.clone()),
             textures: Default::default(),
         }
     }
@@ -95,6 +112,11 @@ impl PlaceholderNode {
         let scales = self.toplevel.state.scales.lock();
         let rect = self.toplevel.pos.get();
         let mut textures = self.textures.borrow_mut();
+        let title = self.toplevel.title.borrow();
+        let text = match self.criteria {
+            Some(_) => &**title,
+            _ => "Fullscreen",
+        };
         for (scale, _) in scales.iter() {
             let tex = textures
                 .get_or_insert_with(*scale, || TextTexture::new(&self.state.cpu_worker, &ctx));
@@ -111,7 +133,7 @@ impl PlaceholderNode {
                     on_completed.clone(),
                     Some(height),
                     &font,
-                    "Fullscreen",
+                    text,
                     self.toplevel.state.theme.colors.unfocused_title_text.get(),
                     false,
                     None,
@@ -198,6 +220,10 @@ impl Node for PlaceholderNode {
     fn node_into_toplevel(self: Rc<Self>) -> Option<Rc<dyn ToplevelNode>> {
         Some(self)
     }
+
+    fn node_into_placeholder(self: Rc<Self>) -> Option<Rc<PlaceholderNode>> {
+        Some(self)
+    }
 }
 
 impl ToplevelNodeBase for PlaceholderNode {
@@ -226,6 +252,9 @@ impl ToplevelNodeBase for PlaceholderNode {
 
     fn tl_destroy_impl(&self) {
         self.destroyed.set(true);
+        if self.criteria.is_some() {
+            self.state.layout_placeholders.remove(&self.node_id());
+        }
     }
 
     fn tl_last_active_child(self: Rc<Self>) -> Rc<dyn ToplevelNode> {
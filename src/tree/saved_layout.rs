@@ -0,0 +1,272 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        state::State,
+        tree::{ContainerNode, ContainerSplit, Node, PlaceholderNode, ToplevelNode, WorkspaceNode},
+    },
+    serde::{Deserialize, Serialize},
+    std::{io, rc::Rc},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum SavedLayoutError {
+    #[error("The workspace does not contain any tiled windows")]
+    Empty,
+    #[error("Layouts cannot be loaded into scrolling workspaces")]
+    Scrolling,
+    #[error("A container in the layout has no children")]
+    EmptyContainer,
+    #[error("The mono child {0} of a container is out of bounds")]
+    MonoChildOutOfBounds(usize),
+    #[error("Could not read {0}")]
+    Read(String, #[source] io::Error),
+    #[error("Could not write {0}")]
+    Write(String, #[source] io::Error),
+    #[error("Could not parse the layout")]
+    Parse(#[source] serde_json::Error),
+    #[error("Could not serialize the layout")]
+    Serialize(#[source] serde_json::Error),
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SavedSplit {
+    Horizontal,
+    Vertical,
+}
+
+/// A node of a layout file.
+///
+/// `factor` is the share of the parent container taken up by the node. `mono_child` is
+/// the index of the child that is shown if `mono` is set.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SavedLayoutNode {
+    Container {
+        #[serde(default = "default_factor")]
+        factor: f64,
+        split: SavedSplit,
+        #[serde(default)]
+        mono: bool,
+        #[serde(default)]
+        mono_child: usize,
+        #[serde(default)]
+        stacked: bool,
+        children: Vec<SavedLayoutNode>,
+    },
+    Window {
+        #[serde(default = "default_factor")]
+        factor: f64,
+        #[serde(flatten)]
+        criteria: LayoutCriteria,
+    },
+}
+
+fn default_factor() -> f64 {
+    1.0
+}
+
+/// The windows that can replace a placeholder created from a layout file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct LayoutCriteria {
+    /// The app-id of the window must be equal to this string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+    /// The title of the window must contain this string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl LayoutCriteria {
+    pub fn matches(&self, tl: &dyn ToplevelNode) -> bool {
+        let data = tl.tl_data();
+        if let Some(app_id) = &self.app_id {
+            if *data.app_id.borrow() != *app_id {
+                return false;
+            }
+        }
+        if let Some(title) = &self.title {
+            if !data.title.borrow().contains(title.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn describe(&self) -> String {
+        match (&self.app_id, &self.title) {
+            (Some(app_id), Some(title)) => format!("{app_id}: {title}"),
+            (Some(s), None) | (None, Some(s)) => s.clone(),
+            (None, None) => "Any window".to_string(),
+        }
+    }
+}
+
+impl SavedLayoutNode {
+    fn factor(&self) -> f64 {
+        match self {
+            SavedLayoutNode::Container { factor, .. } => *factor,
+            SavedLayoutNode::Window { factor, .. } => *factor,
+        }
+    }
+
+    fn validate(&self) -> Result<(), SavedLayoutError> {
+        if let SavedLayoutNode::Container {
+            children,
+            mono_child,
+            ..
+        } = self
+        {
+            if children.is_empty() {
+                return Err(SavedLayoutError::EmptyContainer);
+            }
+            if *mono_child >= children.len() {
+                return Err(SavedLayoutError::MonoChildOutOfBounds(*mono_child));
+            }
+            for child in children {
+                child.validate()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the layout of the tiled windows of the workspace to a file.
+pub fn save_layout(ws: &WorkspaceNode, path: &str) -> Result<(), SavedLayoutError> {
+    let Some(container) = ws.container.get() else {
+        return Err(SavedLayoutError::Empty);
+    };
+    let layout = save_container(&container, 1.0);
+    let json = serde_json::to_string_pretty(&layout).map_err(SavedLayoutError::Serialize)?;
+    std::fs::write(path, json).map_err(|e| SavedLayoutError::Write(path.to_string(), e))
+}
+
+fn save_container(container: &ContainerNode, factor: f64) -> SavedLayoutNode {
+    let children = container
+        .children
+        .iter()
+        .map(|child| save_node(&child.node, child.factor()))
+        .collect();
+    let split = match container.split.get() {
+        ContainerSplit::Horizontal => SavedSplit::Horizontal,
+        ContainerSplit::Vertical => SavedSplit::Vertical,
+    };
+    let mono = container.mono_child.get();
+    let mono_child = mono
+        .as_ref()
+        .and_then(|mc| {
+            container
+                .children
+                .iter()
+                .position(|c| c.node.node_id() == mc.node.node_id())
+        })
+        .unwrap_or(0);
+    SavedLayoutNode::Container {
+        factor,
+        split,
+        mono: mono.is_some(),
+        mono_child,
+        stacked: container.stacked.get(),
+        children,
+    }
+}
+
+fn save_node(node: &Rc<dyn ToplevelNode>, factor: f64) -> SavedLayoutNode {
+    if let Some(container) = node.clone().node_into_container() {
+        return save_container(&container, factor);
+    }
+    if let Some(placeholder) = node.clone().node_into_placeholder() {
+        if let Some(criteria) = &placeholder.criteria {
+            return SavedLayoutNode::Window {
+                factor,
+                criteria: criteria.clone(),
+            };
+        }
+    }
+    let app_id = node.tl_data().app_id.borrow().clone();
+    SavedLayoutNode::Window {
+        factor,
+        criteria: LayoutCriteria {
+            app_id: Some(app_id).filter(|a| !a.is_empty()),
+            title: None,
+        },
+    }
+}
+
+/// Reads a layout file and adds its windows to the workspace as placeholders.
+///
+/// Windows that match the criteria of a placeholder replace the placeholder when they
+/// are mapped.
+pub fn load_layout(
+    state: &Rc<State>,
+    ws: &Rc<WorkspaceNode>,
+    path: &str,
+) -> Result<(), SavedLayoutError> {
+    let json = std::fs::read(path).map_err(|e| SavedLayoutError::Read(path.to_string(), e))?;
+    let layout: SavedLayoutNode = serde_json::from_slice(&json).map_err(SavedLayoutError::Parse)?;
+    if ws.scrolling.get().is_some() {
+        return Err(SavedLayoutError::Scrolling);
+    }
+    layout.validate()?;
+    let node = load_node(state, ws, &layout);
+    if let Some(container) = ws.container.get() {
+        container.append_child(node);
+    } else {
+        let container = match node.clone().node_into_container() {
+            Some(c) => c,
+            _ => ContainerNode::new(state, ws, node, ContainerSplit::Horizontal),
+        };
+        ws.set_container(&container);
+    }
+    Ok(())
+}
+
+fn load_node(
+    state: &Rc<State>,
+    ws: &Rc<WorkspaceNode>,
+    node: &SavedLayoutNode,
+) -> Rc<dyn ToplevelNode> {
+    let (split, mono, mono_child, stacked, children) = match node {
+        SavedLayoutNode::Container {
+            split,
+            mono,
+            mono_child,
+            stacked,
+            children,
+            ..
+        } => (*split, *mono, *mono_child, *stacked, children),
+        SavedLayoutNode::Window { criteria, .. } => {
+            let placeholder =
+                Rc::new_cyclic(|weak| PlaceholderNode::new_for_layout(state, criteria, weak));
+            state
+                .layout_placeholders
+                .set(placeholder.node_id(), placeholder.clone());
+            return placeholder;
+        }
+    };
+    let nodes: Vec<_> = children.iter().map(|c| load_node(state, ws, c)).collect();
+    let split = match split {
+        SavedSplit::Horizontal => ContainerSplit::Horizontal,
+        SavedSplit::Vertical => ContainerSplit::Vertical,
+    };
+    let container = ContainerNode::new(state, ws, nodes[0].clone(), split);
+    for node in &nodes[1..] {
+        container.append_child(node.clone());
+    }
+    let factors: Vec<_> = children
+        .iter()
+        .map(|c| match c.factor() {
+            f if f.is_finite() && f > 0.0 => f,
+            _ => 1.0,
+        })
+        .collect();
+    container.set_child_factors(&factors);
+    container.set_stacked(stacked);
+    if mono {
+        container.set_mono(Some(&*nodes[mono_child]));
+    }
+    container
+}
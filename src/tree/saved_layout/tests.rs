@@ -0,0 +1,89 @@
+use crate::tree::saved_layout::{LayoutCriteria, SavedLayoutError, SavedLayoutNode, SavedSplit};
+
+fn window(app_id: &str, factor: f64) -> SavedLayoutNode {
+    SavedLayoutNode::Window {
+        factor,
+        criteria: LayoutCriteria {
+            app_id: Some(app_id.to_string()),
+            title: None,
+        },
+    }
+}
+
+fn container(mono_child: usize, children: Vec<SavedLayoutNode>) -> SavedLayoutNode {
+    SavedLayoutNode::Container {
+        factor: 1.0,
+        split: SavedSplit::Horizontal,
+        mono: true,
+        mono_child,
+        stacked: false,
+        children,
+    }
+}
+
+#[test]
+fn round_trip() {
+    let layout = SavedLayoutNode::Container {
+        factor: 1.0,
+        split: SavedSplit::Vertical,
+        mono: false,
+        mono_child: 0,
+        stacked: true,
+        children: vec![
+            window("firefox", 0.25),
+            container(1, vec![window("foot", 1.0), window("mpv", 1.0)]),
+        ],
+    };
+    let json = serde_json::to_string(&layout).unwrap();
+    let parsed: SavedLayoutNode = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, layout);
+}
+
+#[test]
+fn defaults() {
+    let json = r#"{
+        "type": "container",
+        "split": "horizontal",
+        "children": [{ "type": "window", "title": "Mozilla" }]
+    }"#;
+    let parsed: SavedLayoutNode = serde_json::from_str(json).unwrap();
+    let expected = SavedLayoutNode::Container {
+        factor: 1.0,
+        split: SavedSplit::Horizontal,
+        mono: false,
+        mono_child: 0,
+        stacked: false,
+        children: vec![SavedLayoutNode::Window {
+            factor: 1.0,
+            criteria: LayoutCriteria {
+                app_id: None,
+                title: Some("Mozilla".to_string()),
+            },
+        }],
+    };
+    assert_eq!(parsed, expected);
+}
+
+#[test]
+fn validate_ok() {
+    let layout = container(1, vec![window("a", 1.0), window("b", 1.0)]);
+    assert!(layout.validate().is_ok());
+}
+
+#[test]
+fn validate_empty_container() {
+    let layout = container(0, vec![window("a", 1.0), container(0, vec![])]);
+    assert!(matches!(
+        layout.validate(),
+        Err(SavedLayoutError::EmptyContainer)
+    ));
+}
+
+#[test]
+fn validate_mono_child_out_of_bounds() {
+    let layout = container(2, vec![window("a", 1.0), window("b", 1.0)]);
+    assert!(matches!(
+        layout.validate(),
+        Err(SavedLayoutError::MonoChildOutOfBounds(2))
+    ));
+}
@@ -0,0 +1,591 @@
+use {
+    crate::{
+        cursor::KnownCursor,
+        fixed::Fixed,
+        ifs::wl_seat::{collect_kb_foci, tablet::TabletTool, NodeSeatState, WlSeatGlobal},
+        rect::Rect,
+        renderer::Renderer,
+        state::State,
+        tree::{
+            walker::NodeVisitor, ContainerNode, ContainerSplit, ContainingNode, Direction,
+            FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, TileDragDestination,
+            ToplevelNode, ToplevelNodeBase, WorkspaceNode,
+        },
+        utils::{
+            clonecell::CloneCell,
+            linkedlist::{LinkedList, LinkedNode, NodeRef},
+            threshold_counter::ThresholdCounter,
+        },
+    },
+    ahash::AHashMap,
+    jay_config::ResizeAmount,
+    std::{
+        cell::{Cell, RefCell},
+        fmt::{Debug, Formatter},
+        rc::Rc,
+    },
+};
+
+pub const DEFAULT_COLUMN_WIDTH: f64 = 0.5;
+pub const DEFAULT_COLUMN_WIDTH_PRESETS: [f64; 3] = [1.0 / 3.0, 0.5, 2.0 / 3.0];
+pub const MIN_COLUMN_WIDTH: f64 = 0.1;
+pub const MAX_COLUMN_WIDTH: f64 = 1.0;
+
+tree_id!(ScrollingNodeId);
+
+/// The root of a workspace that uses the scrolling layout.
+///
+/// The windows are arranged in an infinite horizontal strip of columns. Each column is
+/// a vertical container. The workspace acts as a viewport into the strip that always
+/// shows the active column.
+pub struct ScrollingNode {
+    pub id: ScrollingNodeId,
+    pub state: Rc<State>,
+    pub workspace: CloneCell<Rc<WorkspaceNode>>,
+    pub seat_state: NodeSeatState,
+    pub columns: LinkedList<ScrollingColumn>,
+    column_nodes: RefCell<AHashMap<NodeId, LinkedNode<ScrollingColumn>>>,
+    active_column: CloneCell<Option<NodeRef<ScrollingColumn>>>,
+    pub position: Cell<Rect>,
+    /// The offset of the viewport from the start of the strip.
+    pub scroll: Cell<i32>,
+    pub visible: Cell<bool>,
+    layout_scheduled: Cell<bool>,
+    attention_requests: ThresholdCounter,
+}
+
+impl Debug for ScrollingNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScrollingNode").finish_non_exhaustive()
+    }
+}
+
+pub struct ScrollingColumn {
+    pub node: Rc<ContainerNode>,
+    /// The width of the column as a fraction of the workspace width.
+    pub width: Cell<f64>,
+    /// The position of the column relative to the start of the strip.
+    pub body: Cell<Rect>,
+    attention_requested: Cell<bool>,
+}
+
+pub async fn scrolling_layout(state: Rc<State>) {
+    loop {
+        let node = state.pending_scrolling_layout.pop().await;
+        if node.layout_scheduled.get() {
+            node.perform_layout();
+        }
+    }
+}
+
+impl ScrollingNode {
+    pub fn new(state: &Rc<State>, workspace: &Rc<WorkspaceNode>) -> Rc<Self> {
+        Rc::new(Self {
+            id: state.node_ids.next(),
+            state: state.clone(),
+            workspace: CloneCell::new(workspace.clone()),
+            seat_state: Default::default(),
+            columns: Default::default(),
+            column_nodes: Default::default(),
+            active_column: Default::default(),
+            position: Default::default(),
+            scroll: Cell::new(0),
+            visible: Cell::new(false),
+            layout_scheduled: Cell::new(false),
+            attention_requests: Default::default(),
+        })
+    }
+
+    /// Places `node` in a new column to the right of the active column.
+    pub fn add_window(self: &Rc<Self>, node: Rc<dyn ToplevelNode>) {
+        let column = self.new_column(node);
+        match self.active_column.get() {
+            Some(ac) => self.add_column(|c| ac.append(c), column),
+            None => self.add_column(|c| self.columns.add_last(c), column),
+        }
+    }
+
+    fn new_column(&self, node: Rc<dyn ToplevelNode>) -> Rc<ContainerNode> {
+        ContainerNode::new(
+            &self.state,
+            &self.workspace.get(),
+            node,
+            ContainerSplit::Vertical,
+        )
+    }
+
+    fn add_column<F>(self: &Rc<Self>, f: F, node: Rc<ContainerNode>)
+    where
+        F: FnOnce(ScrollingColumn) -> LinkedNode<ScrollingColumn>,
+    {
+        let column = {
+            let mut links = self.column_nodes.borrow_mut();
+            if links.contains_key(&node.node_id()) {
+                log::error!("Tried to add a column that is already part of the strip");
+                return;
+            }
+            let link = f(ScrollingColumn {
+                node: node.clone(),
+                width: Cell::new(DEFAULT_COLUMN_WIDTH),
+                body: Default::default(),
+                attention_requested: Cell::new(false),
+            });
+            let r = link.to_ref();
+            links.insert(node.node_id(), link);
+            r
+        };
+        node.tl_set_parent(self.clone());
+        node.tl_set_visible(self.visible.get());
+        let data = node.tl_data();
+        if data.wants_attention.get() {
+            column.attention_requested.set(true);
+            self.mod_attention_requests(true);
+        }
+        if data.active() {
+            self.active_column.set(Some(column));
+        }
+        self.schedule_layout();
+    }
+
+    fn column(&self, node: &dyn Node) -> Option<NodeRef<ScrollingColumn>> {
+        self.column_nodes
+            .borrow()
+            .get(&node.node_id())
+            .map(|c| c.to_ref())
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.visible.set(visible);
+        for column in self.columns.iter() {
+            column
+                .node
+                .tl_set_visible(visible && self.in_viewport(&column));
+        }
+        self.seat_state.set_visible(self, visible);
+    }
+
+    /// Returns whether any part of the column is inside the viewport.
+    ///
+    /// Columns outside the viewport have extents that overlap neighboring outputs. They
+    /// are hidden so that they are neither rendered nor associated with those outputs.
+    fn in_viewport(&self, column: &ScrollingColumn) -> bool {
+        let body = column.body.get();
+        let scroll = self.scroll.get();
+        body.x2() > scroll && body.x1() < scroll + self.position.get().width()
+    }
+
+    pub fn change_extents(&self, rect: &Rect) {
+        self.position.set(*rect);
+        self.perform_layout();
+    }
+
+    fn schedule_layout(self: &Rc<Self>) {
+        if !self.layout_scheduled.replace(true) {
+            self.state.pending_scrolling_layout.push(self.clone());
+        }
+    }
+
+    fn perform_layout(&self) {
+        self.layout_scheduled.set(false);
+        let pos = self.position.get();
+        let bw = self.state.theme.sizes.border_width.get();
+        let mut x = 0;
+        for column in self.columns.iter() {
+            let width = (pos.width() as f64 * column.width.get()).round() as i32;
+            let body = Rect::new_sized(x, 0, width.max(1), pos.height()).unwrap();
+            column.body.set(body);
+            x = body.x2() + bw;
+        }
+        let strip_width = (x - bw).max(0);
+        let mut scroll = self.scroll.get();
+        if let Some(ac) = self.active_column.get() {
+            let body = ac.body.get();
+            if body.x1() < scroll {
+                scroll = body.x1();
+            } else if body.x2() > scroll + pos.width() {
+                scroll = body.x2() - pos.width();
+            }
+        }
+        scroll = scroll.min(strip_width - pos.width()).max(0);
+        self.scroll.set(scroll);
+        let visible = self.visible.get();
+        for column in self.columns.iter() {
+            let body = column.body.get().move_(pos.x1() - scroll, pos.y1());
+            column.node.clone().tl_change_extents(&body);
+            let column_visible = visible && self.in_viewport(&column);
+            if column.node.node_visible() != column_visible {
+                column.node.tl_set_visible(column_visible);
+            }
+        }
+        if self.visible.get() {
+            // Damage the whole viewport since the scroll offset might have changed.
+            // This also causes output and workspace screencasts to be updated.
+            self.state.damage(pos);
+        }
+        self.state.tree_changed();
+    }
+
+    /// Moves the keyboard focus from `column` to the neighboring column.
+    pub fn move_focus_from_column(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        column: &dyn Node,
+        direction: Direction,
+    ) {
+        let Some(column) = self.column(column) else {
+            return;
+        };
+        let sibling = match direction {
+            Direction::Left => column.prev(),
+            Direction::Right => column.next(),
+            _ => return,
+        };
+        if let Some(sibling) = sibling {
+            sibling.node.clone().node_do_focus(seat, direction);
+        }
+    }
+
+    /// Moves `child`, which is a descendant of `column`, to the neighboring column.
+    ///
+    /// If `child` is the only window in its column, it is moved into the neighboring
+    /// column. Otherwise it is moved into a new column next to its column.
+    pub fn move_child(
+        self: &Rc<Self>,
+        column: &dyn Node,
+        child: Rc<dyn ToplevelNode>,
+        direction: Direction,
+    ) {
+        let Some(column) = self.column(column) else {
+            return;
+        };
+        let prev = match direction {
+            Direction::Left => true,
+            Direction::Right => false,
+            _ => return,
+        };
+        let Some(parent) = child.tl_data().parent.get() else {
+            return;
+        };
+        if is_only_descendant(&column.node, &*child) {
+            let neighbor = match prev {
+                true => column.prev(),
+                false => column.next(),
+            };
+            if let Some(neighbor) = neighbor {
+                parent.cnode_remove_child2(child.tl_as_node(), true);
+                neighbor.node.append_child(child);
+            }
+            return;
+        }
+        parent.cnode_remove_child2(child.tl_as_node(), true);
+        let new = self.new_column(child);
+        match prev {
+            true => self.add_column(|c| column.prepend(c), new),
+            false => self.add_column(|c| column.append(c), new),
+        }
+    }
+
+    /// Swaps `column` with the neighboring column.
+    pub fn move_column(self: &Rc<Self>, column: &dyn Node, direction: Direction) {
+        let Some(column) = self.column(column) else {
+            return;
+        };
+        match direction {
+            Direction::Left => {
+                if let Some(prev) = column.prev() {
+                    prev.prepend_existing(&column);
+                }
+            }
+            Direction::Right => {
+                if let Some(next) = column.next() {
+                    next.append_existing(&column);
+                }
+            }
+            _ => return,
+        }
+        self.schedule_layout();
+    }
+
+    /// Grows or shrinks `column` horizontally.
+    pub fn resize_column(
+        self: &Rc<Self>,
+        column: &dyn Node,
+        split: ContainerSplit,
+        amount: ResizeAmount,
+    ) {
+        if split != ContainerSplit::Horizontal {
+            return;
+        }
+        let Some(column) = self.column(column) else {
+            return;
+        };
+        let delta = match amount {
+            ResizeAmount::Px(px) => px as f64 / self.position.get().width().max(1) as f64,
+            ResizeAmount::Ppt(ppt) => ppt as f64 / 100.0,
+        };
+        self.set_column_width(&column, column.width.get() + delta);
+    }
+
+    /// Sets the width of `column` to the next width in the list of presets.
+    pub fn cycle_column_width(self: &Rc<Self>, column: &dyn Node) {
+        let Some(column) = self.column(column) else {
+            return;
+        };
+        let width = {
+            let presets = self.state.column_width_presets.borrow();
+            let current = column.width.get();
+            presets
+                .iter()
+                .copied()
+                .find(|&w| w > current + 0.01)
+                .or_else(|| presets.first().copied())
+        };
+        if let Some(width) = width {
+            self.set_column_width(&column, width);
+        }
+    }
+
+    fn set_column_width(self: &Rc<Self>, column: &ScrollingColumn, width: f64) {
+        let width = width.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+        if column.width.replace(width) != width {
+            self.schedule_layout();
+        }
+    }
+
+    pub fn tile_drag_destination(
+        &self,
+        source: NodeId,
+        abs_x: i32,
+        abs_y: i32,
+    ) -> Option<TileDragDestination> {
+        for column in self.columns.iter() {
+            let pos = column.node.node_absolute_position();
+            if pos.contains(abs_x, abs_y) {
+                return column.node.tile_drag_destination(source, pos, abs_x, abs_y);
+            }
+        }
+        None
+    }
+
+    fn mod_attention_requests(&self, set: bool) {
+        if self.attention_requests.adj(set) {
+            self.workspace
+                .get()
+                .cnode_child_attention_request_changed(self, set);
+        }
+    }
+}
+
+/// Returns whether `child` is the only window in `column`.
+fn is_only_descendant(column: &ContainerNode, child: &dyn ToplevelNode) -> bool {
+    let mut parent = child.tl_data().parent.get();
+    while let Some(c) = parent.and_then(|p| p.node_into_container()) {
+        if c.num_children() != 1 {
+            return false;
+        }
+        if c.node_id() == column.node_id() {
+            return true;
+        }
+        parent = c.tl_data().parent.get();
+    }
+    false
+}
+
+impl Node for ScrollingNode {
+    fn node_id(&self) -> NodeId {
+        self.id.into()
+    }
+
+    fn node_seat_state(&self) -> &NodeSeatState {
+        &self.seat_state
+    }
+
+    fn node_visit(self: Rc<Self>, visitor: &mut dyn NodeVisitor) {
+        visitor.visit_scrolling(&self);
+    }
+
+    fn node_visit_children(&self, visitor: &mut dyn NodeVisitor) {
+        for column in self.columns.iter() {
+            column.node.clone().node_visit(visitor);
+        }
+    }
+
+    fn node_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    fn node_absolute_position(&self) -> Rect {
+        self.position.get()
+    }
+
+    fn node_do_focus(self: Rc<Self>, seat: &Rc<WlSeatGlobal>, direction: Direction) {
+        let column = self.active_column.get().or_else(|| self.columns.first());
+        if let Some(column) = column {
+            column.node.clone().node_do_focus(seat, direction);
+        }
+    }
+
+    fn node_find_tree_at(
+        &self,
+        x: i32,
+        y: i32,
+        tree: &mut Vec<FoundNode>,
+        usecase: FindTreeUsecase,
+    ) -> FindTreeResult {
+        let x_strip = x + self.scroll.get();
+        for column in self.columns.iter() {
+            let body = column.body.get();
+            if body.contains(x_strip, y) {
+                let (x, y) = body.translate(x_strip, y);
+                tree.push(FoundNode {
+                    node: column.node.clone(),
+                    x,
+                    y,
+                });
+                column.node.node_find_tree_at(x, y, tree, usecase);
+                break;
+            }
+        }
+        FindTreeResult::AcceptsInput
+    }
+
+    fn node_child_active_changed(self: Rc<Self>, child: &dyn Node, active: bool, _depth: u32) {
+        if !active {
+            return;
+        }
+        if let Some(column) = self.column(child) {
+            self.active_column.set(Some(column));
+            self.schedule_layout();
+        }
+    }
+
+    fn node_render(&self, renderer: &mut Renderer, x: i32, y: i32, _bounds: Option<&Rect>) {
+        renderer.render_scrolling(self, x, y);
+    }
+
+    fn node_on_pointer_focus(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.pointer_cursor().set_known(KnownCursor::Default);
+    }
+
+    fn node_on_tablet_tool_enter(
+        self: Rc<Self>,
+        tool: &Rc<TabletTool>,
+        _time_usec: u64,
+        _x: Fixed,
+        _y: Fixed,
+    ) {
+        tool.cursor().set_known(KnownCursor::Default)
+    }
+
+    fn node_into_scrolling(self: Rc<Self>) -> Option<Rc<ScrollingNode>> {
+        Some(self)
+    }
+
+    fn node_into_containing_node(self: Rc<Self>) -> Option<Rc<dyn ContainingNode>> {
+        Some(self)
+    }
+}
+
+impl ContainingNode for ScrollingNode {
+    fn cnode_replace_child(self: Rc<Self>, old: &dyn Node, new: Rc<dyn ToplevelNode>) {
+        let Some(new) = new.tl_into_node().node_into_container() else {
+            log::error!("cnode_replace_child called with non-container new");
+            return;
+        };
+        let Some(old) = self.column_nodes.borrow_mut().remove(&old.node_id()) else {
+            log::error!("Trying to replace a node that isn't a column of this strip");
+            return;
+        };
+        if old.attention_requested.get() {
+            self.mod_attention_requests(false);
+        }
+        let attention_requested = new.tl_data().wants_attention.get();
+        if attention_requested {
+            self.mod_attention_requests(true);
+        }
+        let link = old.append(ScrollingColumn {
+            node: new.clone(),
+            width: Cell::new(old.width.get()),
+            body: Cell::new(old.body.get()),
+            attention_requested: Cell::new(attention_requested),
+        });
+        if let Some(ac) = self.active_column.get() {
+            if ac.node.node_id() == old.node.node_id() {
+                self.active_column.set(Some(link.to_ref()));
+            }
+        }
+        drop(old);
+        self.column_nodes.borrow_mut().insert(new.node_id(), link);
+        new.tl_set_parent(self.clone());
+        new.tl_set_visible(self.visible.get());
+        self.schedule_layout();
+    }
+
+    fn cnode_remove_child2(self: Rc<Self>, child: &dyn Node, preserve_focus: bool) {
+        let Some(column) = self.column_nodes.borrow_mut().remove(&child.node_id()) else {
+            return;
+        };
+        if column.attention_requested.get() {
+            self.mod_attention_requests(false);
+        }
+        if let Some(ac) = self.active_column.get() {
+            if ac.node.node_id() == child.node_id() {
+                let new = column.prev().or_else(|| column.next());
+                if let Some(new) = &new {
+                    if !preserve_focus {
+                        for seat in collect_kb_foci(column.node.clone()) {
+                            new.node
+                                .clone()
+                                .node_do_focus(&seat, Direction::Unspecified);
+                        }
+                    }
+                }
+                self.active_column.set(new);
+            }
+        }
+        drop(column);
+        if self.columns.is_empty() {
+            self.seat_state.destroy_node(&*self);
+            self.workspace.get().remove_scrolling(&self);
+            return;
+        }
+        self.schedule_layout();
+    }
+
+    fn cnode_accepts_child(&self, node: &dyn Node) -> bool {
+        node.node_is_container()
+    }
+
+    fn cnode_child_attention_request_changed(self: Rc<Self>, child: &dyn Node, set: bool) {
+        let Some(column) = self.column(child) else {
+            return;
+        };
+        if column.attention_requested.replace(set) != set {
+            self.mod_attention_requests(set);
+        }
+    }
+
+    fn cnode_workspace(self: Rc<Self>) -> Rc<WorkspaceNode> {
+        self.workspace.get()
+    }
+
+    fn cnode_resize_child(
+        self: Rc<Self>,
+        child: &dyn Node,
+        new_x1: Option<i32>,
+        _new_y1: Option<i32>,
+        new_x2: Option<i32>,
+        _new_y2: Option<i32>,
+    ) {
+        let Some(column) = self.column(child) else {
+            return;
+        };
+        let pos = column.node.node_absolute_position();
+        let x1 = new_x1.unwrap_or(pos.x1());
+        let x2 = new_x2.unwrap_or(pos.x2());
+        if (x1, x2) != (pos.x1(), pos.x2()) {
+            let width = (x2 - x1).max(1) as f64 / self.position.get().width().max(1) as f64;
+            self.set_column_width(&column, width);
+        }
+    }
+}
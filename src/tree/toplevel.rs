@@ -1,5 +1,9 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
+        animation::{interpolate_rect, Animation, AnimationKind, ClosingWindow, MoveAnimation},
         client::{Client, ClientId},
         ifs::{
             ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
@@ -7,11 +11,12 @@ use {
             ext_image_copy::ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
             jay_screencast::JayScreencast,
             jay_toplevel::JayToplevel,
-            wl_seat::{collect_kb_foci, collect_kb_foci2, NodeSeatState, SeatId},
-            wl_surface::WlSurface,
+            wl_seat::{collect_kb_foci, collect_kb_foci2, NodeSeatState, SeatId, WlSeatGlobal},
+            wl_surface::{SurfaceBuffer, WlSurface},
         },
         rect::Rect,
         state::State,
+        theme::Color,
         tree::{
             ContainerNode, ContainerSplit, ContainingNode, Direction, Node, NodeId, OutputNode,
             PlaceholderNode, WorkspaceNode,
@@ -21,8 +26,9 @@ use {
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
             hash_map_ext::HashMapExt,
+            linkedlist::LinkedNode,
             numcell::NumCell,
-            smallmap::SmallMap,
+            smallmap::{SmallMap, SmallMapMut},
             threshold_counter::ThresholdCounter,
             toplevel_identifier::{toplevel_identifier, ToplevelIdentifier},
         },
@@ -31,11 +37,16 @@ use {
             JayToplevelId,
         },
     },
+    smallvec::SmallVec,
     std::{
+        borrow::Cow,
         cell::{Cell, RefCell},
+        fmt::Write,
+        mem,
         ops::Deref,
         rc::{Rc, Weak},
     },
+    uapi::c,
 };
 
 tree_id!(ToplevelNodeId);
@@ -143,6 +154,9 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
     fn tl_change_extents(self: Rc<Self>, rect: &Rect) {
         let data = self.tl_data();
         let prev = data.desired_extents.replace(*rect);
+        if !self.node_is_container() {
+            data.animate_move(prev);
+        }
         if prev.size() != rect.size() {
             for sc in data.jay_screencasts.lock().values() {
                 sc.schedule_realloc_or_reconfigure();
@@ -164,7 +178,13 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
     }
 
     fn tl_destroy(&self) {
-        self.tl_data().destroy_node(self);
+        let data = self.tl_data();
+        if let Some(surface) = self.tl_scanout_surface() {
+            if let Some(buffer) = surface.buffer.get() {
+                data.animate_close(&surface, &buffer);
+            }
+        }
+        data.destroy_node(self);
         self.tl_destroy_impl();
     }
 }
@@ -230,6 +250,43 @@ pub trait ToplevelNodeBase: Node {
         let _ = start;
         default_tile_drag_bounds(self, split)
     }
+
+    fn tl_size_hints(&self) -> ToplevelSizeHints {
+        ToplevelSizeHints::default()
+    }
+
+    /// Returns the process id of the process that created the toplevel.
+    fn tl_pid(&self) -> Option<c::pid_t> {
+        self.tl_data().client.as_ref().map(|c| c.pid_info.pid)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ToplevelSizeHints {
+    pub min_width: Option<i32>,
+    pub min_height: Option<i32>,
+    pub max_width: Option<i32>,
+    pub max_height: Option<i32>,
+}
+
+impl ToplevelSizeHints {
+    /// Applies `delta` to `size` along the axis without violating the hints.
+    ///
+    /// If `size` already violates the hints, the result is never further away from the
+    /// allowed range than `size`.
+    pub fn resize(&self, split: ContainerSplit, size: i32, delta: i32) -> i32 {
+        let (min, max) = match split {
+            ContainerSplit::Horizontal => (self.min_width, self.max_width),
+            ContainerSplit::Vertical => (self.min_height, self.max_height),
+        };
+        let mut new = size + delta;
+        if delta < 0 {
+            new = new.max(min.unwrap_or(1).max(1)).min(size);
+        } else if let Some(max) = max {
+            new = new.min(max).max(size);
+        }
+        new
+    }
 }
 
 pub struct FullscreenedData {
@@ -254,6 +311,48 @@ impl ToplevelOpt {
     }
 }
 
+/// Overrides of the border of a toplevel.
+///
+/// Fields that are `None` fall back to the theme.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct WindowBorder {
+    pub color: Option<Color>,
+    pub focused_color: Option<Color>,
+    pub width: Option<i32>,
+}
+
+/// Replaces the placeholders in a title format.
+///
+/// `{{` and `}}` are replaced by `{` and `}`. For every other `{key}`, `expand` is
+/// called with the key. If it returns `false`, the placeholder is shown unchanged.
+fn expand_title_format(format: &str, mut expand: impl FnMut(&str, &mut String) -> bool) -> String {
+    let mut res = String::new();
+    let mut rem = format;
+    while let Some(start) = rem.find(['{', '}']) {
+        res.push_str(&rem[..start]);
+        rem = &rem[start..];
+        if let Some(tail) = rem.strip_prefix("{{").or_else(|| rem.strip_prefix("}}")) {
+            res.push_str(&rem[..1]);
+            rem = tail;
+            continue;
+        }
+        if let Some(tail) = rem.strip_prefix('}') {
+            res.push('}');
+            rem = tail;
+            continue;
+        }
+        let Some(end) = rem.find('}') else {
+            break;
+        };
+        if !expand(&rem[1..end], &mut res) {
+            res.push_str(&rem[..=end]);
+        }
+        rem = &rem[end + 1..];
+    }
+    res.push_str(rem);
+    res
+}
+
 pub struct ToplevelData {
     pub self_active: Cell<bool>,
     pub client: Option<Rc<Client>>,
@@ -275,6 +374,17 @@ pub struct ToplevelData {
     pub wants_attention: Cell<bool>,
     pub requested_attention: Cell<bool>,
     pub app_id: RefCell<String>,
+    pub marks: RefCell<Vec<String>>,
+    pub focus_history: RefCell<SmallMapMut<SeatId, LinkedNode<Weak<dyn ToplevelNode>>, 1>>,
+    pub sticky: Cell<Option<bool>>,
+    pub opacity: Cell<Option<f32>>,
+    pub hide_title: Cell<Option<bool>>,
+    pub border: Cell<WindowBorder>,
+    pub minimized: RefCell<Option<LinkedNode<Weak<dyn ToplevelNode>>>>,
+    pub swallowed: CloneCell<Option<Rc<dyn ToplevelNode>>>,
+    pub swallowed_by: CloneCell<Option<Weak<dyn ToplevelNode>>>,
+    pub move_animation: Cell<Option<MoveAnimation>>,
+    pub open_animation: Cell<Option<Animation>>,
     pub identifier: Cell<ToplevelIdentifier>,
     pub handles:
         CopyHashMap<(ClientId, ExtForeignToplevelHandleV1Id), Rc<ExtForeignToplevelHandleV1>>,
@@ -316,6 +426,17 @@ impl ToplevelData {
             wants_attention: Cell::new(false),
             requested_attention: Cell::new(false),
             app_id: Default::default(),
+            marks: Default::default(),
+            focus_history: Default::default(),
+            sticky: Default::default(),
+            opacity: Default::default(),
+            hide_title: Default::default(),
+            border: Default::default(),
+            minimized: Default::default(),
+            swallowed: Default::default(),
+            swallowed_by: Default::default(),
+            move_animation: Default::default(),
+            open_animation: Default::default(),
             identifier: Cell::new(id),
             handles: Default::default(),
             render_highlight: Default::default(),
@@ -339,6 +460,9 @@ impl ToplevelData {
             if let Some(parent) = self.parent.get() {
                 parent.node_child_active_changed(tl.tl_as_node(), active_new, 1);
             }
+            if self.state.theme.colors.inactive_dim.get() != Color::TRANSPARENT {
+                self.state.damage(tl.tl_as_node().node_absolute_position());
+            }
         }
     }
 
@@ -346,6 +470,118 @@ impl ToplevelData {
         self.update_active(node, || self.self_active.set(active));
     }
 
+    fn is_pointer_grabbed(&self) -> bool {
+        if self.seat_state.has_pointer_grab() {
+            return true;
+        }
+        let mut parent = self.parent.get();
+        while let Some(p) = parent {
+            if p.node_seat_state().has_pointer_grab() {
+                return true;
+            }
+            parent = p.node_toplevel().and_then(|tl| tl.tl_data().parent.get());
+        }
+        false
+    }
+
+    /// Animates the move of the toplevel from `prev` to its desired extents.
+    ///
+    /// Toplevels that are being moved or resized with the pointer are not animated.
+    fn animate_move(&self, prev: Rect) {
+        let rect = self.desired_extents.get();
+        if prev.is_empty() || prev == rect || !self.visible.get() || self.is_fullscreen.get() {
+            return;
+        }
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        if self.is_pointer_grabbed() {
+            self.move_animation.take();
+            return;
+        }
+        let mut from = prev;
+        if let Some(ma) = self.move_animation.get() {
+            if let Some(progress) = ma.animation.progress(self.state.now_nsec()) {
+                from = interpolate_rect(ma.from, prev, progress);
+            }
+        }
+        let kind = match self.is_floating.get() {
+            true => AnimationKind::Float,
+            false => AnimationKind::Tile,
+        };
+        let animation = ws.output.get().start_animation(kind);
+        self.move_animation
+            .set(animation.map(|animation| MoveAnimation { animation, from }));
+    }
+
+    /// Fades in the toplevel after it has been mapped.
+    pub fn animate_open(&self) {
+        if !self.visible.get() {
+            return;
+        }
+        if let Some(ws) = self.workspace.get() {
+            let animation = ws.output.get().start_animation(AnimationKind::Open);
+            self.open_animation.set(animation);
+        }
+    }
+
+    /// Fades out the last contents of the toplevel after it has been unmapped.
+    ///
+    /// `surface` must be the main surface of the toplevel.
+    pub fn animate_close(&self, surface: &WlSurface, buffer: &Rc<SurfaceBuffer>) {
+        if !self.visible.get() {
+            return;
+        }
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        let Some(texture) = buffer.buffer.get_texture(surface) else {
+            return;
+        };
+        let output = ws.output.get();
+        let Some(animation) = output.start_animation(AnimationKind::Close) else {
+            return;
+        };
+        output.closing_windows.borrow_mut().push(ClosingWindow {
+            animation,
+            buffer: buffer.clone(),
+            texture,
+            tpoints: *surface.buffer_points_norm.borrow(),
+            rect: surface.buffer_abs_pos.get(),
+            bounds: self.desired_extents.get(),
+        });
+    }
+
+    /// Returns how running animations change the position and opacity of the toplevel.
+    pub fn animated_state(&self, now: u64) -> Option<AnimatedState> {
+        let mut res = None::<AnimatedState>;
+        if let Some(ma) = self.move_animation.get() {
+            match ma.animation.progress(now) {
+                Some(progress) => {
+                    let to = self.desired_extents.get();
+                    let rect = interpolate_rect(ma.from, to, progress);
+                    res = Some(AnimatedState {
+                        dx: rect.x1() - to.x1(),
+                        dy: rect.y1() - to.y1(),
+                        dwidth: rect.width() - to.width(),
+                        dheight: rect.height() - to.height(),
+                        alpha: None,
+                    });
+                }
+                None => self.move_animation.set(None),
+            }
+        }
+        if let Some(animation) = self.open_animation.get() {
+            match animation.progress(now) {
+                Some(progress) => {
+                    res.get_or_insert_default().alpha = Some(progress.clamp(0.0, 1.0) as f32);
+                }
+                None => self.open_animation.set(None),
+            }
+        }
+        res
+    }
+
     pub fn float_size(&self, ws: &WorkspaceNode) -> (i32, i32) {
         let output = ws.output.get().global.pos.get();
         let mut width = self.float_width.get();
@@ -360,6 +596,16 @@ impl ToplevelData {
     }
 
     pub fn destroy_node(&self, node: &dyn Node) {
+        self.marks.borrow_mut().clear();
+        self.move_animation.take();
+        self.open_animation.take();
+        self.desired_extents.take();
+        self.focus_history.borrow_mut().clear();
+        self.minimized.borrow_mut().take();
+        if let Some(tl) = self.swallowed_by.take().and_then(|tl| tl.upgrade()) {
+            tl.tl_data().swallowed.take();
+        }
+        let swallowed = self.reinstate_swallowed(node);
         for jay_tl in self.jay_toplevels.lock().drain_values() {
             jay_tl.destroy();
         }
@@ -382,6 +628,40 @@ impl ToplevelData {
             }
         }
         self.detach_node(node);
+        if let Some((swallowed, kb_foci)) = swallowed {
+            for seat in kb_foci {
+                swallowed
+                    .clone()
+                    .node_do_focus(&seat, Direction::Unspecified);
+            }
+        }
+    }
+
+    /// Puts the window swallowed by this window back into the tree in place of
+    /// this window.
+    fn reinstate_swallowed(
+        &self,
+        node: &dyn Node,
+    ) -> Option<(Rc<dyn ToplevelNode>, SmallVec<[Rc<WlSeatGlobal>; 3]>)> {
+        let swallowed = self.swallowed.take()?;
+        swallowed.tl_data().swallowed_by.take();
+        let fd = self.fullscrceen_data.borrow();
+        let (data, slot) = match &*fd {
+            Some(fd) => (fd.placeholder.tl_data(), fd.placeholder.tl_as_node()),
+            _ => (self, node),
+        };
+        let Some(parent) = data.parent.take() else {
+            // This window is not part of the tree, e.g. because it is minimized.
+            let seat = self.state.seat_queue.last();
+            self.state.do_map_tiled(seat.as_deref(), swallowed.clone());
+            return Some((swallowed, Default::default()));
+        };
+        let kb_foci = match self.slf.upgrade() {
+            Some(tl) => collect_kb_foci(tl.tl_into_node()),
+            _ => Default::default(),
+        };
+        parent.cnode_replace_child(slot, swallowed.clone());
+        Some((swallowed, kb_foci))
     }
 
     pub fn detach_node(&self, node: &dyn Node) {
@@ -445,7 +725,18 @@ impl ToplevelData {
     }
 
     pub fn set_app_id(&self, app_id: &str) {
+        let title_hidden = self.title_hidden();
+        let border = self.border_overrides();
         *self.app_id.borrow_mut() = app_id.to_string();
+        if self.title_hidden() != title_hidden {
+            self.title_visibility_changed();
+        }
+        if self.border_overrides() != border {
+            self.border_changed();
+        }
+        if self.state.title_format.borrow().is_some() {
+            self.formatted_title_changed();
+        }
         for handle in self.handles.lock().values() {
             handle.send_app_id(app_id);
             handle.send_done();
@@ -491,6 +782,10 @@ impl ToplevelData {
             log::warn!("Cannot fullscreen root container in a workspace");
             return;
         }
+        if parent.clone().node_into_scrolling().is_some() {
+            log::warn!("Cannot fullscreen a column of a scrolling workspace");
+            return;
+        }
         let placeholder =
             Rc::new_cyclic(|weak| PlaceholderNode::new_for(state, node.clone(), weak));
         parent.cnode_replace_child(node.tl_as_node(), placeholder.clone());
@@ -499,6 +794,9 @@ impl ToplevelData {
             if let Some(container) = ws.container.get() {
                 kb_foci = collect_kb_foci(container);
             }
+            if let Some(scrolling) = ws.scrolling.get() {
+                collect_kb_foci2(scrolling, &mut kb_foci);
+            }
             for stacked in ws.stacked.iter() {
                 collect_kb_foci2(stacked.deref().clone().stacked_into_node(), &mut kb_foci);
             }
@@ -585,6 +883,184 @@ impl ToplevelData {
         }
     }
 
+    pub fn add_mark(&self, mark: &str) {
+        if let Some(tl) = self.state.find_mark(mark) {
+            let data = tl.tl_data();
+            data.marks.borrow_mut().retain(|m| m != mark);
+            data.formatted_title_changed();
+        }
+        self.marks.borrow_mut().push(mark.to_string());
+        self.formatted_title_changed();
+    }
+
+    pub fn clear_marks(&self) {
+        let had_marks = !mem::take(&mut *self.marks.borrow_mut()).is_empty();
+        if had_marks {
+            self.formatted_title_changed();
+        }
+    }
+
+    fn formatted_title_changed(&self) {
+        let Some(parent) = self.parent.get() else {
+            return;
+        };
+        if let Some(c) = parent.clone().node_into_container() {
+            c.schedule_render_titles();
+        } else if let Some(f) = parent.node_into_float() {
+            f.schedule_render_titles();
+        }
+    }
+
+    /// Returns the title as it is shown in title bars.
+    ///
+    /// If a title format is configured, its placeholders are replaced by the properties
+    /// of the toplevel. Otherwise the marks of the toplevel are shown in front of the
+    /// title if enabled.
+    pub fn formatted_title<'a>(&self, title: &'a str) -> Cow<'a, str> {
+        let format = self.state.title_format.borrow();
+        let Some(format) = &*format else {
+            let marks = self.marks.borrow();
+            if marks.is_empty() || !self.state.show_marks.get() {
+                return Cow::Borrowed(title);
+            }
+            let mut res = String::new();
+            self.write_marks(&mut res);
+            res.push_str(title);
+            return Cow::Owned(res);
+        };
+        let res = expand_title_format(format, |key, res| {
+            match key {
+                "title" => res.push_str(title),
+                "app_id" => res.push_str(&self.app_id.borrow()),
+                "pid" => {
+                    if let Some(pid) = self.slf.upgrade().and_then(|tl| tl.tl_pid()) {
+                        let _ = write!(res, "{}", pid);
+                    }
+                }
+                "xwayland" => {
+                    if self.client.as_ref().is_some_and(|c| c.is_xwayland) {
+                        res.push_str("[X] ");
+                    }
+                }
+                "marks" => self.write_marks(res),
+                _ => return false,
+            }
+            true
+        });
+        Cow::Owned(res)
+    }
+
+    fn write_marks(&self, res: &mut String) {
+        if !self.state.show_marks.get() {
+            return;
+        }
+        for mark in self.marks.borrow().iter() {
+            let _ = write!(res, "[{}] ", mark);
+        }
+    }
+
+    pub fn set_border(&self, border: WindowBorder) {
+        if self.border.replace(border) != border {
+            self.border_changed();
+        }
+    }
+
+    fn border_changed(&self) {
+        let Some(parent) = self.parent.get() else {
+            return;
+        };
+        if let Some(c) = parent.clone().node_into_container() {
+            c.on_colors_changed();
+        } else if let Some(f) = parent.node_into_float() {
+            f.on_spaces_changed();
+            if f.visible.get() {
+                self.state.damage(f.position.get());
+            }
+        }
+    }
+
+    /// Returns the border overrides of the toplevel.
+    ///
+    /// Overrides set for this toplevel take precedence over those configured for its
+    /// app-id.
+    fn border_overrides(&self) -> WindowBorder {
+        let mut border = self.border.get();
+        if let Some(rule) = self
+            .state
+            .window_borders
+            .borrow()
+            .get(&*self.app_id.borrow())
+        {
+            border.color = border.color.or(rule.color);
+            border.focused_color = border.focused_color.or(rule.focused_color);
+            border.width = border.width.or(rule.width);
+        }
+        border
+    }
+
+    pub fn border_width(&self) -> i32 {
+        self.border_overrides()
+            .width
+            .unwrap_or_else(|| self.state.theme.sizes.border_width.get())
+    }
+
+    /// Returns the border color of the toplevel if it differs from the theme.
+    pub fn custom_border_color(&self, focused: bool) -> Option<Color> {
+        let border = self.border_overrides();
+        match focused {
+            true => border.focused_color,
+            false => border.color,
+        }
+    }
+
+    pub fn border_color(&self, focused: bool) -> Color {
+        let colors = &self.state.theme.colors;
+        self.custom_border_color(focused)
+            .unwrap_or_else(|| match focused {
+                true => colors.focused_border(),
+                false => colors.border.get(),
+            })
+    }
+
+    /// Returns whether the title bar of the toplevel is hidden when it is tiled.
+    pub fn title_hidden(&self) -> bool {
+        match self.hide_title.get() {
+            Some(hidden) => hidden,
+            None => {
+                let app_id = self.app_id.borrow();
+                self.state.hidden_title_app_ids.borrow().contains(&app_id)
+            }
+        }
+    }
+
+    /// Returns whether the toplevel stays visible when the workspace of its output
+    /// changes while it is floating.
+    pub fn is_sticky(&self) -> bool {
+        match self.sticky.get() {
+            Some(sticky) => sticky,
+            None => {
+                let app_id = self.app_id.borrow();
+                self.state.sticky_app_ids.borrow().contains(&app_id)
+            }
+        }
+    }
+
+    pub fn set_title_hidden(&self, hidden: bool) {
+        let old = self.title_hidden();
+        self.hide_title.set(Some(hidden));
+        if old != hidden {
+            self.title_visibility_changed();
+        }
+    }
+
+    fn title_visibility_changed(&self) {
+        if let Some(parent) = self.parent.get() {
+            if let Some(c) = parent.node_into_container() {
+                c.on_spaces_changed();
+            }
+        }
+    }
+
     pub fn request_attention(&self, node: &dyn Node) {
         if self.visible.get() {
             return;
@@ -621,6 +1097,17 @@ impl Drop for ToplevelData {
     }
 }
 
+/// The deviation of a toplevel from its actual position, size, and opacity caused by
+/// running animations.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AnimatedState {
+    pub dx: i32,
+    pub dy: i32,
+    pub dwidth: i32,
+    pub dheight: i32,
+    pub alpha: Option<f32>,
+}
+
 pub struct TileDragDestination {
     pub highlight: Rect,
     pub ty: TddType,
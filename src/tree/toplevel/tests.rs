@@ -0,0 +1,73 @@
+use crate::tree::{toplevel::expand_title_format, ContainerSplit, ToplevelSizeHints};
+
+#[test]
+fn resize_unconstrained() {
+    let hints = ToplevelSizeHints::default();
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 100, 20), 120);
+    assert_eq!(hints.resize(ContainerSplit::Vertical, 100, -20), 80);
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 10, -20), 1);
+}
+
+#[test]
+fn resize_clamps_to_hints() {
+    let hints = ToplevelSizeHints {
+        min_width: Some(50),
+        min_height: Some(30),
+        max_width: Some(200),
+        max_height: None,
+    };
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 100, -80), 50);
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 100, 150), 200);
+    assert_eq!(hints.resize(ContainerSplit::Vertical, 100, -80), 30);
+    assert_eq!(hints.resize(ContainerSplit::Vertical, 100, 1000), 1100);
+}
+
+#[test]
+fn resize_never_moves_away_from_hints() {
+    let hints = ToplevelSizeHints {
+        min_width: Some(50),
+        min_height: None,
+        max_width: Some(200),
+        max_height: None,
+    };
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 40, -10), 40);
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 40, 5), 45);
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 250, 10), 250);
+    assert_eq!(hints.resize(ContainerSplit::Horizontal, 250, -10), 240);
+}
+
+fn format(format: &str) -> String {
+    expand_title_format(format, |key, res| {
+        match key {
+            "title" => res.push_str("Terminal"),
+            "app_id" => res.push_str("foot"),
+            "empty" => {}
+            _ => return false,
+        }
+        true
+    })
+}
+
+#[test]
+fn title_format_placeholders() {
+    assert_eq!(format(""), "");
+    assert_eq!(format("plain"), "plain");
+    assert_eq!(format("{title}"), "Terminal");
+    assert_eq!(format("{title} ({app_id})"), "Terminal (foot)");
+    assert_eq!(format("a{empty}b"), "ab");
+}
+
+#[test]
+fn title_format_escapes() {
+    assert_eq!(format("{{title}}"), "{title}");
+    assert_eq!(format("{{{title}}}"), "{Terminal}");
+    assert_eq!(format("a}b"), "a}b");
+    assert_eq!(format("{title"), "{title");
+}
+
+#[test]
+fn title_format_unknown_keys() {
+    assert_eq!(format("{pid}"), "{pid}");
+    assert_eq!(format("{} {title}"), "{} Terminal");
+    assert_eq!(format("{{{unknown}"), "{{unknown}");
+}
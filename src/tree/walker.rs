@@ -9,7 +9,8 @@ use {
             WlSurface,
         },
         tree::{
-            ContainerNode, DisplayNode, FloatNode, Node, OutputNode, PlaceholderNode, WorkspaceNode,
+            ContainerNode, DisplayNode, FloatNode, Node, OutputNode, PlaceholderNode,
+            ScrollingNode, WorkspaceNode,
         },
     },
     std::rc::Rc,
@@ -24,6 +25,10 @@ pub trait NodeVisitorBase: Sized {
         node.node_visit_children(self);
     }
 
+    fn visit_scrolling(&mut self, node: &Rc<ScrollingNode>) {
+        node.node_visit_children(self);
+    }
+
     fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
         node.node_visit_children(self);
     }
@@ -72,6 +77,7 @@ pub trait NodeVisitorBase: Sized {
 pub trait NodeVisitor {
     fn visit_surface(&mut self, node: &Rc<WlSurface>);
     fn visit_container(&mut self, node: &Rc<ContainerNode>);
+    fn visit_scrolling(&mut self, node: &Rc<ScrollingNode>);
     fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>);
     fn visit_popup(&mut self, node: &Rc<XdgPopup>);
     fn visit_display(&mut self, node: &Rc<DisplayNode>);
@@ -94,6 +100,10 @@ impl<T: NodeVisitorBase> NodeVisitor for T {
         <T as NodeVisitorBase>::visit_container(self, node)
     }
 
+    fn visit_scrolling(&mut self, node: &Rc<ScrollingNode>) {
+        <T as NodeVisitorBase>::visit_scrolling(self, node)
+    }
+
     fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
         <T as NodeVisitorBase>::visit_toplevel(self, node)
     }
@@ -158,6 +168,11 @@ impl<F: FnMut(Rc<dyn Node>)> NodeVisitor for GenericNodeVisitor<F> {
         node.node_visit_children(self);
     }
 
+    fn visit_scrolling(&mut self, node: &Rc<ScrollingNode>) {
+        (self.f)(node.clone());
+        node.node_visit_children(self);
+    }
+
     fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
         (self.f)(node.clone());
         node.node_visit_children(self);
@@ -16,9 +16,10 @@ use {
         state::State,
         text::TextTexture,
         tree::{
-            container::ContainerNode, walker::NodeVisitor, ContainingNode, Direction,
-            FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitorBase, OutputNode,
-            PlaceholderNode, StackedNode, ToplevelNode,
+            arrange_workspace, collect_tiled_toplevels, container::ContainerNode,
+            walker::NodeVisitor, ContainingNode, Direction, FindTreeResult, FindTreeUsecase,
+            FoundNode, Node, NodeId, NodeVisitorBase, OutputNode, PlaceholderNode, ScrollingNode,
+            StackedNode, ToplevelNode, WorkspaceLayout, MAX_MASTER_RATIO, MIN_MASTER_RATIO,
         },
         utils::{
             clonecell::CloneCell,
@@ -46,6 +47,7 @@ pub struct WorkspaceNode {
     pub output: CloneCell<Rc<OutputNode>>,
     pub position: Cell<Rect>,
     pub container: CloneCell<Option<Rc<ContainerNode>>>,
+    pub scrolling: CloneCell<Option<Rc<ScrollingNode>>>,
     pub stacked: LinkedList<Rc<dyn StackedNode>>,
     pub seat_state: NodeSeatState,
     pub name: String,
@@ -60,11 +62,17 @@ pub struct WorkspaceNode {
     pub title_texture: RefCell<Option<TextTexture>>,
     pub attention_requests: ThresholdCounter,
     pub render_highlight: NumCell<u32>,
+    pub layout: Cell<WorkspaceLayout>,
+    pub master_count: Cell<u32>,
+    pub master_ratio: Cell<f64>,
+    pub layout_scheduled: Cell<bool>,
+    pub reset_layout_factors: Cell<bool>,
 }
 
 impl WorkspaceNode {
     pub fn clear(&self) {
         self.container.set(None);
+        self.scrolling.set(None);
         *self.output_link.borrow_mut() = None;
         self.fullscreen.set(None);
         self.jay_workspaces.clear();
@@ -145,8 +153,104 @@ impl WorkspaceNode {
         self.state.damage(self.position.get());
     }
 
+    pub fn set_scrolling(self: &Rc<Self>, scrolling: &Rc<ScrollingNode>) {
+        scrolling.change_extents(&self.position.get());
+        scrolling.set_visible(self.container_visible());
+        self.scrolling.set(Some(scrolling.clone()));
+        self.state.damage(self.position.get());
+    }
+
+    pub fn remove_scrolling(&self, scrolling: &ScrollingNode) {
+        if let Some(s) = self.scrolling.get() {
+            if s.id == scrolling.id {
+                self.scrolling.set(None);
+                self.state.damage(self.position.get());
+            }
+        }
+    }
+
+    pub fn set_layout(self: &Rc<Self>, layout: WorkspaceLayout) {
+        if self.layout.replace(layout) != layout {
+            self.reset_layout_factors.set(true);
+            self.schedule_auto_layout();
+        }
+    }
+
+    pub fn set_master_count(self: &Rc<Self>, count: u32) {
+        if self.master_count.replace(count) != count {
+            self.reset_layout_factors.set(true);
+            self.schedule_auto_layout();
+        }
+    }
+
+    pub fn set_master_ratio(self: &Rc<Self>, ratio: f64) {
+        let ratio = ratio.clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO);
+        if self.master_ratio.replace(ratio) != ratio {
+            self.reset_layout_factors.set(true);
+            self.schedule_auto_layout();
+        }
+    }
+
+    pub fn schedule_auto_layout(self: &Rc<Self>) {
+        if self.layout.get() == WorkspaceLayout::Manual && self.scrolling.is_none() {
+            return;
+        }
+        if !self.layout_scheduled.replace(true) {
+            self.state.pending_workspace_layout.push(self.clone());
+        }
+    }
+
+    pub fn perform_auto_layout(self: &Rc<Self>) {
+        arrange_workspace(
+            &self.state,
+            self,
+            self.layout.get(),
+            self.master_count.get(),
+            self.master_ratio.get(),
+            self.reset_layout_factors.take(),
+        );
+        self.layout_scheduled.set(false);
+    }
+
+    pub fn promote_to_master(self: &Rc<Self>, node: &dyn ToplevelNode) {
+        if self.layout.get() == WorkspaceLayout::Manual {
+            return;
+        }
+        let Some(container) = self.container.get() else {
+            return;
+        };
+        let mut toplevels = vec![];
+        collect_tiled_toplevels(&container, &mut toplevels);
+        let Some(pos) = toplevels.iter().position(|t| t.node_id() == node.node_id()) else {
+            return;
+        };
+        let (node, master) = match pos {
+            0 => match toplevels.get(1) {
+                Some(next) => (next, &toplevels[0]),
+                _ => return,
+            },
+            _ => (&toplevels[pos], &toplevels[0]),
+        };
+        let Some(src) = node.tl_data().parent.get() else {
+            return;
+        };
+        let Some(dst) = master
+            .tl_data()
+            .parent
+            .get()
+            .and_then(|p| p.node_into_container())
+        else {
+            return;
+        };
+        src.cnode_remove_child2(node.tl_as_node(), true);
+        dst.add_child_before(master.tl_as_node(), node.clone());
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.stacked.is_empty() && self.fullscreen.is_none() && self.container.is_none()
+        self.stacked.is_empty()
+            && self.fullscreen.is_none()
+            && self.container.is_none()
+            && self.scrolling.is_none()
     }
 
     pub fn container_visible(&self) -> bool {
@@ -158,6 +262,9 @@ impl WorkspaceNode {
         if let Some(c) = self.container.get() {
             c.tl_change_extents(rect);
         }
+        if let Some(s) = self.scrolling.get() {
+            s.change_extents(rect);
+        }
     }
 
     pub fn flush_jay_workspaces(&self) {
@@ -180,6 +287,9 @@ impl WorkspaceNode {
         if let Some(container) = self.container.get() {
             container.tl_set_visible(self.container_visible());
         }
+        if let Some(scrolling) = self.scrolling.get() {
+            scrolling.set_visible(self.container_visible());
+        }
         for stacked in self.stacked.iter() {
             if stacked.stacked_needs_set_visible() {
                 stacked.stacked_set_visible(self.container_visible());
@@ -258,6 +368,9 @@ impl Node for WorkspaceNode {
         if let Some(c) = self.container.get() {
             visitor.visit_container(&c);
         }
+        if let Some(s) = self.scrolling.get() {
+            visitor.visit_scrolling(&s);
+        }
         if let Some(fs) = self.fullscreen.get() {
             fs.tl_into_node().node_visit(visitor);
         }
@@ -276,6 +389,8 @@ impl Node for WorkspaceNode {
             fs.tl_into_node().node_do_focus(seat, direction);
         } else if let Some(container) = self.container.get() {
             container.node_do_focus(seat, direction);
+        } else if let Some(scrolling) = self.scrolling.get() {
+            scrolling.node_do_focus(seat, direction);
         }
     }
 
@@ -294,6 +409,14 @@ impl Node for WorkspaceNode {
             });
             return n.node_find_tree_at(x, y, tree, usecase);
         }
+        if let Some(n) = self.scrolling.get() {
+            tree.push(FoundNode {
+                node: n.clone(),
+                x,
+                y,
+            });
+            return n.node_find_tree_at(x, y, tree, usecase);
+        }
         FindTreeResult::Other
     }
 
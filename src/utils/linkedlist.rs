@@ -90,7 +90,6 @@ impl<T> LinkedList<T> {
         self.root.prepend_existing(t)
     }
 
-    #[expect(dead_code)]
     pub fn add_first_existing(&self, t: &NodeRef<T>) {
         self.root.append_existing(t)
     }
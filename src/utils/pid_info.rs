@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::utils::{errorfmt::ErrorFmt, oserror::OsError, trim::AsciiTrim},
     bstr::ByteSlice,
@@ -38,3 +41,15 @@ pub fn get_socket_creds(socket: &OwnedFd) -> Option<(c::uid_t, c::pid_t)> {
         }
     }
 }
+
+pub fn get_parent_pid(pid: c::pid_t) -> Option<c::pid_t> {
+    let stat = std::fs::read(format!("/proc/{}/stat", pid)).ok()?;
+    parse_parent_pid(&stat.to_str_lossy())
+}
+
+fn parse_parent_pid(stat: &str) -> Option<c::pid_t> {
+    // The process name can contain spaces and parentheses. The parent pid is the
+    // second field after the last `)`.
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_ascii_whitespace().nth(1)?.parse().ok()
+}
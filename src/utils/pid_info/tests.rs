@@ -0,0 +1,23 @@
+use crate::utils::pid_info::parse_parent_pid;
+
+#[test]
+fn parent_pid() {
+    let stat = "1234 (foot) S 1200 1234 1200 34816 1234 4194304 0 0";
+    assert_eq!(parse_parent_pid(stat), Some(1200));
+}
+
+#[test]
+fn parent_pid_with_special_name() {
+    let stat = "1234 (a) b (c) S 99 1234 1200 34816 1234 4194304 0 0";
+    assert_eq!(parse_parent_pid(stat), Some(99));
+    let stat = "1234 (a b)) R 7 1234 1200 34816 1234 4194304 0 0";
+    assert_eq!(parse_parent_pid(stat), Some(7));
+}
+
+#[test]
+fn parent_pid_invalid() {
+    assert_eq!(parse_parent_pid(""), None);
+    assert_eq!(parse_parent_pid("1234 foot S 1200"), None);
+    assert_eq!(parse_parent_pid("1234 (foot) S"), None);
+    assert_eq!(parse_parent_pid("1234 (foot) S x"), None);
+}
@@ -0,0 +1,218 @@
+use {
+    crate::{
+        cpu_worker::{AsyncCpuWork, CpuJob, CpuWork},
+        format::ARGB8888,
+        gfx_api::{GfxError, GfxTexture},
+        tree::OutputNode,
+        utils::errorfmt::ErrorFmt,
+    },
+    jay_algorithms::qoi::{self, QoiError},
+    jay_config::video::WallpaperMode as ConfigWallpaperMode,
+    std::{
+        cell::Cell,
+        io::{self, Cursor},
+        rc::{Rc, Weak},
+    },
+    thiserror::Error,
+};
+
+/// The largest number of pixels of a wallpaper.
+const MAX_PIXELS: u64 = 16384 * 16384;
+
+#[derive(Debug, Error)]
+pub enum WallpaperError {
+    #[error("Could not read {0}")]
+    Read(String, #[source] io::Error),
+    #[error("The file is neither a PNG nor a QOI image")]
+    UnknownFormat,
+    #[error("Could not decode the PNG image")]
+    Png(#[source] png::DecodingError),
+    #[error("The PNG image has an unsupported color type {0:?}")]
+    UnsupportedColorType(png::ColorType),
+    #[error("The image has the invalid size {0}x{1}")]
+    InvalidSize(u32, u32),
+    #[error("Could not decode the QOI image")]
+    Qoi(#[from] QoiError),
+    #[error("Could not upload the image")]
+    Upload(#[source] GfxError),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum WallpaperMode {
+    /// Scales the image to cover the output and crops the overflow.
+    #[default]
+    Fill,
+    /// Scales the image to fit into the output.
+    Fit,
+    /// Shows the image unscaled in the center of the output.
+    Center,
+    /// Repeats the unscaled image to cover the output.
+    Tile,
+}
+
+impl WallpaperMode {
+    pub fn from_config(mode: ConfigWallpaperMode) -> Option<Self> {
+        let res = match mode {
+            ConfigWallpaperMode::FILL => Self::Fill,
+            ConfigWallpaperMode::FIT => Self::Fit,
+            ConfigWallpaperMode::CENTER => Self::Center,
+            ConfigWallpaperMode::TILE => Self::Tile,
+            _ => return None,
+        };
+        Some(res)
+    }
+}
+
+/// The wallpaper configured for an output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WallpaperConfig {
+    pub path: String,
+    pub mode: WallpaperMode,
+}
+
+/// A wallpaper that has been uploaded to the GPU.
+pub struct Wallpaper {
+    pub texture: Rc<dyn GfxTexture>,
+    pub mode: WallpaperMode,
+}
+
+pub struct DecodedImage {
+    pub width: i32,
+    pub height: i32,
+    /// The pixels in the ARGB8888 format.
+    pub data: Vec<Cell<u8>>,
+}
+
+fn decode(path: &str) -> Result<DecodedImage, WallpaperError> {
+    let data = std::fs::read(path).map_err(|e| WallpaperError::Read(path.to_string(), e))?;
+    if data.starts_with(b"\x89PNG") {
+        decode_png(&data)
+    } else if data.starts_with(qoi::MAGIC) {
+        let image = qoi::decode(&data, MAX_PIXELS)?;
+        Ok(to_argb8888(image.width, image.height, image.pixels))
+    } else {
+        Err(WallpaperError::UnknownFormat)
+    }
+}
+
+fn decode_png(data: &[u8]) -> Result<DecodedImage, WallpaperError> {
+    let limits = png::Limits {
+        bytes: MAX_PIXELS as usize * 8,
+    };
+    let mut decoder = png::Decoder::new_with_limits(Cursor::new(data), limits);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(WallpaperError::Png)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(WallpaperError::Png)?;
+    let (width, height) = (info.width, info.height);
+    if width as u64 * height as u64 > MAX_PIXELS {
+        return Err(WallpaperError::InvalidSize(width, height));
+    }
+    let buf = &buf[..info.buffer_size()];
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buf
+            .chunks_exact(4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+            .collect(),
+        png::ColorType::Rgb => buf
+            .chunks_exact(3)
+            .map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buf
+            .chunks_exact(2)
+            .map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buf.iter().map(|&p| [p, p, p, 255]).collect(),
+        ct => return Err(WallpaperError::UnsupportedColorType(ct)),
+    };
+    Ok(to_argb8888(width, height, pixels))
+}
+
+fn to_argb8888(width: u32, height: u32, pixels: Vec<[u8; 4]>) -> DecodedImage {
+    let premultiply = |c: u8, a: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+    let data = pixels
+        .into_iter()
+        .flat_map(|[r, g, b, a]| [premultiply(b, a), premultiply(g, a), premultiply(r, a), a])
+        .map(Cell::new)
+        .collect();
+    DecodedImage {
+        width: width as i32,
+        height: height as i32,
+        data,
+    }
+}
+
+struct DecodeWork {
+    path: String,
+    result: Option<Result<DecodedImage, WallpaperError>>,
+}
+
+impl CpuWork for DecodeWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        self.result = Some(decode(&self.path));
+        None
+    }
+}
+
+pub struct DecodeJob {
+    work: DecodeWork,
+    mode: WallpaperMode,
+    output: Weak<OutputNode>,
+}
+
+impl DecodeJob {
+    pub fn new(config: &WallpaperConfig, output: &Rc<OutputNode>) -> Box<Self> {
+        Box::new(Self {
+            work: DecodeWork {
+                path: config.path.clone(),
+                result: None,
+            },
+            mode: config.mode,
+            output: Rc::downgrade(output),
+        })
+    }
+}
+
+impl CpuJob for DecodeJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(mut self: Box<Self>) {
+        let Some(output) = self.output.upgrade() else {
+            return;
+        };
+        output.wallpaper_job.take();
+        let res = self.work.result.take().unwrap().and_then(|image| {
+            let Some(ctx) = output.state.render_ctx.get() else {
+                return Ok(None);
+            };
+            let texture = ctx
+                .shmem_texture(
+                    None,
+                    &image.data,
+                    ARGB8888,
+                    image.width,
+                    image.height,
+                    image.width * 4,
+                    None,
+                )
+                .map_err(WallpaperError::Upload)?;
+            Ok(Some(texture.into_texture()))
+        });
+        match res {
+            Ok(Some(texture)) => output.set_wallpaper(Some(Rc::new(Wallpaper {
+                texture,
+                mode: self.mode,
+            }))),
+            Ok(None) => {}
+            Err(e) => {
+                log::error!(
+                    "Could not load wallpaper {}: {}",
+                    self.work.path,
+                    ErrorFmt(e)
+                );
+            }
+        }
+    }
+}
@@ -1,10 +1,17 @@
+pub mod usr_ext_data_control_device;
+pub mod usr_ext_data_control_manager;
+pub mod usr_ext_data_control_offer;
+pub mod usr_ext_data_control_source;
 pub mod usr_jay_compositor;
 pub mod usr_jay_ei_session;
 pub mod usr_jay_ei_session_builder;
+pub mod usr_jay_global_shortcuts;
+pub mod usr_jay_input_capture;
 pub mod usr_jay_output;
 pub mod usr_jay_pointer;
 pub mod usr_jay_render_ctx;
 pub mod usr_jay_screencast;
+pub mod usr_jay_screenshot;
 pub mod usr_jay_select_toplevel;
 pub mod usr_jay_select_workspace;
 pub mod usr_jay_toplevel;
@@ -0,0 +1,114 @@
+use {
+    crate::{
+        object::Version,
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+        wire::{
+            ext_data_control_device_v1::*, ExtDataControlDeviceV1Id, ExtDataControlOfferV1Id,
+            ExtDataControlSourceV1Id,
+        },
+        wl_usr::{
+            usr_ifs::{
+                usr_ext_data_control_offer::UsrExtDataControlOffer,
+                usr_ext_data_control_source::UsrExtDataControlSource,
+            },
+            usr_object::UsrObject,
+            UsrCon,
+        },
+    },
+    std::{convert::Infallible, ops::Deref, rc::Rc},
+};
+
+pub struct UsrExtDataControlDevice {
+    pub id: ExtDataControlDeviceV1Id,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrExtDataControlDeviceOwner>>>,
+    pub version: Version,
+    pub offers: CopyHashMap<ExtDataControlOfferV1Id, Rc<UsrExtDataControlOffer>>,
+    pub selection: CloneCell<Option<Rc<UsrExtDataControlOffer>>>,
+}
+
+pub trait UsrExtDataControlDeviceOwner {
+    fn selection(&self, offer: Option<&Rc<UsrExtDataControlOffer>>) {
+        let _ = offer;
+    }
+}
+
+impl UsrExtDataControlDevice {
+    pub fn set_selection(&self, source: Option<&UsrExtDataControlSource>) {
+        self.con.request(SetSelection {
+            self_id: self.id,
+            source: source
+                .map(|s| s.id)
+                .unwrap_or(ExtDataControlSourceV1Id::NONE),
+        });
+    }
+
+    fn take_offer(&self, id: ExtDataControlOfferV1Id) -> Option<Rc<UsrExtDataControlOffer>> {
+        if id.is_none() {
+            return None;
+        }
+        self.offers.remove(&id)
+    }
+}
+
+impl ExtDataControlDeviceV1EventHandler for UsrExtDataControlDevice {
+    type Error = Infallible;
+
+    fn data_offer(&self, ev: DataOffer, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let offer = Rc::new(UsrExtDataControlOffer {
+            id: ev.id,
+            con: self.con.clone(),
+            version: self.version,
+            mime_types: Default::default(),
+        });
+        self.con.add_object(offer.clone());
+        self.offers.set(ev.id, offer);
+        Ok(())
+    }
+
+    fn selection(&self, ev: Selection, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let offer = self.take_offer(ev.id);
+        if let Some(old) = self.selection.set(offer.clone()) {
+            self.con.remove_obj(old.deref());
+        }
+        if let Some(owner) = self.owner.get() {
+            owner.selection(offer.as_ref());
+        }
+        Ok(())
+    }
+
+    fn finished(&self, _ev: Finished, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.con.remove_obj(self);
+        Ok(())
+    }
+
+    fn primary_selection(&self, ev: PrimarySelection, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(offer) = self.take_offer(ev.id) {
+            self.con.remove_obj(offer.deref());
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrExtDataControlDevice = ExtDataControlDeviceV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrExtDataControlDevice {
+    fn destroy(&self) {
+        for offer in self.offers.lock().drain_values() {
+            self.con.remove_obj(offer.deref());
+        }
+        if let Some(offer) = self.selection.take() {
+            self.con.remove_obj(offer.deref());
+        }
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+        self.offers.clear();
+        self.selection.take();
+    }
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        object::Version,
+        wire::{ext_data_control_manager_v1::*, ExtDataControlManagerV1Id},
+        wl_usr::{
+            usr_ifs::{
+                usr_ext_data_control_device::UsrExtDataControlDevice,
+                usr_ext_data_control_source::UsrExtDataControlSource, usr_wl_seat::UsrWlSeat,
+            },
+            usr_object::UsrObject,
+            UsrCon,
+        },
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrExtDataControlManager {
+    pub id: ExtDataControlManagerV1Id,
+    pub con: Rc<UsrCon>,
+    pub version: Version,
+}
+
+impl UsrExtDataControlManager {
+    pub fn create_data_source(&self) -> Rc<UsrExtDataControlSource> {
+        let source = Rc::new(UsrExtDataControlSource {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.add_object(source.clone());
+        self.con.request(CreateDataSource {
+            self_id: self.id,
+            id: source.id,
+        });
+        source
+    }
+
+    pub fn get_data_device(&self, seat: &UsrWlSeat) -> Rc<UsrExtDataControlDevice> {
+        let device = Rc::new(UsrExtDataControlDevice {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+            offers: Default::default(),
+            selection: Default::default(),
+        });
+        self.con.add_object(device.clone());
+        self.con.request(GetDataDevice {
+            self_id: self.id,
+            id: device.id,
+            seat: seat.id,
+        });
+        device
+    }
+}
+
+impl ExtDataControlManagerV1EventHandler for UsrExtDataControlManager {
+    type Error = Infallible;
+}
+
+usr_object_base! {
+    self = UsrExtDataControlManager = ExtDataControlManagerV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrExtDataControlManager {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+}
@@ -0,0 +1,46 @@
+use {
+    crate::{
+        object::Version,
+        wire::{ext_data_control_offer_v1::*, ExtDataControlOfferV1Id},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{cell::RefCell, convert::Infallible, rc::Rc},
+    uapi::OwnedFd,
+};
+
+pub struct UsrExtDataControlOffer {
+    pub id: ExtDataControlOfferV1Id,
+    pub con: Rc<UsrCon>,
+    pub version: Version,
+    pub mime_types: RefCell<Vec<String>>,
+}
+
+impl UsrExtDataControlOffer {
+    pub fn receive(&self, mime_type: &str, fd: &Rc<OwnedFd>) {
+        self.con.request(Receive {
+            self_id: self.id,
+            mime_type,
+            fd: fd.clone(),
+        });
+    }
+}
+
+impl ExtDataControlOfferV1EventHandler for UsrExtDataControlOffer {
+    type Error = Infallible;
+
+    fn offer(&self, ev: Offer<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.mime_types.borrow_mut().push(ev.mime_type.to_string());
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrExtDataControlOffer = ExtDataControlOfferV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrExtDataControlOffer {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+}
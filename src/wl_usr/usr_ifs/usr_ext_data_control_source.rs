@@ -0,0 +1,68 @@
+use {
+    crate::{
+        object::Version,
+        utils::clonecell::CloneCell,
+        wire::{ext_data_control_source_v1::*, ExtDataControlSourceV1Id},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{convert::Infallible, rc::Rc},
+    uapi::OwnedFd,
+};
+
+pub struct UsrExtDataControlSource {
+    pub id: ExtDataControlSourceV1Id,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrExtDataControlSourceOwner>>>,
+    pub version: Version,
+}
+
+pub trait UsrExtDataControlSourceOwner {
+    fn send(&self, mime_type: &str, fd: Rc<OwnedFd>) {
+        let _ = mime_type;
+        let _ = fd;
+    }
+
+    fn cancelled(&self) {}
+}
+
+impl UsrExtDataControlSource {
+    pub fn offer(&self, mime_type: &str) {
+        self.con.request(Offer {
+            self_id: self.id,
+            mime_type,
+        });
+    }
+}
+
+impl ExtDataControlSourceV1EventHandler for UsrExtDataControlSource {
+    type Error = Infallible;
+
+    fn send(&self, ev: Send<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.send(ev.mime_type, ev.fd);
+        }
+        Ok(())
+    }
+
+    fn cancelled(&self, _ev: Cancelled, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.cancelled();
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrExtDataControlSource = ExtDataControlSourceV1;
+    version = self.version;
+}
+
+impl UsrObject for UsrExtDataControlSource {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
@@ -6,9 +6,11 @@ use {
         wire::{jay_compositor::*, JayCompositorId},
         wl_usr::{
             usr_ifs::{
-                usr_jay_ei_session_builder::UsrJayEiSessionBuilder, usr_jay_output::UsrJayOutput,
+                usr_jay_ei_session_builder::UsrJayEiSessionBuilder,
+                usr_jay_global_shortcuts::UsrJayGlobalShortcuts,
+                usr_jay_input_capture::UsrJayInputCapture, usr_jay_output::UsrJayOutput,
                 usr_jay_pointer::UsrJayPointer, usr_jay_render_ctx::UsrJayRenderCtx,
-                usr_jay_screencast::UsrJayScreencast,
+                usr_jay_screencast::UsrJayScreencast, usr_jay_screenshot::UsrJayScreenshot,
                 usr_jay_select_toplevel::UsrJaySelectToplevel,
                 usr_jay_select_workspace::UsrJaySelectWorkspace,
                 usr_jay_workspace_watcher::UsrJayWorkspaceWatcher, usr_wl_output::UsrWlOutput,
@@ -80,6 +82,55 @@ impl UsrJayCompositor {
         sc
     }
 
+    pub fn take_screenshot(&self, include_cursor: bool) -> Rc<UsrJayScreenshot> {
+        let ss = Rc::new(UsrJayScreenshot {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+            pending_drm_dev: Default::default(),
+            pending_planes: Default::default(),
+        });
+        self.con.request(TakeScreenshot2 {
+            self_id: self.id,
+            id: ss.id,
+            include_cursor: include_cursor as _,
+        });
+        self.con.add_object(ss.clone());
+        ss
+    }
+
+    pub fn create_global_shortcuts(&self) -> Rc<UsrJayGlobalShortcuts> {
+        let gs = Rc::new(UsrJayGlobalShortcuts {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.request(CreateGlobalShortcuts {
+            self_id: self.id,
+            id: gs.id,
+        });
+        self.con.add_object(gs.clone());
+        gs
+    }
+
+    pub fn create_input_capture(&self) -> Rc<UsrJayInputCapture> {
+        let ic = Rc::new(UsrJayInputCapture {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+            pending_zones: Default::default(),
+        });
+        self.con.request(CreateInputCapture {
+            self_id: self.id,
+            id: ic.id,
+        });
+        self.con.add_object(ic.clone());
+        ic
+    }
+
     pub fn get_output(&self, output: &UsrWlOutput) -> Rc<UsrJayOutput> {
         let jo = Rc::new(UsrJayOutput {
             id: self.con.id(),
@@ -13,7 +13,7 @@ use {
     isnt::std_1::primitive::IsntConstPtrExt,
     std::{
         cell::{Ref, RefCell},
-        ffi::CStr,
+        ffi::{CStr, CString},
         io::Write,
         ops::Deref,
         ptr,
@@ -114,6 +114,16 @@ unsafe extern "C" {
         latched_layout: xkb_layout_index_t,
         locked_layout: xkb_layout_index_t,
     ) -> xkb_state_component;
+    fn xkb_keysym_from_name(name: *const c::c_char, flags: c::c_int) -> xkb_keysym_t;
+}
+
+const XKB_KEYSYM_CASE_INSENSITIVE: c::c_int = 1;
+
+/// Looks up a keysym by name, ignoring case. Lowercase keysyms are preferred.
+pub fn keysym_from_name(name: &str) -> Option<xkb_keysym_t> {
+    let name = CString::new(name).ok()?;
+    let sym = unsafe { xkb_keysym_from_name(name.as_ptr(), XKB_KEYSYM_CASE_INSENSITIVE) };
+    (sym != 0).then_some(sym)
 }
 
 pub struct XkbContext {
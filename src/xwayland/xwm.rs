@@ -2304,7 +2304,10 @@ impl Wm {
             _ => return Ok(()),
         };
         let minimize = match event.data[0] {
-            ICCCM_WM_STATE_NORMAL => false,
+            ICCCM_WM_STATE_NORMAL => {
+                self.handle_unminimize_requested(data);
+                false
+            }
             ICCCM_WM_STATE_ICONIC => self.handle_minimize_requested(data).await,
             _ => return Ok(()),
         };
@@ -2315,7 +2318,8 @@ impl Wm {
 
     async fn handle_minimize_requested(&self, data: &Rc<XwindowData>) -> bool {
         if let Some(w) = data.window.get() {
-            if w.toplevel_data.active_surfaces.active() {
+            self.state.minimize(w.clone());
+            if w.toplevel_data.minimized.borrow().is_none() {
                 self.set_wm_state(data, ICCCM_WM_STATE_NORMAL).await;
                 return false;
             }
@@ -2324,6 +2328,18 @@ impl Wm {
         true
     }
 
+    fn handle_unminimize_requested(&self, data: &Rc<XwindowData>) {
+        let Some(w) = data.window.get() else {
+            return;
+        };
+        if w.toplevel_data.minimized.borrow().is_none() {
+            return;
+        }
+        if let Some(seat) = self.state.seat_queue.last() {
+            self.state.unminimize(&seat, w);
+        }
+    }
+
     async fn handle_net_startup_info(
         &mut self,
         event: &ClientMessage<'_>,
@@ -2427,6 +2443,8 @@ impl Wm {
         if minimized != data.info.minimized.get() {
             if minimized {
                 minimized = self.handle_minimize_requested(data).await;
+            } else {
+                self.handle_unminimize_requested(data);
             }
         }
         if fullscreen != data.info.fullscreen.get() {
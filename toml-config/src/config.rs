@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 mod context;
 pub mod error;
 mod extractor;
@@ -17,14 +20,15 @@ use {
     },
     ahash::AHashMap,
     jay_config::{
+        animation::{AnimationCurve, AnimationKind},
         input::{acceleration::AccelProfile, SwitchEvent},
         keyboard::{mods::Modifiers, Keymap, ModifiedKeySym},
         logging::LogLevel,
         status::MessageFormat,
-        theme::Color,
-        video::{Format, GfxApi, TearingMode, Transform, VrrMode},
+        theme::{Color, WindowBorder},
+        video::{Format, GfxApi, TearingMode, Transform, VrrMode, WallpaperMode},
         xwayland::XScalingMode,
-        Axis, Direction, Workspace,
+        Axis, BarMode, BarPosition, Direction, ResizeAmount, Workspace, WorkspaceLayout,
     },
     std::{
         error::Error,
@@ -53,6 +57,19 @@ pub enum SimpleCommand {
     ToggleSplit,
     Forward(bool),
     EnableWindowManagement(bool),
+    CycleLayout,
+    PromoteToMaster,
+    CycleColumnWidth,
+    ToggleLayout,
+    Unmark,
+    FocusPrevious,
+    FocusNextInHistory,
+    WindowSwitcher,
+    WindowSwitcherReverse,
+    ToggleSticky,
+    ToggleTitleHidden,
+    Minimize,
+    UnminimizeLast,
 }
 
 #[derive(Debug, Clone)]
@@ -124,6 +141,45 @@ pub enum Action {
     SetRepeatRate {
         rate: RepeatRate,
     },
+    Resize {
+        axis: Axis,
+        amount: ResizeAmount,
+    },
+    SetLayout {
+        layout: WorkspaceLayout,
+    },
+    AdjustMasterCount {
+        delta: i32,
+    },
+    AdjustMasterRatio {
+        delta: f64,
+    },
+    Mark {
+        name: String,
+    },
+    FocusMark {
+        name: String,
+    },
+    SwapWithMark {
+        name: String,
+    },
+    MoveToMark {
+        name: String,
+    },
+    SaveLayout {
+        path: String,
+        workspace: Option<String>,
+    },
+    LoadLayout {
+        path: String,
+        workspace: Option<String>,
+    },
+    SetWindowOpacity {
+        opacity: Option<f64>,
+    },
+    SetWindowBorder {
+        border: WindowBorder,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -143,9 +199,23 @@ pub struct Theme {
     pub unfocused_title_bg_color: Option<Color>,
     pub unfocused_title_text_color: Option<Color>,
     pub highlight_color: Option<Color>,
+    pub shadow_color: Option<Color>,
+    pub inactive_dim_color: Option<Color>,
+    pub title_button_hover_color: Option<Color>,
+    pub focused_border_color: Option<Color>,
     pub border_width: Option<i32>,
     pub title_height: Option<i32>,
     pub font: Option<String>,
+    pub title_font: Option<String>,
+    pub bar_font: Option<String>,
+    pub status_font: Option<String>,
+    pub font_fallbacks: Option<Vec<String>>,
+    pub corner_radius: Option<i32>,
+    pub shadow_blur: Option<i32>,
+    pub shadow_offset_x: Option<i32>,
+    pub shadow_offset_y: Option<i32>,
+    pub blur_passes: Option<i32>,
+    pub blur_offset: Option<i32>,
 }
 
 #[derive(Debug, Clone)]
@@ -216,6 +286,7 @@ pub struct Output {
     pub vrr: Option<Vrr>,
     pub tearing: Option<Tearing>,
     pub format: Option<Format>,
+    pub wallpaper: Option<Wallpaper>,
 }
 
 #[derive(Debug, Clone)]
@@ -303,16 +374,44 @@ pub struct Vrr {
     pub cursor_hz: Option<f64>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct Bar {
+    pub position: Option<BarPosition>,
+    pub mode: Option<BarMode>,
+    pub modifiers: Option<Modifiers>,
+    pub connectors: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Xwayland {
     pub scaling_mode: Option<XScalingMode>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct AnimationSettings {
+    pub duration_ms: Option<u64>,
+    pub curve: Option<AnimationCurve>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Animations {
+    pub enabled: Option<bool>,
+    pub in_screencasts: Option<bool>,
+    pub all: AnimationSettings,
+    pub kinds: Vec<(AnimationKind, AnimationSettings)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Tearing {
     pub mode: Option<TearingMode>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Wallpaper {
+    pub path: String,
+    pub mode: Option<WallpaperMode>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Libei {
     pub enable_socket: Option<bool>,
@@ -337,6 +436,8 @@ pub struct Config {
     pub connectors: Vec<ConfigConnector>,
     pub outputs: Vec<Output>,
     pub workspace_capture: bool,
+    pub workspace_layout: WorkspaceLayout,
+    pub column_width_presets: Option<Vec<f64>>,
     pub env: Vec<(String, String)>,
     pub on_startup: Option<Action>,
     pub keymaps: Vec<ConfigKeymap>,
@@ -350,12 +451,25 @@ pub struct Config {
     pub idle: Option<Duration>,
     pub explicit_sync_enabled: Option<bool>,
     pub focus_follows_mouse: bool,
+    pub show_marks: bool,
+    pub swallow_terminals: Vec<String>,
+    pub swallow_exceptions: Vec<String>,
+    pub blur_app_ids: Vec<String>,
+    pub blur_layer_namespaces: Vec<String>,
+    pub window_opacities: Vec<(String, f64)>,
+    pub title_format: Option<String>,
+    pub hidden_title_app_ids: Vec<String>,
+    pub sticky_app_ids: Vec<String>,
+    pub show_title_buttons: bool,
+    pub window_borders: Vec<(String, WindowBorder)>,
+    pub bar: Bar,
     pub window_management_key: Option<ModifiedKeySym>,
     pub vrr: Option<Vrr>,
     pub tearing: Option<Tearing>,
     pub libei: Libei,
     pub ui_drag: UiDrag,
     pub xwayland: Option<Xwayland>,
+    pub animations: Option<Animations>,
 }
 
 #[derive(Debug, Error)]
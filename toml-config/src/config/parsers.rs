@@ -7,6 +7,8 @@ use {
 };
 
 pub mod action;
+mod animations;
+mod bar;
 mod color;
 pub mod config;
 mod connector;
@@ -34,6 +36,10 @@ mod tearing;
 mod theme;
 mod ui_drag;
 mod vrr;
+mod wallpaper;
+mod window_border;
+mod window_opacity;
+mod workspace_layout;
 mod xwayland;
 
 #[derive(Debug, Error)]
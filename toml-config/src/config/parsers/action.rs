@@ -2,7 +2,9 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{arr, bol, n32, opt, str, val, Extractor, ExtractorError},
+            extractor::{
+                arr, bol, fltorint, n32, opt, recover, s32, str, val, Extractor, ExtractorError,
+            },
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 connector::{ConnectorParser, ConnectorParserError},
@@ -20,6 +22,8 @@ use {
                 repeat_rate::{RepeatRateParser, RepeatRateParserError},
                 status::{StatusParser, StatusParserError},
                 theme::{ThemeParser, ThemeParserError},
+                window_border::parse_border_color,
+                workspace_layout::{WorkspaceLayoutParser, WorkspaceLayoutParserError},
                 StringParser, StringParserError,
             },
             spanned::SpannedErrorExt,
@@ -33,7 +37,9 @@ use {
     indexmap::IndexMap,
     jay_config::{
         get_workspace,
+        theme::WindowBorder,
         Axis::{Horizontal, Vertical},
+        ResizeAmount,
     },
     thiserror::Error,
 };
@@ -80,6 +86,14 @@ pub enum ActionParserError {
     MoveToOutput(#[source] OutputMatchParserError),
     #[error("Could not parse a set-repeat-rate action")]
     RepeatRate(#[source] RepeatRateParserError),
+    #[error("Unknown resize change {0}")]
+    UnknownResizeChange(String),
+    #[error("Unknown resize dimension {0}")]
+    UnknownResizeDimension(String),
+    #[error("Resize action must contain exactly one of `px`, `ppt`")]
+    ResizeAmount,
+    #[error("Could not parse a set-layout action")]
+    SetLayout(#[source] WorkspaceLayoutParserError),
 }
 
 pub struct ActionParser<'a>(pub &'a Context<'a>);
@@ -113,6 +127,19 @@ impl ActionParser<'_> {
             "consume" => Forward(false),
             "enable-window-management" => EnableWindowManagement(true),
             "disable-window-management" => EnableWindowManagement(false),
+            "cycle-layout" => CycleLayout,
+            "promote-to-master" => PromoteToMaster,
+            "cycle-column-width" => CycleColumnWidth,
+            "toggle-layout" => ToggleLayout,
+            "unmark" => Unmark,
+            "focus-previous" => FocusPrevious,
+            "focus-next-in-history" => FocusNextInHistory,
+            "window-switcher" => WindowSwitcher,
+            "window-switcher-reverse" => WindowSwitcherReverse,
+            "toggle-sticky" => ToggleSticky,
+            "toggle-title-hidden" => ToggleTitleHidden,
+            "minimize" => Minimize,
+            "unminimize-last" => UnminimizeLast,
             _ => {
                 return Err(ActionParserError::UnknownSimpleAction(string.to_string()).spanned(span))
             }
@@ -312,6 +339,111 @@ impl ActionParser<'_> {
             .map_spanned_err(ActionParserError::RepeatRate)?;
         Ok(Action::SetRepeatRate { rate })
     }
+
+    fn parse_set_layout(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let layout = ext
+            .extract(val("layout"))?
+            .parse_map(&mut WorkspaceLayoutParser)
+            .map_spanned_err(ActionParserError::SetLayout)?;
+        Ok(Action::SetLayout { layout })
+    }
+
+    fn parse_adjust_master_count(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let delta = ext.extract(s32("delta"))?.value;
+        Ok(Action::AdjustMasterCount { delta })
+    }
+
+    fn parse_adjust_master_ratio(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let delta = ext.extract(fltorint("delta"))?.value;
+        Ok(Action::AdjustMasterRatio { delta })
+    }
+
+    fn parse_set_window_opacity(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let opacity = ext.extract(opt(fltorint("opacity")))?.despan();
+        Ok(Action::SetWindowOpacity { opacity })
+    }
+
+    fn parse_set_window_border(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (color, focused_color, width) = ext.extract((
+            opt(val("color")),
+            opt(val("focused-color")),
+            recover(opt(s32("width"))),
+        ))?;
+        let border = WindowBorder {
+            color: parse_border_color(self.0, color),
+            focused_color: parse_border_color(self.0, focused_color),
+            width: width.despan(),
+        };
+        Ok(Action::SetWindowBorder { border })
+    }
+
+    fn parse_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let name = ext.extract(str("name"))?.value.to_string();
+        Ok(Action::Mark { name })
+    }
+
+    fn parse_focus_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let name = ext.extract(str("name"))?.value.to_string();
+        Ok(Action::FocusMark { name })
+    }
+
+    fn parse_swap_with_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let name = ext.extract(str("name"))?.value.to_string();
+        Ok(Action::SwapWithMark { name })
+    }
+
+    fn parse_move_to_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let name = ext.extract(str("name"))?.value.to_string();
+        Ok(Action::MoveToMark { name })
+    }
+
+    fn parse_save_layout(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (path, workspace) = ext.extract((str("path"), opt(str("workspace"))))?;
+        Ok(Action::SaveLayout {
+            path: path.value.to_string(),
+            workspace: workspace.despan_into(),
+        })
+    }
+
+    fn parse_load_layout(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (path, workspace) = ext.extract((str("path"), opt(str("workspace"))))?;
+        Ok(Action::LoadLayout {
+            path: path.value.to_string(),
+            workspace: workspace.despan_into(),
+        })
+    }
+
+    fn parse_resize(&mut self, span: Span, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (change, dimension, px, ppt) = ext.extract((
+            str("change"),
+            str("dimension"),
+            opt(s32("px")),
+            opt(s32("ppt")),
+        ))?;
+        let sign = match change.value {
+            "grow" => 1,
+            "shrink" => -1,
+            v => {
+                return Err(
+                    ActionParserError::UnknownResizeChange(v.to_string()).spanned(change.span)
+                )
+            }
+        };
+        let axis = match dimension.value {
+            "width" => Horizontal,
+            "height" => Vertical,
+            v => {
+                return Err(ActionParserError::UnknownResizeDimension(v.to_string())
+                    .spanned(dimension.span))
+            }
+        };
+        let amount = match (px, ppt) {
+            (Some(px), None) => ResizeAmount::Px(sign * px.value),
+            (None, Some(ppt)) => ResizeAmount::Ppt(sign * ppt.value),
+            _ => return Err(ActionParserError::ResizeAmount.spanned(span)),
+        };
+        Ok(Action::Resize { axis, amount })
+    }
 }
 
 impl<'a> Parser for ActionParser<'a> {
@@ -363,6 +495,18 @@ impl<'a> Parser for ActionParser<'a> {
             "configure-idle" => self.parse_configure_idle(&mut ext),
             "move-to-output" => self.parse_move_to_output(&mut ext),
             "set-repeat-rate" => self.parse_set_repeat_rate(&mut ext),
+            "resize" => self.parse_resize(span, &mut ext),
+            "set-layout" => self.parse_set_layout(&mut ext),
+            "adjust-master-count" => self.parse_adjust_master_count(&mut ext),
+            "adjust-master-ratio" => self.parse_adjust_master_ratio(&mut ext),
+            "mark" => self.parse_mark(&mut ext),
+            "focus-mark" => self.parse_focus_mark(&mut ext),
+            "swap-with-mark" => self.parse_swap_with_mark(&mut ext),
+            "move-to-mark" => self.parse_move_to_mark(&mut ext),
+            "save-layout" => self.parse_save_layout(&mut ext),
+            "load-layout" => self.parse_load_layout(&mut ext),
+            "set-window-opacity" => self.parse_set_window_opacity(&mut ext),
+            "set-window-border" => self.parse_set_window_border(&mut ext),
             v => {
                 ext.ignore_unused();
                 return Err(ActionParserError::UnknownType(v.to_string()).spanned(ty.span));
@@ -0,0 +1,178 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, n64, opt, recover, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            AnimationSettings, Animations,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::animation::{AnimationCurve, AnimationKind},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum AnimationsParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct AnimationsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for AnimationsParser<'_> {
+    type Value = Animations;
+    type Error = AnimationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (
+            enabled,
+            duration_ms,
+            curve_val,
+            in_screencasts,
+            open_val,
+            close_val,
+            tile_val,
+            workspace_val,
+            float_val,
+        ) = ext.extract((
+            recover(opt(bol("enabled"))),
+            recover(opt(n64("duration-ms"))),
+            opt(val("curve")),
+            recover(opt(bol("in-screencasts"))),
+            opt(val("open")),
+            opt(val("close")),
+            opt(val("tile")),
+            opt(val("workspace")),
+            opt(val("float")),
+        ))?;
+        let all = AnimationSettings {
+            duration_ms: duration_ms.despan(),
+            curve: parse_curve(self.0, curve_val),
+        };
+        let mut kinds = vec![];
+        let kind_vals = [
+            (AnimationKind::OPEN, "open", open_val),
+            (AnimationKind::CLOSE, "close", close_val),
+            (AnimationKind::TILE, "tile", tile_val),
+            (AnimationKind::WORKSPACE, "workspace", workspace_val),
+            (AnimationKind::FLOAT, "float", float_val),
+        ];
+        for (kind, name, value) in kind_vals {
+            let Some(value) = value else {
+                continue;
+            };
+            match value.parse(&mut AnimationSettingsParser(self.0)) {
+                Ok(v) => kinds.push((kind, v)),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the {name} animation settings: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        Ok(Animations {
+            enabled: enabled.despan(),
+            in_screencasts: in_screencasts.despan(),
+            all,
+            kinds,
+        })
+    }
+}
+
+struct AnimationSettingsParser<'a>(&'a Context<'a>);
+
+impl Parser for AnimationSettingsParser<'_> {
+    type Value = AnimationSettings;
+    type Error = AnimationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (duration_ms, curve_val) =
+            ext.extract((recover(opt(n64("duration-ms"))), opt(val("curve"))))?;
+        Ok(AnimationSettings {
+            duration_ms: duration_ms.despan(),
+            curve: parse_curve(self.0, curve_val),
+        })
+    }
+}
+
+fn parse_curve(cx: &Context<'_>, value: Option<Spanned<&Value>>) -> Option<AnimationCurve> {
+    match value?.parse(&mut AnimationCurveParser) {
+        Ok(c) => Some(c),
+        Err(e) => {
+            log::warn!("Could not parse the animation curve: {}", cx.error(e));
+            None
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AnimationCurveParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown curve {0}")]
+    UnknownCurve(String),
+    #[error("Expected an array of 4 numbers but found {0} elements")]
+    Length(usize),
+    #[error("Expected a number")]
+    Number,
+}
+
+struct AnimationCurveParser;
+
+impl Parser for AnimationCurveParser {
+    type Value = AnimationCurve;
+    type Error = AnimationCurveParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String, DataType::Array];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let curve = match string {
+            "linear" => AnimationCurve::Linear,
+            "ease" => AnimationCurve::Ease,
+            "ease-in" => AnimationCurve::EaseIn,
+            "ease-out" => AnimationCurve::EaseOut,
+            "ease-in-out" => AnimationCurve::EaseInOut,
+            _ => {
+                return Err(
+                    AnimationCurveParserError::UnknownCurve(string.to_string()).spanned(span)
+                )
+            }
+        };
+        Ok(curve)
+    }
+
+    fn parse_array(&mut self, span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut points = [0.0; 4];
+        if array.len() != points.len() {
+            return Err(AnimationCurveParserError::Length(array.len()).spanned(span));
+        }
+        for (point, v) in points.iter_mut().zip(array) {
+            *point = match v.value {
+                Value::Float(f) => f,
+                Value::Integer(i) => i as _,
+                _ => return Err(AnimationCurveParserError::Number.spanned(v.span)),
+            };
+        }
+        let [x1, y1, x2, y2] = points;
+        Ok(AnimationCurve::CubicBezier(x1, y1, x2, y2))
+    }
+}
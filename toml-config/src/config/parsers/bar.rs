@@ -0,0 +1,127 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{arr, opt, recover, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::{modified_keysym::ModifiersParser, StringParser},
+            Bar,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::{BarMode, BarPosition},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum BarParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown position {0}")]
+    UnknownPosition(String),
+    #[error("Unknown mode {0}")]
+    UnknownMode(String),
+}
+
+pub struct BarParser<'a>(pub &'a Context<'a>);
+
+impl Parser for BarParser<'_> {
+    type Value = Bar;
+    type Error = BarParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (position, mode, modifiers, connectors_val) = ext.extract((
+            opt(val("position")),
+            opt(val("mode")),
+            opt(val("modifiers")),
+            recover(opt(arr("connectors"))),
+        ))?;
+        let position = position.and_then(|p| match p.parse(&mut BarPositionParser) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                log::warn!("Could not parse the bar position: {}", self.0.error(e));
+                None
+            }
+        });
+        let mode = mode.and_then(|m| match m.parse(&mut BarModeParser) {
+            Ok(m) => Some(m),
+            Err(e) => {
+                log::warn!("Could not parse the bar mode: {}", self.0.error(e));
+                None
+            }
+        });
+        let modifiers = modifiers.and_then(|m| match m.parse(&mut ModifiersParser) {
+            Ok(m) => Some(m),
+            Err(e) => {
+                log::warn!("Could not parse the bar modifiers: {}", self.0.error(e));
+                None
+            }
+        });
+        let mut connectors = None;
+        if let Some(value) = connectors_val {
+            let mut res = vec![];
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => res.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse a bar connector: {}", self.0.error(e));
+                    }
+                }
+            }
+            connectors = Some(res);
+        }
+        Ok(Bar {
+            position,
+            mode,
+            modifiers,
+            connectors,
+        })
+    }
+}
+
+struct BarPositionParser;
+
+impl Parser for BarPositionParser {
+    type Value = BarPosition;
+    type Error = BarParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let position = match string {
+            "top" => BarPosition::TOP,
+            "bottom" => BarPosition::BOTTOM,
+            _ => return Err(BarParserError::UnknownPosition(string.to_string()).spanned(span)),
+        };
+        Ok(position)
+    }
+}
+
+struct BarModeParser;
+
+impl Parser for BarModeParser {
+    type Value = BarMode;
+    type Error = BarParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let mode = match string {
+            "visible" => BarMode::VISIBLE,
+            "hidden" => BarMode::HIDDEN,
+            "on-modifier" => BarMode::ON_MODIFIER,
+            _ => return Err(BarParserError::UnknownMode(string.to_string()).spanned(span)),
+        };
+        Ok(mode)
+    }
+}
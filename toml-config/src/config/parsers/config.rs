@@ -6,6 +6,8 @@ use {
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
+                animations::AnimationsParser,
+                bar::BarParser,
                 connector::ConnectorsParser,
                 drm_device::DrmDevicesParser,
                 drm_device_match::DrmDeviceMatchParser,
@@ -27,10 +29,14 @@ use {
                 theme::ThemeParser,
                 ui_drag::UiDragParser,
                 vrr::VrrParser,
+                window_border::WindowBordersParser,
+                window_opacity::WindowOpacityParser,
+                workspace_layout::{ColumnWidthPresetsParser, WorkspaceLayoutParser},
                 xwayland::XwaylandParser,
+                StringParser,
             },
             spanned::SpannedErrorExt,
-            Action, Config, Libei, Theme, UiDrag,
+            Action, Bar, Config, Libei, Theme, UiDrag,
         },
         toml::{
             toml_span::{DespanExt, Span, Spanned},
@@ -38,6 +44,7 @@ use {
         },
     },
     indexmap::IndexMap,
+    jay_config::WorkspaceLayout,
     std::collections::HashSet,
     thiserror::Error,
 };
@@ -117,6 +124,25 @@ impl Parser for ConfigParser<'_> {
                 ui_drag_val,
                 xwayland_val,
             ),
+            (
+                workspace_layout_val,
+                column_width_presets_val,
+                show_marks,
+                swallow_terminals_val,
+                swallow_exceptions_val,
+                animations_val,
+                blur_app_ids_val,
+                blur_layer_namespaces_val,
+                window_opacity_val,
+            ),
+            (
+                title_format,
+                hidden_title_app_ids_val,
+                show_title_buttons,
+                window_borders_val,
+                bar_val,
+                sticky_app_ids_val,
+            ),
         ) = ext.extract((
             (
                 opt(val("keymap")),
@@ -154,6 +180,25 @@ impl Parser for ConfigParser<'_> {
                 opt(val("ui-drag")),
                 opt(val("xwayland")),
             ),
+            (
+                opt(val("workspace-layout")),
+                opt(val("column-width-presets")),
+                recover(opt(bol("show-marks"))),
+                recover(opt(arr("swallow-terminals"))),
+                recover(opt(arr("swallow-exceptions"))),
+                opt(val("animations")),
+                recover(opt(arr("blur-app-ids"))),
+                recover(opt(arr("blur-layer-namespaces"))),
+                opt(val("window-opacity")),
+            ),
+            (
+                recover(opt(str("title-format"))),
+                recover(opt(arr("hidden-title-app-ids"))),
+                recover(opt(bol("show-title-buttons"))),
+                opt(val("window-borders")),
+                opt(val("bar")),
+                recover(opt(arr("sticky-app-ids"))),
+            ),
         ))?;
         let mut keymap = None;
         if let Some(value) = keymap_val {
@@ -362,6 +407,135 @@ impl Parser for ConfigParser<'_> {
                 }
             }
         }
+        let mut animations = None;
+        if let Some(value) = animations_val {
+            match value.parse(&mut AnimationsParser(self.0)) {
+                Ok(v) => animations = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the animation settings: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut workspace_layout = WorkspaceLayout::MANUAL;
+        if let Some(value) = workspace_layout_val {
+            match value.parse(&mut WorkspaceLayoutParser) {
+                Ok(v) => workspace_layout = v,
+                Err(e) => {
+                    log::warn!("Could not parse the workspace layout: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut swallow_terminals = vec![];
+        if let Some(value) = swallow_terminals_val {
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => swallow_terminals.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse a swallow terminal: {}", self.0.error(e));
+                    }
+                }
+            }
+        }
+        let mut swallow_exceptions = vec![];
+        if let Some(value) = swallow_exceptions_val {
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => swallow_exceptions.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse a swallow exception: {}", self.0.error(e));
+                    }
+                }
+            }
+        }
+        let mut blur_app_ids = vec![];
+        if let Some(value) = blur_app_ids_val {
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => blur_app_ids.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse a blur app-id: {}", self.0.error(e));
+                    }
+                }
+            }
+        }
+        let mut blur_layer_namespaces = vec![];
+        if let Some(value) = blur_layer_namespaces_val {
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => blur_layer_namespaces.push(v),
+                    Err(e) => {
+                        log::warn!(
+                            "Could not parse a blur layer namespace: {}",
+                            self.0.error(e)
+                        );
+                    }
+                }
+            }
+        }
+        let mut window_opacities = vec![];
+        if let Some(value) = window_opacity_val {
+            match value.parse(&mut WindowOpacityParser) {
+                Ok(v) => window_opacities = v,
+                Err(e) => {
+                    log::warn!("Could not parse the window opacities: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut hidden_title_app_ids = vec![];
+        if let Some(value) = hidden_title_app_ids_val {
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => hidden_title_app_ids.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse a hidden title app-id: {}", self.0.error(e));
+                    }
+                }
+            }
+        }
+        let mut sticky_app_ids = vec![];
+        if let Some(value) = sticky_app_ids_val {
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => sticky_app_ids.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse a sticky app-id: {}", self.0.error(e));
+                    }
+                }
+            }
+        }
+        let mut bar = Bar::default();
+        if let Some(value) = bar_val {
+            match value.parse(&mut BarParser(self.0)) {
+                Ok(v) => bar = v,
+                Err(e) => {
+                    log::warn!("Could not parse the bar setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut window_borders = vec![];
+        if let Some(value) = window_borders_val {
+            match value.parse(&mut WindowBordersParser(self.0)) {
+                Ok(v) => window_borders = v,
+                Err(e) => {
+                    log::warn!("Could not parse the window borders: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut column_width_presets = None;
+        if let Some(value) = column_width_presets_val {
+            match value.parse(&mut ColumnWidthPresetsParser) {
+                Ok(v) => column_width_presets = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the column width presets: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
         Ok(Config {
             keymap,
             repeat_rate,
@@ -372,6 +546,8 @@ impl Parser for ConfigParser<'_> {
             outputs,
             connectors,
             workspace_capture: workspace_capture.despan().unwrap_or(true),
+            workspace_layout,
+            column_width_presets,
             env,
             on_startup,
             keymaps,
@@ -385,12 +561,25 @@ impl Parser for ConfigParser<'_> {
             inputs,
             idle,
             focus_follows_mouse: focus_follows_mouse.despan().unwrap_or(true),
+            show_marks: show_marks.despan().unwrap_or(true),
+            swallow_terminals,
+            swallow_exceptions,
+            blur_app_ids,
+            blur_layer_namespaces,
+            window_opacities,
+            title_format: title_format.map(|f| f.value.to_string()),
+            hidden_title_app_ids,
+            sticky_app_ids,
+            show_title_buttons: show_title_buttons.despan().unwrap_or(false),
+            window_borders,
+            bar,
             window_management_key,
             vrr,
             tearing,
             libei,
             ui_drag,
             xwayland,
+            animations,
         })
     }
 }
@@ -10,6 +10,7 @@ use {
                 output_match::{OutputMatchParser, OutputMatchParserError},
                 tearing::TearingParser,
                 vrr::VrrParser,
+                wallpaper::WallpaperParser,
             },
             Output,
         },
@@ -49,8 +50,11 @@ impl<'a> Parser for OutputParser<'a> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.cx, span, table);
-        let (name, match_val, x, y, scale, transform, mode, vrr_val, tearing_val, format_val) = ext
-            .extract((
+        let (
+            (name, match_val, x, y, scale, transform, mode, vrr_val, tearing_val, format_val),
+            (wallpaper_val,),
+        ) = ext.extract((
+            (
                 opt(str("name")),
                 val("match"),
                 recover(opt(s32("x"))),
@@ -61,7 +65,9 @@ impl<'a> Parser for OutputParser<'a> {
                 opt(val("vrr")),
                 opt(val("tearing")),
                 opt(val("format")),
-            ))?;
+            ),
+            (opt(val("wallpaper")),),
+        ))?;
         let transform = match transform {
             None => None,
             Some(t) => match t.value {
@@ -133,6 +139,15 @@ impl<'a> Parser for OutputParser<'a> {
                 }
             }
         }
+        let mut wallpaper = None;
+        if let Some(value) = wallpaper_val {
+            match value.parse(&mut WallpaperParser(self.cx)) {
+                Ok(v) => wallpaper = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse wallpaper setting: {}", self.cx.error(e));
+                }
+            }
+        }
         Ok(Output {
             name: name.despan().map(|v| v.to_string()),
             match_: match_val.parse_map(&mut OutputMatchParser(self.cx))?,
@@ -144,6 +159,7 @@ impl<'a> Parser for OutputParser<'a> {
             vrr,
             tearing,
             format,
+            wallpaper,
         })
     }
 }
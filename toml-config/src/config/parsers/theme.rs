@@ -2,9 +2,9 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{opt, recover, s32, str, val, Extractor, ExtractorError},
+            extractor::{arr, opt, recover, s32, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
-            parsers::color::ColorParser,
+            parsers::{color::ColorParser, StringParser},
             Theme,
         },
         toml::{
@@ -60,6 +60,19 @@ impl Parser for ThemeParser<'_> {
                 title_height,
                 font,
             ),
+            (
+                shadow_color,
+                corner_radius,
+                shadow_blur,
+                shadow_offset_x,
+                shadow_offset_y,
+                blur_passes,
+                blur_offset,
+                inactive_dim_color,
+                title_button_hover_color,
+                focused_border_color,
+            ),
+            (title_font, bar_font, status_font, font_fallbacks_val),
         ) = ext.extract((
             (
                 opt(val("attention-requested-bg-color")),
@@ -83,6 +96,24 @@ impl Parser for ThemeParser<'_> {
                 recover(opt(s32("title-height"))),
                 recover(opt(str("font"))),
             ),
+            (
+                opt(val("shadow-color")),
+                recover(opt(s32("corner-radius"))),
+                recover(opt(s32("shadow-blur"))),
+                recover(opt(s32("shadow-offset-x"))),
+                recover(opt(s32("shadow-offset-y"))),
+                recover(opt(s32("blur-passes"))),
+                recover(opt(s32("blur-offset"))),
+                opt(val("inactive-dim-color")),
+                opt(val("title-button-hover-color")),
+                opt(val("focused-border-color")),
+            ),
+            (
+                recover(opt(str("title-font"))),
+                recover(opt(str("bar-font"))),
+                recover(opt(str("status-font"))),
+                recover(opt(arr("font-fallbacks"))),
+            ),
         ))?;
         macro_rules! color {
             ($e:expr) => {
@@ -98,6 +129,19 @@ impl Parser for ThemeParser<'_> {
                 }
             };
         }
+        let mut font_fallbacks = None;
+        if let Some(value) = font_fallbacks_val {
+            let mut res = vec![];
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => res.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse a font fallback: {}", self.0.error(e));
+                    }
+                }
+            }
+            font_fallbacks = Some(res);
+        }
         Ok(Theme {
             attention_requested_bg_color: color!(attention_requested_bg_color),
             bg_color: color!(bg_color),
@@ -114,9 +158,23 @@ impl Parser for ThemeParser<'_> {
             unfocused_title_bg_color: color!(unfocused_title_bg_color),
             unfocused_title_text_color: color!(unfocused_title_text_color),
             highlight_color: color!(highlight_color),
+            shadow_color: color!(shadow_color),
+            inactive_dim_color: color!(inactive_dim_color),
+            title_button_hover_color: color!(title_button_hover_color),
+            focused_border_color: color!(focused_border_color),
             border_width: border_width.despan(),
             title_height: title_height.despan(),
             font: font.map(|f| f.value.to_string()),
+            title_font: title_font.map(|f| f.value.to_string()),
+            bar_font: bar_font.map(|f| f.value.to_string()),
+            status_font: status_font.map(|f| f.value.to_string()),
+            font_fallbacks,
+            corner_radius: corner_radius.despan(),
+            shadow_blur: shadow_blur.despan(),
+            shadow_offset_x: shadow_offset_x.despan(),
+            shadow_offset_y: shadow_offset_y.despan(),
+            blur_passes: blur_passes.despan(),
+            blur_offset: blur_offset.despan(),
         })
     }
 }
@@ -0,0 +1,82 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{opt, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            Wallpaper,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::video::WallpaperMode,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WallpaperParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct WallpaperParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WallpaperParser<'_> {
+    type Value = Wallpaper;
+    type Error = WallpaperParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (path, mode) = ext.extract((str("path"), opt(val("mode"))))?;
+        let mode = mode.and_then(|m| match m.parse(&mut WallpaperModeParser) {
+            Ok(m) => Some(m),
+            Err(e) => {
+                log::error!("Could not parse mode: {}", self.0.error(e));
+                None
+            }
+        });
+        Ok(Wallpaper {
+            path: path.value.to_string(),
+            mode,
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WallpaperModeParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown mode {0}")]
+    UnknownMode(String),
+}
+
+struct WallpaperModeParser;
+
+impl Parser for WallpaperModeParser {
+    type Value = WallpaperMode;
+    type Error = WallpaperModeParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let mode = match string {
+            "fill" => WallpaperMode::FILL,
+            "fit" => WallpaperMode::FIT,
+            "center" => WallpaperMode::CENTER,
+            "tile" => WallpaperMode::TILE,
+            _ => {
+                return Err(WallpaperModeParserError::UnknownMode(string.to_string()).spanned(span))
+            }
+        };
+        Ok(mode)
+    }
+}
@@ -0,0 +1,90 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{opt, recover, s32, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::color::ColorParser,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::theme::{Color, WindowBorder},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowBorderParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extractor(#[from] ExtractorError),
+}
+
+pub struct WindowBorderParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WindowBorderParser<'_> {
+    type Value = WindowBorder;
+    type Error = WindowBorderParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (color, focused_color, width) = ext.extract((
+            opt(val("color")),
+            opt(val("focused-color")),
+            recover(opt(s32("width"))),
+        ))?;
+        Ok(WindowBorder {
+            color: parse_border_color(self.0, color),
+            focused_color: parse_border_color(self.0, focused_color),
+            width: width.despan(),
+        })
+    }
+}
+
+pub fn parse_border_color(cx: &Context<'_>, value: Option<Spanned<&Value>>) -> Option<Color> {
+    match value?.parse(&mut ColorParser) {
+        Ok(c) => Some(c),
+        Err(e) => {
+            log::warn!("Could not parse a border color: {}", cx.error(e));
+            None
+        }
+    }
+}
+
+pub struct WindowBordersParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WindowBordersParser<'_> {
+    type Value = Vec<(String, WindowBorder)>;
+    type Error = WindowBorderParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut borders = vec![];
+        for (k, v) in table {
+            match v.parse(&mut WindowBorderParser(self.0)) {
+                Ok(border) => borders.push((k.value.to_string(), border)),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the border of `{}`: {}",
+                        k.value,
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        Ok(borders)
+    }
+}
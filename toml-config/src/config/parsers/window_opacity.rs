@@ -0,0 +1,44 @@
+use {
+    crate::{
+        config::parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowOpacityParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Expected a number")]
+    Number,
+}
+
+pub struct WindowOpacityParser;
+
+impl Parser for WindowOpacityParser {
+    type Value = Vec<(String, f64)>;
+    type Error = WindowOpacityParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut opacities = vec![];
+        for (k, v) in table {
+            let opacity = match v.value {
+                Value::Float(f) => f,
+                Value::Integer(i) => i as _,
+                _ => return Err(WindowOpacityParserError::Number.spanned(v.span)),
+            };
+            opacities.push((k.value.to_string(), opacity));
+        }
+        Ok(opacities)
+    }
+}
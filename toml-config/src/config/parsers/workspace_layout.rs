@@ -0,0 +1,62 @@
+use {
+    crate::{
+        config::parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    jay_config::WorkspaceLayout,
+    thiserror::Error,
+};
+
+pub struct WorkspaceLayoutParser;
+
+#[derive(Debug, Error)]
+pub enum WorkspaceLayoutParserError {
+    #[error(transparent)]
+    DataType(#[from] UnexpectedDataType),
+    #[error("Unknown layout {0}")]
+    Unknown(String),
+    #[error("Expected a number")]
+    Float,
+}
+
+impl Parser for WorkspaceLayoutParser {
+    type Value = WorkspaceLayout;
+    type Error = WorkspaceLayoutParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let layout = match string {
+            "manual" => WorkspaceLayout::MANUAL,
+            "master-stack" => WorkspaceLayout::MASTER_STACK,
+            "spiral" => WorkspaceLayout::SPIRAL,
+            "columns" => WorkspaceLayout::COLUMNS,
+            "rows" => WorkspaceLayout::ROWS,
+            "scrolling" => WorkspaceLayout::SCROLLING,
+            _ => return Err(WorkspaceLayoutParserError::Unknown(string.to_string()).spanned(span)),
+        };
+        Ok(layout)
+    }
+}
+
+pub struct ColumnWidthPresetsParser;
+
+impl Parser for ColumnWidthPresetsParser {
+    type Value = Vec<f64>;
+    type Error = WorkspaceLayoutParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for v in array {
+            match v.value {
+                Value::Float(f) => res.push(f),
+                Value::Integer(i) => res.push(i as _),
+                _ => return Err(WorkspaceLayoutParserError::Float.spanned(v.span)),
+            }
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,125 @@
+use {
+    crate::config::{parse_config, Config},
+    jay_config::{
+        keyboard::mods::{Modifiers, ALT, LOGO, SHIFT},
+        BarMode, BarPosition,
+    },
+};
+
+fn parse(input: &str) -> Config {
+    parse_config(input.as_bytes(), |e| {
+        panic!("Could not parse the config: {e}")
+    })
+    .unwrap()
+}
+
+#[test]
+fn bar_defaults() {
+    let bar = parse("").bar;
+    assert_eq!(bar.position, None);
+    assert_eq!(bar.mode, None);
+    assert_eq!(bar.modifiers, None);
+    assert_eq!(bar.connectors, None);
+}
+
+#[test]
+fn bar() {
+    let bar = parse(
+        r#"
+            [bar]
+            position = "bottom"
+            mode = "on-modifier"
+            modifiers = "logo-shift"
+            connectors = ["DP-1", "HDMI-A-1"]
+        "#,
+    )
+    .bar;
+    assert_eq!(bar.position, Some(BarPosition::BOTTOM));
+    assert_eq!(bar.mode, Some(BarMode::ON_MODIFIER));
+    assert_eq!(bar.modifiers, Some(LOGO | SHIFT));
+    assert_eq!(
+        bar.connectors,
+        Some(vec!["DP-1".to_string(), "HDMI-A-1".to_string()]),
+    );
+}
+
+#[test]
+fn bar_values() {
+    let bar = |s: &str| parse(&format!("bar = {{ {s} }}")).bar;
+    assert_eq!(bar(r#"position = "top""#).position, Some(BarPosition::TOP));
+    assert_eq!(bar(r#"mode = "visible""#).mode, Some(BarMode::VISIBLE));
+    assert_eq!(bar(r#"mode = "hidden""#).mode, Some(BarMode::HIDDEN));
+    assert_eq!(bar(r#"modifiers = "alt""#).modifiers, Some(ALT));
+    assert_eq!(bar(r#"modifiers = """#).modifiers, Some(Modifiers(0)));
+    assert_eq!(bar("connectors = []").connectors, Some(vec![]));
+}
+
+#[test]
+fn bar_invalid_values() {
+    let bar = parse(
+        r#"
+            [bar]
+            position = "left"
+            mode = "sometimes"
+            modifiers = "logo-x"
+            connectors = ["DP-1", 1]
+        "#,
+    )
+    .bar;
+    assert_eq!(bar.position, None);
+    assert_eq!(bar.mode, None);
+    assert_eq!(bar.modifiers, None);
+    assert_eq!(bar.connectors, Some(vec!["DP-1".to_string()]));
+}
+
+#[test]
+fn fonts() {
+    let theme = parse(
+        r#"
+            [theme]
+            font = "monospace 8"
+            title-font = "sans 9"
+            bar-font = "sans 10"
+            status-font = "monospace 10"
+            font-fallbacks = ["Noto Color Emoji", "Symbols Nerd Font"]
+        "#,
+    )
+    .theme;
+    assert_eq!(theme.font.as_deref(), Some("monospace 8"));
+    assert_eq!(theme.title_font.as_deref(), Some("sans 9"));
+    assert_eq!(theme.bar_font.as_deref(), Some("sans 10"));
+    assert_eq!(theme.status_font.as_deref(), Some("monospace 10"));
+    assert_eq!(
+        theme.font_fallbacks,
+        Some(vec![
+            "Noto Color Emoji".to_string(),
+            "Symbols Nerd Font".to_string(),
+        ]),
+    );
+}
+
+#[test]
+fn font_defaults() {
+    let theme = parse(r#"theme = { font = "monospace 8" }"#).theme;
+    assert_eq!(theme.title_font, None);
+    assert_eq!(theme.bar_font, None);
+    assert_eq!(theme.status_font, None);
+    assert_eq!(theme.font_fallbacks, None);
+}
+
+#[test]
+fn invalid_fonts() {
+    let theme = parse(
+        r#"
+            [theme]
+            title-font = 1
+            font-fallbacks = ["Noto Color Emoji", true]
+        "#,
+    )
+    .theme;
+    assert_eq!(theme.title_font, None);
+    assert_eq!(
+        theme.font_fallbacks,
+        Some(vec!["Noto Color Emoji".to_string()]),
+    );
+}
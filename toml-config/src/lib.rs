@@ -12,6 +12,10 @@ use {
     ahash::{AHashMap, AHashSet},
     error_reporter::Report,
     jay_config::{
+        animation::{
+            set_animation_curve, set_animation_duration, set_animations_enabled,
+            set_animations_in_screencasts, AnimationKind,
+        },
         config, config_dir,
         exec::{set_env, unset_env, Command},
         get_workspace,
@@ -21,20 +25,29 @@ use {
             Seat, SwitchEvent,
         },
         is_reload,
-        keyboard::{Keymap, ModifiedKeySym},
+        keyboard::{mods::LOGO, Keymap, ModifiedKeySym},
         logging::set_log_level,
-        on_devices_enumerated, on_idle, quit, reload, set_default_workspace_capture,
-        set_explicit_sync_enabled, set_idle, set_ui_drag_enabled, set_ui_drag_threshold,
+        on_devices_enumerated, on_idle, quit, reload, set_bar_connectors, set_bar_mode,
+        set_bar_modifiers, set_bar_position, set_blur_app_ids, set_blur_layer_namespaces,
+        set_column_width_presets, set_default_workspace_capture, set_default_workspace_layout,
+        set_explicit_sync_enabled, set_hidden_title_app_ids, set_idle, set_show_marks,
+        set_show_title_buttons, set_sticky_app_ids, set_swallow_exceptions, set_swallow_terminals,
+        set_title_format, set_ui_drag_enabled, set_ui_drag_threshold, set_window_borders,
+        set_window_opacities,
         status::{set_i3bar_separator, set_status, set_status_command, unset_status_command},
         switch_to_vt,
-        theme::{reset_colors, reset_font, reset_sizes, set_font},
+        theme::{
+            reset_colors, reset_font, reset_sizes, set_bar_font, set_font, set_font_fallbacks,
+            set_status_font, set_title_font,
+        },
         video::{
             connectors, drm_devices, on_connector_connected, on_connector_disconnected,
             on_graphics_initialized, on_new_connector, on_new_drm_device,
             set_direct_scanout_enabled, set_gfx_api, set_tearing_mode, set_vrr_cursor_hz,
-            set_vrr_mode, Connector, DrmDevice,
+            set_vrr_mode, Connector, DrmDevice, WallpaperMode,
         },
         xwayland::set_x_scaling_mode,
+        BarMode, BarPosition,
     },
     std::{cell::RefCell, io::ErrorKind, path::PathBuf, rc::Rc, time::Duration},
 };
@@ -95,6 +108,19 @@ impl Action {
                 SimpleCommand::EnableWindowManagement(bool) => {
                     B::new(move || s.set_window_management_enabled(bool))
                 }
+                SimpleCommand::CycleLayout => B::new(move || s.cycle_workspace_layout()),
+                SimpleCommand::PromoteToMaster => B::new(move || s.promote_to_master()),
+                SimpleCommand::CycleColumnWidth => B::new(move || s.cycle_column_width()),
+                SimpleCommand::ToggleLayout => B::new(move || s.toggle_layout()),
+                SimpleCommand::Unmark => B::new(move || s.unmark()),
+                SimpleCommand::FocusPrevious => B::new(move || s.focus_previous()),
+                SimpleCommand::FocusNextInHistory => B::new(move || s.focus_next_in_history()),
+                SimpleCommand::WindowSwitcher => B::new(move || s.window_switcher()),
+                SimpleCommand::WindowSwitcherReverse => B::new(move || s.window_switcher_reverse()),
+                SimpleCommand::ToggleSticky => B::new(move || s.toggle_sticky()),
+                SimpleCommand::ToggleTitleHidden => B::new(move || s.toggle_title_hidden()),
+                SimpleCommand::Minimize => B::new(move || s.minimize()),
+                SimpleCommand::UnminimizeLast => B::new(move || s.unminimize_last()),
             },
             Action::Multi { actions } => {
                 let actions: Vec<_> = actions.into_iter().map(|a| a.into_fn(state)).collect();
@@ -209,6 +235,30 @@ impl Action {
             Action::SetRepeatRate { rate } => {
                 B::new(move || s.set_repeat_rate(rate.rate, rate.delay))
             }
+            Action::Resize { axis, amount } => B::new(move || s.resize(axis, amount)),
+            Action::SetLayout { layout } => B::new(move || s.set_workspace_layout(layout)),
+            Action::AdjustMasterCount { delta } => B::new(move || s.adjust_master_count(delta)),
+            Action::AdjustMasterRatio { delta } => B::new(move || s.adjust_master_ratio(delta)),
+            Action::Mark { name } => B::new(move || s.mark(&name)),
+            Action::FocusMark { name } => B::new(move || s.focus_mark(&name)),
+            Action::SwapWithMark { name } => B::new(move || s.swap_with_mark(&name)),
+            Action::MoveToMark { name } => B::new(move || s.move_to_mark(&name)),
+            Action::SaveLayout { path, workspace } => {
+                let workspace = workspace.map(|ws| get_workspace(&ws));
+                B::new(move || match workspace {
+                    Some(ws) => ws.save_layout(&path),
+                    _ => s.save_layout(&path),
+                })
+            }
+            Action::LoadLayout { path, workspace } => {
+                let workspace = workspace.map(|ws| get_workspace(&ws));
+                B::new(move || match workspace {
+                    Some(ws) => ws.load_layout(&path),
+                    _ => s.load_layout(&path),
+                })
+            }
+            Action::SetWindowOpacity { opacity } => B::new(move || s.set_window_opacity(opacity)),
+            Action::SetWindowBorder { border } => B::new(move || s.set_window_border(border)),
         }
     }
 }
@@ -577,6 +627,12 @@ impl Output {
                 c.set_tearing_mode(mode);
             }
         }
+        if let Some(wallpaper) = &self.wallpaper {
+            c.set_wallpaper(
+                &wallpaper.path,
+                wallpaper.mode.unwrap_or(WallpaperMode::FILL),
+            );
+        }
         if let Some(format) = self.format {
             c.set_format(format);
         }
@@ -714,6 +770,10 @@ impl State {
         color!(UNFOCUSED_TITLE_BACKGROUND_COLOR, unfocused_title_bg_color);
         color!(UNFOCUSED_TITLE_TEXT_COLOR, unfocused_title_text_color);
         color!(HIGHLIGHT_COLOR, highlight_color);
+        color!(SHADOW_COLOR, shadow_color);
+        color!(INACTIVE_DIM_COLOR, inactive_dim_color);
+        color!(TITLE_BUTTON_HOVER_COLOR, title_button_hover_color);
+        color!(FOCUSED_BORDER_COLOR, focused_border_color);
         macro_rules! size {
             ($sized:ident, $field:ident) => {
                 if let Some(size) = theme.$field {
@@ -723,9 +783,28 @@ impl State {
         }
         size!(BORDER_WIDTH, border_width);
         size!(TITLE_HEIGHT, title_height);
+        size!(CORNER_RADIUS, corner_radius);
+        size!(SHADOW_BLUR, shadow_blur);
+        size!(SHADOW_OFFSET_X, shadow_offset_x);
+        size!(SHADOW_OFFSET_Y, shadow_offset_y);
+        size!(BLUR_PASSES, blur_passes);
+        size!(BLUR_OFFSET, blur_offset);
         if let Some(font) = &theme.font {
             set_font(font);
         }
+        if let Some(font) = &theme.title_font {
+            set_title_font(font);
+        }
+        if let Some(font) = &theme.bar_font {
+            set_bar_font(font);
+        }
+        if let Some(font) = &theme.status_font {
+            set_status_font(font);
+        }
+        if let Some(fonts) = &theme.font_fallbacks {
+            let fonts: Vec<_> = fonts.iter().map(|f| f.as_str()).collect();
+            set_font_fallbacks(&fonts);
+        }
     }
 
     fn handle_switch_device(self: &Rc<Self>, dev: InputDevice, actions: &Rc<SwitchActions>) {
@@ -954,6 +1033,46 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         }
     });
     set_default_workspace_capture(config.workspace_capture);
+    set_default_workspace_layout(config.workspace_layout);
+    set_show_marks(config.show_marks);
+    let swallow_terminals: Vec<_> = config.swallow_terminals.iter().map(|s| &**s).collect();
+    set_swallow_terminals(&swallow_terminals);
+    let swallow_exceptions: Vec<_> = config.swallow_exceptions.iter().map(|s| &**s).collect();
+    set_swallow_exceptions(&swallow_exceptions);
+    let blur_app_ids: Vec<_> = config.blur_app_ids.iter().map(|s| &**s).collect();
+    set_blur_app_ids(&blur_app_ids);
+    let blur_layer_namespaces: Vec<_> = config.blur_layer_namespaces.iter().map(|s| &**s).collect();
+    set_blur_layer_namespaces(&blur_layer_namespaces);
+    let window_opacities: Vec<_> = config
+        .window_opacities
+        .iter()
+        .map(|(app_id, opacity)| (&**app_id, *opacity))
+        .collect();
+    set_window_opacities(&window_opacities);
+    set_title_format(config.title_format.as_deref());
+    let hidden_title_app_ids: Vec<_> = config.hidden_title_app_ids.iter().map(|s| &**s).collect();
+    set_hidden_title_app_ids(&hidden_title_app_ids);
+    let sticky_app_ids: Vec<_> = config.sticky_app_ids.iter().map(|s| &**s).collect();
+    set_sticky_app_ids(&sticky_app_ids);
+    set_show_title_buttons(config.show_title_buttons);
+    let window_borders: Vec<_> = config
+        .window_borders
+        .iter()
+        .map(|(app_id, border)| (&**app_id, *border))
+        .collect();
+    set_window_borders(&window_borders);
+    set_bar_position(config.bar.position.unwrap_or(BarPosition::TOP));
+    set_bar_mode(config.bar.mode.unwrap_or(BarMode::VISIBLE));
+    set_bar_modifiers(config.bar.modifiers.unwrap_or(LOGO));
+    let bar_connectors: Option<Vec<_>> = config
+        .bar
+        .connectors
+        .as_ref()
+        .map(|c| c.iter().map(|s| &**s).collect());
+    set_bar_connectors(bar_connectors.as_deref());
+    if let Some(presets) = &config.column_width_presets {
+        set_column_width_presets(presets);
+    }
     for (k, v) in config.env {
         set_env(&k, &v);
     }
@@ -1067,6 +1186,33 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             set_x_scaling_mode(mode);
         }
     }
+    if let Some(animations) = config.animations {
+        if let Some(enabled) = animations.enabled {
+            set_animations_enabled(enabled);
+        }
+        if let Some(enabled) = animations.in_screencasts {
+            set_animations_in_screencasts(enabled);
+        }
+        let kinds = [
+            AnimationKind::OPEN,
+            AnimationKind::CLOSE,
+            AnimationKind::TILE,
+            AnimationKind::WORKSPACE,
+            AnimationKind::FLOAT,
+        ];
+        let settings = kinds
+            .into_iter()
+            .map(|kind| (kind, &animations.all))
+            .chain(animations.kinds.iter().map(|(kind, s)| (*kind, s)));
+        for (kind, settings) in settings {
+            if let Some(ms) = settings.duration_ms {
+                set_animation_duration(kind, Duration::from_millis(ms));
+            }
+            if let Some(curve) = settings.curve {
+                set_animation_curve(kind, curve);
+            }
+        }
+    }
 }
 
 fn create_command(exec: &Exec) -> Command {